@@ -0,0 +1,675 @@
+//! Creating presentations from scratch.
+//!
+//! PresentationBuilder starts from a document with a single slide master, the most common slide layouts and the
+//! default Office theme. Slides are added based on one of the layouts and filled with text, pictures, tables and
+//! shapes. The content is built with the same parsers that are used for reading a package, so the resulting
+//! PPTXDocument holds the same model types as a parsed one and can be saved with PPTXDocument::save.
+
+use crate::document::{
+    relationships_path, relative_relationship_target, PPTXDocument, IMAGE_RELATIONSHIP_TYPE, PRESENTATION_PATH,
+    SLIDE_LAYOUT_RELATIONSHIP_TYPE, SLIDE_MASTER_RELATIONSHIP_TYPE, SLIDE_RELATIONSHIP_TYPE, THEME_RELATIONSHIP_TYPE,
+};
+use crate::drawingml::{geometry::Rect, table::TABLE_GRAPHIC_DATA_URI};
+use crate::lookup::is_placeholder;
+use crate::opc::next_relationship_id;
+use crate::pml::{
+    presentation::{Presentation, SlideIdListEntry},
+    slides::{
+        GraphicalObjectFrame, Picture, PlaceholderType, Shape, ShapeGroup, Slide, SlideLayout, SlideLayoutType,
+        SlideMaster,
+    },
+};
+use crate::xml::{SchemaValue, XmlNodeBuilder};
+use msoffice_shared::{
+    drawingml::{
+        core::{ShapeProperties, TextBody},
+        sharedstylesheet::OfficeStyleSheet,
+        simpletypes::{DrawingElementId, ShapeType},
+    },
+    relationship::Relationship,
+    xml::XmlNode,
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+const PRESENTATION_XML: &str = include_str!("presentation.xml");
+const THEME_XML: &str = include_str!("theme1.xml");
+const SLIDE_MASTER_XML: &str = include_str!("slideMaster1.xml");
+/// The default slide layouts, in the order of the slide layout id list of the default slide master.
+const SLIDE_LAYOUT_XMLS: [&str; 6] = [
+    include_str!("slideLayout1.xml"),
+    include_str!("slideLayout2.xml"),
+    include_str!("slideLayout3.xml"),
+    include_str!("slideLayout4.xml"),
+    include_str!("slideLayout5.xml"),
+    include_str!("slideLayout6.xml"),
+];
+const SLIDE_XML: &str = r#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+    xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"
+    xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+    <p:cSld>
+        <p:spTree>
+            <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+            <p:grpSpPr/>
+        </p:spTree>
+    </p:cSld>
+    <p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr>
+</p:sld>"#;
+
+const THEME_PATH: &str = "ppt/theme/theme1.xml";
+const SLIDE_MASTER_PATH: &str = "ppt/slideMasters/slideMaster1.xml";
+
+/// Builds a presentation by adding slides and their content to a document.
+///
+/// # Example
+///
+/// ```no_run
+/// use msoffice_pptx::builder::PresentationBuilder;
+/// use msoffice_pptx::drawingml::geometry::Rect;
+/// use msoffice_pptx::pml::slides::{PlaceholderType, SlideLayoutType};
+/// use std::fs::File;
+///
+/// let mut builder = PresentationBuilder::new().unwrap();
+/// let slide_path = builder.add_slide(SlideLayoutType::Title).unwrap();
+/// builder
+///     .set_placeholder_text(&slide_path, PlaceholderType::CenteredTitle, None, "Weekly report")
+///     .unwrap();
+///
+/// let slide_path = builder.add_slide(SlideLayoutType::TitleOnly).unwrap();
+/// builder.set_placeholder_text(&slide_path, PlaceholderType::Title, None, "Numbers").unwrap();
+/// let rows = vec![vec!["Region", "Sales"], vec!["North", "42"]];
+/// builder
+///     .add_table(&slide_path, &rows, Rect::new(838_200.0, 1_825_625.0, 6_838_200.0, 2_825_625.0))
+///     .unwrap();
+///
+/// builder.build().save(File::create("report.pptx").unwrap()).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PresentationBuilder {
+    document: PPTXDocument,
+}
+
+impl PresentationBuilder {
+    /// Creates a builder for an empty presentation with the default slide master, slide layouts and theme. The slides
+    /// are 16:9 (13.333 by 7.5 inches).
+    pub fn new() -> Result<Self> {
+        let mut theme_map = HashMap::new();
+        theme_map.insert(
+            PathBuf::from(THEME_PATH),
+            Box::new(OfficeStyleSheet::from_xml_element(&XmlNode::from_str(THEME_XML)?)?),
+        );
+        let mut raw_part_map = HashMap::new();
+        raw_part_map.insert(PathBuf::from(THEME_PATH), THEME_XML.as_bytes().to_vec());
+
+        let slide_master_path = Path::new(SLIDE_MASTER_PATH);
+        let mut slide_master_rels = Vec::new();
+        let mut slide_layout_map = HashMap::new();
+        let mut slide_layout_rels_map = HashMap::new();
+        for (i, slide_layout_xml) in SLIDE_LAYOUT_XMLS.iter().enumerate() {
+            let slide_layout_path = PathBuf::from(format!("ppt/slideLayouts/slideLayout{}.xml", i + 1));
+            slide_master_rels.push(relationship(
+                &slide_master_rels,
+                SLIDE_LAYOUT_RELATIONSHIP_TYPE,
+                relative_relationship_target(slide_master_path, &slide_layout_path),
+            ));
+
+            let slide_layout_rels = vec![relationship(
+                &[],
+                SLIDE_MASTER_RELATIONSHIP_TYPE,
+                relative_relationship_target(&slide_layout_path, slide_master_path),
+            )];
+            slide_layout_rels_map.insert(relationships_path(&slide_layout_path), slide_layout_rels);
+
+            let slide_layout = SlideLayout::from_xml_element(&XmlNode::from_str(slide_layout_xml)?)?;
+            slide_layout_map.insert(slide_layout_path, Box::new(slide_layout));
+        }
+        slide_master_rels.push(relationship(
+            &slide_master_rels,
+            THEME_RELATIONSHIP_TYPE,
+            relative_relationship_target(slide_master_path, Path::new(THEME_PATH)),
+        ));
+
+        let mut slide_master_map = HashMap::new();
+        slide_master_map.insert(
+            PathBuf::from(SLIDE_MASTER_PATH),
+            Box::new(SlideMaster::from_xml_element(&XmlNode::from_str(SLIDE_MASTER_XML)?)?),
+        );
+        let mut slide_master_rels_map = HashMap::new();
+        slide_master_rels_map.insert(relationships_path(slide_master_path), slide_master_rels);
+
+        let presentation_path = Path::new(PRESENTATION_PATH);
+        let mut presentation_rels = vec![relationship(
+            &[],
+            SLIDE_MASTER_RELATIONSHIP_TYPE,
+            relative_relationship_target(presentation_path, slide_master_path),
+        )];
+        presentation_rels.push(relationship(
+            &presentation_rels,
+            THEME_RELATIONSHIP_TYPE,
+            relative_relationship_target(presentation_path, Path::new(THEME_PATH)),
+        ));
+        let presentation = Presentation::from_xml_element(&XmlNode::from_str(PRESENTATION_XML)?)?;
+
+        let document = PPTXDocument {
+            presentation: Some(Box::new(presentation)),
+            theme_map,
+            slide_master_map,
+            slide_layout_map,
+            slide_master_rels_map,
+            slide_layout_rels_map,
+            presentation_rels,
+            raw_part_map,
+            ..Default::default()
+        };
+
+        Ok(Self { document })
+    }
+
+    /// Creates a builder that adds slides to an existing document, using the slide layouts of the document.
+    pub fn from_document(document: PPTXDocument) -> Self {
+        Self { document }
+    }
+
+    /// Returns the document built so far.
+    pub fn document(&self) -> &PPTXDocument {
+        &self.document
+    }
+
+    /// Returns the built document.
+    pub fn build(self) -> PPTXDocument {
+        self.document
+    }
+
+    /// Adds a slide at the end of the presentation that uses the first slide layout of the given type, in the order of
+    /// the slide layout id lists of the slide masters, and returns the path of the new slide. The slide contains an
+    /// empty placeholder for every content placeholder of the layout, while the date, footer and slide number
+    /// placeholders are left out, like PowerPoint does.
+    pub fn add_slide(&mut self, slide_layout_type: SlideLayoutType) -> Result<PathBuf> {
+        let (slide_layout_path, slide_layout) = self
+            .document
+            .ordered_slide_layouts()
+            .into_iter()
+            .find(|(_, slide_layout)| slide_layout.slide_layout_type == Some(slide_layout_type))
+            .ok_or_else(|| format!("slide layout not found: {}", slide_layout_type))?;
+        let slide_layout_path = slide_layout_path.clone();
+
+        let mut slide = Slide::from_xml_element(&XmlNode::from_str(SLIDE_XML)?)?;
+        let content_placeholders = slide_layout
+            .common_slide_data
+            .shape_tree
+            .shape_array
+            .iter()
+            .filter_map(|shape_group| match shape_group {
+                ShapeGroup::Shape(shape) => Some(shape),
+                _ => None,
+            })
+            .filter(|shape| match &shape.non_visual_props.app_props.placeholder {
+                Some(placeholder) => !matches!(
+                    placeholder.placeholder_type,
+                    Some(PlaceholderType::DateTime)
+                        | Some(PlaceholderType::Footer)
+                        | Some(PlaceholderType::SlideNumber)
+                        | Some(PlaceholderType::Header)
+                ),
+                None => false,
+            });
+        for layout_shape in content_placeholders {
+            let mut shape = layout_shape.clone();
+            shape.non_visual_props.drawing_props.id = next_element_id(&slide);
+            shape.shape_props = Box::new(ShapeProperties::from_xml_element(&XmlNode::new("p:spPr"))?);
+            shape.shape_style = None;
+            shape.text_body = None;
+            for local_name in &["spPr", "style", "txBody"] {
+                shape.unknown_xml.remove_known_child(local_name);
+            }
+            slide
+                .common_slide_data
+                .shape_tree
+                .shape_array
+                .push(ShapeGroup::Shape(shape));
+        }
+
+        let slide_path = self.document.unused_part_path("ppt/slides", "slide", "xml");
+        let slide_rels = vec![relationship(
+            &[],
+            SLIDE_LAYOUT_RELATIONSHIP_TYPE,
+            relative_relationship_target(&slide_path, &slide_layout_path),
+        )];
+
+        let slide_relationship = relationship(
+            &self.document.presentation_rels,
+            SLIDE_RELATIONSHIP_TYPE,
+            relative_relationship_target(Path::new(PRESENTATION_PATH), &slide_path),
+        );
+        let slide_id = self.document.next_slide_id();
+        let presentation = self
+            .document
+            .presentation_mut()
+            .ok_or("the document doesn't have a presentation")?;
+        presentation.slide_id_list.push(SlideIdListEntry {
+            id: slide_id,
+            relationship_id: slide_relationship.id.clone(),
+            unknown_xml: Default::default(),
+        });
+        self.document.presentation_rels.push(slide_relationship);
+
+        self.document
+            .slide_rels_map
+            .insert(relationships_path(&slide_path), slide_rels);
+        self.document.slide_map.insert(slide_path.clone(), Box::new(slide));
+        self.document.mark_modified(&slide_path);
+
+        Ok(slide_path)
+    }
+
+    /// Replaces the text of the first placeholder of the given type on the slide. If `index` is Some, the index of
+    /// the placeholder has to match too. Every line of `text` becomes a paragraph, which inherits its formatting from
+    /// the slide layout.
+    pub fn set_placeholder_text(
+        &mut self,
+        slide_path: &Path,
+        placeholder_type: PlaceholderType,
+        index: Option<u32>,
+        text: &str,
+    ) -> Result<()> {
+        let mut text_body = Some(TextBody::from_xml_element(&text_body_element("p:txBody", text, false))?);
+        let slide = self
+            .document
+            .slide_mut(slide_path)
+            .ok_or_else(|| format!("slide not found: {}", slide_path.display()))?;
+
+        slide.common_slide_data.for_each_shape_mut(|_, _, shape_group| {
+            if text_body.is_none() || !is_placeholder(shape_group, placeholder_type, index) {
+                return;
+            }
+
+            if let ShapeGroup::Shape(shape) = shape_group {
+                shape.text_body = text_body.take();
+                shape.unknown_xml.remove_known_child("txBody");
+            }
+        });
+
+        match text_body {
+            None => Ok(()),
+            Some(_) => Err(format!("placeholder not found: {}", placeholder_type).into()),
+        }
+    }
+
+    /// Adds a preset shape to the slide within `bounds`, given in EMUs. The shape is styled by the theme like a
+    /// shape inserted in PowerPoint, and `text` is centered in the shape. Returns the id of the new shape.
+    pub fn add_shape(
+        &mut self,
+        slide_path: &Path,
+        preset: ShapeType,
+        bounds: Rect,
+        text: Option<&str>,
+    ) -> Result<DrawingElementId> {
+        let style_reference = |node_name: &str, index: &str| {
+            XmlNode::new(node_name)
+                .with_attribute("idx", index)
+                .with_child(XmlNode::new("a:schemeClr").with_attribute("val", "accent1"))
+        };
+        let line_reference = XmlNode::new("a:lnRef").with_attribute("idx", "2").with_child(
+            XmlNode::new("a:schemeClr")
+                .with_attribute("val", "accent1")
+                .with_child(XmlNode::new("a:shade").with_attribute("val", "50000")),
+        );
+        let font_reference = XmlNode::new("a:fontRef")
+            .with_attribute("idx", "minor")
+            .with_child(XmlNode::new("a:schemeClr").with_attribute("val", "lt1"));
+
+        self.add_element(slide_path, |id| {
+            let xml_node = XmlNode::new("p:sp")
+                .with_child(
+                    XmlNode::new("p:nvSpPr")
+                        .with_child(drawing_props_element(id, "Shape"))
+                        .with_child(XmlNode::new("p:cNvSpPr"))
+                        .with_child(XmlNode::new("p:nvPr")),
+                )
+                .with_child(
+                    XmlNode::new("p:spPr")
+                        .with_child(transform_element("a:xfrm", &bounds))
+                        .with_child(preset_geometry_element(preset)),
+                )
+                .with_child(
+                    XmlNode::new("p:style")
+                        .with_child(line_reference)
+                        .with_child(style_reference("a:fillRef", "1"))
+                        .with_child(style_reference("a:effectRef", "0"))
+                        .with_child(font_reference),
+                )
+                .with_child(text_body_element("p:txBody", text.unwrap_or_default(), true));
+
+            Ok(ShapeGroup::Shape(Box::new(Shape::from_xml_element(&xml_node)?)))
+        })
+    }
+
+    /// Adds a picture to the slide within `bounds`, given in EMUs. `data` is stored as a new media part with the given
+    /// file extension (e.g. "png"). Returns the id of the new picture.
+    pub fn add_picture(
+        &mut self,
+        slide_path: &Path,
+        data: &[u8],
+        extension: &str,
+        bounds: Rect,
+    ) -> Result<DrawingElementId> {
+        let slide_rels_path = relationships_path(slide_path);
+        if !self.document.slide_map.contains_key(slide_path) {
+            return Err(format!("slide not found: {}", slide_path.display()).into());
+        }
+
+        let extension = extension.trim_start_matches('.').to_lowercase();
+        let media_path = self.document.unused_part_path("ppt/media", "image", &extension);
+        let slide_rels = self.document.slide_rels_map.entry(slide_rels_path).or_default();
+        let image_relationship = relationship(
+            slide_rels,
+            IMAGE_RELATIONSHIP_TYPE,
+            relative_relationship_target(slide_path, &media_path),
+        );
+        let relationship_id = image_relationship.id.clone();
+        slide_rels.push(image_relationship);
+
+        self.document.medias.push(media_path.clone());
+        self.document.raw_part_map.insert(media_path, data.to_vec());
+
+        self.add_element(slide_path, |id| {
+            let xml_node = XmlNode::new("p:pic")
+                .with_child(
+                    XmlNode::new("p:nvPicPr")
+                        .with_child(drawing_props_element(id, "Picture"))
+                        .with_child(
+                            XmlNode::new("p:cNvPicPr")
+                                .with_child(XmlNode::new("a:picLocks").with_attribute("noChangeAspect", "1")),
+                        )
+                        .with_child(XmlNode::new("p:nvPr")),
+                )
+                .with_child(
+                    XmlNode::new("p:blipFill")
+                        .with_child(XmlNode::new("a:blip").with_attribute("r:embed", &relationship_id))
+                        .with_child(XmlNode::new("a:stretch").with_child(XmlNode::new("a:fillRect"))),
+                )
+                .with_child(
+                    XmlNode::new("p:spPr")
+                        .with_child(transform_element("a:xfrm", &bounds))
+                        .with_child(preset_geometry_element(ShapeType::Rect)),
+                );
+
+            Ok(ShapeGroup::Picture(Box::new(Picture::from_xml_element(&xml_node)?)))
+        })
+    }
+
+    /// Adds a table to the slide within `bounds`, given in EMUs. Every item of `rows` is a row of the table and every
+    /// row has to have the same number of cells. The width and the height of `bounds` is divided evenly between the
+    /// columns and the rows. Returns the id of the graphic frame containing the table.
+    pub fn add_table<R, C>(&mut self, slide_path: &Path, rows: &[R], bounds: Rect) -> Result<DrawingElementId>
+    where
+        R: AsRef<[C]>,
+        C: AsRef<str>,
+    {
+        let column_count = rows.first().map(|row| row.as_ref().len()).unwrap_or(0);
+        if column_count == 0 || rows.iter().any(|row| row.as_ref().len() != column_count) {
+            return Err("every row of a table has to have the same, non-zero number of cells".into());
+        }
+
+        let column_width = (bounds.width() / column_count as f64).round() as i64;
+        let row_height = (bounds.height() / rows.len() as f64).round() as i64;
+        let grid_columns = (0..column_count).map(|_| XmlNode::new("a:gridCol").with_attribute("w", column_width));
+        let table_rows = rows.iter().map(|row| {
+            let cells = row.as_ref().iter().map(|cell| {
+                XmlNode::new("a:tc")
+                    .with_child(text_body_element("a:txBody", cell.as_ref(), false))
+                    .with_child(XmlNode::new("a:tcPr"))
+            });
+            XmlNode::new("a:tr")
+                .with_attribute("h", row_height)
+                .with_children(cells)
+        });
+        let table = XmlNode::new("a:tbl")
+            .with_child(XmlNode::new("a:tblGrid").with_children(grid_columns))
+            .with_children(table_rows);
+
+        self.add_element(slide_path, |id| {
+            let xml_node = XmlNode::new("p:graphicFrame")
+                .with_child(
+                    XmlNode::new("p:nvGraphicFramePr")
+                        .with_child(drawing_props_element(id, "Table"))
+                        .with_child(
+                            XmlNode::new("p:cNvGraphicFramePr")
+                                .with_child(XmlNode::new("a:graphicFrameLocks").with_attribute("noGrp", "1")),
+                        )
+                        .with_child(XmlNode::new("p:nvPr")),
+                )
+                .with_child(transform_element("p:xfrm", &bounds))
+                .with_child(
+                    XmlNode::new("a:graphic").with_child(
+                        XmlNode::new("a:graphicData")
+                            .with_attribute("uri", TABLE_GRAPHIC_DATA_URI)
+                            .with_child(table),
+                    ),
+                );
+
+            Ok(ShapeGroup::GraphicFrame(Box::new(
+                GraphicalObjectFrame::from_xml_element(&xml_node)?,
+            )))
+        })
+    }
+
+    /// Adds the element created by `create` to the end of the shape tree of the slide. `create` is called with the id
+    /// of the new element.
+    fn add_element<F>(&mut self, slide_path: &Path, create: F) -> Result<DrawingElementId>
+    where
+        F: FnOnce(DrawingElementId) -> Result<ShapeGroup>,
+    {
+        let slide = self
+            .document
+            .slide_mut(slide_path)
+            .ok_or_else(|| format!("slide not found: {}", slide_path.display()))?;
+        let id = next_element_id(slide);
+        let shape_group = create(id)?;
+        slide.common_slide_data.shape_tree.shape_array.push(shape_group);
+
+        Ok(id)
+    }
+}
+
+/// Returns a new relationship with an id that isn't used by `relationships`.
+fn relationship(relationships: &[Relationship], rel_type: &str, target: String) -> Relationship {
+    Relationship {
+        id: next_relationship_id(relationships),
+        rel_type: String::from(rel_type),
+        target,
+    }
+}
+
+/// Returns an id that isn't used by any element of the slide.
+fn next_element_id(slide: &Slide) -> DrawingElementId {
+    let shape_tree_id = slide.common_slide_data.shape_tree.non_visual_props.drawing_props.id;
+    slide
+        .common_slide_data
+        .iter_shapes()
+        .filter_map(|(_, _, shape_group)| shape_group.drawing_props())
+        .map(|drawing_props| drawing_props.id)
+        .fold(shape_tree_id, DrawingElementId::max)
+        + 1
+}
+
+/// Returns the non-visual drawing properties of a new element, which is named after its kind and its id like
+/// PowerPoint does (e.g. "Picture 3").
+fn drawing_props_element(id: DrawingElementId, kind: &str) -> XmlNode {
+    XmlNode::new("p:cNvPr")
+        .with_attribute("id", id)
+        .with_attribute("name", format!("{} {}", kind, id - 1))
+}
+
+fn transform_element(node_name: &str, bounds: &Rect) -> XmlNode {
+    XmlNode::new(node_name)
+        .with_child(
+            XmlNode::new("a:off")
+                .with_attribute("x", bounds.left.round() as i64)
+                .with_attribute("y", bounds.top.round() as i64),
+        )
+        .with_child(
+            XmlNode::new("a:ext")
+                .with_attribute("cx", bounds.width().round() as i64)
+                .with_attribute("cy", bounds.height().round() as i64),
+        )
+}
+
+fn preset_geometry_element(preset: ShapeType) -> XmlNode {
+    XmlNode::new("a:prstGeom")
+        .with_attribute("prst", preset.schema_value())
+        .with_child(XmlNode::new("a:avLst"))
+}
+
+/// Returns a text body with a paragraph for every line of `text`. If `centered` is true, the text is centered both
+/// horizontally and vertically.
+fn text_body_element(node_name: &str, text: &str, centered: bool) -> XmlNode {
+    let paragraphs = text.split('\n').map(|line| {
+        let paragraph_properties = if centered {
+            Some(XmlNode::new("a:pPr").with_attribute("algn", "ctr"))
+        } else {
+            None
+        };
+        let run = if line.is_empty() {
+            None
+        } else {
+            let run_properties = XmlNode::new("a:rPr").with_attribute("lang", "en-US");
+            Some(
+                XmlNode::new("a:r")
+                    .with_child(run_properties)
+                    .with_child(XmlNode::new("a:t").with_text(line)),
+            )
+        };
+
+        XmlNode::new("a:p")
+            .with_optional_child(paragraph_properties)
+            .with_optional_child(run)
+            .with_child(XmlNode::new("a:endParaRPr").with_attribute("lang", "en-US"))
+    });
+    let body_properties = if centered {
+        XmlNode::new("a:bodyPr")
+            .with_attribute("rtlCol", "0")
+            .with_attribute("anchor", "ctr")
+    } else {
+        XmlNode::new("a:bodyPr")
+    };
+
+    XmlNode::new(node_name)
+        .with_child(body_properties)
+        .with_child(XmlNode::new("a:lstStyle"))
+        .with_children(paragraphs)
+}
+
+#[cfg(test)]
+#[test]
+fn test_presentation_builder() {
+    use crate::document::load_saved_document;
+    use crate::pml::slides::GraphicalObjectContent;
+    use std::io::Cursor;
+
+    let mut builder = PresentationBuilder::new().unwrap();
+    let title_slide_path = builder.add_slide(SlideLayoutType::Title).unwrap();
+    builder
+        .set_placeholder_text(&title_slide_path, PlaceholderType::CenteredTitle, None, "Title")
+        .unwrap();
+    builder
+        .set_placeholder_text(
+            &title_slide_path,
+            PlaceholderType::SubTitle,
+            Some(1),
+            "First line\nSecond line",
+        )
+        .unwrap();
+    assert!(builder
+        .set_placeholder_text(&title_slide_path, PlaceholderType::Body, Some(5), "Missing")
+        .is_err());
+
+    let content_slide_path = builder.add_slide(SlideLayoutType::Object).unwrap();
+    let bounds = Rect::new(838_200.0, 1_825_625.0, 4_838_200.0, 3_825_625.0);
+    let picture_id = builder
+        .add_picture(&content_slide_path, &[0x89, b'P', b'N', b'G'], "png", bounds)
+        .unwrap();
+    let table_id = builder
+        .add_table(&content_slide_path, &[["a", "b"], ["c", "d"]], bounds)
+        .unwrap();
+    let shape_id = builder
+        .add_shape(&content_slide_path, ShapeType::Ellipse, bounds, Some("Shape"))
+        .unwrap();
+    assert!(picture_id < table_id && table_id < shape_id);
+
+    let document = builder.build();
+    assert_eq!(title_slide_path, Path::new("ppt/slides/slide1.xml"));
+    assert_eq!(content_slide_path, Path::new("ppt/slides/slide2.xml"));
+    let slide_id_list = &document.presentation.as_ref().unwrap().slide_id_list;
+    assert_eq!(
+        slide_id_list.iter().map(|entry| entry.id).collect::<Vec<_>>(),
+        vec![256, 257]
+    );
+
+    let content_slide = &document.slide_map[&content_slide_path];
+    let table_row_count =
+        content_slide
+            .common_slide_data
+            .iter_shapes()
+            .find_map(|(_, _, shape_group)| match shape_group {
+                ShapeGroup::GraphicFrame(frame) => match &frame.graphic_content {
+                    Some(GraphicalObjectContent::Table(table)) => Some(table.row_list.len()),
+                    _ => None,
+                },
+                _ => None,
+            });
+    assert_eq!(table_row_count, Some(2));
+
+    let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+    let saved_document = load_saved_document(&data);
+
+    assert_eq!(saved_document.presentation, document.presentation);
+    assert_eq!(saved_document.slide_master_map, document.slide_master_map);
+    assert_eq!(saved_document.slide_layout_map, document.slide_layout_map);
+    assert_eq!(saved_document.slide_map, document.slide_map);
+    assert_eq!(saved_document.slide_rels_map, document.slide_rels_map);
+    assert_eq!(saved_document.theme_map, document.theme_map);
+    assert_eq!(saved_document.medias, vec![PathBuf::from("ppt/media/image1.png")]);
+    assert_eq!(
+        saved_document.read_part(Path::new("ppt/media/image1.png")).unwrap(),
+        vec![0x89, b'P', b'N', b'G']
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_add_slide_layout_order() {
+    let mut builder = PresentationBuilder::new().unwrap();
+    let last_slide_layout_path = PathBuf::from("ppt/slideLayouts/slideLayout6.xml");
+    let slide_master = builder.document.slide_master_map.values_mut().next().unwrap();
+    slide_master.slide_layout_id_list.as_mut().unwrap().0.reverse();
+    builder
+        .document
+        .slide_layout_map
+        .get_mut(&last_slide_layout_path)
+        .unwrap()
+        .slide_layout_type = Some(SlideLayoutType::Title);
+
+    // The slide layouts follow the slide layout id list of the slide master instead of their paths
+    let ordered_paths = builder
+        .document
+        .ordered_slide_layouts()
+        .into_iter()
+        .map(|(slide_layout_path, _)| slide_layout_path.clone())
+        .collect::<Vec<_>>();
+    let expected_paths = (1..=6)
+        .rev()
+        .map(|i| PathBuf::from(format!("ppt/slideLayouts/slideLayout{}.xml", i)))
+        .collect::<Vec<_>>();
+    assert_eq!(ordered_paths, expected_paths);
+
+    let slide_path = builder.add_slide(SlideLayoutType::Title).unwrap();
+    let document = builder.build();
+    assert_eq!(document.slide_layout_path(&slide_path), Some(last_slide_layout_path));
+}
@@ -0,0 +1,263 @@
+use crate::drawingml::geometry::{evaluate_geometry, Point};
+use crate::placement::{shape_placements, PlacedShape, Placement};
+use crate::pml::slides::{CommonSlideData, Connector, ShapeGroup, Slide};
+use crate::style::StyleContext;
+use msoffice_shared::drawingml::{core::Connection, simpletypes::DrawingElementId};
+
+/// An end of a connector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectorEnd<'a> {
+    /// The connection of the end as specified by the stCxn or endCxn element of the connector.
+    pub connection: Option<Connection>,
+    /// The connected shape. None if the end isn't connected or the connected shape can't be found on the slide.
+    pub shape: Option<&'a ShapeGroup>,
+    /// The position of the end on the slide in EMUs. This is the position of the connection site if it can be
+    /// resolved, otherwise the end of the connector's own geometry.
+    pub position: Point,
+}
+
+impl<'a> ConnectorEnd<'a> {
+    /// Returns the id of the connected shape.
+    pub fn shape_id(&self) -> Option<DrawingElementId> {
+        self.shape
+            .and_then(|shape| shape.drawing_props())
+            .map(|drawing_props| drawing_props.id)
+    }
+}
+
+/// A connector of a slide with its resolved ends.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectorEdge<'a> {
+    pub connector: &'a Connector,
+    pub placement: Placement,
+    pub start: ConnectorEnd<'a>,
+    pub end: ConnectorEnd<'a>,
+}
+
+/// The graph of the shapes of a slide connected by connectors. Each connector is a directed edge from the shape its
+/// start is connected to, to the shape its end is connected to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectorGraph<'a> {
+    /// The connectors of the slide in document order.
+    pub edges: Vec<ConnectorEdge<'a>>,
+}
+
+impl<'a> ConnectorGraph<'a> {
+    /// Builds the graph from the placements of the elements of a slide, as returned by shape_placements.
+    ///
+    /// Connection sites are resolved with the geometry of the connected shape, evaluated at its placed size. Elements
+    /// without a geometry, like groups and graphic frames, have the connection sites of a rectangle. Connectors
+    /// without a placement are skipped.
+    pub fn from_placements(placed_shapes: &[PlacedShape<'a>]) -> Self {
+        let find_placed_shape = |id: DrawingElementId| {
+            placed_shapes.iter().find(|placed_shape| {
+                placed_shape
+                    .shape
+                    .drawing_props()
+                    .filter(|drawing_props| drawing_props.id == id)
+                    .is_some()
+            })
+        };
+
+        let resolve_end = |connection: Option<&Connection>, placement: &Placement, local_position: Point| {
+            let placed_shape = connection.and_then(|connection| find_placed_shape(connection.id));
+            let site_position = connection.zip(placed_shape).and_then(|(connection, placed_shape)| {
+                connection_site_position(placed_shape, connection.shape_index as usize)
+            });
+
+            ConnectorEnd {
+                connection: connection.cloned(),
+                shape: placed_shape.map(|placed_shape| placed_shape.shape),
+                position: site_position.unwrap_or_else(|| placement.map_point(local_position)),
+            }
+        };
+
+        let edges = placed_shapes
+            .iter()
+            .filter_map(|placed_shape| match placed_shape.shape {
+                ShapeGroup::Connector(connector) => Some((&**connector, placed_shape.placement)),
+                _ => None,
+            })
+            .map(|(connector, placement)| {
+                let connector_props = &connector.non_visual_props.connector_props;
+                let bounds = &placement.bounds;
+                ConnectorEdge {
+                    connector,
+                    placement,
+                    start: resolve_end(
+                        connector_props.start_connection.as_ref(),
+                        &placement,
+                        Point::new(0.0, 0.0),
+                    ),
+                    end: resolve_end(
+                        connector_props.end_connection.as_ref(),
+                        &placement,
+                        Point::new(bounds.width(), bounds.height()),
+                    ),
+                }
+            })
+            .collect();
+
+        Self { edges }
+    }
+
+    /// Returns the ids of the shapes connected by at least one connector, in the order of their first occurrence.
+    pub fn nodes(&self) -> Vec<DrawingElementId> {
+        let mut nodes = Vec::new();
+        for edge in &self.edges {
+            for id in edge.start.shape_id().into_iter().chain(edge.end.shape_id()) {
+                if !nodes.contains(&id) {
+                    nodes.push(id);
+                }
+            }
+        }
+
+        nodes
+    }
+
+    /// Returns the ids of the shapes that the shape with the given id is connected to by connectors starting at it.
+    pub fn successors(&self, id: DrawingElementId) -> Vec<DrawingElementId> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.start.shape_id() == Some(id))
+            .filter_map(|edge| edge.end.shape_id())
+            .collect()
+    }
+
+    /// Returns the ids of the shapes that are connected to the shape with the given id by connectors ending at it.
+    pub fn predecessors(&self, id: DrawingElementId) -> Vec<DrawingElementId> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.end.shape_id() == Some(id))
+            .filter_map(|edge| edge.start.shape_id())
+            .collect()
+    }
+}
+
+/// Returns the position of a connection site of a placed shape on the slide.
+fn connection_site_position(placed_shape: &PlacedShape, site_index: usize) -> Option<Point> {
+    let geometry = match placed_shape.shape {
+        ShapeGroup::Shape(shape) => shape.shape_props.geometry.as_ref(),
+        ShapeGroup::Connector(connector) => connector.shape_props.geometry.as_ref(),
+        ShapeGroup::Picture(picture) => picture.shape_props.geometry.as_ref(),
+        _ => None,
+    };
+
+    let placement = &placed_shape.placement;
+    let evaluated_geometry = evaluate_geometry(geometry, placement.bounds.width(), placement.bounds.height());
+    evaluated_geometry
+        .connection_sites
+        .get(site_index)
+        .map(|site| placement.map_point(site.position))
+}
+
+impl CommonSlideData {
+    /// Returns the graph of the shapes connected by the connectors of the shape tree. Placeholders without a transform
+    /// can't be resolved, see StyleContext::connector_graph.
+    pub fn connector_graph(&self) -> ConnectorGraph<'_> {
+        ConnectorGraph::from_placements(&shape_placements(&self.shape_tree))
+    }
+}
+
+impl Slide {
+    /// Returns the graph of the shapes connected by the connectors of the slide. See CommonSlideData::connector_graph.
+    pub fn connector_graph(&self) -> ConnectorGraph<'_> {
+        self.common_slide_data.connector_graph()
+    }
+}
+
+impl<'a> StyleContext<'a> {
+    /// Returns the graph of the shapes connected by the connectors of the slide. Placeholders without a transform are
+    /// placed at the position inherited from the slide layout or the slide master.
+    pub fn connector_graph(&self) -> ConnectorGraph<'a> {
+        ConnectorGraph::from_placements(&self.shape_placements())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use msoffice_shared::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_connector_graph() {
+        let xml = r#"<p:cSld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+                xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+                <p:spTree>
+                    <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                    <p:grpSpPr/>
+                    <p:sp>
+                        <p:nvSpPr><p:cNvPr id="2" name="Start"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>
+                        <p:spPr>
+                            <a:xfrm><a:off x="0" y="0"/><a:ext cx="100" cy="100"/></a:xfrm>
+                            <a:prstGeom prst="ellipse"><a:avLst/></a:prstGeom>
+                        </p:spPr>
+                    </p:sp>
+                    <p:sp>
+                        <p:nvSpPr><p:cNvPr id="3" name="Decision"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>
+                        <p:spPr>
+                            <a:xfrm rot="5400000"><a:off x="300" y="0"/><a:ext cx="200" cy="100"/></a:xfrm>
+                            <a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
+                        </p:spPr>
+                    </p:sp>
+                    <p:cxnSp>
+                        <p:nvCxnSpPr>
+                            <p:cNvPr id="4" name="Arrow"/>
+                            <p:cNvCxnSpPr><a:stCxn id="2" idx="6"/><a:endCxn id="3" idx="1"/></p:cNvCxnSpPr>
+                            <p:nvPr/>
+                        </p:nvCxnSpPr>
+                        <p:spPr>
+                            <a:xfrm><a:off x="100" y="50"/><a:ext cx="250" cy="0"/></a:xfrm>
+                            <a:prstGeom prst="straightConnector1"><a:avLst/></a:prstGeom>
+                        </p:spPr>
+                    </p:cxnSp>
+                    <p:cxnSp>
+                        <p:nvCxnSpPr>
+                            <p:cNvPr id="5" name="Dangling"/>
+                            <p:cNvCxnSpPr><a:stCxn id="3" idx="2"/><a:endCxn id="9" idx="0"/></p:cNvCxnSpPr>
+                            <p:nvPr/>
+                        </p:nvCxnSpPr>
+                        <p:spPr>
+                            <a:xfrm flipH="1"><a:off x="400" y="100"/><a:ext cx="100" cy="200"/></a:xfrm>
+                            <a:prstGeom prst="straightConnector1"><a:avLst/></a:prstGeom>
+                        </p:spPr>
+                    </p:cxnSp>
+                </p:spTree>
+            </p:cSld>"#;
+        let common_slide_data = CommonSlideData::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        let graph = common_slide_data.connector_graph();
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.nodes(), vec![2, 3]);
+        assert_eq!(graph.successors(2), vec![3]);
+        assert_eq!(graph.predecessors(3), vec![2]);
+        assert!(graph.successors(3).is_empty());
+
+        let assert_position = |actual: Point, x: f64, y: f64| {
+            assert!(
+                (actual.x - x).abs() < 1e-6 && (actual.y - y).abs() < 1e-6,
+                "{:?} != ({}, {})",
+                actual,
+                x,
+                y
+            );
+        };
+
+        // The right connection site of the ellipse
+        let edge = &graph.edges[0];
+        assert_eq!(edge.connector.non_visual_props.drawing_props.name, "Arrow");
+        assert_eq!(edge.start.shape_id(), Some(2));
+        assert_position(edge.start.position, 100.0, 50.0);
+        // The left connection site of the rectangle, which is at the top after rotating it by 90 degrees
+        assert_eq!(edge.end.shape_id(), Some(3));
+        assert_position(edge.end.position, 400.0, -50.0);
+
+        // The bottom connection site of the rotated rectangle is on its left side
+        let edge = &graph.edges[1];
+        assert_position(edge.start.position, 350.0, 50.0);
+        // The end is connected to a missing shape, so the end of the flipped connector is used
+        assert_eq!(edge.end.connection.as_ref().map(|connection| connection.id), Some(9));
+        assert!(edge.end.shape.is_none());
+        assert_position(edge.end.position, 400.0, 300.0);
+    }
+}
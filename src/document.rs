@@ -1,19 +1,86 @@
+use crate::drawingml::diagram::{DiagramData, DiagramRelationshipIds};
+use crate::edit::insert_section_slide_id;
+use crate::opc::{
+    next_relationship_id, relationships_from_zip_file, write_relationships, ContentTypes, ExternalRelationships,
+    CONTENT_TYPES_PATH, NOTES_SLIDE_CONTENT_TYPE, OFFICE_DOCUMENT_RELATIONSHIP_TYPE, PACKAGE_RELATIONSHIPS_PATH,
+    PRESENTATION_CONTENT_TYPE, RELATIONSHIPS_CONTENT_TYPE, SLIDE_CONTENT_TYPE, SLIDE_LAYOUT_CONTENT_TYPE,
+    SLIDE_MASTER_CONTENT_TYPE, THEME_CONTENT_TYPE, XML_CONTENT_TYPE,
+};
 use crate::pml::{
-    presentation::Presentation,
-    slides::{Slide, SlideLayout, SlideMaster},
+    presentation::{Presentation, SlideId, SlideIdListEntry, SlideMasterId},
+    slides::{BackgroundGroup, CommonSlideData, NotesSlide, Slide, SlideLayout, SlideMaster},
+    util::encode_base64,
 };
+use crate::xml;
 use log::info;
 use msoffice_shared::{
     docprops::{AppInfo, Core},
-    drawingml::sharedstylesheet::OfficeStyleSheet,
-    relationship::Relationship,
+    drawingml::{
+        colors::Color,
+        coordsys::PositiveSize2D,
+        shapeprops::{EffectProperties, FillProperties},
+        sharedstylesheet::OfficeStyleSheet,
+        simpletypes::{BlackWhiteMode, SchemeColorVal},
+    },
+    relationship::{self, Relationship},
+    xml::XmlNode,
+};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, Seek, Write};
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use zip::{
+    result::ZipError,
+    write::{FileOptions, ZipWriter},
+    ZipArchive,
 };
-use std::collections::HashMap;
-use std::fs::File;
-use std::path::{Path, PathBuf};
-use zip::ZipArchive;
 
+pub const PRESENTATION_PATH: &str = "ppt/presentation.xml";
+pub const PRESENTATION_RELATIONSHIPS_PATH: &str = "ppt/_rels/presentation.xml.rels";
+/// The directories of the relationship parts that are represented by the model, besides the one of the presentation.
+const MODEL_RELATIONSHIPS_DIRECTORIES: [&str; 4] = [
+    "ppt/slideMasters/_rels",
+    "ppt/slideLayouts/_rels",
+    "ppt/slides/_rels",
+    "ppt/notesSlides/_rels",
+];
+
+pub const SLIDE_LAYOUT_RELATIONSHIP_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideLayout";
+pub const SLIDE_MASTER_RELATIONSHIP_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideMaster";
+pub const THEME_RELATIONSHIP_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme";
+pub const NOTES_SLIDE_RELATIONSHIP_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/notesSlide";
+pub const SLIDE_RELATIONSHIP_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide";
+pub const NOTES_MASTER_RELATIONSHIP_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/notesMaster";
+pub const IMAGE_RELATIONSHIP_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image";
+
+/// The lowest slide id allowed by the schema.
+const MIN_SLIDE_ID: SlideId = 256;
+/// The lowest slide master and slide layout id allowed by the schema.
+const MIN_SLIDE_MASTER_ID: SlideMasterId = 2_147_483_648;
+
+/// The background of a slide after resolving the slide -> slide layout -> slide master inheritance chain and any
+/// style matrix reference into the theme.
 #[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveBackground {
+    /// The concrete fill of the background. If the background was specified as a style matrix reference, the
+    /// placeholder color (phClr) of the referenced fill style is already replaced with the color of the reference.
+    pub fill: FillProperties,
+    pub effect: Option<EffectProperties>,
+    pub black_and_white_mode: Option<BlackWhiteMode>,
+    /// The path of the part in which the fill has been defined. Relationship ids inside the fill (e.g. the r:embed
+    /// attribute of a blip fill) have to be resolved using the relationships of this part.
+    pub source_part: PathBuf,
+}
+
+/// The path and the content of a part that's written by `PPTXDocument::save`.
+type SerializedPart = (PathBuf, Vec<u8>);
+
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct PPTXDocument {
     pub file_path: PathBuf,
     pub app: Option<Box<AppInfo>>,
@@ -23,10 +90,30 @@ pub struct PPTXDocument {
     pub slide_master_map: HashMap<PathBuf, Box<SlideMaster>>,
     pub slide_layout_map: HashMap<PathBuf, Box<SlideLayout>>,
     pub slide_map: HashMap<PathBuf, Box<Slide>>,
+    pub notes_slide_map: HashMap<PathBuf, Box<NotesSlide>>,
+    pub diagram_data_map: HashMap<PathBuf, Box<DiagramData>>,
     pub slide_master_rels_map: HashMap<PathBuf, Vec<Relationship>>,
     pub slide_layout_rels_map: HashMap<PathBuf, Vec<Relationship>>,
     pub slide_rels_map: HashMap<PathBuf, Vec<Relationship>>,
+    pub notes_slide_rels_map: HashMap<PathBuf, Vec<Relationship>>,
+    pub presentation_rels: Vec<Relationship>,
     pub medias: Vec<PathBuf>,
+    /// The content of the parts that have been added to the document but aren't represented by the model, like
+    /// images and themes. They are written by `save` in place of the parts of the source package with the same path.
+    pub raw_part_map: HashMap<PathBuf, Vec<u8>>,
+    /// The content types of the parts of raw_part_map that can't be derived from their location or extension, like
+    /// charts or notes masters.
+    pub raw_part_content_types: HashMap<PathBuf, String>,
+    /// The paths of the parts of the source package that have been removed from the document but aren't represented
+    /// by the model, like charts. They are left out of the saved package along with their relationship parts.
+    pub removed_parts: HashSet<PathBuf>,
+    /// The paths of the parts that have been modified since the document was loaded. A modified part is serialized
+    /// along with its relationship part by `save_incremental`, so marking either one of them is enough.
+    pub modified_parts: HashSet<PathBuf>,
+    /// The relationships that reference resources outside of the package, like the urls of hyperlinks or linked
+    /// movies. They're collected from the target modes of the relationship parts of the source package, and get an
+    /// external target mode when they're saved.
+    pub external_relationships: ExternalRelationships,
 }
 
 impl PPTXDocument {
@@ -44,16 +131,32 @@ impl PPTXDocument {
         let mut slide_master_map = HashMap::new();
         let mut slide_layout_map = HashMap::new();
         let mut slide_map = HashMap::new();
+        let mut notes_slide_map = HashMap::new();
+        let mut diagram_data_map = HashMap::new();
         let mut slide_master_rels_map = HashMap::new();
         let mut slide_layout_rels_map = HashMap::new();
         let mut slide_rels_map = HashMap::new();
+        let mut notes_slide_rels_map = HashMap::new();
+        let mut presentation_rels = Vec::new();
         let mut medias = Vec::new();
+        let mut external_relationships = ExternalRelationships::default();
 
         for i in 0..zipper.len() {
             let mut zip_file = zipper.by_index(i)?;
 
             let file_path = PathBuf::from(zip_file.name());
-            if file_path.starts_with("ppt/theme") {
+            if file_path == Path::new(PRESENTATION_RELATIONSHIPS_PATH) {
+                info!("parsing presentation relationship file: {}", zip_file.name());
+                presentation_rels = relationships_from_zip_file(&mut zip_file, &mut external_relationships)?;
+            } else if file_path.extension().unwrap_or_default() == "rels"
+                && !MODEL_RELATIONSHIPS_DIRECTORIES
+                    .iter()
+                    .any(|directory| file_path.starts_with(directory))
+            {
+                // The relationship parts of the other parts are copied as they are, but their target modes are
+                // needed when the parts are copied to another document
+                relationships_from_zip_file(&mut zip_file, &mut external_relationships)?;
+            } else if file_path.starts_with("ppt/theme") {
                 info!("parsing theme file: {}", zip_file.name());
                 theme_map.insert(file_path, Box::new(OfficeStyleSheet::from_zip_file(&mut zip_file)?));
             } else if file_path.starts_with("ppt/slideMasters/_rels") {
@@ -64,7 +167,7 @@ impl PPTXDocument {
                 info!("parsing slide master relationship file: {}", zip_file.name());
                 slide_master_rels_map.insert(
                     file_path,
-                    msoffice_shared::relationship::relationships_from_zip_file(&mut zip_file)?,
+                    relationships_from_zip_file(&mut zip_file, &mut external_relationships)?,
                 );
             } else if file_path.starts_with("ppt/slideMasters") {
                 if file_path.extension().unwrap_or_default() != "xml" {
@@ -81,7 +184,7 @@ impl PPTXDocument {
                 info!("parsing slide layout relationship file: {}", zip_file.name());
                 slide_layout_rels_map.insert(
                     file_path,
-                    msoffice_shared::relationship::relationships_from_zip_file(&mut zip_file)?,
+                    relationships_from_zip_file(&mut zip_file, &mut external_relationships)?,
                 );
             } else if file_path.starts_with("ppt/slideLayouts") {
                 if file_path.extension().unwrap_or_default() != "xml" {
@@ -98,7 +201,7 @@ impl PPTXDocument {
                 info!("parsing slide relationship file: {}", zip_file.name());
                 slide_rels_map.insert(
                     file_path,
-                    msoffice_shared::relationship::relationships_from_zip_file(&mut zip_file)?,
+                    relationships_from_zip_file(&mut zip_file, &mut external_relationships)?,
                 );
             } else if file_path.starts_with("ppt/slides") {
                 if file_path.extension().unwrap_or_default() != "xml" {
@@ -107,6 +210,35 @@ impl PPTXDocument {
 
                 info!("parsing slide file: {}", zip_file.name());
                 slide_map.insert(file_path, Box::new(Slide::from_zip_file(&mut zip_file)?));
+            } else if file_path.starts_with("ppt/notesSlides/_rels") {
+                if file_path.extension().unwrap_or_default() != "rels" {
+                    continue;
+                }
+
+                info!("parsing notes slide relationship file: {}", zip_file.name());
+                notes_slide_rels_map.insert(
+                    file_path,
+                    relationships_from_zip_file(&mut zip_file, &mut external_relationships)?,
+                );
+            } else if file_path.starts_with("ppt/notesSlides") {
+                if file_path.extension().unwrap_or_default() != "xml" {
+                    continue;
+                }
+
+                info!("parsing notes slide file: {}", zip_file.name());
+                notes_slide_map.insert(file_path, Box::new(NotesSlide::from_zip_file(&mut zip_file)?));
+            } else if file_path.starts_with("ppt/diagrams") {
+                let is_data_part = file_path.extension().unwrap_or_default() == "xml"
+                    && file_path
+                        .file_name()
+                        .map(|file_name| file_name.to_string_lossy().starts_with("data"))
+                        .unwrap_or(false);
+                if !is_data_part {
+                    continue;
+                }
+
+                info!("parsing diagram data file: {}", zip_file.name());
+                diagram_data_map.insert(file_path, Box::new(DiagramData::from_zip_file(&mut zip_file)?));
             } else if file_path.starts_with("ppt/media") {
                 medias.push(file_path);
             }
@@ -121,28 +253,1235 @@ impl PPTXDocument {
             slide_master_map,
             slide_layout_map,
             slide_map,
+            notes_slide_map,
+            diagram_data_map,
             slide_master_rels_map,
             slide_layout_rels_map,
             slide_rels_map,
+            notes_slide_rels_map,
+            presentation_rels,
             medias,
+            raw_part_map: HashMap::new(),
+            raw_part_content_types: HashMap::new(),
+            removed_parts: HashSet::new(),
+            modified_parts: HashSet::new(),
+            external_relationships,
         })
     }
 
+    /// Writes the document as a pptx package to `writer` and returns the writer.
+    ///
+    /// The presentation, the slide masters, slide layouts, slides and notes slides are serialized from the model along
+    /// with their relationships, and the content types part is updated to list them. Themes and media are written as
+    /// long as they are part of the model, while every other part of the source package (document properties,
+    /// presentation properties, tags, etc.) is copied through byte-for-byte. The source package at `file_path` is read
+    /// while saving, so it can't be the destination of `writer`.
+    pub fn save<W: Write + Seek>(&self, writer: W) -> Result<W, Box<dyn ::std::error::Error>> {
+        self.write_package(writer, false)
+    }
+
+    /// Writes the document as a pptx package to `writer` like `save`, but only the parts listed in `modified_parts`
+    /// and the parts that are missing from the source package are serialized from the model. Every other part is
+    /// copied from the source package as is, without decompressing and compressing it again, which makes saving a
+    /// deck with lots of media much cheaper.
+    ///
+    /// Changes of the model that aren't tracked in `modified_parts` are not saved. Use the `*_mut` accessors or
+    /// `mark_modified` to track them.
+    pub fn save_incremental<W: Write + Seek>(&self, writer: W) -> Result<W, Box<dyn ::std::error::Error>> {
+        self.write_package(writer, true)
+    }
+
+    /// Marks the part at `part_path` as modified, so it's serialized by `save_incremental`.
+    pub fn mark_modified(&mut self, part_path: &Path) {
+        self.modified_parts.insert(PathBuf::from(part_path));
+    }
+
+    /// Returns whether the part at `part_path` or its relationship part has been marked as modified.
+    pub fn is_modified(&self, part_path: &Path) -> bool {
+        self.modified_parts.contains(part_path) || self.modified_parts.contains(&relationships_path(part_path))
+    }
+
+    /// Returns the presentation for modification and marks it as modified.
+    pub fn presentation_mut(&mut self) -> Option<&mut Presentation> {
+        self.modified_parts.insert(PathBuf::from(PRESENTATION_PATH));
+        self.presentation.as_deref_mut()
+    }
+
+    /// Returns the slide master at `part_path` for modification and marks it as modified.
+    pub fn slide_master_mut(&mut self, part_path: &Path) -> Option<&mut SlideMaster> {
+        self.modified_parts.insert(PathBuf::from(part_path));
+        self.slide_master_map
+            .get_mut(part_path)
+            .map(|slide_master| &mut **slide_master)
+    }
+
+    /// Returns the slide layout at `part_path` for modification and marks it as modified.
+    pub fn slide_layout_mut(&mut self, part_path: &Path) -> Option<&mut SlideLayout> {
+        self.modified_parts.insert(PathBuf::from(part_path));
+        self.slide_layout_map
+            .get_mut(part_path)
+            .map(|slide_layout| &mut **slide_layout)
+    }
+
+    /// Returns the slide at `part_path` for modification and marks it as modified.
+    pub fn slide_mut(&mut self, part_path: &Path) -> Option<&mut Slide> {
+        self.modified_parts.insert(PathBuf::from(part_path));
+        self.slide_map.get_mut(part_path).map(|slide| &mut **slide)
+    }
+
+    /// Returns the notes slide at `part_path` for modification and marks it as modified.
+    pub fn notes_slide_mut(&mut self, part_path: &Path) -> Option<&mut NotesSlide> {
+        self.modified_parts.insert(PathBuf::from(part_path));
+        self.notes_slide_map
+            .get_mut(part_path)
+            .map(|notes_slide| &mut **notes_slide)
+    }
+
+    /// Writes the package for `save` and `save_incremental`. If `incremental` is true, model parts that are present
+    /// in the source package and haven't been modified are copied instead of being serialized.
+    fn write_package<W: Write + Seek>(&self, writer: W, incremental: bool) -> Result<W, Box<dyn ::std::error::Error>> {
+        let mut source = if self.file_path.is_file() {
+            Some(ZipArchive::new(File::open(&self.file_path)?)?)
+        } else {
+            None
+        };
+
+        let mut content_types = match source.as_mut().map(|zipper| zipper.by_name(CONTENT_TYPES_PATH)) {
+            Some(Ok(mut zip_file)) => ContentTypes::from_zip_file(&mut zip_file)?,
+            _ => Default::default(),
+        };
+        content_types.add_default("rels", RELATIONSHIPS_CONTENT_TYPE);
+        content_types.add_default("xml", XML_CONTENT_TYPE);
+
+        let source_part_paths = source
+            .as_ref()
+            .map(|zipper| zipper.file_names().map(PathBuf::from).collect::<HashSet<_>>())
+            .unwrap_or_default();
+        let should_serialize = |part_path: &Path| {
+            !incremental || !source_part_paths.contains(part_path) || self.modified_parts.contains(part_path)
+        };
+        let mut parts = self.serialize_model_parts(&mut content_types, &should_serialize)?;
+        self.add_raw_parts(&mut content_types, &mut parts);
+
+        let mut copied_part_indices = Vec::new();
+        let mut written_part_paths = parts
+            .iter()
+            .map(|(part_path, _)| part_path.clone())
+            .collect::<HashSet<_>>();
+        if let Some(zipper) = &mut source {
+            for i in 0..zipper.len() {
+                let zip_file = zipper.by_index(i)?;
+                let file_path = PathBuf::from(zip_file.name());
+                if zip_file.is_dir()
+                    || file_path == Path::new(CONTENT_TYPES_PATH)
+                    || written_part_paths.contains(&file_path)
+                    || (self.is_model_part(&file_path) && !self.contains_model_part(&file_path))
+                    || !self.is_retained_part(&file_path)
+                    || self.is_removed_part(&file_path)
+                {
+                    continue;
+                }
+
+                copied_part_indices.push(i);
+                written_part_paths.insert(file_path);
+            }
+        }
+
+        // A document that doesn't come from a package still needs the relationship to its main part
+        if !written_part_paths.contains(Path::new(PACKAGE_RELATIONSHIPS_PATH)) {
+            let office_document = Relationship {
+                id: String::from("rId1"),
+                rel_type: String::from(OFFICE_DOCUMENT_RELATIONSHIP_TYPE),
+                target: String::from(PRESENTATION_PATH),
+            };
+            let mut data = Vec::new();
+            write_relationships(
+                &[office_document],
+                Path::new(PACKAGE_RELATIONSHIPS_PATH),
+                &self.external_relationships,
+                &mut data,
+            )?;
+            parts.push((PathBuf::from(PACKAGE_RELATIONSHIPS_PATH), data));
+            written_part_paths.insert(PathBuf::from(PACKAGE_RELATIONSHIPS_PATH));
+        }
+
+        content_types
+            .overrides
+            .retain(|content_type_override| written_part_paths.contains(&content_type_override.part_name));
+
+        let options = FileOptions::default();
+        let mut zip_writer = ZipWriter::new(writer);
+        zip_writer.start_file(CONTENT_TYPES_PATH, options)?;
+        content_types.to_xml(&mut zip_writer)?;
+
+        for (part_path, data) in &parts {
+            zip_writer.start_file(part_path.to_string_lossy(), options)?;
+            zip_writer.write_all(data)?;
+        }
+
+        // Copied parts are written with their original compression, without decompressing them
+        if let Some(zipper) = &mut source {
+            for i in copied_part_indices {
+                zip_writer.raw_copy_file(zipper.by_index(i)?)?;
+            }
+        }
+
+        Ok(zip_writer.finish()?)
+    }
+
+    /// Returns an iterator over the slides in the order they are listed in the slide id list of the presentation. See
+    /// ordered_slides.
     pub fn slides(&self) -> Slides {
-        Slides::new(&self.slide_map)
+        Slides::new(self)
+    }
+
+    /// Returns the path of the given slide within the package. The slide has to be borrowed from this document's
+    /// slide_map, as the lookup is done by identity.
+    pub fn slide_path(&self, slide: &Slide) -> Option<&PathBuf> {
+        self.slide_map
+            .iter()
+            .find(|(_, value)| std::ptr::eq(value.as_ref(), slide))
+            .map(|(path, _)| path)
+    }
+
+    /// Returns the slides in the order they are listed in the slide id list of the presentation, paired with their
+    /// paths. If the presentation part is missing, the slides are returned in the order of their file names.
+    pub fn ordered_slides(&self) -> Vec<(&PathBuf, &Slide)> {
+        let presentation = match &self.presentation {
+            Some(presentation) => presentation,
+            None => {
+                return (1..=self.slide_map.len())
+                    .filter_map(|page_num| {
+                        self.slide_map
+                            .get_key_value(&PathBuf::from(format!("ppt/slides/slide{}.xml", page_num)))
+                    })
+                    .map(|(path, slide)| (path, slide.as_ref()))
+                    .collect()
+            }
+        };
+
+        presentation
+            .slide_id_list
+            .iter()
+            .filter_map(|entry| self.relationship_target_path(Path::new(PRESENTATION_PATH), &entry.relationship_id))
+            .filter_map(|slide_path| self.slide_map.get_key_value(&slide_path))
+            .map(|(path, slide)| (path, slide.as_ref()))
+            .collect()
+    }
+
+    /// Returns the slide layouts in the order they are listed in the slide layout id lists of the slide masters, with
+    /// the slide masters in the order of the slide master id list of the presentation, paired with their paths. Slide
+    /// layouts that aren't listed by any slide master follow in the order of their paths.
+    pub fn ordered_slide_layouts(&self) -> Vec<(&PathBuf, &SlideLayout)> {
+        let presentation_path = Path::new(PRESENTATION_PATH);
+        let mut slide_master_paths = match &self.presentation {
+            Some(presentation) => presentation
+                .slide_master_id_list
+                .iter()
+                .filter_map(|entry| self.relationship_target_path(presentation_path, &entry.relationship_id))
+                .collect(),
+            None => Vec::new(),
+        };
+        let mut unlisted_slide_master_paths = self
+            .slide_master_map
+            .keys()
+            .filter(|slide_master_path| !slide_master_paths.contains(slide_master_path))
+            .cloned()
+            .collect::<Vec<_>>();
+        unlisted_slide_master_paths.sort();
+        slide_master_paths.extend(unlisted_slide_master_paths);
+
+        let mut slide_layouts: Vec<(&PathBuf, &SlideLayout)> = Vec::new();
+        for slide_master_path in &slide_master_paths {
+            let entries = self
+                .slide_master_map
+                .get(slide_master_path)
+                .and_then(|slide_master| slide_master.slide_layout_id_list.as_ref())
+                .map(|slide_layout_id_list| slide_layout_id_list.0.as_slice())
+                .unwrap_or_default();
+            for entry in entries {
+                let slide_layout = self
+                    .relationship_target_path(slide_master_path, &entry.relationship_id)
+                    .and_then(|slide_layout_path| self.slide_layout_map.get_key_value(&slide_layout_path));
+                if let Some((path, slide_layout)) = slide_layout {
+                    if !slide_layouts.iter().any(|(listed_path, _)| *listed_path == path) {
+                        slide_layouts.push((path, slide_layout.as_ref()));
+                    }
+                }
+            }
+        }
+
+        let mut unlisted_slide_layouts = self
+            .slide_layout_map
+            .iter()
+            .filter(|(path, _)| !slide_layouts.iter().any(|(listed_path, _)| listed_path == path))
+            .map(|(path, slide_layout)| (path, slide_layout.as_ref()))
+            .collect::<Vec<_>>();
+        unlisted_slide_layouts.sort_by_key(|(path, _)| *path);
+        slide_layouts.extend(unlisted_slide_layouts);
+        slide_layouts
+    }
+
+    /// Returns the path of the slide that's listed at the given position of the slide id list of the presentation.
+    pub fn slide_path_at(&self, position: usize) -> Option<PathBuf> {
+        let entry = self.presentation.as_ref()?.slide_id_list.get(position)?;
+        self.relationship_target_path(Path::new(PRESENTATION_PATH), &entry.relationship_id)
+    }
+
+    /// Returns the position of the slide at `slide_path` in the slide id list of the presentation.
+    pub fn slide_position(&self, slide_path: &Path) -> Option<usize> {
+        let slide_count = self.presentation.as_ref()?.slide_id_list.len();
+        (0..slide_count).find(|position| self.slide_path_at(*position).as_deref() == Some(slide_path))
+    }
+
+    /// Returns a slide id that isn't used by any slide of the presentation.
+    pub fn next_slide_id(&self) -> SlideId {
+        self.presentation
+            .iter()
+            .flat_map(|presentation| presentation.slide_id_list.iter())
+            .map(|entry| entry.id + 1)
+            .max()
+            .unwrap_or(MIN_SLIDE_ID)
+            .max(MIN_SLIDE_ID)
+    }
+
+    /// Returns the first path in `directory` named `<name><n>.<extension>` that isn't used by a part of the document,
+    /// e.g. ppt/slides/slide3.xml. Parts of the source package that aren't represented by the model, like charts, are
+    /// taken into account as well.
+    pub fn unused_part_path(&self, directory: &str, name: &str, extension: &str) -> PathBuf {
+        self.find_unused_part_path(&self.package_part_paths(), directory, name, extension)
+    }
+
+    /// Same as unused_part_path, with the parts of the source package collected beforehand by package_part_paths, so
+    /// finding several paths reads the package once.
+    pub(crate) fn find_unused_part_path(
+        &self,
+        package_part_paths: &HashSet<PathBuf>,
+        directory: &str,
+        name: &str,
+        extension: &str,
+    ) -> PathBuf {
+        (1..)
+            .map(|n| Path::new(directory).join(format!("{}{}.{}", name, n, extension)))
+            .find(|part_path| !self.contains_part(part_path) && !package_part_paths.contains(part_path))
+            .unwrap_or_default()
+    }
+
+    /// Returns the paths of the parts of the source package that aren't represented by the model. Removed parts are
+    /// included, so their paths aren't reused.
+    pub(crate) fn package_part_paths(&self) -> HashSet<PathBuf> {
+        let zipper = match File::open(&self.file_path).map(ZipArchive::new) {
+            Ok(Ok(zipper)) => zipper,
+            _ => return HashSet::new(),
+        };
+        zipper
+            .file_names()
+            .map(PathBuf::from)
+            .filter(|part_path| !self.is_model_part(part_path))
+            .collect()
+    }
+
+    /// Returns an unused path for a copy of the part at `part_path`, in the same directory and numbered like the part,
+    /// e.g. ppt/media/image3.png for ppt/media/image1.png. Parts that aren't numbered, like ppt/presProps.xml, keep
+    /// their path as long as it's unused.
+    pub fn unused_copy_path(&self, part_path: &Path) -> PathBuf {
+        self.find_unused_copy_path(&self.package_part_paths(), part_path)
+    }
+
+    /// Same as unused_copy_path, with the parts of the source package collected beforehand by package_part_paths.
+    pub(crate) fn find_unused_copy_path(&self, package_part_paths: &HashSet<PathBuf>, part_path: &Path) -> PathBuf {
+        let directory = part_path.parent().unwrap_or_else(|| Path::new("")).to_string_lossy();
+        let stem = part_path.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match stem.trim_end_matches(|c: char| c.is_ascii_digit()) {
+            "" => stem.as_ref(),
+            name if name == stem && !self.contains_part(part_path) && !package_part_paths.contains(part_path) => {
+                return PathBuf::from(part_path)
+            }
+            name => name,
+        };
+        let extension = part_path.extension().unwrap_or_default().to_string_lossy();
+        self.find_unused_part_path(package_part_paths, &directory, name, &extension)
+    }
+
+    /// Returns a slide master id that isn't used by the presentation. Slide master and slide layout ids share the same
+    /// range, so the ids of the slide layouts are taken into account as well.
+    pub fn next_slide_master_id(&self) -> SlideMasterId {
+        let slide_master_ids = self
+            .presentation
+            .iter()
+            .flat_map(|presentation| presentation.slide_master_id_list.iter())
+            .filter_map(|entry| entry.id);
+        let slide_layout_ids = self
+            .slide_master_map
+            .values()
+            .filter_map(|slide_master| slide_master.slide_layout_id_list.as_ref())
+            .flat_map(|slide_layout_id_list| slide_layout_id_list.0.iter())
+            .filter_map(|entry| entry.id);
+
+        slide_master_ids
+            .chain(slide_layout_ids)
+            .map(|id| id + 1)
+            .max()
+            .unwrap_or(MIN_SLIDE_MASTER_ID)
+            .max(MIN_SLIDE_MASTER_ID)
+    }
+
+    /// Copies the slide at `slide_path` along with its notes slide, and lists the copy right after the slide in the
+    /// slide id list and in its section. Parts that belong to the slide, like charts, diagrams and embedded objects,
+    /// are copied along with the parts they reference, while the parts shared with other slides, like the slide layout
+    /// and media, are shared by the copy as well. Returns the path of the copy.
+    pub(crate) fn copy_slide(&mut self, slide_path: &Path) -> Result<PathBuf, Box<dyn ::std::error::Error>> {
+        let slide = self
+            .slide_map
+            .get(slide_path)
+            .ok_or_else(|| format!("slide not found: {}", slide_path.display()))?
+            .clone();
+        let position = self
+            .slide_position(slide_path)
+            .ok_or_else(|| format!("slide isn't listed by the presentation: {}", slide_path.display()))?;
+
+        let package_part_paths = self.package_part_paths();
+        let copy_path = self.find_unused_part_path(&package_part_paths, "ppt/slides", "slide", "xml");
+        let mut copy_rels = self
+            .slide_rels_map
+            .get(&relationships_path(slide_path))
+            .cloned()
+            .unwrap_or_default();
+        let mut copied_parts = HashMap::new();
+        self.copy_slide_part_relationships(
+            slide_path,
+            &copy_path,
+            &mut copy_rels,
+            &package_part_paths,
+            &mut copied_parts,
+        )?;
+
+        let notes_slide = self.notes_slide_path(slide_path).and_then(|notes_slide_path| {
+            Some((self.notes_slide_map.get(&notes_slide_path)?.clone(), notes_slide_path))
+        });
+        if let Some((notes_slide, notes_slide_path)) = notes_slide {
+            let notes_copy_path =
+                self.find_unused_part_path(&package_part_paths, "ppt/notesSlides", "notesSlide", "xml");
+            let mut notes_copy_rels = self
+                .notes_slide_rels_map
+                .get(&relationships_path(&notes_slide_path))
+                .cloned()
+                .unwrap_or_default();
+            self.copy_slide_part_relationships(
+                &notes_slide_path,
+                &notes_copy_path,
+                &mut notes_copy_rels,
+                &package_part_paths,
+                &mut copied_parts,
+            )?;
+            for relationship in &mut notes_copy_rels {
+                if relationship.rel_type == SLIDE_RELATIONSHIP_TYPE {
+                    relationship.target = relative_relationship_target(&notes_copy_path, &copy_path);
+                }
+            }
+            for relationship in &mut copy_rels {
+                if relationship.rel_type == NOTES_SLIDE_RELATIONSHIP_TYPE {
+                    relationship.target = relative_relationship_target(&copy_path, &notes_copy_path);
+                }
+            }
+
+            self.notes_slide_rels_map
+                .insert(relationships_path(&notes_copy_path), notes_copy_rels);
+            self.notes_slide_map.insert(notes_copy_path, notes_slide);
+        }
+
+        let slide_relationship = Relationship {
+            id: next_relationship_id(&self.presentation_rels),
+            rel_type: String::from(SLIDE_RELATIONSHIP_TYPE),
+            target: relative_relationship_target(Path::new(PRESENTATION_PATH), &copy_path),
+        };
+        let slide_id = self.next_slide_id();
+        if let Some(presentation) = self.presentation_mut() {
+            let original_slide_id = presentation.slide_id_list[position].id;
+            presentation.slide_id_list.insert(
+                position + 1,
+                SlideIdListEntry {
+                    id: slide_id,
+                    relationship_id: slide_relationship.id.clone(),
+                    unknown_xml: Default::default(),
+                },
+            );
+            insert_section_slide_id(&mut presentation.unknown_xml, original_slide_id, slide_id);
+        }
+        self.presentation_rels.push(slide_relationship);
+
+        self.slide_rels_map.insert(relationships_path(&copy_path), copy_rels);
+        self.slide_map.insert(copy_path.clone(), slide);
+
+        Ok(copy_path)
+    }
+
+    /// Updates `relationships` of the part at `source_part` for its copy at `target_part`, copying the referenced parts
+    /// that belong to the slide with copy_slide_part.
+    fn copy_slide_part_relationships(
+        &mut self,
+        source_part: &Path,
+        target_part: &Path,
+        relationships: &mut [Relationship],
+        package_part_paths: &HashSet<PathBuf>,
+        copied_parts: &mut HashMap<PathBuf, PathBuf>,
+    ) -> Result<(), Box<dyn ::std::error::Error>> {
+        let target_rels_path = relationships_path(target_part);
+        for relationship in relationships {
+            if self.is_external_relationship(source_part, relationship) {
+                self.external_relationships.insert(&target_rels_path, &relationship.id);
+                continue;
+            }
+
+            let part_path = resolve_relationship_target(source_part, &relationship.target);
+            let target_path = self.copy_slide_part(&part_path, package_part_paths, copied_parts)?;
+            relationship.target = relative_relationship_target(target_part, &target_path);
+        }
+
+        Ok(())
+    }
+
+    /// Copies the part at `part_path` that belongs to a slide along with the parts it references, and returns the path
+    /// of the copy. Parts that can be shared are returned as they are: parts of the model, themes, media, parts of the
+    /// presentation itself like the notes master, and missing parts. `copied_parts` maps the parts that have been
+    /// copied to their copies, so parts referencing each other are copied once. `package_part_paths` are the parts of
+    /// the source package as returned by package_part_paths.
+    fn copy_slide_part(
+        &mut self,
+        part_path: &Path,
+        package_part_paths: &HashSet<PathBuf>,
+        copied_parts: &mut HashMap<PathBuf, PathBuf>,
+    ) -> Result<PathBuf, Box<dyn ::std::error::Error>> {
+        if let Some(copy_path) = copied_parts.get(part_path) {
+            return Ok(copy_path.clone());
+        }
+
+        let presentation_path = Path::new(PRESENTATION_PATH);
+        let is_shared = part_path == presentation_path
+            || self.contains_model_part(part_path)
+            || self.theme_map.contains_key(part_path)
+            || self.medias.iter().any(|media_path| media_path == part_path)
+            || self.presentation_rels.iter().any(|relationship| {
+                !self.is_external_relationship(presentation_path, relationship)
+                    && resolve_relationship_target(presentation_path, &relationship.target) == part_path
+            });
+        if is_shared {
+            return Ok(PathBuf::from(part_path));
+        }
+        let data = match self.read_existing_part(part_path)? {
+            Some(data) => data,
+            None => return Ok(PathBuf::from(part_path)),
+        };
+
+        let copy_path = self.find_unused_copy_path(package_part_paths, part_path);
+        copied_parts.insert(PathBuf::from(part_path), copy_path.clone());
+        if let Some(content_type) = self.part_content_type(part_path)? {
+            self.raw_part_content_types.insert(copy_path.clone(), content_type);
+        }
+        if let Some(diagram_data) = self.diagram_data_map.get(part_path).cloned() {
+            self.diagram_data_map.insert(copy_path.clone(), diagram_data);
+        }
+        self.raw_part_map.insert(copy_path.clone(), data);
+
+        let mut relationships = self.read_relationships(part_path)?;
+        self.copy_slide_part_relationships(
+            part_path,
+            &copy_path,
+            &mut relationships,
+            package_part_paths,
+            copied_parts,
+        )?;
+        if !relationships.is_empty() {
+            let mut data = Vec::new();
+            let rels_path = relationships_path(&copy_path);
+            write_relationships(&relationships, &rels_path, &self.external_relationships, &mut data)?;
+            self.raw_part_map.insert(rels_path, data);
+        }
+
+        Ok(copy_path)
+    }
+
+    /// Copies the media files of the package (ppt/media) into `output_dir`, keeping their file names. Returns the paths
+    /// of the written files.
+    pub fn extract_media(&self, output_dir: &Path) -> Result<Vec<PathBuf>, Box<dyn ::std::error::Error>> {
+        let pptx_file = File::open(&self.file_path)?;
+        let mut zipper = ZipArchive::new(&pptx_file)?;
+        fs::create_dir_all(output_dir)?;
+
+        let mut written_files = Vec::new();
+        for media_path in &self.medias {
+            let file_name = match media_path.file_name() {
+                Some(file_name) => file_name,
+                None => continue,
+            };
+
+            let mut zip_file = zipper.by_name(&media_path.to_string_lossy())?;
+            let output_path = output_dir.join(file_name);
+            io::copy(&mut zip_file, &mut File::create(&output_path)?)?;
+            written_files.push(output_path);
+        }
+
+        Ok(written_files)
+    }
+
+    /// Reads the raw content of the part at `part_path`, e.g. an image of ppt/media. Parts of raw_part_map take
+    /// precedence over the parts of the source package.
+    pub fn read_part(&self, part_path: &Path) -> Result<Vec<u8>, Box<dyn ::std::error::Error>> {
+        let data = self
+            .read_existing_part(part_path)?
+            .ok_or_else(|| format!("part not found: {}", part_path.display()))?;
+        Ok(data)
+    }
+
+    /// Same as read_part, but returns None if neither raw_part_map nor the source package has the part, and an error
+    /// only if the part can't be read.
+    pub(crate) fn read_existing_part(&self, part_path: &Path) -> Result<Option<Vec<u8>>, Box<dyn ::std::error::Error>> {
+        if let Some(data) = self.raw_part_map.get(part_path) {
+            return Ok(Some(data.clone()));
+        }
+        if !self.file_path.is_file() {
+            return Ok(None);
+        }
+
+        let pptx_file = File::open(&self.file_path)?;
+        let mut zipper = ZipArchive::new(&pptx_file)?;
+        let mut zip_file = match zipper.by_name(&part_path.to_string_lossy()) {
+            Ok(zip_file) => zip_file,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut data = Vec::with_capacity(zip_file.size() as usize);
+        io::copy(&mut zip_file, &mut data)?;
+        Ok(Some(data))
+    }
+
+    /// Returns the paths of the parts that are written by `save`, sorted by their paths. Relationship parts and the
+    /// content types part aren't included.
+    pub fn part_paths(&self) -> Result<Vec<PathBuf>, Box<dyn ::std::error::Error>> {
+        let mut part_paths = HashSet::new();
+        if self.file_path.is_file() {
+            let zipper = ZipArchive::new(File::open(&self.file_path)?)?;
+            part_paths.extend(
+                zipper
+                    .file_names()
+                    .filter(|file_name| !file_name.ends_with('/'))
+                    .map(PathBuf::from)
+                    .filter(|part_path| {
+                        !self.is_model_part(part_path)
+                            && self.is_retained_part(part_path)
+                            && !self.is_removed_part(part_path)
+                    }),
+            );
+        }
+
+        if self.presentation.is_some() {
+            part_paths.insert(PathBuf::from(PRESENTATION_PATH));
+        }
+        part_paths.extend(self.slide_master_map.keys().cloned());
+        part_paths.extend(self.slide_layout_map.keys().cloned());
+        part_paths.extend(self.slide_map.keys().cloned());
+        part_paths.extend(self.notes_slide_map.keys().cloned());
+        part_paths.extend(self.theme_map.keys().cloned());
+        part_paths.extend(self.medias.iter().cloned());
+        part_paths.extend(self.raw_part_map.keys().cloned());
+
+        let mut part_paths = part_paths
+            .into_iter()
+            .filter(|part_path| {
+                part_path != Path::new(CONTENT_TYPES_PATH) && !part_path.to_string_lossy().ends_with(".rels")
+            })
+            .collect::<Vec<_>>();
+        part_paths.sort();
+        Ok(part_paths)
+    }
+
+    /// Returns the relationships of the part at `part_path`. The relationships of parts that aren't represented by the
+    /// model, like charts or notes masters, are read from raw_part_map or the package. A part without a relationship
+    /// part has no relationships.
+    pub fn read_relationships(&self, part_path: &Path) -> Result<Vec<Relationship>, Box<dyn ::std::error::Error>> {
+        if let Some(relationships) = self.part_relationships(part_path) {
+            return Ok(relationships.clone());
+        }
+
+        let rels_path = relationships_path(part_path);
+        if let Some(data) = self.raw_part_map.get(&rels_path) {
+            let xml_node = XmlNode::from_str(::std::str::from_utf8(data)?)?;
+            return xml_node
+                .child_nodes
+                .iter()
+                .filter(|child_node| child_node.local_name() == "Relationship")
+                .map(Relationship::from_xml_element)
+                .collect();
+        }
+
+        if !self.file_path.is_file() {
+            return Ok(Vec::new());
+        }
+        let mut zipper = ZipArchive::new(File::open(&self.file_path)?)?;
+        let result = zipper.by_name(&rels_path.to_string_lossy());
+        match result {
+            Ok(mut zip_file) => relationship::relationships_from_zip_file(&mut zip_file),
+            Err(ZipError::FileNotFound) => Ok(Vec::new()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Returns the content type of the part at `part_path`, as specified by raw_part_content_types or by the content
+    /// types part of the package.
+    pub fn part_content_type(&self, part_path: &Path) -> Result<Option<String>, Box<dyn ::std::error::Error>> {
+        if let Some(content_type) = self.raw_part_content_types.get(part_path) {
+            return Ok(Some(content_type.clone()));
+        }
+
+        if !self.file_path.is_file() {
+            return Ok(None);
+        }
+        let mut zipper = ZipArchive::new(File::open(&self.file_path)?)?;
+        let content_types = ContentTypes::from_zip_file(&mut zipper.by_name(CONTENT_TYPES_PATH)?)?;
+        Ok(content_types.content_type(part_path).map(String::from))
+    }
+
+    /// Reads the part at `part_path` and returns it as a base64 encoded data URI. The media type is guessed from the
+    /// extension of the part.
+    pub fn part_data_uri(&self, part_path: &Path) -> Result<String, Box<dyn ::std::error::Error>> {
+        let data = self.read_part(part_path)?;
+        Ok(format!(
+            "data:{};base64,{}",
+            media_type(part_path),
+            encode_base64(&data)
+        ))
+    }
+
+    /// Returns the size of the slides in EMUs. If the presentation doesn't specify the slide size, the default size of
+    /// 10 by 7.5 inches is returned.
+    pub fn slide_size(&self) -> PositiveSize2D {
+        self.presentation
+            .as_ref()
+            .and_then(|presentation| presentation.slide_size.as_ref())
+            .map(|slide_size| PositiveSize2D::new(u64::from(slide_size.width), u64::from(slide_size.height)))
+            .unwrap_or_else(|| PositiveSize2D::new(9_144_000, 6_858_000))
+    }
+
+    /// Returns the relationships of the part at the given path, if the part is the presentation, a slide, slide
+    /// layout, slide master or notes slide.
+    pub fn part_relationships(&self, part_path: &Path) -> Option<&Vec<Relationship>> {
+        if part_path == Path::new(PRESENTATION_PATH) {
+            return Some(&self.presentation_rels);
+        }
+
+        let rels_path = relationships_path(part_path);
+        self.slide_rels_map
+            .get(&rels_path)
+            .or_else(|| self.slide_layout_rels_map.get(&rels_path))
+            .or_else(|| self.slide_master_rels_map.get(&rels_path))
+            .or_else(|| self.notes_slide_rels_map.get(&rels_path))
+    }
+
+    /// Returns the relationships of every part of the model along with the path of the part, starting with the
+    /// presentation.
+    pub fn all_part_relationships(&self) -> Vec<(PathBuf, &[Relationship])> {
+        let mut part_relationships = vec![(PathBuf::from(PRESENTATION_PATH), self.presentation_rels.as_slice())];
+        for rels_map in &[
+            &self.slide_master_rels_map,
+            &self.slide_layout_rels_map,
+            &self.slide_rels_map,
+            &self.notes_slide_rels_map,
+        ] {
+            part_relationships.extend(
+                rels_map
+                    .iter()
+                    .map(|(rels_path, relationships)| (relationships_source_path(rels_path), relationships.as_slice())),
+            );
+        }
+
+        part_relationships
+    }
+
+    /// Returns whether `relationship` of the part at `part_path` references a resource outside of the package, like
+    /// the url of a hyperlink.
+    pub fn is_external_relationship(&self, part_path: &Path, relationship: &Relationship) -> bool {
+        self.external_relationships
+            .contains(&relationships_path(part_path), &relationship.id)
+    }
+
+    /// Returns whether any part of the model references the part at `part_path`.
+    pub fn is_part_referenced(&self, part_path: &Path) -> bool {
+        self.all_part_relationships()
+            .iter()
+            .any(|(source_path, relationships)| {
+                relationships.iter().any(|relationship| {
+                    !self.is_external_relationship(source_path, relationship)
+                        && resolve_relationship_target(source_path, &relationship.target) == part_path
+                })
+            })
+    }
+
+    /// Resolves the path of the part that's referenced by the part at `part_path` with the given relationship id.
+    pub fn relationship_target_path(&self, part_path: &Path, relationship_id: &str) -> Option<PathBuf> {
+        self.part_relationships(part_path)?
+            .iter()
+            .find(|rel| rel.id == relationship_id)
+            .map(|rel| resolve_relationship_target(part_path, &rel.target))
+    }
+
+    /// Resolves the path of the first part that's referenced by the part at `part_path` with a relationship of the
+    /// given type.
+    pub fn related_part_path(&self, part_path: &Path, relationship_type: &str) -> Option<PathBuf> {
+        self.part_relationships(part_path)?
+            .iter()
+            .find(|rel| rel.rel_type == relationship_type)
+            .map(|rel| resolve_relationship_target(part_path, &rel.target))
+    }
+
+    /// Returns the path of the slide layout that the slide at `slide_path` follows.
+    pub fn slide_layout_path(&self, slide_path: &Path) -> Option<PathBuf> {
+        self.related_part_path(slide_path, SLIDE_LAYOUT_RELATIONSHIP_TYPE)
+    }
+
+    /// Returns the path of the slide master of the slide layout at `slide_layout_path`.
+    pub fn slide_master_path(&self, slide_layout_path: &Path) -> Option<PathBuf> {
+        self.related_part_path(slide_layout_path, SLIDE_MASTER_RELATIONSHIP_TYPE)
+    }
+
+    /// Returns the path of the notes slide that belongs to the slide at `slide_path`.
+    pub fn notes_slide_path(&self, slide_path: &Path) -> Option<PathBuf> {
+        self.related_part_path(slide_path, NOTES_SLIDE_RELATIONSHIP_TYPE)
+    }
+
+    /// Returns the notes slide of the given slide, if it has any.
+    pub fn notes_slide(&self, slide: &Slide) -> Option<&NotesSlide> {
+        let notes_slide_path = self.notes_slide_path(self.slide_path(slide)?)?;
+        self.notes_slide_map.get(&notes_slide_path).map(Box::as_ref)
+    }
+
+    /// Returns the data model of a diagram that's referenced by the part at `part_path`.
+    pub fn diagram_data(&self, part_path: &Path, relationship_ids: &DiagramRelationshipIds) -> Option<&DiagramData> {
+        let data_path = self.relationship_target_path(part_path, &relationship_ids.data)?;
+        self.diagram_data_map.get(&data_path).map(Box::as_ref)
+    }
+
+    /// Returns the path of the theme used by the slide master at `slide_master_path`.
+    pub fn theme_path(&self, slide_master_path: &Path) -> Option<PathBuf> {
+        self.related_part_path(slide_master_path, THEME_RELATIONSHIP_TYPE)
+    }
+
+    /// Resolves the background that is displayed for the given slide.
+    ///
+    /// The background is looked up on the slide first, then on its slide layout and finally on the slide master. If
+    /// the found background is a reference to the style matrix, the index is resolved into the fill style list
+    /// (1-999) or the background fill style list (1001 and above) of the master's theme. Indices 0 and 1000 mean no
+    /// background, in which case None is returned.
+    pub fn effective_background(&self, slide: &Slide) -> Option<EffectiveBackground> {
+        let slide_path = self.slide_path(slide)?;
+        let slide_layout_path = self.slide_layout_path(slide_path);
+        let slide_master_path = slide_layout_path
+            .as_ref()
+            .and_then(|layout_path| self.slide_master_path(layout_path));
+
+        let mut hierarchy: Vec<(PathBuf, &CommonSlideData)> = vec![(slide_path.clone(), &*slide.common_slide_data)];
+        if let Some(layout_path) = &slide_layout_path {
+            if let Some(slide_layout) = self.slide_layout_map.get(layout_path) {
+                hierarchy.push((layout_path.clone(), &*slide_layout.common_slide_data));
+            }
+        }
+        if let Some(master_path) = &slide_master_path {
+            if let Some(slide_master) = self.slide_master_map.get(master_path) {
+                hierarchy.push((master_path.clone(), &*slide_master.common_slide_data));
+            }
+        }
+
+        let (source_part, background) = hierarchy
+            .into_iter()
+            .find_map(|(path, common_slide_data)| common_slide_data.background.as_ref().map(|bg| (path, bg)))?;
+
+        match &background.background {
+            BackgroundGroup::Properties(properties) => Some(EffectiveBackground {
+                fill: properties.fill.clone(),
+                effect: properties.effect.clone(),
+                black_and_white_mode: background.black_and_white_mode,
+                source_part,
+            }),
+            BackgroundGroup::Reference(reference) => {
+                let theme_path = self.theme_path(slide_master_path.as_ref()?)?;
+                let format_scheme = &self.theme_map.get(&theme_path)?.theme_elements.format_scheme;
+                let index = reference.index as usize;
+                let mut fill = match index {
+                    1..=999 => format_scheme.fill_style_list.get(index - 1),
+                    _ if index > 1000 => format_scheme.bg_fill_style_list.get(index - 1001),
+                    _ => None,
+                }?
+                .clone();
+
+                if let Some(color) = &reference.color {
+                    replace_placeholder_color_in_fill(&mut fill, color);
+                }
+
+                Some(EffectiveBackground {
+                    fill,
+                    effect: None,
+                    black_and_white_mode: background.black_and_white_mode,
+                    source_part: theme_path,
+                })
+            }
+        }
+    }
+
+    /// Serializes the parts that are represented by the model, sorted by their paths, and sets their content types. A
+    /// part is serialized along with its relationship part if `should_serialize` accepts either of them.
+    fn serialize_model_parts(
+        &self,
+        content_types: &mut ContentTypes,
+        should_serialize: &dyn Fn(&Path) -> bool,
+    ) -> Result<Vec<SerializedPart>, Box<dyn ::std::error::Error>> {
+        let mut parts = Vec::new();
+
+        if let Some(presentation) = &self.presentation {
+            content_types.set_override(Path::new(PRESENTATION_PATH), PRESENTATION_CONTENT_TYPE);
+
+            if should_serialize(Path::new(PRESENTATION_PATH))
+                || should_serialize(Path::new(PRESENTATION_RELATIONSHIPS_PATH))
+            {
+                let mut data = Vec::new();
+                presentation.to_xml(&mut data)?;
+                parts.push((PathBuf::from(PRESENTATION_PATH), data));
+
+                let mut data = Vec::new();
+                write_relationships(
+                    &self.presentation_rels,
+                    Path::new(PRESENTATION_RELATIONSHIPS_PATH),
+                    &self.external_relationships,
+                    &mut data,
+                )?;
+                parts.push((PathBuf::from(PRESENTATION_RELATIONSHIPS_PATH), data));
+            }
+        }
+
+        serialize_parts(
+            &self.slide_master_map,
+            &self.slide_master_rels_map,
+            &self.external_relationships,
+            |slide_master, writer| slide_master.to_xml(writer),
+            SLIDE_MASTER_CONTENT_TYPE,
+            content_types,
+            should_serialize,
+            &mut parts,
+        )?;
+        serialize_parts(
+            &self.slide_layout_map,
+            &self.slide_layout_rels_map,
+            &self.external_relationships,
+            |slide_layout, writer| slide_layout.to_xml(writer),
+            SLIDE_LAYOUT_CONTENT_TYPE,
+            content_types,
+            should_serialize,
+            &mut parts,
+        )?;
+        serialize_parts(
+            &self.slide_map,
+            &self.slide_rels_map,
+            &self.external_relationships,
+            |slide, writer| slide.to_xml(writer),
+            SLIDE_CONTENT_TYPE,
+            content_types,
+            should_serialize,
+            &mut parts,
+        )?;
+        serialize_parts(
+            &self.notes_slide_map,
+            &self.notes_slide_rels_map,
+            &self.external_relationships,
+            |notes_slide, writer| notes_slide.to_xml(writer),
+            NOTES_SLIDE_CONTENT_TYPE,
+            content_types,
+            should_serialize,
+            &mut parts,
+        )?;
+
+        parts.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+        Ok(parts)
+    }
+
+    /// Adds the parts of raw_part_map that are still part of the document to `parts`, sorted by their paths, and sets
+    /// their content types.
+    fn add_raw_parts(&self, content_types: &mut ContentTypes, parts: &mut Vec<SerializedPart>) {
+        let mut raw_parts = self
+            .raw_part_map
+            .iter()
+            .filter(|(part_path, _)| self.is_retained_part(part_path))
+            .map(|(part_path, data)| (part_path.clone(), data.clone()))
+            .collect::<Vec<_>>();
+        raw_parts.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+        for (part_path, _) in &raw_parts {
+            if let Some(content_type) = self.raw_part_content_types.get(part_path) {
+                content_types.set_override(part_path, content_type);
+            } else if part_path.starts_with("ppt/theme") {
+                content_types.set_override(part_path, THEME_CONTENT_TYPE);
+            } else if let Some(extension) = part_path.extension() {
+                content_types.add_default(&extension.to_string_lossy(), media_type(part_path));
+            }
+        }
+
+        parts.extend(raw_parts);
+    }
+
+    /// Returns whether a part of the source package is written from the model by `save`. These are the same parts that
+    /// `from_file` parses into the presentation, the slide masters, slide layouts, slides, notes slides and their
+    /// relationships.
+    fn is_model_part(&self, part_path: &Path) -> bool {
+        if part_path == Path::new(PRESENTATION_PATH) || part_path == Path::new(PRESENTATION_RELATIONSHIPS_PATH) {
+            return self.presentation.is_some();
+        }
+
+        let is_xml_or_rels = match part_path.extension() {
+            Some(extension) => extension == "xml" || extension == "rels",
+            None => false,
+        };
+        is_xml_or_rels
+            && ["ppt/slideMasters", "ppt/slideLayouts", "ppt/slides", "ppt/notesSlides"]
+                .iter()
+                .any(|directory| part_path.starts_with(directory))
+    }
+
+    /// Returns whether a part that is written from the model by `save` is part of the model, so the part isn't removed
+    /// from the saved package.
+    fn contains_model_part(&self, part_path: &Path) -> bool {
+        if part_path == Path::new(PRESENTATION_PATH) || part_path == Path::new(PRESENTATION_RELATIONSHIPS_PATH) {
+            return self.presentation.is_some();
+        }
+
+        self.slide_master_map.contains_key(part_path)
+            || self.slide_layout_map.contains_key(part_path)
+            || self.slide_map.contains_key(part_path)
+            || self.notes_slide_map.contains_key(part_path)
+            || self.slide_master_rels_map.contains_key(part_path)
+            || self.slide_layout_rels_map.contains_key(part_path)
+            || self.slide_rels_map.contains_key(part_path)
+            || self.notes_slide_rels_map.contains_key(part_path)
+    }
+
+    /// Returns whether the document has a part at `part_path`, either in its model, as a theme, as a media file or as a
+    /// raw part.
+    fn contains_part(&self, part_path: &Path) -> bool {
+        self.contains_model_part(part_path)
+            || self.theme_map.contains_key(part_path)
+            || self.medias.iter().any(|media_path| media_path == part_path)
+            || self.raw_part_map.contains_key(part_path)
+    }
+
+    /// Returns whether the part at `part_path` or the part that owns the relationship part at `part_path` is listed by
+    /// removed_parts.
+    fn is_removed_part(&self, part_path: &Path) -> bool {
+        if part_path.to_string_lossy().ends_with(".rels") {
+            self.removed_parts.contains(&relationships_source_path(part_path))
+        } else {
+            self.removed_parts.contains(part_path)
+        }
+    }
+
+    /// Returns whether a part of the source package that isn't written from the model is still part of the document.
+    /// Themes and media that have been removed from the model are left out of the saved package.
+    fn is_retained_part(&self, part_path: &Path) -> bool {
+        if part_path.starts_with("ppt/theme") {
+            self.theme_map.contains_key(part_path)
+        } else if part_path.starts_with("ppt/media") {
+            self.medias.iter().any(|media_path| media_path == part_path)
+        } else {
+            true
+        }
+    }
+}
+
+/// Serializes the parts of `part_map` that are accepted by `should_serialize` with `to_xml`, along with their
+/// relationships from `rels_map`. The content type of every part is set, even if it isn't serialized.
+#[allow(clippy::too_many_arguments)]
+fn serialize_parts<T>(
+    part_map: &HashMap<PathBuf, Box<T>>,
+    rels_map: &HashMap<PathBuf, Vec<Relationship>>,
+    external_relationships: &ExternalRelationships,
+    to_xml: fn(&T, &mut Vec<u8>) -> xml::Result<()>,
+    content_type: &str,
+    content_types: &mut ContentTypes,
+    should_serialize: &dyn Fn(&Path) -> bool,
+    parts: &mut Vec<SerializedPart>,
+) -> Result<(), Box<dyn ::std::error::Error>> {
+    for (part_path, part) in part_map {
+        content_types.set_override(part_path, content_type);
+
+        let rels_path = relationships_path(part_path);
+        let relationships = rels_map.get(&rels_path);
+        let is_relationships_serialized = relationships.is_some() && should_serialize(&rels_path);
+        if !should_serialize(part_path) && !is_relationships_serialized {
+            continue;
+        }
+
+        let mut data = Vec::new();
+        to_xml(part, &mut data)?;
+        parts.push((part_path.clone(), data));
+
+        if let Some(relationships) = relationships {
+            let mut data = Vec::new();
+            write_relationships(relationships, &rels_path, external_relationships, &mut data)?;
+            parts.push((rels_path, data));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the path of the relationship part that belongs to the part at `part_path`.
+///
+/// # Example
+///
+/// `ppt/slides/slide1.xml` -> `ppt/slides/_rels/slide1.xml.rels`
+pub fn relationships_path(part_path: &Path) -> PathBuf {
+    let file_name = part_path.file_name().unwrap_or_default().to_string_lossy();
+    part_path
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join("_rels")
+        .join(format!("{}.rels", file_name))
+}
+
+/// Returns the path of the part that owns the relationship part at `rels_path`. This is the inverse of
+/// relationships_path.
+pub fn relationships_source_path(rels_path: &Path) -> PathBuf {
+    let file_name = rels_path.file_name().unwrap_or_default().to_string_lossy();
+    let directory = rels_path
+        .parent()
+        .and_then(Path::parent)
+        .unwrap_or_else(|| Path::new(""));
+    directory.join(file_name.trim_end_matches(".rels"))
+}
+
+/// Resolves the target of a relationship that is defined by the part at `source_part` into a path relative to the
+/// root of the package. Targets starting with '/' are already relative to the package root.
+pub fn resolve_relationship_target<T: AsRef<Path>>(source_part: &Path, target: T) -> PathBuf {
+    let target = target.as_ref();
+    let (mut components, relative_target): (Vec<Component>, &Path) = match target.strip_prefix("/") {
+        Ok(stripped) => (Vec::new(), stripped),
+        Err(_) => (
+            source_part
+                .parent()
+                .map(|parent| parent.components().collect())
+                .unwrap_or_default(),
+            target,
+        ),
+    };
+
+    for component in relative_target.components() {
+        match component {
+            Component::ParentDir => {
+                components.pop();
+            }
+            Component::Normal(_) => components.push(component),
+            _ => (),
+        }
     }
+
+    components.iter().collect()
+}
+
+/// Returns the target of a relationship from the part at `source_part` to the part at `target_part`, relative to the
+/// directory of the source part. This is the inverse of resolve_relationship_target.
+///
+/// # Example
+///
+/// `ppt/slides/slide1.xml`, `ppt/media/image1.png` -> `../media/image1.png`
+pub fn relative_relationship_target(source_part: &Path, target_part: &Path) -> String {
+    let source_directory = source_part
+        .parent()
+        .map(|parent| parent.components().collect::<Vec<_>>())
+        .unwrap_or_default();
+    let target_components = target_part.components().collect::<Vec<_>>();
+    let common_length = source_directory
+        .iter()
+        .zip(&target_components)
+        .take_while(|(lhs, rhs)| lhs == rhs)
+        .count();
+
+    let parent_segments = (common_length..source_directory.len()).map(|_| String::from(".."));
+    let target_segments = target_components[common_length..]
+        .iter()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned());
+    parent_segments.chain(target_segments).collect::<Vec<_>>().join("/")
 }
+
+fn replace_placeholder_color_in_fill(fill: &mut FillProperties, placeholder_color: &Color) {
+    match fill {
+        FillProperties::SolidFill(color) => replace_placeholder_color(color, placeholder_color),
+        FillProperties::GradientFill(gradient) => {
+            if let Some(stops) = &mut gradient.gradient_stop_list {
+                for stop in stops {
+                    replace_placeholder_color(&mut stop.color, placeholder_color);
+                }
+            }
+        }
+        FillProperties::PatternFill(pattern) => {
+            if let Some(color) = &mut pattern.fg_color {
+                replace_placeholder_color(color, placeholder_color);
+            }
+            if let Some(color) = &mut pattern.bg_color {
+                replace_placeholder_color(color, placeholder_color);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Replaces a phClr scheme color with `placeholder_color`. Color transforms specified on the placeholder are applied
+/// on top of the transforms of the substituted color.
+fn replace_placeholder_color(color: &mut Color, placeholder_color: &Color) {
+    let transforms = match color {
+        Color::SchemeColor(scheme_color) if scheme_color.value == SchemeColorVal::PlaceholderColor => {
+            std::mem::take(&mut scheme_color.color_transforms)
+        }
+        _ => return,
+    };
+
+    let mut resolved = placeholder_color.clone();
+    match &mut resolved {
+        Color::ScRgbColor(color) => color.color_transforms.extend(transforms),
+        Color::SRgbColor(color) => color.color_transforms.extend(transforms),
+        Color::HslColor(color) => color.color_transforms.extend(transforms),
+        Color::SystemColor(color) => color.color_transforms.extend(transforms),
+        Color::SchemeColor(color) => color.color_transforms.extend(transforms),
+        Color::PresetColor(color) => color.color_transforms.extend(transforms),
+    }
+
+    *color = resolved;
+}
+
+/// Returns the media type of a part based on its extension.
+fn media_type(part_path: &Path) -> &'static str {
+    let extension = part_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" | "jpe" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "tif" | "tiff" => "image/tiff",
+        "emf" => "image/emf",
+        "wmf" => "image/wmf",
+        "xml" => "application/xml",
+        _ => "application/octet-stream",
+    }
+}
+/// An iterator over the slides of a presentation in presentation order, as returned by PPTXDocument::ordered_slides.
 #[derive(Debug, Clone)]
 pub struct Slides<'a> {
-    slide_map: &'a HashMap<PathBuf, Box<Slide>>,
-    current_page_num: usize,
+    slides: ::std::vec::IntoIter<(&'a PathBuf, &'a Slide)>,
 }
 
 impl<'a> Slides<'a> {
-    pub fn new(slide_map: &'a HashMap<PathBuf, Box<Slide>>) -> Self {
+    pub fn new(document: &'a PPTXDocument) -> Self {
         Self {
-            slide_map,
-            current_page_num: 1,
+            slides: document.ordered_slides().into_iter(),
         }
     }
 }
@@ -151,16 +1490,49 @@ impl<'a> Iterator for Slides<'a> {
     type Item = &'a Slide;
 
     fn next(&mut self) -> Option<Self::Item> {
-        for i in self.current_page_num..=self.slide_map.len() {
-            let opt_slide = self.slide_map.get(&PathBuf::from(format!("ppt/slides/slide{}.xml", i)));
-            self.current_page_num += 1;
-            if let Some(slide) = opt_slide {
-                return Some(slide);
-            }
-        }
+        self.slides.next().map(|(_, slide)| slide)
+    }
+}
 
-        None
+/// A document that has been loaded from a temporary file, which is removed when the document is dropped. The file has
+/// to outlive the loading, since parts that aren't represented by the model are read from it on demand.
+#[cfg(test)]
+pub(crate) struct SavedDocument(PPTXDocument);
+
+#[cfg(test)]
+impl std::ops::Deref for SavedDocument {
+    type Target = PPTXDocument;
+
+    fn deref(&self) -> &PPTXDocument {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for SavedDocument {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0.file_path);
+    }
+}
+
+/// Loads a saved package from `data` through a temporary file with a name that's unique among the tests.
+#[cfg(test)]
+pub(crate) fn load_saved_document(data: &[u8]) -> SavedDocument {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static FILE_COUNT: AtomicUsize = AtomicUsize::new(0);
+    let file_name = format!(
+        "msoffice_pptx_test_{}_{}.pptx",
+        std::process::id(),
+        FILE_COUNT.fetch_add(1, Ordering::Relaxed)
+    );
+    let saved_pptx_path = std::env::temp_dir().join(file_name);
+    fs::write(&saved_pptx_path, data).unwrap();
+    let document = PPTXDocument::from_file(&saved_pptx_path);
+    if document.is_err() {
+        let _ = fs::remove_file(&saved_pptx_path);
     }
+    SavedDocument(document.unwrap())
 }
 
 #[cfg(test)]
@@ -199,3 +1571,350 @@ fn test_sample_pptx() {
 
     assert_eq!(slides.next().is_none(), true);
 }
+
+#[cfg(test)]
+#[test]
+fn test_effective_background() {
+    let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sample_pptx_path = test_dir.join("tests/samplepptx.pptx");
+
+    let document = PPTXDocument::from_file(&sample_pptx_path).unwrap();
+    let mut slides = document.slides();
+
+    // The first slide inherits the background of its slide layout
+    let background = document.effective_background(slides.next().unwrap()).unwrap();
+    assert_eq!(
+        background.source_part,
+        PathBuf::from("ppt/slideLayouts/slideLayout1.xml")
+    );
+    match background.fill {
+        FillProperties::BlipFill(_) => (),
+        _ => panic!("expected a blip fill"),
+    }
+
+    // The second slide's layout has no background, so the slide master's background is used
+    let background = document.effective_background(slides.next().unwrap()).unwrap();
+    assert_eq!(
+        background.source_part,
+        PathBuf::from("ppt/slideMasters/slideMaster1.xml")
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_resolve_relationship_target() {
+    assert_eq!(
+        resolve_relationship_target(Path::new("ppt/slides/slide1.xml"), "../slideLayouts/slideLayout1.xml"),
+        PathBuf::from("ppt/slideLayouts/slideLayout1.xml"),
+    );
+    assert_eq!(
+        resolve_relationship_target(Path::new("ppt/presentation.xml"), "slides/slide1.xml"),
+        PathBuf::from("ppt/slides/slide1.xml"),
+    );
+    assert_eq!(
+        resolve_relationship_target(Path::new("ppt/slides/slide1.xml"), "/ppt/media/image1.png"),
+        PathBuf::from("ppt/media/image1.png"),
+    );
+    assert_eq!(
+        relationships_path(Path::new("ppt/slides/slide1.xml")),
+        PathBuf::from("ppt/slides/_rels/slide1.xml.rels"),
+    );
+    assert_eq!(
+        relative_relationship_target(Path::new("ppt/slides/slide1.xml"), Path::new("ppt/media/image1.png")),
+        "../media/image1.png",
+    );
+    assert_eq!(
+        relative_relationship_target(Path::new("ppt/presentation.xml"), Path::new("ppt/slides/slide1.xml")),
+        "slides/slide1.xml",
+    );
+    assert_eq!(
+        relationships_source_path(&relationships_path(Path::new("ppt/slides/slide1.xml"))),
+        Path::new("ppt/slides/slide1.xml"),
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_round_trip_sample_pptx() {
+    use crate::xml::parse_part_xml;
+
+    let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sample_pptx_path = test_dir.join("tests/samplepptx.pptx");
+
+    let document = PPTXDocument::from_file(&sample_pptx_path).unwrap();
+
+    let presentation = document.presentation.as_ref().unwrap();
+    let mut buffer = Vec::new();
+    presentation.to_xml(&mut buffer).unwrap();
+    let xml_node = parse_part_xml(::std::str::from_utf8(&buffer).unwrap()).unwrap();
+    assert_eq!(Presentation::from_xml_element(&xml_node).unwrap(), **presentation);
+
+    for slide_master in document.slide_master_map.values() {
+        let mut buffer = Vec::new();
+        slide_master.to_xml(&mut buffer).unwrap();
+        let xml_node = parse_part_xml(::std::str::from_utf8(&buffer).unwrap()).unwrap();
+        assert_eq!(SlideMaster::from_xml_element(&xml_node).unwrap(), **slide_master);
+    }
+
+    for slide_layout in document.slide_layout_map.values() {
+        let mut buffer = Vec::new();
+        slide_layout.to_xml(&mut buffer).unwrap();
+        let xml_node = parse_part_xml(::std::str::from_utf8(&buffer).unwrap()).unwrap();
+        assert_eq!(SlideLayout::from_xml_element(&xml_node).unwrap(), **slide_layout);
+    }
+
+    for slide in document.slide_map.values() {
+        let mut buffer = Vec::new();
+        slide.to_xml(&mut buffer).unwrap();
+        let xml_node = parse_part_xml(::std::str::from_utf8(&buffer).unwrap()).unwrap();
+        assert_eq!(Slide::from_xml_element(&xml_node).unwrap(), **slide);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_save() {
+    use crate::opc::ContentTypes;
+    use std::io::Cursor;
+
+    let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sample_pptx_path = test_dir.join("tests/samplepptx.pptx");
+
+    let document = PPTXDocument::from_file(&sample_pptx_path).unwrap();
+    let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+
+    let saved_document = load_saved_document(&data);
+
+    assert_eq!(saved_document.presentation, document.presentation);
+    assert_eq!(saved_document.theme_map, document.theme_map);
+    assert_eq!(saved_document.slide_master_map, document.slide_master_map);
+    assert_eq!(saved_document.slide_layout_map, document.slide_layout_map);
+    assert_eq!(saved_document.slide_map, document.slide_map);
+    assert_eq!(saved_document.slide_master_rels_map, document.slide_master_rels_map);
+    assert_eq!(saved_document.slide_layout_rels_map, document.slide_layout_rels_map);
+    assert_eq!(saved_document.slide_rels_map, document.slide_rels_map);
+    assert_eq!(saved_document.presentation_rels, document.presentation_rels);
+
+    let mut saved_medias = saved_document.medias.clone();
+    let mut medias = document.medias.clone();
+    saved_medias.sort();
+    medias.sort();
+    assert_eq!(saved_medias, medias);
+
+    // Parts that aren't represented by the model are copied through
+    for part_path in &[
+        "ppt/tags/tag1.xml",
+        "ppt/viewProps.xml",
+        "docProps/thumbnail.jpeg",
+        "_rels/.rels",
+    ] {
+        assert_eq!(
+            saved_document.read_part(Path::new(part_path)).unwrap(),
+            document.read_part(Path::new(part_path)).unwrap()
+        );
+    }
+
+    let mut zipper = ZipArchive::new(Cursor::new(data)).unwrap();
+    let content_types = ContentTypes::from_zip_file(&mut zipper.by_name("[Content_Types].xml").unwrap()).unwrap();
+    assert_eq!(
+        content_types.content_type(Path::new("ppt/slides/slide2.xml")),
+        Some("application/vnd.openxmlformats-officedocument.presentationml.slide+xml")
+    );
+    assert_eq!(
+        content_types.content_type(Path::new("ppt/media/image1.jpeg")),
+        Some("image/jpeg")
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_unknown_xml_round_trip() {
+    use msoffice_shared::xml::XmlNode;
+    use std::str::FromStr;
+
+    let xml = r#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+        xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"
+        xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+        xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006"
+        xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main"
+        mc:Ignorable="p14" p14:unknownAttr="1">
+        <p:cSld>
+            <p:spTree>
+                <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                <p:grpSpPr/>
+                <p:sp>
+                    <p:nvSpPr>
+                        <p:cNvPr id="2" name="Title 1"/>
+                        <p:cNvSpPr/>
+                        <p:nvPr>
+                            <p:ph type="title"/>
+                            <p:extLst><p:ext uri="{DCECCB84-F9BA-43D5-87BE-67443E8EF086}"/></p:extLst>
+                        </p:nvPr>
+                    </p:nvSpPr>
+                    <p:spPr/>
+                </p:sp>
+                <p:graphicFrame>
+                    <p:nvGraphicFramePr><p:cNvPr id="3" name="Chart 2"/><p:cNvGraphicFramePr/><p:nvPr/></p:nvGraphicFramePr>
+                    <p:xfrm><a:off x="0" y="0"/><a:ext cx="100" cy="100"/></p:xfrm>
+                    <a:graphic>
+                        <a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/chart">
+                            <c:chart xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" r:id="rId2"/>
+                        </a:graphicData>
+                    </a:graphic>
+                </p:graphicFrame>
+            </p:spTree>
+        </p:cSld>
+        <p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr>
+        <mc:AlternateContent>
+            <mc:Choice Requires="p14"><p:transition spd="slow" p14:dur="2000"><p14:prism/></p:transition></mc:Choice>
+            <mc:Fallback><p:transition spd="slow"><p:fade/></p:transition></mc:Fallback>
+        </mc:AlternateContent>
+        <p:extLst><p:ext uri="{BB962C8B-B14F-4D97-AF65-F5344CB8AC3E}"><p14:creationId val="1"/></p:ext></p:extLst>
+    </p:sld>"#;
+    let slide = Slide::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+    let mut buffer = Vec::new();
+    slide.to_xml(&mut buffer).unwrap();
+    let written_xml = String::from_utf8(buffer).unwrap();
+    assert!(written_xml.contains("xmlns:mc=\"http://schemas.openxmlformats.org/markup-compatibility/2006\""));
+    assert!(written_xml.contains("xmlns:p14=\"http://schemas.microsoft.com/office/powerpoint/2010/main\""));
+    assert!(written_xml.contains("xmlns:c=\"http://schemas.openxmlformats.org/drawingml/2006/chart\""));
+
+    let xml_node = XmlNode::from_str(&written_xml).unwrap();
+    let child_names = xml_node
+        .child_nodes
+        .iter()
+        .map(|child_node| child_node.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        child_names,
+        vec!["p:cSld", "p:clrMapOvr", "mc:AlternateContent", "p:extLst"]
+    );
+    assert_eq!(xml_node.attributes.get("mc:Ignorable").map(String::as_str), Some("p14"));
+    assert_eq!(Slide::from_xml_element(&xml_node).unwrap(), slide);
+}
+
+#[cfg(test)]
+#[test]
+fn test_presentation_unknown_xml_round_trip() {
+    use crate::xml::parse_part_xml;
+
+    let xml = r#"<p:presentation xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+        xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"
+        xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+        xmlns:x="urn:example">
+        <p:sldMasterIdLst><p:sldMasterId id="2147483648" r:id="rId1"/></p:sldMasterIdLst>
+        <p:sldIdLst><p:sldId id="256" r:id="rId2"/></p:sldIdLst>
+        <p:sldSz cx="9144000" cy="6858000" x:unknownAttr="1"/>
+        <p:notesSz cx="6858000" cy="9144000"/>
+        <p:custShowLst>
+            <p:custShow name="Short" id="0">
+                <p:sldLst><p:sld r:id="rId2"/></p:sldLst>
+                <p:extLst><p:ext uri="{1}"><x:data/></p:ext></p:extLst>
+            </p:custShow>
+        </p:custShowLst>
+        <p:photoAlbum bw="1"><p:extLst><p:ext uri="{2}"><x:data/></p:ext></p:extLst></p:photoAlbum>
+        <p:kinsoku invalStChars="a" invalEndChars="b" x:unknownAttr="1"/>
+    </p:presentation>"#;
+    let presentation = Presentation::from_xml_element(&parse_part_xml(xml).unwrap()).unwrap();
+    assert_eq!(presentation.custom_show_list[0].unknown_xml.child_nodes.len(), 1);
+    assert_eq!(
+        presentation.photo_album.as_ref().unwrap().unknown_xml.child_nodes.len(),
+        1
+    );
+
+    let mut buffer = Vec::new();
+    presentation.to_xml(&mut buffer).unwrap();
+    let written_xml = String::from_utf8(buffer).unwrap();
+    assert!(written_xml.contains("xmlns:x=\"urn:example\""));
+    assert!(written_xml.contains("<p:sldSz cx=\"9144000\" cy=\"6858000\" x:unknownAttr=\"1\"/>"));
+
+    let xml_node = parse_part_xml(&written_xml).unwrap();
+    assert_eq!(Presentation::from_xml_element(&xml_node).unwrap(), presentation);
+}
+
+#[cfg(test)]
+#[test]
+fn test_drawingml_unknown_xml_round_trip() {
+    use crate::xml::parse_part_xml;
+
+    let xml = r#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+        xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"
+        xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+        xmlns:x="urn:example">
+        <p:cSld>
+            <p:spTree>
+                <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                <p:grpSpPr/>
+                <p:sp>
+                    <p:nvSpPr>
+                        <p:cNvPr id="2" name="Title 1" x:unknownAttr="1">
+                            <a:extLst><a:ext uri="{1}"><x:drawing/></a:ext></a:extLst>
+                        </p:cNvPr>
+                        <p:cNvSpPr/>
+                        <p:nvPr/>
+                    </p:nvSpPr>
+                    <p:spPr>
+                        <a:xfrm><a:off x="0" y="0"/><a:ext cx="100" cy="100"/></a:xfrm>
+                        <a:extLst><a:ext uri="{2}"><x:shape/></a:ext></a:extLst>
+                    </p:spPr>
+                    <p:txBody>
+                        <a:bodyPr/>
+                        <a:p>
+                            <a:r>
+                                <a:rPr lang="en-US"><a:extLst><a:ext uri="{3}"><x:run/></a:ext></a:extLst></a:rPr>
+                                <a:t>Title</a:t>
+                            </a:r>
+                        </a:p>
+                    </p:txBody>
+                </p:sp>
+            </p:spTree>
+        </p:cSld>
+    </p:sld>"#;
+    let slide = Slide::from_xml_element(&parse_part_xml(xml).unwrap()).unwrap();
+
+    let mut buffer = Vec::new();
+    slide.to_xml(&mut buffer).unwrap();
+    let written_xml = String::from_utf8(buffer).unwrap();
+    assert!(written_xml.contains("x:unknownAttr=\"1\""));
+    assert!(written_xml.contains("<a:ext uri=\"{1}\"><x:drawing/></a:ext>"));
+    assert!(written_xml.contains("<a:ext uri=\"{2}\"><x:shape/></a:ext>"));
+    assert!(written_xml.contains("<a:ext uri=\"{3}\"><x:run/></a:ext>"));
+
+    let xml_node = parse_part_xml(&written_xml).unwrap();
+    assert_eq!(Slide::from_xml_element(&xml_node).unwrap(), slide);
+}
+
+#[cfg(test)]
+#[test]
+fn test_save_incremental() {
+    use std::io::Cursor;
+
+    let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sample_pptx_path = test_dir.join("tests/samplepptx.pptx");
+
+    let mut document = PPTXDocument::from_file(&sample_pptx_path).unwrap();
+    let modified_slide_path = Path::new("ppt/slides/slide1.xml");
+    let unmodified_slide_path = Path::new("ppt/slides/slide2.xml");
+    document.slide_mut(modified_slide_path).unwrap().show = Some(false);
+    assert!(document.is_modified(modified_slide_path));
+    assert!(!document.is_modified(unmodified_slide_path));
+
+    let data = document.save_incremental(Cursor::new(Vec::new())).unwrap().into_inner();
+
+    let saved_document = load_saved_document(&data);
+    assert_eq!(saved_document.slide_map, document.slide_map);
+    assert_eq!(saved_document.slide_rels_map, document.slide_rels_map);
+    assert_eq!(saved_document.presentation, document.presentation);
+
+    // Unmodified parts are copied with their original compression
+    let mut source_zipper = ZipArchive::new(File::open(&sample_pptx_path).unwrap()).unwrap();
+    let mut saved_zipper = ZipArchive::new(Cursor::new(data)).unwrap();
+    for part_path in &["ppt/slides/slide2.xml", "ppt/presentation.xml", "ppt/media/image1.jpeg"] {
+        let source_file = source_zipper.by_name(part_path).unwrap();
+        let saved_file = saved_zipper.by_name(part_path).unwrap();
+        assert_eq!(saved_file.compression(), source_file.compression());
+        assert_eq!(saved_file.compressed_size(), source_file.compressed_size());
+        assert_eq!(saved_file.crc32(), source_file.crc32());
+    }
+}
@@ -0,0 +1,411 @@
+use msoffice_shared::drawingml::{
+    colors::{Color, ColorTransform},
+    sharedstylesheet::{ColorMapping, ColorScheme},
+    simpletypes::{ColorSchemeIndex, PresetColorVal, SchemeColorVal, SystemColorVal},
+};
+
+/// A color in the sRGB color space with an alpha channel. This is the result of resolving a DrawingML color against a
+/// color scheme and applying its color transforms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RgbaColor {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    /// The opacity of the color, 0 being fully transparent and 255 being fully opaque.
+    pub alpha: u8,
+}
+
+impl RgbaColor {
+    pub fn new(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Creates an opaque color from a 24 bit RRGGBB value.
+    pub fn from_rgb(value: u32) -> Self {
+        Self::new((value >> 16) as u8, (value >> 8) as u8, value as u8, 255)
+    }
+
+    /// Returns the color as a hexadecimal #rrggbb string, ignoring the alpha channel.
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+
+    /// Returns the opacity of the color in the range of 0.0 to 1.0.
+    pub fn opacity(&self) -> f64 {
+        f64::from(self.alpha) / 255.0
+    }
+
+    /// Returns the color as a CSS color value. Opaque colors are written as #rrggbb, translucent colors are written as
+    /// rgba().
+    pub fn to_css(&self) -> String {
+        if self.alpha == 255 {
+            self.to_hex()
+        } else {
+            format!(
+                "rgba({}, {}, {}, {:.3})",
+                self.red,
+                self.green,
+                self.blue,
+                self.opacity()
+            )
+        }
+    }
+}
+
+/// Returns the color of the color scheme that belongs to the given index.
+pub fn scheme_color(color_scheme: &ColorScheme, index: ColorSchemeIndex) -> &Color {
+    match index {
+        ColorSchemeIndex::Dark1 => &color_scheme.dark1,
+        ColorSchemeIndex::Light1 => &color_scheme.light1,
+        ColorSchemeIndex::Dark2 => &color_scheme.dark2,
+        ColorSchemeIndex::Light2 => &color_scheme.light2,
+        ColorSchemeIndex::Accent1 => &color_scheme.accent1,
+        ColorSchemeIndex::Accent2 => &color_scheme.accent2,
+        ColorSchemeIndex::Accent3 => &color_scheme.accent3,
+        ColorSchemeIndex::Accent4 => &color_scheme.accent4,
+        ColorSchemeIndex::Accent5 => &color_scheme.accent5,
+        ColorSchemeIndex::Accent6 => &color_scheme.accent6,
+        ColorSchemeIndex::Hyperlink => &color_scheme.hyperlink,
+        ColorSchemeIndex::FollowedHyperlink => &color_scheme.followed_hyperlink,
+    }
+}
+
+/// Maps a scheme color value to an index of the color scheme. The background and text values are mapped through the
+/// color mapping. If no color mapping is given, the default mapping (bg1 = lt1, tx1 = dk1, bg2 = lt2, tx2 = dk2) is
+/// used. Returns None for the placeholder color.
+pub fn scheme_color_index(value: SchemeColorVal, color_mapping: Option<&ColorMapping>) -> Option<ColorSchemeIndex> {
+    let index = match value {
+        SchemeColorVal::Background1 => color_mapping.map_or(ColorSchemeIndex::Light1, |mapping| mapping.background1),
+        SchemeColorVal::Text1 => color_mapping.map_or(ColorSchemeIndex::Dark1, |mapping| mapping.text1),
+        SchemeColorVal::Background2 => color_mapping.map_or(ColorSchemeIndex::Light2, |mapping| mapping.background2),
+        SchemeColorVal::Text2 => color_mapping.map_or(ColorSchemeIndex::Dark2, |mapping| mapping.text2),
+        SchemeColorVal::Accent1 => ColorSchemeIndex::Accent1,
+        SchemeColorVal::Accent2 => ColorSchemeIndex::Accent2,
+        SchemeColorVal::Accent3 => ColorSchemeIndex::Accent3,
+        SchemeColorVal::Accent4 => ColorSchemeIndex::Accent4,
+        SchemeColorVal::Accent5 => ColorSchemeIndex::Accent5,
+        SchemeColorVal::Accent6 => ColorSchemeIndex::Accent6,
+        SchemeColorVal::Hyperlink => ColorSchemeIndex::Hyperlink,
+        SchemeColorVal::FollowedHyperlink => ColorSchemeIndex::FollowedHyperlink,
+        SchemeColorVal::Dark1 => ColorSchemeIndex::Dark1,
+        SchemeColorVal::Light1 => ColorSchemeIndex::Light1,
+        SchemeColorVal::Dark2 => ColorSchemeIndex::Dark2,
+        SchemeColorVal::Light2 => ColorSchemeIndex::Light2,
+        SchemeColorVal::PlaceholderColor => return None,
+    };
+
+    Some(index)
+}
+
+/// Resolves a color into an sRGB color and applies its color transforms.
+///
+/// Scheme colors are looked up in `color_scheme` after being mapped through `color_mapping`. The placeholder color
+/// (phClr) is replaced by `placeholder_color`, in which case the transforms of the placeholder color are applied
+/// before the transforms of `color`. Returns None if the color can't be resolved, e.g. if it's a placeholder color
+/// and no `placeholder_color` is given.
+pub fn resolve_color(
+    color: &Color,
+    color_scheme: Option<&ColorScheme>,
+    color_mapping: Option<&ColorMapping>,
+    placeholder_color: Option<&Color>,
+) -> Option<RgbaColor> {
+    let (base, transforms) = match color {
+        Color::SRgbColor(color) => (RgbaColor::from_rgb(color.value), &color.color_transforms),
+        Color::ScRgbColor(color) => (
+            RgbaColor::new(
+                linear_to_srgb(color.r),
+                linear_to_srgb(color.g),
+                linear_to_srgb(color.b),
+                255,
+            ),
+            &color.color_transforms,
+        ),
+        Color::HslColor(color) => {
+            let (red, green, blue) = hsl_to_rgb(
+                f64::from(color.hue) / 60000.0,
+                f64::from(color.saturation) / 100000.0,
+                f64::from(color.luminance) / 100000.0,
+            );
+            (
+                RgbaColor::new(to_byte(red), to_byte(green), to_byte(blue), 255),
+                &color.color_transforms,
+            )
+        }
+        Color::SystemColor(color) => {
+            let base = match color
+                .last_color
+                .as_ref()
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            {
+                Some(value) => RgbaColor::from_rgb(value),
+                None => system_color(color.value)?,
+            };
+            (base, &color.color_transforms)
+        }
+        Color::PresetColor(color) => (preset_color(color.value)?, &color.color_transforms),
+        Color::SchemeColor(color) => {
+            let base = match scheme_color_index(color.value, color_mapping) {
+                // Colors of the color scheme can't reference the scheme again, so no mapping is needed
+                Some(index) => resolve_color(scheme_color(color_scheme?, index), None, None, None)?,
+                None => resolve_color(placeholder_color?, color_scheme, color_mapping, None)?,
+            };
+            (base, &color.color_transforms)
+        }
+    };
+
+    Some(apply_color_transforms(base, transforms))
+}
+
+/// Applies the color transforms in order to the given color.
+pub fn apply_color_transforms(color: RgbaColor, transforms: &[ColorTransform]) -> RgbaColor {
+    let mut red = f64::from(color.red) / 255.0;
+    let mut green = f64::from(color.green) / 255.0;
+    let mut blue = f64::from(color.blue) / 255.0;
+    let mut alpha = f64::from(color.alpha) / 255.0;
+
+    let percentage = |value: f32| f64::from(value) / 100000.0;
+    for transform in transforms {
+        let (mut hue, mut saturation, mut luminance) = rgb_to_hsl(red, green, blue);
+        let mut hsl_changed = false;
+        match transform {
+            ColorTransform::Tint(value) => {
+                let tint = percentage(*value);
+                red = 1.0 - (1.0 - red) * tint;
+                green = 1.0 - (1.0 - green) * tint;
+                blue = 1.0 - (1.0 - blue) * tint;
+            }
+            ColorTransform::Shade(value) => {
+                let shade = percentage(*value);
+                red *= shade;
+                green *= shade;
+                blue *= shade;
+            }
+            ColorTransform::Complement => {
+                hue = (hue + 180.0) % 360.0;
+                hsl_changed = true;
+            }
+            ColorTransform::Inverse => {
+                red = 1.0 - red;
+                green = 1.0 - green;
+                blue = 1.0 - blue;
+            }
+            ColorTransform::Grayscale => {
+                let gray = 0.3 * red + 0.59 * green + 0.11 * blue;
+                red = gray;
+                green = gray;
+                blue = gray;
+            }
+            ColorTransform::Alpha(value) => alpha = percentage(*value),
+            ColorTransform::AlphaOffset(value) => alpha += percentage(*value),
+            ColorTransform::AlphaModulate(value) => alpha *= percentage(*value),
+            ColorTransform::Hue(value) => {
+                hue = f64::from(*value) / 60000.0;
+                hsl_changed = true;
+            }
+            ColorTransform::HueOffset(value) => {
+                hue = (hue + f64::from(*value) / 60000.0).rem_euclid(360.0);
+                hsl_changed = true;
+            }
+            ColorTransform::HueModulate(value) => {
+                hue = (hue * percentage(*value)).rem_euclid(360.0);
+                hsl_changed = true;
+            }
+            ColorTransform::Saturation(value) => {
+                saturation = percentage(*value);
+                hsl_changed = true;
+            }
+            ColorTransform::SaturationOffset(value) => {
+                saturation += percentage(*value);
+                hsl_changed = true;
+            }
+            ColorTransform::SaturationModulate(value) => {
+                saturation *= percentage(*value);
+                hsl_changed = true;
+            }
+            ColorTransform::Luminance(value) => {
+                luminance = percentage(*value);
+                hsl_changed = true;
+            }
+            ColorTransform::LuminanceOffset(value) => {
+                luminance += percentage(*value);
+                hsl_changed = true;
+            }
+            ColorTransform::LuminanceModulate(value) => {
+                luminance *= percentage(*value);
+                hsl_changed = true;
+            }
+            ColorTransform::Red(value) => red = percentage(*value),
+            ColorTransform::RedOffset(value) => red += percentage(*value),
+            ColorTransform::RedModulate(value) => red *= percentage(*value),
+            ColorTransform::Green(value) => green = percentage(*value),
+            ColorTransform::GreenOffset(value) => green += percentage(*value),
+            ColorTransform::GreenModulate(value) => green *= percentage(*value),
+            ColorTransform::Blue(value) => blue = percentage(*value),
+            ColorTransform::BlueOffset(value) => blue += percentage(*value),
+            ColorTransform::BlueModulate(value) => blue *= percentage(*value),
+            ColorTransform::Gamma => {
+                red = red.powf(1.0 / 2.2);
+                green = green.powf(1.0 / 2.2);
+                blue = blue.powf(1.0 / 2.2);
+            }
+            ColorTransform::InverseGamma => {
+                red = red.powf(2.2);
+                green = green.powf(2.2);
+                blue = blue.powf(2.2);
+            }
+        }
+
+        if hsl_changed {
+            let rgb = hsl_to_rgb(hue, saturation.clamp(0.0, 1.0), luminance.clamp(0.0, 1.0));
+            red = rgb.0;
+            green = rgb.1;
+            blue = rgb.2;
+        }
+    }
+
+    RgbaColor::new(to_byte(red), to_byte(green), to_byte(blue), to_byte(alpha))
+}
+
+/// Returns the sRGB value of a preset color. Returns None for preset colors that are not known by this function.
+pub fn preset_color(value: PresetColorVal) -> Option<RgbaColor> {
+    let rgb = match value {
+        PresetColorVal::Black => 0x00_00_00,
+        PresetColorVal::White => 0xff_ff_ff,
+        PresetColorVal::Red => 0xff_00_00,
+        PresetColorVal::Green => 0x00_80_00,
+        PresetColorVal::Blue => 0x00_00_ff,
+        PresetColorVal::Yellow => 0xff_ff_00,
+        PresetColorVal::Gray => 0x80_80_80,
+        _ => return None,
+    };
+
+    Some(RgbaColor::from_rgb(rgb))
+}
+
+/// Returns the default value of a system color, used when the color doesn't specify its last computed value.
+fn system_color(value: SystemColorVal) -> Option<RgbaColor> {
+    match value {
+        SystemColorVal::WindowText => Some(RgbaColor::from_rgb(0x00_00_00)),
+        SystemColorVal::Window => Some(RgbaColor::from_rgb(0xff_ff_ff)),
+        _ => None,
+    }
+}
+
+fn linear_to_srgb(value: f32) -> u8 {
+    let linear = (f64::from(value) / 100000.0).clamp(0.0, 1.0);
+    let srgb = if linear <= 0.003_130_8 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    };
+
+    (srgb * 255.0).round() as u8
+}
+
+/// Converts a color from RGB (0.0 - 1.0) into HSL. The hue is returned in degrees.
+fn rgb_to_hsl(red: f64, green: f64, blue: f64) -> (f64, f64, f64) {
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let luminance = (max + min) / 2.0;
+    if (max - min).abs() < f64::EPSILON {
+        return (0.0, 0.0, luminance);
+    }
+
+    let delta = max - min;
+    let saturation = if luminance > 0.5 {
+        delta / (2.0 - max - min)
+    } else {
+        delta / (max + min)
+    };
+
+    let hue = if (max - red).abs() < f64::EPSILON {
+        (green - blue) / delta + if green < blue { 6.0 } else { 0.0 }
+    } else if (max - green).abs() < f64::EPSILON {
+        (blue - red) / delta + 2.0
+    } else {
+        (red - green) / delta + 4.0
+    };
+
+    (hue * 60.0, saturation, luminance)
+}
+
+/// Converts a color from HSL into RGB (0.0 - 1.0). The hue is expected in degrees.
+fn hsl_to_rgb(hue: f64, saturation: f64, luminance: f64) -> (f64, f64, f64) {
+    if saturation <= 0.0 {
+        return (luminance, luminance, luminance);
+    }
+
+    let q = if luminance < 0.5 {
+        luminance * (1.0 + saturation)
+    } else {
+        luminance + saturation - luminance * saturation
+    };
+    let p = 2.0 * luminance - q;
+    let hue = hue.rem_euclid(360.0) / 360.0;
+
+    let channel = |t: f64| {
+        let t = t.rem_euclid(1.0);
+        if t < 1.0 / 6.0 {
+            p + (q - p) * 6.0 * t
+        } else if t < 0.5 {
+            q
+        } else if t < 2.0 / 3.0 {
+            p + (q - p) * (2.0 / 3.0 - t) * 6.0
+        } else {
+            p
+        }
+    };
+
+    (channel(hue + 1.0 / 3.0), channel(hue), channel(hue - 1.0 / 3.0))
+}
+
+fn to_byte(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use msoffice_shared::drawingml::colors::{SRgbColor, SchemeColor};
+
+    #[test]
+    pub fn test_resolve_color() {
+        let accent = Color::SRgbColor(Box::new(SRgbColor {
+            value: 0x4f_81_bd,
+            color_transforms: Vec::new(),
+        }));
+        assert_eq!(
+            resolve_color(&accent, None, None, None),
+            Some(RgbaColor::new(0x4f, 0x81, 0xbd, 255))
+        );
+
+        let placeholder = Color::SchemeColor(Box::new(SchemeColor {
+            value: SchemeColorVal::PlaceholderColor,
+            color_transforms: vec![ColorTransform::Shade(50000.0), ColorTransform::Alpha(50000.0)],
+        }));
+        assert_eq!(resolve_color(&placeholder, None, None, None), None);
+        assert_eq!(
+            resolve_color(&placeholder, None, None, Some(&accent)),
+            Some(RgbaColor::new(0x28, 0x41, 0x5f, 128))
+        );
+    }
+
+    #[test]
+    pub fn test_apply_luminance_transforms() {
+        let color = RgbaColor::from_rgb(0x4f_81_bd);
+        let lighter = apply_color_transforms(
+            color,
+            &[
+                ColorTransform::LuminanceModulate(40000.0),
+                ColorTransform::LuminanceOffset(60000.0),
+            ],
+        );
+        assert_eq!(lighter.to_hex(), "#b9cde5");
+        assert_eq!(RgbaColor::new(255, 0, 0, 128).to_css(), "rgba(255, 0, 0, 0.502)");
+    }
+}
@@ -0,0 +1,339 @@
+use msoffice_shared::{
+    drawingml::core::TextBody,
+    error::{MissingAttributeError, MissingChildNodeError},
+    relationship::RelationshipId,
+    xml::XmlNode,
+};
+use std::{collections::HashMap, error::Error, io::Read, str::FromStr};
+use zip::read::ZipFile;
+
+use crate::xml::XmlNodeBuilder;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// The uri of the graphicData element that contains a diagram (SmartArt).
+pub const DIAGRAM_GRAPHIC_DATA_URI: &str = "http://schemas.openxmlformats.org/drawingml/2006/diagram";
+
+/// Specifies the type of a point in the data model.
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
+pub enum DiagramPointType {
+    #[strum(serialize = "node")]
+    Node,
+    #[strum(serialize = "asst")]
+    Assistant,
+    #[strum(serialize = "doc")]
+    Document,
+    #[strum(serialize = "pres")]
+    Presentation,
+    #[strum(serialize = "parTrans")]
+    ParentTransition,
+    #[strum(serialize = "sibTrans")]
+    SiblingTransition,
+}
+
+/// Specifies the type of a connection in the data model.
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
+pub enum DiagramConnectionType {
+    #[strum(serialize = "parOf")]
+    ParentOf,
+    #[strum(serialize = "presOf")]
+    PresentationOf,
+    #[strum(serialize = "presParOf")]
+    PresentationParentOf,
+    #[strum(serialize = "unknownRelationship")]
+    UnknownRelationship,
+}
+
+/// This element specifies the relationship IDs used to explicitly reference each of the four constituent parts of a
+/// diagram.
+///
+/// # Xml example
+///
+/// ```xml
+/// <a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/diagram">
+///   <dgm:relIds r:dm="rId2" r:lo="rId3" r:qs="rId4" r:cs="rId5"/>
+/// </a:graphicData>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramRelationshipIds {
+    /// Specifies the relationship ID of the data model part.
+    pub data: RelationshipId,
+    /// Specifies the relationship ID of the layout definition part.
+    pub layout: RelationshipId,
+    /// Specifies the relationship ID of the style definition part.
+    pub quick_style: RelationshipId,
+    /// Specifies the relationship ID of the color transformation part.
+    pub colors: RelationshipId,
+}
+
+impl DiagramRelationshipIds {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let get_rel_id = |attr: &str| {
+            xml_node
+                .attributes
+                .get(attr)
+                .cloned()
+                .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), attr))
+        };
+
+        Ok(Self {
+            data: get_rel_id("r:dm")?,
+            layout: get_rel_id("r:lo")?,
+            quick_style: get_rel_id("r:qs")?,
+            colors: get_rel_id("r:cs")?,
+        })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        // The dgm prefix isn't declared on the root element of the parts, so it's declared on the element itself
+        XmlNode::new("dgm:relIds")
+            .with_attribute("xmlns:dgm", DIAGRAM_GRAPHIC_DATA_URI)
+            .with_attribute("r:dm", &self.data)
+            .with_attribute("r:lo", &self.layout)
+            .with_attribute("r:qs", &self.quick_style)
+            .with_attribute("r:cs", &self.colors)
+    }
+}
+
+/// This element is the root element of the data model part of a diagram. The data model contains the content of the
+/// diagram as a list of points and the connections between them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramData {
+    pub point_list: Vec<DiagramPoint>,
+    pub connection_list: Vec<DiagramConnection>,
+}
+
+impl DiagramData {
+    pub fn from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Self> {
+        let mut xml_string = String::new();
+        zip_file.read_to_string(&mut xml_string)?;
+
+        Self::from_xml_element(&XmlNode::from_str(xml_string.as_str())?)
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut point_list = None;
+        let mut connection_list = Vec::new();
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "ptLst" => {
+                    point_list = Some(
+                        child_node
+                            .child_nodes
+                            .iter()
+                            .filter(|point_node| point_node.local_name() == "pt")
+                            .map(DiagramPoint::from_xml_element)
+                            .collect::<Result<Vec<_>>>()?,
+                    )
+                }
+                "cxnLst" => {
+                    connection_list = child_node
+                        .child_nodes
+                        .iter()
+                        .filter(|cxn_node| cxn_node.local_name() == "cxn")
+                        .map(DiagramConnection::from_xml_element)
+                        .collect::<Result<Vec<_>>>()?
+                }
+                _ => (),
+            }
+        }
+
+        let point_list = point_list.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "ptLst"))?;
+
+        Ok(Self {
+            point_list,
+            connection_list,
+        })
+    }
+
+    /// Returns the content points (nodes and assistants) of the diagram in reading order, paired with their depth in
+    /// the hierarchy. Top level points have a depth of 0.
+    ///
+    /// The hierarchy is built from the parent-of connections starting at the document point, with siblings ordered by
+    /// their source order. If the data model has no document point, the points are returned in document order.
+    pub fn content_points(&self) -> Vec<(usize, &DiagramPoint)> {
+        let is_content = |point: &DiagramPoint| {
+            matches!(
+                point.point_type.unwrap_or(DiagramPointType::Node),
+                DiagramPointType::Node | DiagramPointType::Assistant
+            )
+        };
+
+        let root = match self
+            .point_list
+            .iter()
+            .find(|point| point.point_type == Some(DiagramPointType::Document))
+        {
+            Some(root) => root,
+            None => {
+                return self
+                    .point_list
+                    .iter()
+                    .filter(|point| is_content(point))
+                    .map(|point| (0, point))
+                    .collect()
+            }
+        };
+
+        let points_by_id: HashMap<&str, &DiagramPoint> = self
+            .point_list
+            .iter()
+            .map(|point| (point.model_id.as_str(), point))
+            .collect();
+
+        let mut children: HashMap<&str, Vec<&DiagramConnection>> = HashMap::new();
+        for connection in &self.connection_list {
+            if connection.connection_type.unwrap_or(DiagramConnectionType::ParentOf) == DiagramConnectionType::ParentOf
+            {
+                children
+                    .entry(connection.source_id.as_str())
+                    .or_default()
+                    .push(connection);
+            }
+        }
+        for connections in children.values_mut() {
+            connections.sort_by_key(|connection| connection.source_order);
+        }
+
+        let mut result = Vec::new();
+        let mut stack = vec![(0, root)];
+        while let Some((depth, point)) = stack.pop() {
+            if is_content(point) {
+                result.push((depth - 1, point));
+            }
+
+            if let Some(connections) = children.get(point.model_id.as_str()) {
+                stack.extend(
+                    connections
+                        .iter()
+                        .rev()
+                        .filter_map(|connection| points_by_id.get(connection.destination_id.as_str()))
+                        .map(|child| (depth + 1, *child)),
+                );
+            }
+        }
+
+        result
+    }
+}
+
+/// This element specifies a point in the data model. A point can hold content (text) or represent the document, the
+/// presentation of a node or a transition between nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramPoint {
+    /// Specifies the unique identifier of the point within the data model.
+    pub model_id: String,
+    /// Specifies the type of the point.
+    ///
+    /// Defaults to DiagramPointType::Node
+    pub point_type: Option<DiagramPointType>,
+    /// Specifies the text of the point.
+    pub text_body: Option<TextBody>,
+}
+
+impl DiagramPoint {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let model_id = xml_node
+            .attributes
+            .get("modelId")
+            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "modelId"))?
+            .clone();
+        let point_type = xml_node.attributes.get("type").map(|value| value.parse()).transpose()?;
+
+        let text_body = xml_node
+            .child_nodes
+            .iter()
+            .find(|child_node| child_node.local_name() == "t")
+            .map(TextBody::from_xml_element)
+            .transpose()?;
+
+        Ok(Self {
+            model_id,
+            point_type,
+            text_body,
+        })
+    }
+}
+
+/// This element specifies a connection between two points in the data model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramConnection {
+    /// Specifies the unique identifier of the connection within the data model.
+    pub model_id: String,
+    /// Specifies the type of the connection.
+    ///
+    /// Defaults to DiagramConnectionType::ParentOf
+    pub connection_type: Option<DiagramConnectionType>,
+    /// Specifies the model id of the source point.
+    pub source_id: String,
+    /// Specifies the model id of the destination point.
+    pub destination_id: String,
+    /// Specifies the ordinal of the connection among the connections starting at the source point.
+    pub source_order: u32,
+    /// Specifies the ordinal of the connection among the connections ending at the destination point.
+    pub destination_order: u32,
+}
+
+impl DiagramConnection {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let get_attr = |attr: &str| {
+            xml_node
+                .attributes
+                .get(attr)
+                .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), attr))
+        };
+
+        Ok(Self {
+            model_id: get_attr("modelId")?.clone(),
+            connection_type: xml_node.attributes.get("type").map(|value| value.parse()).transpose()?,
+            source_id: get_attr("srcId")?.clone(),
+            destination_id: get_attr("destId")?.clone(),
+            source_order: get_attr("srcOrd")?.parse()?,
+            destination_order: get_attr("destOrd")?.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl DiagramData {
+        pub fn test_xml(node_name: &'static str) -> String {
+            format!(
+                r#"<{node_name}>
+                <ptLst>
+                    <pt modelId="0" type="doc"/>
+                    <pt modelId="2"><t><bodyPr/><p><r><t>Second</t></r></p></t></pt>
+                    <pt modelId="1"><t><bodyPr/><p><r><t>First</t></r></p></t></pt>
+                    <pt modelId="3"><t><bodyPr/><p><r><t>Child</t></r></p></t></pt>
+                    <pt modelId="4" type="parTrans"/>
+                </ptLst>
+                <cxnLst>
+                    <cxn modelId="10" srcId="0" destId="2" srcOrd="1" destOrd="0"/>
+                    <cxn modelId="11" srcId="0" destId="1" srcOrd="0" destOrd="0"/>
+                    <cxn modelId="12" srcId="1" destId="3" srcOrd="0" destOrd="0"/>
+                    <cxn modelId="13" type="presOf" srcId="2" destId="4" srcOrd="0" destOrd="0"/>
+                </cxnLst>
+            </{node_name}>"#,
+                node_name = node_name
+            )
+        }
+    }
+
+    #[test]
+    pub fn test_diagram_data_content_points() {
+        let xml = DiagramData::test_xml("dataModel");
+        let data = DiagramData::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap();
+        assert_eq!(data.point_list.len(), 5);
+        assert_eq!(data.connection_list.len(), 4);
+
+        let content_points = data
+            .content_points()
+            .into_iter()
+            .map(|(depth, point)| (depth, point.model_id.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(content_points, vec![(0, "1"), (1, "3"), (0, "2")]);
+    }
+}
@@ -0,0 +1,827 @@
+use crate::xml::SchemaValue;
+use log::{error, warn};
+use msoffice_shared::{
+    drawingml::{
+        shapedefs::{
+            AdjAngle, AdjCoordinate, AdjPoint2D, CustomGeometry2D, GeomGuide, Geometry, Path2DCommand, PresetGeometry2D,
+        },
+        simpletypes::{PathFillMode, ShapeType},
+    },
+    xml::XmlNode,
+};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// The number of angle units (60000ths of a degree) in a degree.
+const ANGLE_UNITS_PER_DEGREE: f64 = 60000.0;
+
+/// A point in the coordinate space of a shape, i.e. relative to the top left corner of the shape's bounding box.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A segment of a path. Arcs are converted to cubic bezier curves, so every segment can be drawn by any renderer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathSegment {
+    MoveTo(Point),
+    LineTo(Point),
+    QuadraticBezierTo(Point, Point),
+    CubicBezierTo(Point, Point, Point),
+    Close,
+}
+
+/// A drawable path of a shape's geometry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapePath {
+    pub segments: Vec<PathSegment>,
+    /// Specifies how the path should be filled. Lighten and darken modes are meant to be drawn with a lighter or darker
+    /// variant of the shape's fill.
+    pub fill_mode: PathFillMode,
+    /// Specifies whether the path should be stroked with the shape's outline.
+    pub stroke: bool,
+}
+
+impl ShapePath {
+    pub fn new(segments: Vec<PathSegment>) -> Self {
+        Self {
+            segments,
+            fill_mode: PathFillMode::Norm,
+            stroke: true,
+        }
+    }
+
+    /// Returns whether the path should be filled.
+    pub fn is_filled(&self) -> bool {
+        self.fill_mode != PathFillMode::None
+    }
+
+    /// Returns whether the path ends with a close segment.
+    pub fn is_closed(&self) -> bool {
+        self.segments.last() == Some(&PathSegment::Close)
+    }
+
+    /// Returns the path in SVG path data syntax, scaling each coordinate with the given factor.
+    pub fn to_svg_path_data(&self, scale: f64) -> String {
+        let mut data = String::new();
+        let point = |data: &mut String, point: &Point| {
+            write!(data, " {},{}", round(point.x * scale), round(point.y * scale)).unwrap();
+        };
+
+        for segment in &self.segments {
+            match segment {
+                PathSegment::MoveTo(to) => {
+                    data.push_str(" M");
+                    point(&mut data, to);
+                }
+                PathSegment::LineTo(to) => {
+                    data.push_str(" L");
+                    point(&mut data, to);
+                }
+                PathSegment::QuadraticBezierTo(control, to) => {
+                    data.push_str(" Q");
+                    point(&mut data, control);
+                    point(&mut data, to);
+                }
+                PathSegment::CubicBezierTo(control1, control2, to) => {
+                    data.push_str(" C");
+                    point(&mut data, control1);
+                    point(&mut data, control2);
+                    point(&mut data, to);
+                }
+                PathSegment::Close => data.push_str(" Z"),
+            }
+        }
+
+        data.trim_start().to_string()
+    }
+}
+
+/// The values of the guides of a geometry.
+///
+/// Guides are evaluated in order, so a guide can reference the built-in guides (e.g. w, h, hc, ss, cd4), the adjust
+/// values and the guides defined before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuideValues {
+    values: HashMap<String, f64>,
+}
+
+impl GuideValues {
+    /// Creates the built-in guides of a shape with the given width and height.
+    pub fn new(width: f64, height: f64) -> Self {
+        let short_side = width.min(height);
+        let mut values = HashMap::new();
+        let mut insert = |name: &str, value: f64| {
+            values.insert(String::from(name), value);
+        };
+
+        insert("w", width);
+        insert("h", height);
+        insert("l", 0.0);
+        insert("t", 0.0);
+        insert("r", width);
+        insert("b", height);
+        insert("hc", width / 2.0);
+        insert("vc", height / 2.0);
+        insert("ss", short_side);
+        insert("ls", width.max(height));
+        for divisor in &[2, 3, 4, 5, 6, 8, 10, 12, 16, 32] {
+            insert(&format!("wd{}", divisor), width / f64::from(*divisor));
+        }
+        for divisor in &[2, 3, 4, 5, 6, 8] {
+            insert(&format!("hd{}", divisor), height / f64::from(*divisor));
+        }
+        for divisor in &[2, 4, 6, 8, 16, 32] {
+            insert(&format!("ssd{}", divisor), short_side / f64::from(*divisor));
+        }
+        insert("cd2", 10_800_000.0);
+        insert("cd4", 5_400_000.0);
+        insert("cd8", 2_700_000.0);
+        insert("3cd4", 16_200_000.0);
+        insert("3cd8", 8_100_000.0);
+        insert("5cd8", 13_500_000.0);
+        insert("7cd8", 18_900_000.0);
+
+        Self { values }
+    }
+
+    /// Returns the value of a guide.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.values.get(name).copied()
+    }
+
+    /// Sets the value of a guide.
+    pub fn set(&mut self, name: &str, value: f64) {
+        self.values.insert(String::from(name), value);
+    }
+
+    /// Evaluates a list of guides in order. Guides with invalid formulas evaluate to 0.
+    pub fn evaluate(&mut self, guides: &[GeomGuide]) {
+        for guide in guides {
+            let value = self.evaluate_formula(&guide.formula).unwrap_or(0.0);
+            self.set(&guide.name, value);
+        }
+    }
+
+    /// Evaluates a guide formula, e.g. "*/ w adj 100000". Returns None if the formula is invalid.
+    ///
+    /// Angles are in 60000ths of a degree, like in the guide formulas of ECMA-376.
+    pub fn evaluate_formula(&self, formula: &str) -> Option<f64> {
+        let mut tokens = formula.split_whitespace();
+        let operator = tokens.next()?;
+        let arguments = tokens.map(|token| self.argument(token)).collect::<Option<Vec<_>>>()?;
+        let argument = |index: usize| arguments.get(index).copied();
+        let radians = |angle: f64| angle / ANGLE_UNITS_PER_DEGREE * PI / 180.0;
+        let angle = |radians: f64| radians * 180.0 / PI * ANGLE_UNITS_PER_DEGREE;
+
+        let value = match operator {
+            "val" => argument(0)?,
+            "*/" => {
+                let divisor = argument(2)?;
+                if divisor == 0.0 {
+                    0.0
+                } else {
+                    argument(0)? * argument(1)? / divisor
+                }
+            }
+            "+-" => argument(0)? + argument(1)? - argument(2)?,
+            "+/" => {
+                let divisor = argument(2)?;
+                if divisor == 0.0 {
+                    0.0
+                } else {
+                    (argument(0)? + argument(1)?) / divisor
+                }
+            }
+            "?:" => {
+                if argument(0)? > 0.0 {
+                    argument(1)?
+                } else {
+                    argument(2)?
+                }
+            }
+            "abs" => argument(0)?.abs(),
+            "at2" => angle(argument(1)?.atan2(argument(0)?)),
+            "cat2" => argument(0)? * argument(2)?.atan2(argument(1)?).cos(),
+            "cos" => argument(0)? * radians(argument(1)?).cos(),
+            "max" => argument(0)?.max(argument(1)?),
+            "min" => argument(0)?.min(argument(1)?),
+            "mod" => {
+                let (x, y, z) = (argument(0)?, argument(1)?, argument(2)?);
+                (x * x + y * y + z * z).sqrt()
+            }
+            "pin" => {
+                let (min, value, max) = (argument(0)?, argument(1)?, argument(2)?);
+                if value < min {
+                    min
+                } else if value > max {
+                    max
+                } else {
+                    value
+                }
+            }
+            "sat2" => argument(0)? * argument(2)?.atan2(argument(1)?).sin(),
+            "sin" => argument(0)? * radians(argument(1)?).sin(),
+            "sqrt" => argument(0)?.max(0.0).sqrt(),
+            "tan" => argument(0)? * radians(argument(1)?).tan(),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    /// Returns the value of a coordinate, which is either a literal or the name of a guide.
+    pub fn coordinate(&self, coordinate: &AdjCoordinate) -> f64 {
+        match coordinate {
+            AdjCoordinate::Coordinate(value) => *value as f64,
+            AdjCoordinate::GeomGuideName(name) => self.get(name).unwrap_or(0.0),
+        }
+    }
+
+    /// Returns the value of an angle in 60000ths of a degree, which is either a literal or the name of a guide.
+    pub fn angle(&self, angle: &AdjAngle) -> f64 {
+        match angle {
+            AdjAngle::Angle(value) => f64::from(*value),
+            AdjAngle::GeomGuideName(name) => self.get(name).unwrap_or(0.0),
+        }
+    }
+
+    fn argument(&self, token: &str) -> Option<f64> {
+        token.parse().ok().or_else(|| self.get(token))
+    }
+}
+
+/// A rectangle in the coordinate space of a shape.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+impl Rect {
+    pub fn new(left: f64, top: f64, right: f64, bottom: f64) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> f64 {
+        self.bottom - self.top
+    }
+}
+
+/// A point of a shape that connectors can be attached to.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ConnectionPoint {
+    pub position: Point,
+    /// The direction in which an attached connector leaves the shape, in 60000ths of a degree measured clockwise from
+    /// the x axis.
+    pub angle: f64,
+}
+
+/// A geometry evaluated for a shape with a specific width and height.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluatedGeometry {
+    pub paths: Vec<ShapePath>,
+    /// The rectangle that the text of the shape is laid out in.
+    pub text_rect: Rect,
+    /// The connection sites of the shape. Connectors reference connection sites by their index within this list.
+    pub connection_sites: Vec<ConnectionPoint>,
+    /// The preset geometry of the shape if it isn't defined by the bundled preset shape definitions. The geometry is
+    /// evaluated as a rectangle in this case.
+    pub unsupported_preset: Option<ShapeType>,
+}
+
+/// A subset of the preset shape definitions of ECMA-376, which define preset geometries as custom geometries. See
+/// `preset_shape_definition` for the presets that are missing.
+const PRESET_SHAPE_DEFINITIONS: &str = include_str!("presetShapeDefinitions.xml");
+
+/// Returns the definition of a preset geometry, or None if the preset is not defined.
+///
+/// The adjust values of the definition hold the default values of the preset's adjust values. Only the most common
+/// presets of ECMA-376 Part 1, Annex D are bundled: rectangles, ellipses, basic polygons, block arrows, the wedge
+/// callouts, a few flowchart shapes and the connectors. Others, like clouds, arcs, curved arrows or most of the stars
+/// and callouts, return None.
+pub fn preset_shape_definition(preset: ShapeType) -> Option<&'static CustomGeometry2D> {
+    static DEFINITIONS: OnceLock<Vec<(ShapeType, CustomGeometry2D)>> = OnceLock::new();
+
+    DEFINITIONS
+        .get_or_init(parse_preset_shape_definitions)
+        .iter()
+        .find(|(defined_preset, _)| *defined_preset == preset)
+        .map(|(_, definition)| definition)
+}
+
+fn parse_preset_shape_definitions() -> Vec<(ShapeType, CustomGeometry2D)> {
+    let root = match XmlNode::from_str(PRESET_SHAPE_DEFINITIONS) {
+        Ok(root) => root,
+        Err(err) => {
+            error!("failed to parse preset shape definitions: {}", err);
+            return Vec::new();
+        }
+    };
+
+    root.child_nodes
+        .iter()
+        .filter_map(|definition_node| {
+            let preset = definition_node.local_name().parse::<ShapeType>().ok()?;
+            match CustomGeometry2D::from_xml_element(definition_node) {
+                Ok(definition) => Some((preset, definition)),
+                Err(err) => {
+                    error!(
+                        "failed to parse preset shape definition {}: {}",
+                        definition_node.name, err
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Evaluates the geometry of a shape with the given width and height.
+///
+/// Shapes without a geometry are evaluated as rectangles. Preset geometries without a definition are evaluated as
+/// rectangles too, but they are reported by the unsupported_preset field of the result and logged as a warning.
+pub fn evaluate_geometry(geometry: Option<&Geometry>, width: f64, height: f64) -> EvaluatedGeometry {
+    match geometry {
+        Some(Geometry::Custom(custom_geometry)) => evaluate_custom_geometry(custom_geometry, &[], width, height),
+        Some(Geometry::Preset(preset_geometry)) => evaluate_preset_geometry(preset_geometry, width, height)
+            .unwrap_or_else(|| {
+                warn!(
+                    "preset geometry {} isn't supported, it's drawn as a rectangle",
+                    preset_geometry.preset.schema_value()
+                );
+                EvaluatedGeometry {
+                    unsupported_preset: Some(preset_geometry.preset),
+                    ..evaluate_rectangle(width, height)
+                }
+            }),
+        None => evaluate_rectangle(width, height),
+    }
+}
+
+/// Evaluates a preset geometry for a shape with the given width and height, or returns None if the preset is not
+/// defined.
+pub fn evaluate_preset_geometry(geometry: &PresetGeometry2D, width: f64, height: f64) -> Option<EvaluatedGeometry> {
+    let definition = preset_shape_definition(geometry.preset)?;
+    let adjust_values = geometry.adjust_value_list.as_deref().unwrap_or_default();
+    Some(evaluate_custom_geometry(definition, adjust_values, width, height))
+}
+
+/// Evaluates a custom geometry for a shape with the given width and height.
+///
+/// `adjust_values` replace the adjust values of the geometry with the same name, like the adjust values of a preset
+/// geometry replace the default values of the preset's definition. The guides are evaluated in order after the adjust
+/// values, then the paths, the text rectangle and the connection sites are resolved with the values of the guides. If
+/// the geometry doesn't specify a text rectangle, text is laid out in the whole shape.
+pub fn evaluate_custom_geometry(
+    geometry: &CustomGeometry2D,
+    adjust_values: &[GeomGuide],
+    width: f64,
+    height: f64,
+) -> EvaluatedGeometry {
+    let mut guides = GuideValues::new(width, height);
+    for default_value in geometry.adjust_value_list.iter().flatten() {
+        let adjust_value = adjust_values
+            .iter()
+            .find(|adjust_value| adjust_value.name == default_value.name)
+            .unwrap_or(default_value);
+        let value = guides.evaluate_formula(&adjust_value.formula).unwrap_or(0.0);
+        guides.set(&default_value.name, value);
+    }
+    if let Some(guide_list) = &geometry.guide_list {
+        guides.evaluate(guide_list);
+    }
+
+    let paths = geometry
+        .path_list
+        .iter()
+        .map(|path| {
+            // Path coordinates are specified in the coordinate space of the path, which is scaled to the shape
+            let scale_x = match path.width {
+                Some(path_width) if path_width > 0 => width / path_width as f64,
+                _ => 1.0,
+            };
+            let scale_y = match path.height {
+                Some(path_height) if path_height > 0 => height / path_height as f64,
+                _ => 1.0,
+            };
+            let point = |point: &AdjPoint2D| {
+                Point::new(
+                    guides.coordinate(&point.x) * scale_x,
+                    guides.coordinate(&point.y) * scale_y,
+                )
+            };
+
+            let mut builder = PathBuilder::default();
+            for command in &path.commands {
+                match command {
+                    Path2DCommand::MoveTo(to) => builder.move_to(point(to)),
+                    Path2DCommand::LineTo(to) => builder.line_to(point(to)),
+                    Path2DCommand::QuadBezierTo(control, to) => builder.quadratic_bezier_to(point(control), point(to)),
+                    Path2DCommand::CubicBezTo(control1, control2, to) => {
+                        builder.cubic_bezier_to(point(control1), point(control2), point(to))
+                    }
+                    Path2DCommand::ArcTo(arc) => builder.arc_to(
+                        guides.coordinate(&arc.width_radius) * scale_x,
+                        guides.coordinate(&arc.height_radius) * scale_y,
+                        guides.angle(&arc.start_angle),
+                        guides.angle(&arc.swing_angle),
+                    ),
+                    Path2DCommand::Close => builder.close(),
+                }
+            }
+
+            ShapePath {
+                segments: builder.segments,
+                fill_mode: path.fill_mode.unwrap_or(PathFillMode::Norm),
+                stroke: path.stroke.unwrap_or(true),
+            }
+        })
+        .collect();
+
+    let text_rect = match &geometry.rect {
+        Some(rect) => Rect::new(
+            guides.coordinate(&rect.left),
+            guides.coordinate(&rect.top),
+            guides.coordinate(&rect.right),
+            guides.coordinate(&rect.bottom),
+        ),
+        None => Rect::new(0.0, 0.0, width, height),
+    };
+
+    let connection_sites = geometry
+        .connection_site_list
+        .iter()
+        .flatten()
+        .map(|connection_site| ConnectionPoint {
+            position: Point::new(
+                guides.coordinate(&connection_site.position.x),
+                guides.coordinate(&connection_site.position.y),
+            ),
+            angle: guides.angle(&connection_site.angle),
+        })
+        .collect();
+
+    EvaluatedGeometry {
+        paths,
+        text_rect,
+        connection_sites,
+        unsupported_preset: None,
+    }
+}
+
+/// Returns the paths of a shape's geometry for a shape with the given width and height.
+///
+/// If the shape has no geometry or the preset geometry is not defined, the paths of a rectangle are returned. See
+/// `evaluate_geometry` to find out whether the preset is supported.
+pub fn shape_paths(geometry: Option<&Geometry>, width: f64, height: f64) -> Vec<ShapePath> {
+    evaluate_geometry(geometry, width, height).paths
+}
+
+/// Returns the paths of a custom geometry for a shape with the given width and height.
+pub fn custom_geometry_paths(geometry: &CustomGeometry2D, width: f64, height: f64) -> Vec<ShapePath> {
+    evaluate_custom_geometry(geometry, &[], width, height).paths
+}
+
+/// Returns the paths of a preset geometry for a shape with the given width and height, or None if the preset is not
+/// defined.
+pub fn preset_geometry_paths(geometry: &PresetGeometry2D, width: f64, height: f64) -> Option<Vec<ShapePath>> {
+    evaluate_preset_geometry(geometry, width, height).map(|evaluated_geometry| evaluated_geometry.paths)
+}
+
+/// Evaluates a rectangle without relying on the preset shape definitions.
+fn evaluate_rectangle(width: f64, height: f64) -> EvaluatedGeometry {
+    let connection_site = |x: f64, y: f64, angle: f64| ConnectionPoint {
+        position: Point::new(x, y),
+        angle,
+    };
+
+    EvaluatedGeometry {
+        paths: vec![ShapePath::new(vec![
+            PathSegment::MoveTo(Point::new(0.0, 0.0)),
+            PathSegment::LineTo(Point::new(width, 0.0)),
+            PathSegment::LineTo(Point::new(width, height)),
+            PathSegment::LineTo(Point::new(0.0, height)),
+            PathSegment::Close,
+        ])],
+        text_rect: Rect::new(0.0, 0.0, width, height),
+        connection_sites: vec![
+            connection_site(width / 2.0, 0.0, 16_200_000.0),
+            connection_site(0.0, height / 2.0, 10_800_000.0),
+            connection_site(width / 2.0, height, 5_400_000.0),
+            connection_site(width, height / 2.0, 0.0),
+        ],
+        unsupported_preset: None,
+    }
+}
+
+/// Builds path segments while keeping track of the current point, which is needed to convert arcs.
+#[derive(Debug, Default)]
+struct PathBuilder {
+    segments: Vec<PathSegment>,
+    start: Point,
+    current: Point,
+}
+
+impl PathBuilder {
+    fn move_to(&mut self, to: Point) {
+        self.segments.push(PathSegment::MoveTo(to));
+        self.start = to;
+        self.current = to;
+    }
+
+    fn line_to(&mut self, to: Point) {
+        self.segments.push(PathSegment::LineTo(to));
+        self.current = to;
+    }
+
+    fn quadratic_bezier_to(&mut self, control: Point, to: Point) {
+        self.segments.push(PathSegment::QuadraticBezierTo(control, to));
+        self.current = to;
+    }
+
+    fn cubic_bezier_to(&mut self, control1: Point, control2: Point, to: Point) {
+        self.segments.push(PathSegment::CubicBezierTo(control1, control2, to));
+        self.current = to;
+    }
+
+    fn close(&mut self) {
+        self.segments.push(PathSegment::Close);
+        self.current = self.start;
+    }
+
+    /// Adds an elliptical arc starting at the current point. Angles are in 60000ths of a degree, measured clockwise
+    /// from the x axis. As in ECMA-376, the angles are visual angles of the ellipse, not parametric ones.
+    fn arc_to(&mut self, width_radius: f64, height_radius: f64, start_angle: f64, swing_angle: f64) {
+        if width_radius <= 0.0 || height_radius <= 0.0 || swing_angle == 0.0 {
+            return;
+        }
+
+        let to_radians = |angle: f64| angle / ANGLE_UNITS_PER_DEGREE * PI / 180.0;
+        let parametric = |angle: f64| {
+            let (sin, cos) = to_radians(angle).sin_cos();
+            (width_radius * sin).atan2(height_radius * cos)
+        };
+
+        let start = parametric(start_angle);
+        let mut sweep = parametric(start_angle + swing_angle) - start;
+        let swing = to_radians(swing_angle);
+        if swing.abs() >= 2.0 * PI {
+            sweep = 2.0 * PI * swing.signum();
+        } else if swing > 0.0 && sweep < 0.0 {
+            sweep += 2.0 * PI;
+        } else if swing < 0.0 && sweep > 0.0 {
+            sweep -= 2.0 * PI;
+        }
+
+        let center = Point::new(
+            self.current.x - width_radius * start.cos(),
+            self.current.y - height_radius * start.sin(),
+        );
+        let ellipse_point = |angle: f64| {
+            Point::new(
+                center.x + width_radius * angle.cos(),
+                center.y + height_radius * angle.sin(),
+            )
+        };
+
+        // Each segment spans at most a quarter of the ellipse, so the bezier approximation stays accurate
+        let segment_count = (sweep.abs() / (PI / 2.0)).ceil().max(1.0) as usize;
+        let segment_sweep = sweep / segment_count as f64;
+        let k = 4.0 / 3.0 * (segment_sweep / 4.0).tan();
+        let mut angle = start;
+        for _ in 0..segment_count {
+            let next_angle = angle + segment_sweep;
+            let from = ellipse_point(angle);
+            let to = ellipse_point(next_angle);
+            let control1 = Point::new(
+                from.x - k * width_radius * angle.sin(),
+                from.y + k * height_radius * angle.cos(),
+            );
+            let control2 = Point::new(
+                to.x + k * width_radius * next_angle.sin(),
+                to.y - k * height_radius * next_angle.cos(),
+            );
+            self.cubic_bezier_to(control1, control2, to);
+            angle = next_angle;
+        }
+    }
+}
+
+/// Rounds to two decimal places, which keeps the generated path data short.
+fn round(value: f64) -> f64 {
+    // Adding zero turns negative zero into zero
+    (value * 100.0).round() / 100.0 + 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use msoffice_shared::drawingml::shapedefs::{Path2D, Path2DArcTo};
+
+    #[test]
+    pub fn test_evaluate_formula() {
+        let mut guides = GuideValues::new(200.0, 100.0);
+        guides.evaluate(&[
+            GeomGuide {
+                name: String::from("adj"),
+                formula: String::from("val 25000"),
+            },
+            GeomGuide {
+                name: String::from("x1"),
+                formula: String::from("*/ ss adj 100000"),
+            },
+            GeomGuide {
+                name: String::from("x2"),
+                formula: String::from("+- r 0 x1"),
+            },
+            GeomGuide {
+                name: String::from("y1"),
+                formula: String::from("pin 0 x2 h"),
+            },
+        ]);
+        assert_eq!(guides.get("x1"), Some(25.0));
+        assert_eq!(guides.get("x2"), Some(175.0));
+        assert_eq!(guides.get("y1"), Some(100.0));
+        assert_eq!(guides.evaluate_formula("at2 w w"), Some(2_700_000.0));
+        assert_eq!(guides.evaluate_formula("?: -1 w h"), Some(100.0));
+        assert_eq!(guides.evaluate_formula("unknown 1 2"), None);
+    }
+
+    #[test]
+    pub fn test_preset_geometry_paths() {
+        let geometry = PresetGeometry2D {
+            preset: ShapeType::Parallelogram,
+            adjust_value_list: Some(vec![GeomGuide {
+                name: String::from("adj"),
+                formula: String::from("val 50000"),
+            }]),
+        };
+        let paths = preset_geometry_paths(&geometry, 200.0, 100.0).unwrap();
+        assert_eq!(paths[0].to_svg_path_data(1.0), "M 0,100 L 50,0 L 200,0 L 150,100 Z");
+
+        let paths = shape_paths(None, 200.0, 100.0);
+        assert_eq!(paths[0].to_svg_path_data(0.5), "M 0,0 L 100,0 L 100,50 L 0,50 Z");
+    }
+
+    #[test]
+    pub fn test_preset_shape_definitions() {
+        let presets = [
+            ShapeType::Rect,
+            ShapeType::RoundRect,
+            ShapeType::Ellipse,
+            ShapeType::RightArrow,
+            ShapeType::WedgeRectCallout,
+            ShapeType::WedgeRoundRectCallout,
+            ShapeType::WedgeEllipseCallout,
+            ShapeType::Star5,
+            ShapeType::Donut,
+        ];
+        for preset in presets.iter() {
+            let definition = preset_shape_definition(*preset).unwrap();
+            assert!(!definition.path_list.is_empty());
+        }
+        assert!(preset_shape_definition(ShapeType::Cloud).is_none());
+    }
+
+    #[test]
+    pub fn test_evaluate_unsupported_preset_geometry() {
+        let geometry = |preset: ShapeType| {
+            Geometry::Preset(Box::new(PresetGeometry2D {
+                preset,
+                adjust_value_list: None,
+            }))
+        };
+
+        let evaluated_geometry = evaluate_geometry(Some(&geometry(ShapeType::Cloud)), 200.0, 100.0);
+        assert_eq!(evaluated_geometry.unsupported_preset, Some(ShapeType::Cloud));
+        assert_eq!(
+            evaluated_geometry.paths[0].to_svg_path_data(1.0),
+            "M 0,0 L 200,0 L 200,100 L 0,100 Z"
+        );
+
+        let evaluated_geometry = evaluate_geometry(Some(&geometry(ShapeType::Ellipse)), 200.0, 100.0);
+        assert_eq!(evaluated_geometry.unsupported_preset, None);
+        assert_eq!(evaluate_geometry(None, 200.0, 100.0).unsupported_preset, None);
+    }
+
+    #[test]
+    pub fn test_evaluate_preset_geometry() {
+        let preset_geometry = |preset: ShapeType, adjust_values: Vec<(&str, &str)>| PresetGeometry2D {
+            preset,
+            adjust_value_list: Some(
+                adjust_values
+                    .into_iter()
+                    .map(|(name, formula)| GeomGuide {
+                        name: String::from(name),
+                        formula: String::from(formula),
+                    })
+                    .collect(),
+            ),
+        };
+
+        let geometry = evaluate_preset_geometry(&preset_geometry(ShapeType::RoundRect, vec![]), 200.0, 100.0).unwrap();
+        assert_eq!(geometry.text_rect.left.round(), 5.0);
+        assert_eq!(geometry.text_rect.bottom.round(), 95.0);
+        assert_eq!(geometry.connection_sites.len(), 4);
+        assert_eq!(geometry.connection_sites[3].position, Point::new(200.0, 50.0));
+        assert_eq!(geometry.connection_sites[3].angle, 0.0);
+
+        let geometry = evaluate_preset_geometry(
+            &preset_geometry(ShapeType::RightArrow, vec![("adj1", "val 60000")]),
+            200.0,
+            100.0,
+        )
+        .unwrap();
+        assert_eq!(
+            geometry.paths[0].to_svg_path_data(1.0),
+            "M 0,20 L 150,20 L 150,0 L 200,50 L 150,100 L 150,80 L 0,80 Z"
+        );
+        assert_eq!(geometry.text_rect, Rect::new(0.0, 20.0, 170.0, 80.0));
+
+        let geometry = evaluate_preset_geometry(
+            &preset_geometry(
+                ShapeType::WedgeRectCallout,
+                vec![("adj1", "val -75000"), ("adj2", "val 0")],
+            ),
+            120.0,
+            120.0,
+        )
+        .unwrap();
+        // The wedge points to the left, so it starts from the left edge
+        assert!(geometry.paths[0]
+            .to_svg_path_data(1.0)
+            .ends_with("L 0,120 L 0,50 L -30,60 L 0,20 Z"));
+        assert_eq!(geometry.connection_sites[4].position, Point::new(-30.0, 60.0));
+
+        let geometry = evaluate_geometry(None, 200.0, 100.0);
+        assert_eq!(geometry.text_rect, Rect::new(0.0, 0.0, 200.0, 100.0));
+        assert_eq!(geometry.connection_sites[0].position, Point::new(100.0, 0.0));
+    }
+
+    #[test]
+    pub fn test_custom_geometry_paths() {
+        let geometry = CustomGeometry2D {
+            adjust_value_list: None,
+            guide_list: None,
+            adjust_handle_list: None,
+            connection_site_list: None,
+            rect: None,
+            path_list: vec![Path2D {
+                width: Some(2),
+                height: Some(2),
+                fill_mode: None,
+                stroke: None,
+                extrusion_ok: None,
+                commands: vec![
+                    Path2DCommand::MoveTo(AdjPoint2D {
+                        x: AdjCoordinate::Coordinate(0),
+                        y: AdjCoordinate::Coordinate(1),
+                    }),
+                    Path2DCommand::ArcTo(Path2DArcTo {
+                        width_radius: AdjCoordinate::Coordinate(1),
+                        height_radius: AdjCoordinate::Coordinate(1),
+                        start_angle: AdjAngle::Angle(10_800_000),
+                        swing_angle: AdjAngle::Angle(10_800_000),
+                    }),
+                    Path2DCommand::Close,
+                ],
+            }],
+        };
+
+        let paths = custom_geometry_paths(&geometry, 100.0, 50.0);
+        assert_eq!(paths.len(), 1);
+        assert!(paths[0].is_filled() && paths[0].is_closed());
+        assert_eq!(paths[0].segments.len(), 4);
+        match paths[0].segments[2] {
+            PathSegment::CubicBezierTo(_, _, to) => {
+                assert!((to.x - 100.0).abs() < 1e-9 && (to.y - 25.0).abs() < 1e-9);
+            }
+            _ => panic!("expected a cubic bezier"),
+        }
+        assert!(paths[0]
+            .to_svg_path_data(1.0)
+            .starts_with("M 0,25 C 0,11.19 22.39,0 50,0 C"));
+    }
+}
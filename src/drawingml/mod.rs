@@ -0,0 +1,5 @@
+pub mod color;
+pub mod diagram;
+pub mod geometry;
+pub mod serialize;
+pub mod table;
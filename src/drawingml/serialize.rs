@@ -0,0 +1,1410 @@
+use crate::xml::{SchemaValue, ToXmlChoice, ToXmlElement, XmlNodeBuilder};
+use msoffice_shared::{
+    drawingml::{
+        audiovideo::{EmbeddedWAVAudioFile, Media},
+        colors::{Color, ColorMappingOverride, ColorTransform},
+        coordsys::{GroupTransform2D, Point2D, PositiveSize2D, Transform2D},
+        core::{
+            AnimationElementChoice, AnimationGraphicalObjectBuildProperties, Connection, GroupShapeProperties,
+            Hyperlink, NonVisualConnectorProperties, NonVisualDrawingProps, NonVisualDrawingShapeProps,
+            NonVisualGraphicFrameProperties, NonVisualGroupDrawingShapeProps, NonVisualPictureProperties,
+            ShapeProperties, ShapeStyle, TextBody,
+        },
+        shapedefs::{
+            AdjAngle, AdjCoordinate, AdjPoint2D, CustomGeometry2D, GeomGuide, Geometry, Path2D, Path2DCommand,
+        },
+        shapeprops::{
+            BlipFillProperties, EffectProperties, FillModeProperties, FillProperties, GradientFillProperties,
+            LineDashProperties, LineEndProperties, LineFillProperties, LineJoinProperties, LineProperties,
+            PatternFillProperties, RelativeRect, Scene3D, ShadeProperties, Shape3D,
+        },
+        sharedstylesheet::ColorMapping,
+        simpletypes::{
+            BlackWhiteMode, ColorSchemeIndex, FontCollectionIndex, LineCap, LineEndLength, LineEndType, LineEndWidth,
+            PathFillMode, PathShadeType, PresetColorVal, PresetLineDashVal, SchemeColorVal, ShapeType, SystemColorVal,
+            TextAlignType, TextAnchoringType, TextCapsType, TextStrikeType, TextUnderlineType, TileFlipMode,
+        },
+        styles::{FontReference, StyleMatrixReference},
+        text::{
+            bodyformatting::TextBodyProperties,
+            bullet::TextListStyle,
+            paragraphs::{TextParagraph, TextParagraphProperties},
+            runformatting::{TextCharacterProperties, TextFont, TextRun},
+        },
+    },
+    sharedtypes::ConformanceClass,
+    xml::XmlNode,
+};
+
+impl SchemaValue for BlackWhiteMode {
+    const VALUES: &'static [&'static str] = &[
+        "clr",
+        "auto",
+        "gray",
+        "ltGray",
+        "invGray",
+        "grayWhite",
+        "blackGray",
+        "blackWhite",
+        "black",
+        "white",
+        "hidden",
+    ];
+}
+
+impl SchemaValue for SchemeColorVal {
+    const VALUES: &'static [&'static str] = &[
+        "bg1", "tx1", "bg2", "tx2", "accent1", "accent2", "accent3", "accent4", "accent5", "accent6", "hlink",
+        "folHlink", "phClr", "dk1", "lt1", "dk2", "lt2",
+    ];
+}
+
+impl SchemaValue for ColorSchemeIndex {
+    const VALUES: &'static [&'static str] = &[
+        "dk1", "lt1", "dk2", "lt2", "accent1", "accent2", "accent3", "accent4", "accent5", "accent6", "hlink",
+        "folHlink",
+    ];
+}
+
+impl SchemaValue for SystemColorVal {
+    const VALUES: &'static [&'static str] = &[
+        "scrollBar",
+        "background",
+        "activeCaption",
+        "inactiveCaption",
+        "menu",
+        "window",
+        "windowFrame",
+        "menuText",
+        "windowText",
+        "captionText",
+        "activeBorder",
+        "inactiveBorder",
+        "appWorkspace",
+        "highlight",
+        "highlightText",
+        "btnFace",
+        "btnShadow",
+        "grayText",
+        "btnText",
+        "inactiveCaptionText",
+        "btnHighlight",
+        "3dDkShadow",
+        "3dLight",
+        "infoText",
+        "infoBk",
+        "hotLight",
+        "gradientActiveCaption",
+        "gradientInactiveCaption",
+        "menuHighlight",
+        "menuBar",
+    ];
+}
+
+impl SchemaValue for PresetColorVal {
+    const VALUES: &'static [&'static str] = &[
+        "aliceBlue",
+        "antiqueWhite",
+        "aqua",
+        "aquamarine",
+        "azure",
+        "beige",
+        "bisque",
+        "black",
+        "blanchedAlmond",
+        "blue",
+        "blueViolet",
+        "brown",
+        "burlyWood",
+        "cadetBlue",
+        "chartreuse",
+        "chocolate",
+        "coral",
+        "cornflowerBlue",
+        "cornsilk",
+        "crimson",
+        "cyan",
+        "darkBlue",
+        "darkCyan",
+        "darkGoldenrod",
+        "darkGray",
+        "darkGrey",
+        "darkGreen",
+        "darkKhaki",
+        "darkMagenta",
+        "darkOliveGreen",
+        "darkOrange",
+        "darkOrchid",
+        "darkRed",
+        "darkSalmon",
+        "darkSeaGreen",
+        "darkSlateBlue",
+        "darkSlateGray",
+        "darkSlateGrey",
+        "darkTurquoise",
+        "darkViolet",
+        "dkBlue",
+        "dkCyan",
+        "dkGoldenrod",
+        "dkGray",
+        "dkGrey",
+        "dkGreen",
+        "dkKhaki",
+        "dkMagenta",
+        "dkOliveGreen",
+        "dkOrange",
+        "dkOrchid",
+        "dkRed",
+        "dkSalmon",
+        "dkSeaGreen",
+        "dkSlateBlue",
+        "dkSlateGray",
+        "dkSlateGrey",
+        "dkTurquoise",
+        "dkViolet",
+        "deepPink",
+        "deepSkyBlue",
+        "dimGray",
+        "dimGrey",
+        "dodgerBlue",
+        "firebrick",
+        "floralWhite",
+        "forestGreen",
+        "fuchsia",
+        "gainsboro",
+        "ghostWhite",
+        "gold",
+        "goldenrod",
+        "gray",
+        "grey",
+        "green",
+        "greenYellow",
+        "honeydew",
+        "hotPink",
+        "indianRed",
+        "indigo",
+        "ivory",
+        "khaki",
+        "lavender",
+        "lavenderBlush",
+        "lawnGreen",
+        "lemonChiffon",
+        "lightBlue",
+        "lightCoral",
+        "lightCyan",
+        "lightGoldenrodYellow",
+        "lightGray",
+        "lightGrey",
+        "lightGreen",
+        "lightPink",
+        "lightSalmon",
+        "lightSeaGreen",
+        "lightSkyBlue",
+        "lightSlateGray",
+        "lightSlateGrey",
+        "lightSteelBlue",
+        "lightYellow",
+        "ltBlue",
+        "ltCoral",
+        "ltCyan",
+        "ltGoldenrodYellow",
+        "ltGray",
+        "ltGrey",
+        "ltGreen",
+        "ltPink",
+        "ltSalmon",
+        "ltSeaGreen",
+        "ltSkyBlue",
+        "ltSlateGray",
+        "ltSlateGrey",
+        "ltSteelBlue",
+        "ltYellow",
+        "lime",
+        "limeGreen",
+        "linen",
+        "magenta",
+        "maroon",
+        "medAquamarine",
+        "medBlue",
+        "medOrchid",
+        "medPurple",
+        "medSeaGreen",
+        "medSlateBlue",
+        "medSpringGreen",
+        "medTurquoise",
+        "medVioletRed",
+        "mediumAquamarine",
+        "mediumBlue",
+        "mediumOrchid",
+        "mediumPurple",
+        "mediumSeaGreen",
+        "mediumSlateBlue",
+        "mediumSpringGreen",
+        "mediumTurquoise",
+        "mediumVioletRed",
+        "midnightBlue",
+        "mintCream",
+        "mistyRose",
+        "moccasin",
+        "navajoWhite",
+        "navy",
+        "oldLace",
+        "olive",
+        "oliveDrab",
+        "orange",
+        "orangeRed",
+        "orchid",
+        "paleGoldenrod",
+        "paleGreen",
+        "paleTurquoise",
+        "paleVioletRed",
+        "papayaWhip",
+        "peachPuff",
+        "peru",
+        "pink",
+        "plum",
+        "powderBlue",
+        "purple",
+        "red",
+        "rosyBrown",
+        "royalBlue",
+        "saddleBrown",
+        "salmon",
+        "sandyBrown",
+        "seaGreen",
+        "seaShell",
+        "sienna",
+        "silver",
+        "skyBlue",
+        "slateBlue",
+        "slateGray",
+        "slateGrey",
+        "snow",
+        "springGreen",
+        "steelBlue",
+        "tan",
+        "teal",
+        "thistle",
+        "tomato",
+        "turquoise",
+        "violet",
+        "wheat",
+        "white",
+        "whiteSmoke",
+        "yellow",
+        "yellowGreen",
+    ];
+}
+
+impl SchemaValue for PresetLineDashVal {
+    const VALUES: &'static [&'static str] = &[
+        "solid",
+        "dot",
+        "dash",
+        "lgDash",
+        "dashDot",
+        "lgDashDot",
+        "lgDashDotDot",
+        "sysDash",
+        "sysDot",
+        "sysDashDot",
+        "sysDashDotDot",
+    ];
+}
+
+impl SchemaValue for LineEndType {
+    const VALUES: &'static [&'static str] = &["none", "triangle", "stealth", "diamond", "oval", "arrow"];
+}
+
+impl SchemaValue for LineEndWidth {
+    const VALUES: &'static [&'static str] = &["sm", "med", "lg"];
+}
+
+impl SchemaValue for LineEndLength {
+    const VALUES: &'static [&'static str] = &["sm", "med", "lg"];
+}
+
+impl SchemaValue for LineCap {
+    const VALUES: &'static [&'static str] = &["rnd", "sq", "flat"];
+}
+
+impl SchemaValue for PathFillMode {
+    const VALUES: &'static [&'static str] = &["none", "norm", "lighten", "lightenLess", "darken", "darkenLess"];
+}
+
+impl SchemaValue for TextAlignType {
+    const VALUES: &'static [&'static str] = &["l", "ctr", "r", "just", "justLow", "dist", "thaiDist"];
+}
+
+impl SchemaValue for TextAnchoringType {
+    const VALUES: &'static [&'static str] = &["t", "ctr", "b", "just", "dist"];
+}
+
+impl SchemaValue for TextUnderlineType {
+    const VALUES: &'static [&'static str] = &[
+        "none",
+        "words",
+        "sng",
+        "dbl",
+        "heavy",
+        "dotted",
+        "dottedHeavy",
+        "dash",
+        "dashHeavy",
+        "dashLong",
+        "dashLongHeavy",
+        "dotDash",
+        "dotDashHeavy",
+        "dotDotDash",
+        "dotDotDashHeavy",
+        "wavy",
+        "wavyHeavy",
+        "wavyDbl",
+    ];
+}
+
+impl SchemaValue for TextStrikeType {
+    const VALUES: &'static [&'static str] = &["noStrike", "sngStrike", "dblStrike"];
+}
+
+impl SchemaValue for TextCapsType {
+    const VALUES: &'static [&'static str] = &["none", "small", "all"];
+}
+
+impl SchemaValue for FontCollectionIndex {
+    const VALUES: &'static [&'static str] = &["major", "minor", "none"];
+}
+
+impl SchemaValue for TileFlipMode {
+    const VALUES: &'static [&'static str] = &["none", "x", "y", "xy"];
+}
+
+impl SchemaValue for PathShadeType {
+    const VALUES: &'static [&'static str] = &["shape", "circle", "rect"];
+}
+
+impl SchemaValue for ConformanceClass {
+    const VALUES: &'static [&'static str] = &["strict", "transitional"];
+}
+
+impl SchemaValue for ShapeType {
+    const VALUES: &'static [&'static str] = &[
+        "line",
+        "lineInv",
+        "triangle",
+        "rtTriangle",
+        "rect",
+        "diamond",
+        "parallelogram",
+        "trapezoid",
+        "nonIsoscelesTrapezoid",
+        "pentagon",
+        "hexagon",
+        "heptagon",
+        "octagon",
+        "decagon",
+        "dodecagon",
+        "star4",
+        "star5",
+        "star6",
+        "star7",
+        "star8",
+        "star10",
+        "star12",
+        "star16",
+        "star24",
+        "star32",
+        "roundRect",
+        "round1Rect",
+        "round2SameRect",
+        "round2DiagRect",
+        "snipRoundRect",
+        "snip1Rect",
+        "snip2SameRect",
+        "snip2DiagRect",
+        "plaque",
+        "ellipse",
+        "teardrop",
+        "homePlate",
+        "chevron",
+        "pieWedge",
+        "pie",
+        "blockArc",
+        "donut",
+        "noSmoking",
+        "rightArrow",
+        "leftArrow",
+        "upArrow",
+        "downArrow",
+        "stripedRightArrow",
+        "notchedRightArrow",
+        "bentUpArrow",
+        "leftRightArrow",
+        "upDownArrow",
+        "leftUpArrow",
+        "leftRightUpArrow",
+        "quadArrow",
+        "leftArrowCallout",
+        "rightArrowCallout",
+        "upArrowCallout",
+        "downArrowCallout",
+        "leftRightArrowCallout",
+        "upDownArrowCallout",
+        "quadArrowCallout",
+        "bentArrow",
+        "uturnArrow",
+        "circularArrow",
+        "leftCircularArrow",
+        "leftRightCircularArrow",
+        "curvedRightArrow",
+        "curvedLeftArrow",
+        "curvedUpArrow",
+        "curvedDownArrow",
+        "swooshArrow",
+        "cube",
+        "can",
+        "lightningBolt",
+        "heart",
+        "sun",
+        "moon",
+        "smileyFace",
+        "irregularSeal1",
+        "irregularSeal2",
+        "foldedCorner",
+        "bevel",
+        "frame",
+        "halfFrame",
+        "corner",
+        "diagStripe",
+        "chord",
+        "arc",
+        "leftBracket",
+        "rightBracket",
+        "leftBrace",
+        "rightBrace",
+        "bracketPair",
+        "bracePair",
+        "straightConnector1",
+        "bentConnector2",
+        "bentConnector3",
+        "bentConnector4",
+        "bentConnector5",
+        "curvedConnector2",
+        "curvedConnector3",
+        "curvedConnector4",
+        "curvedConnector5",
+        "callout1",
+        "callout2",
+        "callout3",
+        "accentCallout1",
+        "accentCallout2",
+        "accentCallout3",
+        "borderCallout1",
+        "borderCallout2",
+        "borderCallout3",
+        "accentBorderCallout1",
+        "accentBorderCallout2",
+        "accentBorderCallout3",
+        "wedgeRectCallout",
+        "wedgeRoundRectCallout",
+        "wedgeEllipseCallout",
+        "cloudCallout",
+        "cloud",
+        "ribbon",
+        "ribbon2",
+        "ellipseRibbon",
+        "ellipseRibbon2",
+        "leftRightRibbon",
+        "verticalScroll",
+        "horizontalScroll",
+        "wave",
+        "doubleWave",
+        "plus",
+        "flowChartProcess",
+        "flowChartDecision",
+        "flowChartInputOutput",
+        "flowChartPredefinedProcess",
+        "flowChartInternalStorage",
+        "flowChartDocument",
+        "flowChartMultidocument",
+        "flowChartTerminator",
+        "flowChartPreparation",
+        "flowChartManualInput",
+        "flowChartManualOperation",
+        "flowChartConnector",
+        "flowChartPunchedCard",
+        "flowChartPunchedTape",
+        "flowChartSummingJunction",
+        "flowChartOr",
+        "flowChartCollate",
+        "flowChartSort",
+        "flowChartExtract",
+        "flowChartMerge",
+        "flowChartOfflineStorage",
+        "flowChartOnlineStorage",
+        "flowChartMagneticTape",
+        "flowChartMagneticDisk",
+        "flowChartMagneticDrum",
+        "flowChartDisplay",
+        "flowChartDelay",
+        "flowChartAlternateProcess",
+        "flowChartOffpageConnector",
+        "actionButtonBlank",
+        "actionButtonHome",
+        "actionButtonHelp",
+        "actionButtonInformation",
+        "actionButtonForwardNext",
+        "actionButtonBackPrevious",
+        "actionButtonEnd",
+        "actionButtonBeginning",
+        "actionButtonReturn",
+        "actionButtonDocument",
+        "actionButtonSound",
+        "actionButtonMovie",
+        "gear6",
+        "gear9",
+        "funnel",
+        "mathPlus",
+        "mathMinus",
+        "mathMultiply",
+        "mathDivide",
+        "mathEqual",
+        "mathNotEqual",
+        "cornerTabs",
+        "squareTabs",
+        "plaqueTabs",
+        "chartX",
+        "chartStar",
+        "chartPlus",
+    ];
+}
+
+impl ToXmlElement for Point2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("x", self.x)
+            .with_attribute("y", self.y)
+    }
+}
+
+impl ToXmlElement for PositiveSize2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("cx", self.width)
+            .with_attribute("cy", self.height)
+    }
+}
+
+impl ToXmlElement for Transform2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("rot", self.rotate_angle)
+            .with_optional_attribute("flipH", self.flip_horizontal)
+            .with_optional_attribute("flipV", self.flip_vertical)
+            .with_optional_child(self.offset.map(|offset| offset.to_xml_element("a:off")))
+            .with_optional_child(self.extents.map(|extents| extents.to_xml_element("a:ext")))
+    }
+}
+
+impl ToXmlElement for GroupTransform2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("rot", self.rotate_angle)
+            .with_optional_attribute("flipH", self.flip_horizontal)
+            .with_optional_attribute("flipV", self.flip_vertical)
+            .with_optional_child(self.offset.map(|offset| offset.to_xml_element("a:off")))
+            .with_optional_child(self.extents.map(|extents| extents.to_xml_element("a:ext")))
+            .with_optional_child(self.child_offset.map(|offset| offset.to_xml_element("a:chOff")))
+            .with_optional_child(self.child_extents.map(|extents| extents.to_xml_element("a:chExt")))
+    }
+}
+
+impl ToXmlElement for Hyperlink {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("r:id", self.relationship_id.as_ref())
+            .with_optional_attribute("action", self.action.as_ref())
+    }
+}
+
+impl ToXmlElement for NonVisualDrawingProps {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("id", self.id)
+            .with_attribute("name", &self.name)
+            .with_optional_attribute("descr", self.description.as_ref())
+            .with_optional_attribute("hidden", self.hidden)
+            .with_optional_attribute("title", self.title.as_ref())
+            .with_optional_child(
+                self.hyperlink_click
+                    .as_ref()
+                    .map(|hyperlink| hyperlink.to_xml_element("a:hlinkClick")),
+            )
+            .with_optional_child(
+                self.hyperlink_hover
+                    .as_ref()
+                    .map(|hyperlink| hyperlink.to_xml_element("a:hlinkHover")),
+            )
+    }
+}
+
+impl ToXmlElement for NonVisualDrawingShapeProps {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_optional_attribute("txBox", self.is_text_box)
+    }
+}
+
+impl ToXmlElement for NonVisualGroupDrawingShapeProps {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+    }
+}
+
+impl ToXmlElement for NonVisualGraphicFrameProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+    }
+}
+
+impl ToXmlElement for NonVisualPictureProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_optional_attribute("preferRelativeResize", self.prefer_relative_resize)
+    }
+}
+
+impl ToXmlElement for Connection {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("id", self.id)
+            .with_attribute("idx", self.shape_index)
+    }
+}
+
+impl ToXmlElement for NonVisualConnectorProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_child(self.connector_locks.as_ref().map(|_| XmlNode::new("a:cxnSpLocks")))
+            .with_optional_child(
+                self.start_connection
+                    .as_ref()
+                    .map(|connection| connection.to_xml_element("a:stCxn")),
+            )
+            .with_optional_child(
+                self.end_connection
+                    .as_ref()
+                    .map(|connection| connection.to_xml_element("a:endCxn")),
+            )
+    }
+}
+
+impl ToXmlElement for ShapeProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("bwMode", self.black_and_white_mode.map(|mode| mode.schema_value()))
+            .with_optional_child(
+                self.transform
+                    .as_ref()
+                    .map(|transform| transform.to_xml_element("a:xfrm")),
+            )
+            .with_optional_child(self.geometry.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(self.fill_properties.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(self.line_properties.as_ref().map(|line| line.to_xml_element("a:ln")))
+            .with_optional_child(self.effect_properties.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(self.scene_3d.as_ref().map(|scene| scene.to_xml_element("a:scene3d")))
+            .with_optional_child(self.shape_3d.as_ref().map(|shape| shape.to_xml_element("a:sp3d")))
+    }
+}
+
+impl ToXmlElement for GroupShapeProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("bwMode", self.black_and_white_mode.map(|mode| mode.schema_value()))
+            .with_optional_child(
+                self.transform
+                    .as_ref()
+                    .map(|transform| transform.to_xml_element("a:xfrm")),
+            )
+            .with_optional_child(self.fill_properties.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(self.effect_properties.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(self.scene_3d.as_ref().map(|scene| scene.to_xml_element("a:scene3d")))
+    }
+}
+
+impl ToXmlElement for StyleMatrixReference {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("idx", self.index)
+            .with_optional_child(self.color.as_ref().map(ToXmlChoice::to_xml_element))
+    }
+}
+
+impl ToXmlElement for FontReference {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("idx", self.index.schema_value())
+            .with_optional_child(self.color.as_ref().map(ToXmlChoice::to_xml_element))
+    }
+}
+
+impl ToXmlElement for ShapeStyle {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_child(self.line_reference.to_xml_element("a:lnRef"))
+            .with_child(self.fill_reference.to_xml_element("a:fillRef"))
+            .with_child(self.effect_reference.to_xml_element("a:effectRef"))
+            .with_child(self.font_reference.to_xml_element("a:fontRef"))
+    }
+}
+
+impl ToXmlElement for ColorMapping {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("bg1", self.background1.schema_value())
+            .with_attribute("tx1", self.text1.schema_value())
+            .with_attribute("bg2", self.background2.schema_value())
+            .with_attribute("tx2", self.text2.schema_value())
+            .with_attribute("accent1", self.accent1.schema_value())
+            .with_attribute("accent2", self.accent2.schema_value())
+            .with_attribute("accent3", self.accent3.schema_value())
+            .with_attribute("accent4", self.accent4.schema_value())
+            .with_attribute("accent5", self.accent5.schema_value())
+            .with_attribute("accent6", self.accent6.schema_value())
+            .with_attribute("hlink", self.hyperlink.schema_value())
+            .with_attribute("folHlink", self.followed_hyperlink.schema_value())
+    }
+}
+
+impl ToXmlChoice for ColorMappingOverride {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            ColorMappingOverride::UseMasterColorMapping => XmlNode::new("a:masterClrMapping"),
+            ColorMappingOverride::OverrideColorMapping(mapping) => mapping.to_xml_element("a:overrideClrMapping"),
+        }
+    }
+}
+
+impl ToXmlChoice for ColorTransform {
+    fn to_xml_element(&self) -> XmlNode {
+        let (node_name, value) = match self {
+            ColorTransform::Tint(value) => ("a:tint", Some(*value)),
+            ColorTransform::Shade(value) => ("a:shade", Some(*value)),
+            ColorTransform::Complement => ("a:comp", None),
+            ColorTransform::Inverse => ("a:inv", None),
+            ColorTransform::Grayscale => ("a:gray", None),
+            ColorTransform::Alpha(value) => ("a:alpha", Some(*value)),
+            ColorTransform::AlphaOffset(value) => ("a:alphaOff", Some(*value)),
+            ColorTransform::AlphaModulate(value) => ("a:alphaMod", Some(*value)),
+            ColorTransform::Hue(value) => return XmlNode::new("a:hue").with_attribute("val", value),
+            ColorTransform::HueOffset(value) => return XmlNode::new("a:hueOff").with_attribute("val", value),
+            ColorTransform::HueModulate(value) => ("a:hueMod", Some(*value)),
+            ColorTransform::Saturation(value) => ("a:sat", Some(*value)),
+            ColorTransform::SaturationOffset(value) => ("a:satOff", Some(*value)),
+            ColorTransform::SaturationModulate(value) => ("a:satMod", Some(*value)),
+            ColorTransform::Luminance(value) => ("a:lum", Some(*value)),
+            ColorTransform::LuminanceOffset(value) => ("a:lumOff", Some(*value)),
+            ColorTransform::LuminanceModulate(value) => ("a:lumMod", Some(*value)),
+            ColorTransform::Red(value) => ("a:red", Some(*value)),
+            ColorTransform::RedOffset(value) => ("a:redOff", Some(*value)),
+            ColorTransform::RedModulate(value) => ("a:redMod", Some(*value)),
+            ColorTransform::Green(value) => ("a:green", Some(*value)),
+            ColorTransform::GreenOffset(value) => ("a:greenOff", Some(*value)),
+            ColorTransform::GreenModulate(value) => ("a:greenMod", Some(*value)),
+            ColorTransform::Blue(value) => ("a:blue", Some(*value)),
+            ColorTransform::BlueOffset(value) => ("a:blueOff", Some(*value)),
+            ColorTransform::BlueModulate(value) => ("a:blueMod", Some(*value)),
+            ColorTransform::Gamma => ("a:gamma", None),
+            ColorTransform::InverseGamma => ("a:invGamma", None),
+        };
+
+        XmlNode::new(node_name).with_optional_attribute("val", value)
+    }
+}
+
+impl ToXmlChoice for Color {
+    fn to_xml_element(&self) -> XmlNode {
+        let (xml_node, color_transforms) = match self {
+            Color::ScRgbColor(color) => (
+                XmlNode::new("a:scrgbClr")
+                    .with_attribute("r", color.r)
+                    .with_attribute("g", color.g)
+                    .with_attribute("b", color.b),
+                &color.color_transforms,
+            ),
+            Color::SRgbColor(color) => (
+                XmlNode::new("a:srgbClr").with_attribute("val", format!("{:06X}", color.value)),
+                &color.color_transforms,
+            ),
+            Color::HslColor(color) => (
+                XmlNode::new("a:hslClr")
+                    .with_attribute("hue", color.hue)
+                    .with_attribute("sat", color.saturation)
+                    .with_attribute("lum", color.luminance),
+                &color.color_transforms,
+            ),
+            Color::SystemColor(color) => (
+                XmlNode::new("a:sysClr")
+                    .with_attribute("val", color.value.schema_value())
+                    .with_optional_attribute("lastClr", color.last_color.as_ref()),
+                &color.color_transforms,
+            ),
+            Color::SchemeColor(color) => (
+                XmlNode::new("a:schemeClr").with_attribute("val", color.value.schema_value()),
+                &color.color_transforms,
+            ),
+            Color::PresetColor(color) => (
+                XmlNode::new("a:prstClr").with_attribute("val", color.value.schema_value()),
+                &color.color_transforms,
+            ),
+        };
+
+        xml_node.with_children(color_transforms.iter().map(ToXmlChoice::to_xml_element))
+    }
+}
+
+impl ToXmlElement for RelativeRect {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("l", self.left)
+            .with_optional_attribute("t", self.top)
+            .with_optional_attribute("r", self.right)
+            .with_optional_attribute("b", self.bottom)
+    }
+}
+
+impl ToXmlChoice for ShadeProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            ShadeProperties::Linear(linear) => XmlNode::new("a:lin")
+                .with_optional_attribute("ang", linear.angle)
+                .with_optional_attribute("scaled", linear.scaled),
+            ShadeProperties::Path(path) => XmlNode::new("a:path")
+                .with_optional_attribute("path", path.path.map(|path| path.schema_value()))
+                .with_optional_child(
+                    path.fill_to_rect
+                        .as_ref()
+                        .map(|rect| rect.to_xml_element("a:fillToRect")),
+                ),
+        }
+    }
+}
+
+impl ToXmlElement for GradientFillProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let gradient_stop_list = self.gradient_stop_list.as_ref().map(|gradient_stop_list| {
+            XmlNode::new("a:gsLst").with_children(gradient_stop_list.iter().map(|gradient_stop| {
+                XmlNode::new("a:gs")
+                    .with_attribute("pos", gradient_stop.position)
+                    .with_child(gradient_stop.color.to_xml_element())
+            }))
+        });
+
+        XmlNode::new(node_name)
+            .with_optional_attribute("flip", self.flip.map(|flip| flip.schema_value()))
+            .with_optional_attribute("rotWithShape", self.rotate_with_shape)
+            .with_optional_child(gradient_stop_list)
+            .with_optional_child(self.shade_properties.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(self.tile_rect.as_ref().map(|rect| rect.to_xml_element("a:tileRect")))
+    }
+}
+
+impl ToXmlChoice for FillModeProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            FillModeProperties::Tile => XmlNode::new("a:tile"),
+            FillModeProperties::Stretch(fill_rect) => XmlNode::new("a:stretch")
+                .with_optional_child(fill_rect.as_ref().map(|rect| rect.to_xml_element("a:fillRect"))),
+        }
+    }
+}
+
+impl ToXmlElement for BlipFillProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let blip = self.blip.as_ref().map(|blip| {
+            XmlNode::new("a:blip")
+                .with_optional_attribute("r:embed", blip.embed_rel_id.as_ref())
+                .with_optional_attribute("r:link", blip.linked_rel_id.as_ref())
+        });
+
+        XmlNode::new(node_name)
+            .with_optional_attribute("dpi", self.dpi)
+            .with_optional_attribute("rotWithShape", self.rotate_with_shape)
+            .with_optional_child(blip)
+            .with_optional_child(self.source_rect.as_ref().map(|rect| rect.to_xml_element("a:srcRect")))
+            .with_optional_child(self.fill_mode_properties.as_ref().map(ToXmlChoice::to_xml_element))
+    }
+}
+
+impl ToXmlElement for PatternFillProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("prst", self.preset.as_ref())
+            .with_optional_child(
+                self.fg_color
+                    .as_ref()
+                    .map(|color| XmlNode::new("a:fgClr").with_child(color.to_xml_element())),
+            )
+            .with_optional_child(
+                self.bg_color
+                    .as_ref()
+                    .map(|color| XmlNode::new("a:bgClr").with_child(color.to_xml_element())),
+            )
+    }
+}
+
+impl ToXmlChoice for FillProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            FillProperties::NoFill => XmlNode::new("a:noFill"),
+            FillProperties::SolidFill(color) => XmlNode::new("a:solidFill").with_child(color.to_xml_element()),
+            FillProperties::GradientFill(gradient) => gradient.to_xml_element("a:gradFill"),
+            FillProperties::BlipFill(blip_fill) => blip_fill.to_xml_element("a:blipFill"),
+            FillProperties::PatternFill(pattern) => pattern.to_xml_element("a:pattFill"),
+            FillProperties::GroupFill => XmlNode::new("a:grpFill"),
+        }
+    }
+}
+
+impl ToXmlChoice for LineFillProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            LineFillProperties::NoFill => XmlNode::new("a:noFill"),
+            LineFillProperties::SolidFill(color) => XmlNode::new("a:solidFill").with_child(color.to_xml_element()),
+            LineFillProperties::GradientFill(gradient) => gradient.to_xml_element("a:gradFill"),
+            LineFillProperties::PatternFill(pattern) => pattern.to_xml_element("a:pattFill"),
+        }
+    }
+}
+
+impl ToXmlChoice for LineDashProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            LineDashProperties::PresetDash(preset) => {
+                XmlNode::new("a:prstDash").with_attribute("val", preset.schema_value())
+            }
+            LineDashProperties::CustomDash(dash_stops) => {
+                XmlNode::new("a:custDash").with_children(dash_stops.iter().map(|dash_stop| {
+                    XmlNode::new("a:ds")
+                        .with_attribute("d", dash_stop.dash_length)
+                        .with_attribute("sp", dash_stop.space_length)
+                }))
+            }
+        }
+    }
+}
+
+impl ToXmlChoice for LineJoinProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            LineJoinProperties::Round => XmlNode::new("a:round"),
+            LineJoinProperties::Bevel => XmlNode::new("a:bevel"),
+            LineJoinProperties::Miter(limit) => XmlNode::new("a:miter").with_optional_attribute("lim", *limit),
+        }
+    }
+}
+
+impl ToXmlElement for LineEndProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("type", self.end_type.map(|end_type| end_type.schema_value()))
+            .with_optional_attribute("w", self.width.map(|width| width.schema_value()))
+            .with_optional_attribute("len", self.length.map(|length| length.schema_value()))
+    }
+}
+
+impl ToXmlElement for LineProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("w", self.width)
+            .with_optional_attribute("cap", self.cap.map(|cap| cap.schema_value()))
+            .with_optional_child(self.fill_properties.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(self.dash_properties.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(self.join_properties.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(
+                self.head_end
+                    .as_ref()
+                    .map(|head_end| head_end.to_xml_element("a:headEnd")),
+            )
+            .with_optional_child(
+                self.tail_end
+                    .as_ref()
+                    .map(|tail_end| tail_end.to_xml_element("a:tailEnd")),
+            )
+    }
+}
+
+impl ToXmlChoice for EffectProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            EffectProperties::EffectList(_) => XmlNode::new("a:effectLst"),
+            EffectProperties::EffectContainer(_) => XmlNode::new("a:effectDag"),
+        }
+    }
+}
+
+impl ToXmlElement for Scene3D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+    }
+}
+
+impl ToXmlElement for Shape3D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+    }
+}
+
+fn adj_coordinate_value(coordinate: &AdjCoordinate) -> String {
+    match coordinate {
+        AdjCoordinate::Coordinate(coordinate) => coordinate.to_string(),
+        AdjCoordinate::GeomGuideName(name) => name.clone(),
+    }
+}
+
+fn adj_angle_value(angle: &AdjAngle) -> String {
+    match angle {
+        AdjAngle::Angle(angle) => angle.to_string(),
+        AdjAngle::GeomGuideName(name) => name.clone(),
+    }
+}
+
+impl ToXmlElement for AdjPoint2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("x", adj_coordinate_value(&self.x))
+            .with_attribute("y", adj_coordinate_value(&self.y))
+    }
+}
+
+impl ToXmlElement for GeomGuide {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("name", &self.name)
+            .with_attribute("fmla", &self.formula)
+    }
+}
+
+fn geom_guide_list(node_name: &str, guides: &[GeomGuide]) -> XmlNode {
+    XmlNode::new(node_name).with_children(guides.iter().map(|guide| guide.to_xml_element("a:gd")))
+}
+
+impl ToXmlChoice for Path2DCommand {
+    fn to_xml_element(&self) -> XmlNode {
+        let with_points = |node_name: &str, points: &[&AdjPoint2D]| {
+            XmlNode::new(node_name).with_children(points.iter().map(|point| point.to_xml_element("a:pt")))
+        };
+
+        match self {
+            Path2DCommand::Close => XmlNode::new("a:close"),
+            Path2DCommand::MoveTo(point) => with_points("a:moveTo", &[point]),
+            Path2DCommand::LineTo(point) => with_points("a:lnTo", &[point]),
+            Path2DCommand::ArcTo(arc) => XmlNode::new("a:arcTo")
+                .with_attribute("wR", adj_coordinate_value(&arc.width_radius))
+                .with_attribute("hR", adj_coordinate_value(&arc.height_radius))
+                .with_attribute("stAng", adj_angle_value(&arc.start_angle))
+                .with_attribute("swAng", adj_angle_value(&arc.swing_angle)),
+            Path2DCommand::QuadBezierTo(control, end) => with_points("a:quadBezTo", &[control, end]),
+            Path2DCommand::CubicBezTo(control1, control2, end) => {
+                with_points("a:cubicBezTo", &[control1, control2, end])
+            }
+        }
+    }
+}
+
+impl ToXmlElement for Path2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("w", self.width)
+            .with_optional_attribute("h", self.height)
+            .with_optional_attribute("fill", self.fill_mode.map(|fill_mode| fill_mode.schema_value()))
+            .with_optional_attribute("stroke", self.stroke)
+            .with_optional_attribute("extrusionOk", self.extrusion_ok)
+            .with_children(self.commands.iter().map(ToXmlChoice::to_xml_element))
+    }
+}
+
+impl ToXmlElement for CustomGeometry2D {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let connection_site_list = self.connection_site_list.as_ref().map(|connection_sites| {
+            XmlNode::new("a:cxnLst").with_children(connection_sites.iter().map(|connection_site| {
+                XmlNode::new("a:cxn")
+                    .with_attribute("ang", adj_angle_value(&connection_site.angle))
+                    .with_child(connection_site.position.to_xml_element("a:pos"))
+            }))
+        });
+        let rect = self.rect.as_ref().map(|rect| {
+            XmlNode::new("a:rect")
+                .with_attribute("l", adj_coordinate_value(&rect.left))
+                .with_attribute("t", adj_coordinate_value(&rect.top))
+                .with_attribute("r", adj_coordinate_value(&rect.right))
+                .with_attribute("b", adj_coordinate_value(&rect.bottom))
+        });
+
+        // The shared crate doesn't expose the contents of adjust handles, so only the list itself can be written
+        XmlNode::new(node_name)
+            .with_optional_child(
+                self.adjust_value_list
+                    .as_ref()
+                    .map(|guides| geom_guide_list("a:avLst", guides)),
+            )
+            .with_optional_child(
+                self.guide_list
+                    .as_ref()
+                    .map(|guides| geom_guide_list("a:gdLst", guides)),
+            )
+            .with_optional_child(self.adjust_handle_list.as_ref().map(|_| XmlNode::new("a:ahLst")))
+            .with_optional_child(connection_site_list)
+            .with_optional_child(rect)
+            .with_child(
+                XmlNode::new("a:pathLst")
+                    .with_children(self.path_list.iter().map(|path| path.to_xml_element("a:path"))),
+            )
+    }
+}
+
+impl ToXmlChoice for Geometry {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            Geometry::Custom(custom) => custom.to_xml_element("a:custGeom"),
+            Geometry::Preset(preset) => XmlNode::new("a:prstGeom")
+                .with_attribute("prst", preset.preset.schema_value())
+                .with_optional_child(
+                    preset
+                        .adjust_value_list
+                        .as_ref()
+                        .map(|guides| geom_guide_list("a:avLst", guides)),
+                ),
+        }
+    }
+}
+
+impl ToXmlElement for TextBodyProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("rot", self.rotate_angle)
+            .with_optional_attribute("lIns", self.left_inset)
+            .with_optional_attribute("tIns", self.top_inset)
+            .with_optional_attribute("rIns", self.right_inset)
+            .with_optional_attribute("bIns", self.bottom_inset)
+            .with_optional_attribute("anchor", self.anchor.map(|anchor| anchor.schema_value()))
+    }
+}
+
+impl ToXmlElement for TextFont {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("typeface", &self.typeface)
+            .with_optional_attribute("panose", self.panose.as_ref())
+            .with_optional_attribute("pitchFamily", self.pitch_family)
+            .with_optional_attribute("charset", self.charset)
+    }
+}
+
+impl ToXmlElement for TextCharacterProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let font = |font: &Option<TextFont>, node_name: &str| font.as_ref().map(|font| font.to_xml_element(node_name));
+
+        XmlNode::new(node_name)
+            .with_optional_attribute("lang", self.language.as_ref())
+            .with_optional_attribute("sz", self.font_size)
+            .with_optional_attribute("b", self.bold)
+            .with_optional_attribute("i", self.italic)
+            .with_optional_attribute("u", self.underline.map(|underline| underline.schema_value()))
+            .with_optional_attribute("strike", self.strikethrough.map(|strike| strike.schema_value()))
+            .with_optional_attribute("cap", self.capitalization.map(|cap| cap.schema_value()))
+            .with_optional_attribute("baseline", self.baseline)
+            .with_optional_child(self.line_properties.as_ref().map(|line| line.to_xml_element("a:ln")))
+            .with_optional_child(self.fill_properties.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(font(&self.latin_font, "a:latin"))
+            .with_optional_child(font(&self.east_asian_font, "a:ea"))
+            .with_optional_child(font(&self.complex_script_font, "a:cs"))
+            .with_optional_child(font(&self.symbol_font, "a:sym"))
+    }
+}
+
+impl ToXmlElement for TextParagraphProperties {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("marL", self.margin_left)
+            .with_optional_attribute("marR", self.margin_right)
+            .with_optional_attribute("lvl", self.level)
+            .with_optional_attribute("indent", self.indent)
+            .with_optional_attribute("algn", self.align.map(|align| align.schema_value()))
+            .with_optional_child(
+                self.default_run_properties
+                    .as_ref()
+                    .map(|properties| properties.to_xml_element("a:defRPr")),
+            )
+    }
+}
+
+impl ToXmlElement for TextListStyle {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let levels = [
+            (&self.def_paragraph_props, "a:defPPr"),
+            (&self.lvl1_paragraph_props, "a:lvl1pPr"),
+            (&self.lvl2_paragraph_props, "a:lvl2pPr"),
+            (&self.lvl3_paragraph_props, "a:lvl3pPr"),
+            (&self.lvl4_paragraph_props, "a:lvl4pPr"),
+            (&self.lvl5_paragraph_props, "a:lvl5pPr"),
+            (&self.lvl6_paragraph_props, "a:lvl6pPr"),
+            (&self.lvl7_paragraph_props, "a:lvl7pPr"),
+            (&self.lvl8_paragraph_props, "a:lvl8pPr"),
+            (&self.lvl9_paragraph_props, "a:lvl9pPr"),
+        ];
+
+        XmlNode::new(node_name).with_children(levels.iter().filter_map(|(properties, node_name)| {
+            properties
+                .as_ref()
+                .map(|properties| properties.to_xml_element(node_name))
+        }))
+    }
+}
+
+impl ToXmlChoice for TextRun {
+    fn to_xml_element(&self) -> XmlNode {
+        let char_properties = |properties: &Option<Box<TextCharacterProperties>>| {
+            properties.as_ref().map(|properties| properties.to_xml_element("a:rPr"))
+        };
+
+        match self {
+            TextRun::RegularTextRun(run) => XmlNode::new("a:r")
+                .with_optional_child(char_properties(&run.char_properties))
+                .with_child(XmlNode::new("a:t").with_text(run.text.as_str())),
+            TextRun::LineBreak(line_break) => {
+                XmlNode::new("a:br").with_optional_child(char_properties(&line_break.char_properties))
+            }
+            TextRun::TextField(field) => XmlNode::new("a:fld")
+                .with_attribute("id", &field.id)
+                .with_optional_attribute("type", field.field_type.as_ref())
+                .with_optional_child(char_properties(&field.char_properties))
+                .with_optional_child(
+                    field
+                        .paragraph_properties
+                        .as_ref()
+                        .map(|properties| properties.to_xml_element("a:pPr")),
+                )
+                .with_optional_child(
+                    field
+                        .text
+                        .as_ref()
+                        .map(|text| XmlNode::new("a:t").with_text(text.as_str())),
+                ),
+        }
+    }
+}
+
+impl ToXmlElement for TextParagraph {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_child(
+                self.properties
+                    .as_ref()
+                    .map(|properties| properties.to_xml_element("a:pPr")),
+            )
+            .with_children(self.text_run_list.iter().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(
+                self.end_paragraph_char_properties
+                    .as_ref()
+                    .map(|properties| properties.to_xml_element("a:endParaRPr")),
+            )
+    }
+}
+
+impl ToXmlElement for TextBody {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_child(self.body_properties.to_xml_element("a:bodyPr"))
+            .with_optional_child(
+                self.list_style
+                    .as_ref()
+                    .map(|list_style| list_style.to_xml_element("a:lstStyle")),
+            )
+            .with_children(
+                self.paragraph_array
+                    .iter()
+                    .map(|paragraph| paragraph.to_xml_element("a:p")),
+            )
+    }
+}
+
+impl ToXmlElement for EmbeddedWAVAudioFile {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("r:embed", &self.embed_rel_id)
+            .with_optional_attribute("name", self.name.as_ref())
+    }
+}
+
+impl ToXmlChoice for Media {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            Media::AudioCd => XmlNode::new("a:audioCd"),
+            Media::WavAudioFile(audio_file) => audio_file.to_xml_element("a:wavAudioFile"),
+            Media::AudioFile(link) => XmlNode::new("a:audioFile").with_attribute("r:link", link),
+            Media::VideoFile(link) => XmlNode::new("a:videoFile").with_attribute("r:link", link),
+            Media::QuickTimeFile(link) => XmlNode::new("a:quickTimeFile").with_attribute("r:link", link),
+        }
+    }
+}
+
+impl ToXmlChoice for AnimationElementChoice {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            AnimationElementChoice::Diagram => XmlNode::new("a:dgm"),
+            AnimationElementChoice::Chart => XmlNode::new("a:chart"),
+        }
+    }
+}
+
+impl ToXmlChoice for AnimationGraphicalObjectBuildProperties {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            AnimationGraphicalObjectBuildProperties::BuildDiagram => XmlNode::new("a:bldDgm"),
+            AnimationGraphicalObjectBuildProperties::BuildChart => XmlNode::new("a:bldChart"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_shape_properties_round_trip() {
+        let xml = r#"<spPr bwMode="auto">
+            <xfrm rot="5400000" flipH="1"><off x="100" y="200"/><ext cx="300" cy="400"/></xfrm>
+            <prstGeom prst="roundRect"><avLst><gd name="adj" fmla="val 16667"/></avLst></prstGeom>
+            <solidFill><schemeClr val="accent1"><lumMod val="75000"/></schemeClr></solidFill>
+            <ln w="12700" cap="rnd">
+                <solidFill><srgbClr val="0A0B0C"/></solidFill>
+                <prstDash val="sysDash"/>
+                <round/>
+                <tailEnd type="triangle" w="med" len="lg"/>
+            </ln>
+        </spPr>"#;
+        let shape_properties = ShapeProperties::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        let xml_node = shape_properties.to_xml_element("p:spPr");
+        assert_eq!(xml_node.attributes.get("bwMode").map(String::as_str), Some("auto"));
+        assert_eq!(ShapeProperties::from_xml_element(&xml_node).unwrap(), shape_properties);
+    }
+
+    #[test]
+    pub fn test_text_body_round_trip() {
+        let xml = r#"<txBody>
+            <bodyPr anchor="ctr" lIns="91440"/>
+            <lstStyle><lvl1pPr marL="0" algn="ctr"><defRPr sz="2400" b="1"/></lvl1pPr></lstStyle>
+            <p>
+                <r><rPr lang="en-US" u="sng"><latin typeface="Arial"/></rPr><t> Hello &amp; </t></r>
+                <br/>
+                <fld id="{1}" type="slidenum"><t>1</t></fld>
+                <endParaRPr lang="en-US"/>
+            </p>
+        </txBody>"#;
+        let text_body = TextBody::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        let xml_node = text_body.to_xml_element("p:txBody");
+        assert_eq!(TextBody::from_xml_element(&xml_node).unwrap(), text_body);
+    }
+}
@@ -0,0 +1,257 @@
+use msoffice_shared::{
+    drawingml::{core::TextBody, simpletypes::Coordinate},
+    error::{MissingAttributeError, MissingChildNodeError},
+    xml::{parse_xml_bool, XmlNode},
+};
+use std::error::Error;
+
+use crate::xml::{ToXmlElement, XmlNodeBuilder};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// The uri of the graphicData element that contains a table.
+pub const TABLE_GRAPHIC_DATA_URI: &str = "http://schemas.openxmlformats.org/drawingml/2006/table";
+
+/// This element is the root element for a table. Within this element is contained everything that one would need to
+/// define a table within DrawingML.
+///
+/// # Xml example
+///
+/// ```xml
+/// <a:tbl>
+///   <a:tblGrid>
+///     <a:gridCol w="2743200"/>
+///     <a:gridCol w="2743200"/>
+///   </a:tblGrid>
+///   <a:tr h="370840">
+///     <a:tc>
+///       <a:txBody>
+///         <a:bodyPr/>
+///         <a:lstStyle/>
+///         <a:p><a:r><a:t>Cell 1</a:t></a:r></a:p>
+///       </a:txBody>
+///     </a:tc>
+///     <a:tc>
+///       <a:txBody>
+///         <a:bodyPr/>
+///         <a:lstStyle/>
+///         <a:p><a:r><a:t>Cell 2</a:t></a:r></a:p>
+///       </a:txBody>
+///     </a:tc>
+///   </a:tr>
+/// </a:tbl>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    /// This element specifies the table grid. The table grid describes the width of each column of the table.
+    pub grid_column_list: Vec<TableGridColumn>,
+    /// This element specifies a row in the table.
+    pub row_list: Vec<TableRow>,
+}
+
+impl Table {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut grid_column_list = None;
+        let mut row_list = Vec::new();
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "tblGrid" => {
+                    grid_column_list = Some(
+                        child_node
+                            .child_nodes
+                            .iter()
+                            .filter(|grid_col_node| grid_col_node.local_name() == "gridCol")
+                            .map(TableGridColumn::from_xml_element)
+                            .collect::<Result<Vec<_>>>()?,
+                    )
+                }
+                "tr" => row_list.push(TableRow::from_xml_element(child_node)?),
+                _ => (),
+            }
+        }
+
+        let grid_column_list =
+            grid_column_list.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "tblGrid"))?;
+
+        Ok(Self {
+            grid_column_list,
+            row_list,
+        })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        let grid =
+            XmlNode::new("a:tblGrid").with_children(self.grid_column_list.iter().map(TableGridColumn::to_xml_element));
+
+        XmlNode::new("a:tbl")
+            .with_child(grid)
+            .with_children(self.row_list.iter().map(TableRow::to_xml_element))
+    }
+}
+
+/// This element specifies a column in the table grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableGridColumn {
+    /// Specifies the width of the grid column.
+    pub width: Coordinate,
+}
+
+impl TableGridColumn {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let width = xml_node
+            .attributes
+            .get("w")
+            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "w"))?
+            .parse()?;
+
+        Ok(Self { width })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("a:gridCol").with_attribute("w", self.width)
+    }
+}
+
+/// This element specifies a row in the table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableRow {
+    /// Specifies the height of the row.
+    pub height: Coordinate,
+    /// This element specifies a cell in the row. The cells are listed in the same order as the columns of the table
+    /// grid, including cells that are covered by a merged cell.
+    pub cell_list: Vec<TableCell>,
+}
+
+impl TableRow {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let height = xml_node
+            .attributes
+            .get("h")
+            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "h"))?
+            .parse()?;
+
+        let cell_list = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "tc")
+            .map(TableCell::from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { height, cell_list })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("a:tr")
+            .with_attribute("h", self.height)
+            .with_children(self.cell_list.iter().map(TableCell::to_xml_element))
+    }
+}
+
+/// This element specifies a cell in a table row.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TableCell {
+    /// Specifies the number of rows that a merged cell spans.
+    ///
+    /// Defaults to 1
+    pub row_span: Option<i32>,
+    /// Specifies the number of columns that a merged cell spans.
+    ///
+    /// Defaults to 1
+    pub grid_span: Option<i32>,
+    /// Specifies whether this cell is covered by a horizontally merged cell to its left.
+    ///
+    /// Defaults to false
+    pub horizontal_merge: Option<bool>,
+    /// Specifies whether this cell is covered by a vertically merged cell above it.
+    ///
+    /// Defaults to false
+    pub vertical_merge: Option<bool>,
+    /// This element specifies the text body of the cell.
+    pub text_body: Option<TextBody>,
+}
+
+impl TableCell {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "rowSpan" => instance.row_span = Some(value.parse()?),
+                "gridSpan" => instance.grid_span = Some(value.parse()?),
+                "hMerge" => instance.horizontal_merge = Some(parse_xml_bool(value)?),
+                "vMerge" => instance.vertical_merge = Some(parse_xml_bool(value)?),
+                _ => (),
+            }
+        }
+
+        for child_node in &xml_node.child_nodes {
+            if child_node.local_name() == "txBody" {
+                instance.text_body = Some(TextBody::from_xml_element(child_node)?);
+            }
+        }
+
+        Ok(instance)
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("a:tc")
+            .with_optional_attribute("rowSpan", self.row_span)
+            .with_optional_attribute("gridSpan", self.grid_span)
+            .with_optional_attribute("hMerge", self.horizontal_merge)
+            .with_optional_attribute("vMerge", self.vertical_merge)
+            .with_optional_child(
+                self.text_body
+                    .as_ref()
+                    .map(|text_body| text_body.to_xml_element("a:txBody")),
+            )
+    }
+
+    /// Returns true if this cell is covered by a merged cell, so it isn't displayed on its own.
+    pub fn is_merged(&self) -> bool {
+        self.horizontal_merge.unwrap_or(false) || self.vertical_merge.unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    impl Table {
+        pub fn test_xml(node_name: &'static str) -> String {
+            format!(
+                r#"<{node_name}>
+                <tblGrid>
+                    <gridCol w="100"/>
+                    <gridCol w="200"/>
+                </tblGrid>
+                <tr h="50">
+                    <tc gridSpan="2">
+                        <txBody><bodyPr/><p><r><t>Header</t></r></p></txBody>
+                    </tc>
+                    <tc hMerge="1"/>
+                </tr>
+            </{node_name}>"#,
+                node_name = node_name
+            )
+        }
+    }
+
+    #[test]
+    pub fn test_table_from_xml() {
+        let xml = Table::test_xml("tbl");
+        let table = Table::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap();
+        assert_eq!(table.grid_column_list.len(), 2);
+        assert_eq!(table.grid_column_list[1].width, 200);
+        assert_eq!(table.row_list.len(), 1);
+
+        let row = &table.row_list[0];
+        assert_eq!(row.height, 50);
+        assert_eq!(row.cell_list.len(), 2);
+        assert_eq!(row.cell_list[0].grid_span, Some(2));
+        assert!(row.cell_list[0].text_body.is_some());
+        assert!(!row.cell_list[0].is_merged());
+        assert!(row.cell_list[1].is_merged());
+    }
+}
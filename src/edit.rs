@@ -0,0 +1,478 @@
+//! Editing the slides of a presentation: deleting, duplicating and reordering them.
+//!
+//! The operations keep the slide id list of the presentation, the relationships and the parts of the document in
+//! sync, so the document can be saved right away. Content type overrides are written by PPTXDocument::save for the
+//! parts of the document only, so removed parts don't leave stale overrides behind.
+
+use crate::document::{
+    relationships_path, relative_relationship_target, resolve_relationship_target, PPTXDocument, PRESENTATION_PATH,
+};
+use crate::opc::ExternalRelationships;
+use crate::pml::presentation::SlideId;
+use crate::xml::UnknownXml;
+use msoffice_shared::{relationship::Relationship, xml::XmlNode};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+impl PPTXDocument {
+    /// Deletes the slide at `slide_path` along with its relationships, its entry in the slide id list and its notes
+    /// slide. The slide is removed from the custom shows and the sections of the presentation as well. Media that was
+    /// only used by the deleted parts is removed too.
+    ///
+    /// # Note
+    ///
+    /// Hyperlinks of other slides that jump to the deleted slide aren't updated.
+    pub fn delete_slide(&mut self, slide_path: &Path) -> Result<()> {
+        if !self.slide_map.contains_key(slide_path) {
+            return Err(format!("slide not found: {}", slide_path.display()).into());
+        }
+
+        let presentation_path = Path::new(PRESENTATION_PATH);
+        let relationship_ids = self
+            .presentation_rels
+            .iter()
+            .filter(|relationship| resolve_relationship_target(presentation_path, &relationship.target) == slide_path)
+            .map(|relationship| relationship.id.clone())
+            .collect::<Vec<_>>();
+        self.presentation_rels
+            .retain(|relationship| !relationship_ids.contains(&relationship.id));
+        if let Some(presentation) = self.presentation_mut() {
+            let slide_ids = presentation
+                .slide_id_list
+                .iter()
+                .filter(|entry| relationship_ids.contains(&entry.relationship_id))
+                .map(|entry| entry.id)
+                .collect::<Vec<_>>();
+            presentation
+                .slide_id_list
+                .retain(|entry| !relationship_ids.contains(&entry.relationship_id));
+            for custom_show in &mut presentation.custom_show_list {
+                custom_show
+                    .slides
+                    .0
+                    .retain(|relationship_id| !relationship_ids.contains(relationship_id));
+            }
+            remove_section_slide_ids(&mut presentation.unknown_xml, &slide_ids);
+        }
+
+        let notes_slide_path = self.notes_slide_path(slide_path);
+        self.slide_map.remove(slide_path);
+        let slide_rels_path = relationships_path(slide_path);
+        let slide_rels = self.slide_rels_map.remove(&slide_rels_path).unwrap_or_default();
+        let mut released_part_paths = internal_targets(slide_path, &slide_rels, &self.external_relationships);
+        self.external_relationships.remove_part(&slide_rels_path);
+
+        if let Some(notes_slide_path) = notes_slide_path {
+            self.notes_slide_map.remove(&notes_slide_path);
+            let notes_slide_rels_path = relationships_path(&notes_slide_path);
+            let notes_slide_rels = self
+                .notes_slide_rels_map
+                .remove(&notes_slide_rels_path)
+                .unwrap_or_default();
+            released_part_paths.extend(internal_targets(
+                &notes_slide_path,
+                &notes_slide_rels,
+                &self.external_relationships,
+            ));
+            self.external_relationships.remove_part(&notes_slide_rels_path);
+        }
+
+        for part_path in released_part_paths {
+            if self.medias.contains(&part_path) && !self.is_part_referenced(&part_path) {
+                self.medias.retain(|media_path| *media_path != part_path);
+                self.raw_part_map.remove(&part_path);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Duplicates the slide at `slide_path` and lists the copy right after it, in the same section. The copy gets a new
+    /// slide id, a copy of the notes slide, charts, diagrams and embedded objects and a copy of every media file it
+    /// uses, so it can be edited independently. Returns the path of the copy.
+    pub fn duplicate_slide(&mut self, slide_path: &Path) -> Result<PathBuf> {
+        let copy_path = self.copy_slide(slide_path)?;
+        let copy_rels_path = relationships_path(&copy_path);
+
+        let mut copy_rels = self.slide_rels_map.remove(&copy_rels_path).unwrap_or_default();
+        let result = self.copy_media(&copy_path, &mut copy_rels);
+        self.slide_rels_map.insert(copy_rels_path, copy_rels);
+        result?;
+
+        Ok(copy_path)
+    }
+
+    /// Moves the slide at `slide_path` to `position` in the slide id list of the presentation. The position is the
+    /// index of the slide after the move.
+    pub fn move_slide(&mut self, slide_path: &Path, position: usize) -> Result<()> {
+        let current_position = self
+            .slide_position(slide_path)
+            .ok_or_else(|| format!("slide isn't listed by the presentation: {}", slide_path.display()))?;
+        let presentation = self
+            .presentation_mut()
+            .ok_or("the document doesn't have a presentation")?;
+        if position >= presentation.slide_id_list.len() {
+            return Err(format!("slide position out of range: {}", position).into());
+        }
+
+        let entry = presentation.slide_id_list.remove(current_position);
+        presentation.slide_id_list.insert(position, entry);
+        Ok(())
+    }
+
+    /// Replaces every media file referenced by `relationships` of the part at `part_path` with a copy under a new
+    /// name, e.g. ppt/media/image1.png is copied to ppt/media/image3.png.
+    fn copy_media(&mut self, part_path: &Path, relationships: &mut [Relationship]) -> Result<()> {
+        let package_part_paths = self.package_part_paths();
+        for relationship in relationships {
+            if self.is_external_relationship(part_path, relationship) {
+                continue;
+            }
+
+            let media_path = resolve_relationship_target(part_path, &relationship.target);
+            if !self.medias.contains(&media_path) {
+                continue;
+            }
+
+            let media_copy_path = self.find_unused_copy_path(&package_part_paths, &media_path);
+            let data = self.read_part(&media_path)?;
+            self.raw_part_map.insert(media_copy_path.clone(), data);
+            self.medias.push(media_copy_path.clone());
+            relationship.target = relative_relationship_target(part_path, &media_copy_path);
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the slide id lists of the sections of the presentation. Sections are stored in an extension of the
+/// extension list, since they were added to the format later on, and list their slides by slide id.
+fn section_slide_id_lists(unknown_xml: &mut UnknownXml) -> impl Iterator<Item = &mut XmlNode> {
+    unknown_xml
+        .child_nodes
+        .iter_mut()
+        .map(|(_, child_node)| child_node)
+        .filter(|child_node| child_node.local_name() == "extLst")
+        .flat_map(|extension_list| extension_list.child_nodes.iter_mut())
+        .flat_map(|extension| extension.child_nodes.iter_mut())
+        .filter(|child_node| child_node.local_name() == "sectionLst")
+        .flat_map(|section_list| section_list.child_nodes.iter_mut())
+        .flat_map(|section| section.child_nodes.iter_mut())
+        .filter(|child_node| child_node.local_name() == "sldIdLst")
+}
+
+fn is_section_slide_id(xml_node: &XmlNode, slide_id: SlideId) -> bool {
+    xml_node.attributes.get("id").and_then(|id| id.parse().ok()) == Some(slide_id)
+}
+
+/// Removes the slides with the given ids from the sections of the presentation.
+fn remove_section_slide_ids(unknown_xml: &mut UnknownXml, slide_ids: &[SlideId]) {
+    for slide_id_list in section_slide_id_lists(unknown_xml) {
+        slide_id_list.child_nodes.retain(|slide_id_node| {
+            !slide_ids
+                .iter()
+                .any(|slide_id| is_section_slide_id(slide_id_node, *slide_id))
+        });
+    }
+}
+
+/// Lists the slide with `new_slide_id` right after the slide with `slide_id` in the section of the presentation that
+/// contains the latter.
+pub(crate) fn insert_section_slide_id(unknown_xml: &mut UnknownXml, slide_id: SlideId, new_slide_id: SlideId) {
+    for slide_id_list in section_slide_id_lists(unknown_xml) {
+        let position = slide_id_list
+            .child_nodes
+            .iter()
+            .position(|slide_id_node| is_section_slide_id(slide_id_node, slide_id));
+        if let Some(position) = position {
+            let mut slide_id_node = slide_id_list.child_nodes[position].clone();
+            slide_id_node
+                .attributes
+                .insert(String::from("id"), new_slide_id.to_string());
+            slide_id_list.child_nodes.insert(position + 1, slide_id_node);
+            return;
+        }
+    }
+}
+
+/// Returns the paths of the parts inside the package that are referenced by `relationships` of the part at
+/// `part_path`. Relationships listed by `external_relationships` reference resources outside of the package.
+fn internal_targets(
+    part_path: &Path,
+    relationships: &[Relationship],
+    external_relationships: &ExternalRelationships,
+) -> Vec<PathBuf> {
+    let rels_path = relationships_path(part_path);
+    relationships
+        .iter()
+        .filter(|relationship| !external_relationships.contains(&rels_path, &relationship.id))
+        .map(|relationship| resolve_relationship_target(part_path, &relationship.target))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::PresentationBuilder;
+    use crate::document::load_saved_document;
+    use crate::drawingml::geometry::Rect;
+    use crate::opc::ContentTypes;
+    use crate::pml::presentation::CustomShow;
+    use std::{io::Cursor, str::FromStr};
+    use zip::ZipArchive;
+
+    const IMAGE_DATA: [u8; 4] = [0x89, b'P', b'N', b'G'];
+
+    fn sample_document_with_picture() -> (PPTXDocument, PathBuf) {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let document = PPTXDocument::from_file(&test_dir.join("tests/samplepptx.pptx")).unwrap();
+
+        let slide_path = PathBuf::from("ppt/slides/slide1.xml");
+        let mut builder = PresentationBuilder::from_document(document);
+        builder
+            .add_picture(&slide_path, &IMAGE_DATA, "png", Rect::new(0.0, 0.0, 100.0, 100.0))
+            .unwrap();
+        (builder.build(), slide_path)
+    }
+
+    fn slide_media(document: &PPTXDocument, slide_path: &Path) -> Vec<PathBuf> {
+        let relationships = document.part_relationships(slide_path).unwrap();
+        internal_targets(slide_path, relationships, &document.external_relationships)
+            .into_iter()
+            .filter(|part_path| document.medias.contains(part_path))
+            .collect()
+    }
+
+    /// Adds a chart with an embedded workbook to the slide at `slide_path`.
+    fn add_chart(document: &mut PPTXDocument, slide_path: &Path) {
+        let chart_path = PathBuf::from("ppt/charts/chart1.xml");
+        let workbook_path = PathBuf::from("ppt/embeddings/Microsoft_Excel_Worksheet1.xlsx");
+        let chart_rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/package"
+                Target="../embeddings/Microsoft_Excel_Worksheet1.xlsx"/>
+        </Relationships>"#;
+        document
+            .raw_part_map
+            .insert(chart_path.clone(), b"<c:chartSpace/>".to_vec());
+        document
+            .raw_part_map
+            .insert(relationships_path(&chart_path), chart_rels.as_bytes().to_vec());
+        document.raw_part_content_types.insert(
+            chart_path.clone(),
+            String::from("application/vnd.openxmlformats-officedocument.drawingml.chart+xml"),
+        );
+        document.raw_part_map.insert(workbook_path.clone(), b"PK".to_vec());
+        document.raw_part_content_types.insert(
+            workbook_path,
+            String::from("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+        );
+
+        let slide_rels = document
+            .slide_rels_map
+            .get_mut(&relationships_path(slide_path))
+            .unwrap();
+        slide_rels.push(Relationship {
+            id: crate::opc::next_relationship_id(slide_rels),
+            rel_type: String::from("http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart"),
+            target: relative_relationship_target(slide_path, &chart_path),
+        });
+    }
+
+    /// Returns the path of the chart of the slide at `slide_path` and the path of the workbook embedded in the chart.
+    fn chart_parts(document: &PPTXDocument, slide_path: &Path) -> (PathBuf, PathBuf) {
+        let chart_type = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart";
+        let chart_path = document.related_part_path(slide_path, chart_type).unwrap();
+        let chart_rels = document.read_relationships(&chart_path).unwrap();
+        let workbook_path = resolve_relationship_target(&chart_path, &chart_rels[0].target);
+        (chart_path, workbook_path)
+    }
+
+    fn ordered_slide_paths(document: &PPTXDocument) -> Vec<PathBuf> {
+        document
+            .ordered_slides()
+            .into_iter()
+            .map(|(slide_path, _)| slide_path.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_duplicate_slide() {
+        let (mut document, slide_path) = sample_document_with_picture();
+        let slide_count = document.slide_map.len();
+
+        let copy_path = document.duplicate_slide(&slide_path).unwrap();
+        assert_eq!(document.slide_map.len(), slide_count + 1);
+        assert_eq!(document.slide_map[&copy_path], document.slide_map[&slide_path]);
+        assert_eq!(document.slide_position(&copy_path), Some(1));
+        assert_eq!(
+            document.slide_layout_path(&copy_path),
+            document.slide_layout_path(&slide_path)
+        );
+
+        let slide_ids = &document.presentation.as_ref().unwrap().slide_id_list;
+        let mut ids = slide_ids.iter().map(|entry| entry.id).collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), slide_count + 1);
+
+        let media = slide_media(&document, &slide_path);
+        let media_copy = slide_media(&document, &copy_path);
+        assert_eq!(media.len(), 1);
+        assert_eq!(media_copy.len(), 1);
+        assert_ne!(media, media_copy);
+        assert_eq!(document.read_part(&media_copy[0]).unwrap(), IMAGE_DATA.to_vec());
+    }
+
+    #[test]
+    fn test_duplicate_slide_with_chart() {
+        let (mut document, slide_path) = sample_document_with_picture();
+        add_chart(&mut document, &slide_path);
+
+        let copy_path = document.duplicate_slide(&slide_path).unwrap();
+        let (chart_path, workbook_path) = chart_parts(&document, &slide_path);
+        let (chart_copy_path, workbook_copy_path) = chart_parts(&document, &copy_path);
+        assert_eq!(chart_copy_path, PathBuf::from("ppt/charts/chart2.xml"));
+        assert_eq!(
+            workbook_copy_path,
+            PathBuf::from("ppt/embeddings/Microsoft_Excel_Worksheet2.xlsx")
+        );
+        assert_eq!(
+            document.read_part(&chart_copy_path).unwrap(),
+            document.read_part(&chart_path).unwrap()
+        );
+        assert_eq!(
+            document.read_part(&workbook_copy_path).unwrap(),
+            document.read_part(&workbook_path).unwrap()
+        );
+
+        let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+        let saved_document = load_saved_document(&data);
+        assert_eq!(
+            chart_parts(&saved_document, &copy_path),
+            (chart_copy_path.clone(), workbook_copy_path)
+        );
+        let mut zipper = ZipArchive::new(Cursor::new(data)).unwrap();
+        let content_types = ContentTypes::from_zip_file(&mut zipper.by_name("[Content_Types].xml").unwrap()).unwrap();
+        assert_eq!(
+            content_types.content_type(&chart_copy_path),
+            Some("application/vnd.openxmlformats-officedocument.drawingml.chart+xml")
+        );
+    }
+
+    #[test]
+    fn test_delete_and_move_slides() {
+        let (mut document, slide_path) = sample_document_with_picture();
+        let copy_path = document.duplicate_slide(&slide_path).unwrap();
+        let media = slide_media(&document, &slide_path);
+        let media_copy = slide_media(&document, &copy_path);
+
+        let last_slide_path = document.slide_path_at(document.slide_map.len() - 1).unwrap();
+        document.move_slide(&last_slide_path, 0).unwrap();
+        assert_eq!(document.slide_path_at(0), Some(last_slide_path.clone()));
+        assert!(document.move_slide(&last_slide_path, 3).is_err());
+
+        document.delete_slide(&slide_path).unwrap();
+        assert!(!document.slide_map.contains_key(&slide_path));
+        assert!(document.slide_position(&slide_path).is_none());
+        assert!(!document.medias.contains(&media[0]));
+        assert!(document.medias.contains(&media_copy[0]));
+        assert!(document.delete_slide(&slide_path).is_err());
+        let slide_paths = document
+            .slides()
+            .filter_map(|slide| document.slide_path(slide).cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(slide_paths, vec![last_slide_path.clone(), copy_path.clone()]);
+
+        let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+        let saved_document = load_saved_document(&data);
+
+        assert_eq!(
+            ordered_slide_paths(&saved_document),
+            vec![last_slide_path, copy_path.clone()]
+        );
+        assert_eq!(saved_document.slide_map, document.slide_map);
+        assert_eq!(saved_document.presentation, document.presentation);
+        assert_eq!(saved_document.medias.len(), document.medias.len());
+        assert_eq!(saved_document.read_part(&media_copy[0]).unwrap(), IMAGE_DATA.to_vec());
+
+        let mut zipper = ZipArchive::new(Cursor::new(data)).unwrap();
+        assert!(zipper.by_name(&slide_path.to_string_lossy()).is_err());
+        assert!(zipper.by_name(&media[0].to_string_lossy()).is_err());
+        let content_types = ContentTypes::from_zip_file(&mut zipper.by_name("[Content_Types].xml").unwrap()).unwrap();
+        assert!(content_types.content_type(&copy_path).is_some());
+        assert!(!content_types
+            .overrides
+            .iter()
+            .any(|content_type_override| content_type_override.part_name == slide_path));
+    }
+
+    fn section_slide_ids(document: &PPTXDocument) -> Vec<Vec<String>> {
+        let mut unknown_xml = document.presentation.as_ref().unwrap().unknown_xml.clone();
+        section_slide_id_lists(&mut unknown_xml)
+            .map(|slide_id_list| {
+                slide_id_list
+                    .child_nodes
+                    .iter()
+                    .map(|slide_id_node| slide_id_node.attributes["id"].clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sections_and_custom_shows() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let mut document = PPTXDocument::from_file(&test_dir.join("tests/samplepptx.pptx")).unwrap();
+        let extension_list = r#"<p:extLst xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+            <p:ext uri="{521415D9-36F7-43E2-AB2F-B90AF26B5E84}">
+                <p14:sectionLst xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main">
+                    <p14:section name="First" id="{8A3F1A2B-1B2C-4D5E-8F90-0A1B2C3D4E5F}">
+                        <p14:sldIdLst><p14:sldId id="256"/></p14:sldIdLst>
+                    </p14:section>
+                    <p14:section name="Second" id="{9B4F2B3C-2C3D-4E5F-9A01-1B2C3D4E5F60}">
+                        <p14:sldIdLst><p14:sldId id="257"/></p14:sldIdLst>
+                    </p14:section>
+                </p14:sectionLst>
+            </p:ext>
+        </p:extLst>"#;
+        let custom_show = r#"<p:custShow xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+            xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" name="Custom" id="0">
+            <p:sldLst><p:sld r:id="rId2"/><p:sld r:id="rId3"/></p:sldLst>
+        </p:custShow>"#;
+        let presentation = document.presentation.as_mut().unwrap();
+        presentation
+            .unknown_xml
+            .push_child(usize::MAX, &XmlNode::from_str(extension_list).unwrap());
+        presentation
+            .custom_show_list
+            .push(CustomShow::from_xml_element(&XmlNode::from_str(custom_show).unwrap()).unwrap());
+
+        let first_slide_path = PathBuf::from("ppt/slides/slide1.xml");
+        let second_slide_path = PathBuf::from("ppt/slides/slide2.xml");
+        let copy_path = document.duplicate_slide(&first_slide_path).unwrap();
+        let copy_id = document.presentation.as_ref().unwrap().slide_id_list[1].id.to_string();
+        assert_eq!(
+            section_slide_ids(&document),
+            vec![vec![String::from("256"), copy_id.clone()], vec![String::from("257")]]
+        );
+
+        document.delete_slide(&second_slide_path).unwrap();
+        document.delete_slide(&first_slide_path).unwrap();
+        assert_eq!(section_slide_ids(&document), vec![vec![copy_id], Vec::new()]);
+        let custom_show = &document.presentation.as_ref().unwrap().custom_show_list[0];
+        assert!(custom_show.slides.0.is_empty());
+
+        let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+        let saved_document = load_saved_document(&data);
+        assert_eq!(section_slide_ids(&saved_document), section_slide_ids(&document));
+        assert_eq!(
+            saved_document.presentation.as_ref().unwrap().custom_show_list,
+            document.presentation.as_ref().unwrap().custom_show_list
+        );
+        assert_eq!(ordered_slide_paths(&saved_document), vec![copy_path]);
+    }
+}
@@ -0,0 +1,310 @@
+use crate::connection::{ConnectorEdge, ConnectorGraph};
+use crate::pml::{
+    slides::{ShapeGroup, Slide},
+    util::escape_xml,
+};
+use crate::text::text_body_paragraphs;
+use msoffice_shared::drawingml::{
+    shapeprops::LineEndProperties,
+    simpletypes::{DrawingElementId, LineEndType},
+};
+
+/// The direction of the arrowheads of a connector, relative to the direction from its start to its end.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrowDirection {
+    /// The connector has no arrowheads.
+    None,
+    /// The connector has an arrowhead at its end (tailEnd).
+    Forward,
+    /// The connector has an arrowhead at its start (headEnd).
+    Backward,
+    /// The connector has arrowheads at both of its ends.
+    Both,
+}
+
+impl ArrowDirection {
+    /// Returns the value of the dir attribute of a Graphviz edge.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ArrowDirection::None => "none",
+            ArrowDirection::Forward => "forward",
+            ArrowDirection::Backward => "back",
+            ArrowDirection::Both => "both",
+        }
+    }
+}
+
+/// A shape connected by connectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub id: DrawingElementId,
+    pub name: String,
+    /// The text of the shape with paragraphs separated by '\n', or the name of the shape if it doesn't have any text.
+    pub label: String,
+}
+
+/// A connector between two shapes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEdge {
+    /// The id of the connector.
+    pub id: DrawingElementId,
+    pub name: String,
+    /// The id of the shape the start of the connector is connected to.
+    pub source: DrawingElementId,
+    /// The id of the shape the end of the connector is connected to.
+    pub target: DrawingElementId,
+    pub direction: ArrowDirection,
+}
+
+/// A diagram drawn with shapes and connectors, like a flow chart, extracted as a graph.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DiagramGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl DiagramGraph {
+    /// Extracts the graph from the connectors of a slide. Only connectors that are connected to shapes at both of their
+    /// ends become edges, and only shapes connected by these connectors become nodes.
+    pub fn from_connector_graph(connector_graph: &ConnectorGraph) -> Self {
+        let mut graph: Self = Default::default();
+        for edge in &connector_graph.edges {
+            let (source, target) = match (edge.start.shape, edge.end.shape) {
+                (Some(source), Some(target)) => (source, target),
+                _ => continue,
+            };
+
+            for shape in &[source, target] {
+                if let Some(node) = graph_node(shape) {
+                    if !graph.nodes.iter().any(|other| other.id == node.id) {
+                        graph.nodes.push(node);
+                    }
+                }
+            }
+
+            let drawing_props = &edge.connector.non_visual_props.drawing_props;
+            graph.edges.extend(
+                edge.start
+                    .shape_id()
+                    .zip(edge.end.shape_id())
+                    .map(|(source, target)| GraphEdge {
+                        id: drawing_props.id,
+                        name: drawing_props.name.clone(),
+                        source,
+                        target,
+                        direction: arrow_direction(edge),
+                    }),
+            );
+        }
+
+        graph
+    }
+
+    /// Exports the graph in the Graphviz DOT language. Nodes are named by their shape ids and labelled with their
+    /// labels, the arrowheads of edges are specified by their dir attributes.
+    pub fn to_dot(&self, name: &str) -> String {
+        let mut dot = format!("digraph {} {{\n", dot_string(name));
+        for node in &self.nodes {
+            dot.push_str(&format!("  {} [label={}];\n", node.id, dot_string(&node.label)));
+        }
+        for edge in &self.edges {
+            dot.push_str(&format!(
+                "  {} -> {} [id={}, dir={}];\n",
+                edge.source,
+                edge.target,
+                dot_string(&edge.id.to_string()),
+                edge.direction.as_str()
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Exports the graph as GraphML. Nodes have a label and a name attribute, edges have a name and an arrow attribute
+    /// containing the direction of their arrowheads, using the values of the Graphviz dir attribute.
+    pub fn to_graphml(&self) -> String {
+        let mut graphml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n  \
+             <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n  \
+             <key id=\"name\" for=\"all\" attr.name=\"name\" attr.type=\"string\"/>\n  \
+             <key id=\"arrow\" for=\"edge\" attr.name=\"arrow\" attr.type=\"string\"/>\n  \
+             <graph edgedefault=\"directed\">\n",
+        );
+        for node in &self.nodes {
+            graphml.push_str(&format!(
+                "    <node id=\"n{}\">\n      <data key=\"label\">{}</data>\n      \
+                 <data key=\"name\">{}</data>\n    </node>\n",
+                node.id,
+                escape_xml(&node.label),
+                escape_xml(&node.name)
+            ));
+        }
+        for edge in &self.edges {
+            graphml.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">\n      <data key=\"name\">{}</data>\n      \
+                 <data key=\"arrow\">{}</data>\n    </edge>\n",
+                edge.id,
+                edge.source,
+                edge.target,
+                escape_xml(&edge.name),
+                edge.direction.as_str()
+            ));
+        }
+        graphml.push_str("  </graph>\n</graphml>\n");
+        graphml
+    }
+}
+
+impl Slide {
+    /// Extracts the diagram drawn with shapes and connectors on the slide as a graph.
+    pub fn diagram_graph(&self) -> DiagramGraph {
+        DiagramGraph::from_connector_graph(&self.connector_graph())
+    }
+}
+
+fn graph_node(shape_group: &ShapeGroup) -> Option<GraphNode> {
+    let drawing_props = shape_group.drawing_props()?;
+    let text = match shape_group {
+        ShapeGroup::Shape(shape) => shape
+            .text_body
+            .as_ref()
+            .map(|text_body| {
+                text_body_paragraphs(text_body)
+                    .into_iter()
+                    .map(|paragraph| paragraph.text)
+                    .filter(|text| !text.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default(),
+        _ => String::new(),
+    };
+
+    Some(GraphNode {
+        id: drawing_props.id,
+        name: drawing_props.name.clone(),
+        label: if text.is_empty() {
+            drawing_props.name.clone()
+        } else {
+            text
+        },
+    })
+}
+
+/// Returns the direction of the arrowheads of a connector from the line end properties of its outline. The headEnd
+/// element decorates the start of the line and the tailEnd element decorates its end.
+fn arrow_direction(edge: &ConnectorEdge) -> ArrowDirection {
+    let line_properties = edge.connector.shape_props.line_properties.as_ref();
+    let has_arrowhead = |line_end: Option<&LineEndProperties>| match line_end.and_then(|line_end| line_end.end_type) {
+        None | Some(LineEndType::None) => false,
+        Some(_) => true,
+    };
+
+    let head = has_arrowhead(line_properties.and_then(|line_properties| line_properties.head_end.as_ref()));
+    let tail = has_arrowhead(line_properties.and_then(|line_properties| line_properties.tail_end.as_ref()));
+    match (head, tail) {
+        (false, false) => ArrowDirection::None,
+        (false, true) => ArrowDirection::Forward,
+        (true, false) => ArrowDirection::Backward,
+        (true, true) => ArrowDirection::Both,
+    }
+}
+
+/// Quotes a string for the DOT language.
+fn dot_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pml::slides::CommonSlideData;
+    use msoffice_shared::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_diagram_graph_exporters() {
+        let shape = |id: u32, name: &str, text: &str| {
+            format!(
+                r#"<p:sp>
+                    <p:nvSpPr><p:cNvPr id="{}" name="{}"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>
+                    <p:spPr><a:xfrm><a:off x="{}" y="0"/><a:ext cx="100" cy="100"/></a:xfrm></p:spPr>
+                    <p:txBody><a:bodyPr/><a:p><a:r><a:t>{}</a:t></a:r></a:p></p:txBody>
+                </p:sp>"#,
+                id,
+                name,
+                id * 200,
+                text
+            )
+        };
+        let connector = |id: u32, start: u32, end: u32, line_ends: &str| {
+            format!(
+                r#"<p:cxnSp>
+                    <p:nvCxnSpPr>
+                        <p:cNvPr id="{}" name="Connector {0}"/>
+                        <p:cNvCxnSpPr><a:stCxn id="{}" idx="3"/><a:endCxn id="{}" idx="1"/></p:cNvCxnSpPr>
+                        <p:nvPr/>
+                    </p:nvCxnSpPr>
+                    <p:spPr>
+                        <a:xfrm><a:off x="0" y="50"/><a:ext cx="100" cy="0"/></a:xfrm>
+                        <a:ln>{}</a:ln>
+                    </p:spPr>
+                </p:cxnSp>"#,
+                id, start, end, line_ends
+            )
+        };
+        let xml = format!(
+            r#"<p:cSld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+                xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+                <p:spTree>
+                    <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                    <p:grpSpPr/>
+                    {}{}{}{}{}{}
+                </p:spTree>
+            </p:cSld>"#,
+            shape(2, "Start", "Say \"hi\""),
+            shape(3, "Check", ""),
+            shape(4, "Unconnected", "Alone"),
+            connector(5, 2, 3, r#"<a:tailEnd type="triangle"/>"#),
+            connector(6, 3, 2, r#"<a:headEnd type="none"/><a:tailEnd type="none"/>"#),
+            connector(7, 3, 8, r#"<a:headEnd type="arrow"/>"#),
+        );
+        let common_slide_data = CommonSlideData::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap();
+        let graph = DiagramGraph::from_connector_graph(&common_slide_data.connector_graph());
+
+        assert_eq!(
+            graph.to_dot("Slide 1"),
+            "digraph \"Slide 1\" {\n  2 [label=\"Say \\\"hi\\\"\"];\n  3 [label=\"Check\"];\n  \
+             2 -> 3 [id=\"5\", dir=forward];\n  3 -> 2 [id=\"6\", dir=none];\n}\n"
+        );
+        assert_eq!(
+            graph.to_graphml(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n  \
+             <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n  \
+             <key id=\"name\" for=\"all\" attr.name=\"name\" attr.type=\"string\"/>\n  \
+             <key id=\"arrow\" for=\"edge\" attr.name=\"arrow\" attr.type=\"string\"/>\n  \
+             <graph edgedefault=\"directed\">\n    \
+             <node id=\"n2\">\n      <data key=\"label\">Say &quot;hi&quot;</data>\n      \
+             <data key=\"name\">Start</data>\n    </node>\n    \
+             <node id=\"n3\">\n      <data key=\"label\">Check</data>\n      \
+             <data key=\"name\">Check</data>\n    </node>\n    \
+             <edge id=\"e5\" source=\"n2\" target=\"n3\">\n      <data key=\"name\">Connector 5</data>\n      \
+             <data key=\"arrow\">forward</data>\n    </edge>\n    \
+             <edge id=\"e6\" source=\"n3\" target=\"n2\">\n      <data key=\"name\">Connector 6</data>\n      \
+             <data key=\"arrow\">none</data>\n    </edge>\n  \
+             </graph>\n</graphml>\n"
+        );
+    }
+}
@@ -0,0 +1,733 @@
+use crate::document::PPTXDocument;
+use crate::drawingml::geometry::shape_paths;
+use crate::pml::{
+    slides::{GraphicalObjectContent, GroupShape, Shape, ShapeGroup},
+    util::escape_xml,
+};
+use crate::style::{is_drawn, Paint, Stroke, StyleContext};
+use msoffice_shared::drawingml::{
+    coordsys::{GroupTransform2D, Transform2D},
+    core::TextBody,
+    shapedefs::Geometry,
+    simpletypes::{PresetLineDashVal, ShapeType, TextAlignType, TextAnchoringType},
+    text::runformatting::TextRun,
+};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// The number of EMUs in a CSS pixel (1/96 inch).
+pub const EMUS_PER_PIXEL: f64 = 9525.0;
+
+const STYLE_SHEET: &str = "body { margin: 0; padding: 16px; background: #808080; }
+.slide { position: relative; overflow: hidden; margin: 0 auto 16px auto; background: #ffffff; }
+.shape { position: absolute; box-sizing: border-box; }
+.geometry { position: absolute; left: 0; top: 0; width: 100%; height: 100%; box-sizing: border-box; }
+.geometry > svg { position: absolute; left: 0; top: 0; width: 100%; height: 100%; overflow: visible; }
+.text { position: absolute; display: flex; flex-direction: column; overflow-wrap: break-word; }
+.text p { margin: 0; }
+.shape > table { width: 100%; height: 100%; border-collapse: collapse; table-layout: fixed; }
+.shape > table td { border: 1px solid #000000; vertical-align: top; padding: 4px 8px; }";
+
+/// Renders the whole presentation into a self-contained HTML document.
+///
+/// Every slide is rendered in the order of the presentation's slide id list as a container of the presentation's slide
+/// size. The shapes of the slide master and the slide layout are rendered behind the shapes of the slide. See
+/// slide_to_html for the details.
+pub fn to_html(document: &PPTXDocument) -> Result<String> {
+    let title = document
+        .core
+        .as_ref()
+        .and_then(|core| core.title.clone())
+        .or_else(|| {
+            document
+                .file_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .unwrap_or_default();
+
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    writeln!(html, "<title>{}</title>", escape_xml(&title))?;
+    writeln!(html, "<style>\n{}\n</style>\n</head>\n<body>", STYLE_SHEET)?;
+
+    let mut data_uris = HashMap::new();
+    for (index, (slide_path, _)) in document.ordered_slides().into_iter().enumerate() {
+        html.push_str(&render_slide(document, slide_path, index + 1, &mut data_uris)?);
+    }
+
+    html.push_str("</body>\n</html>\n");
+    Ok(html)
+}
+
+/// Renders a single slide into an HTML section element.
+///
+/// The section has the size of the presentation's slides and the background of the slide. Each shape is an absolutely
+/// positioned element using the transform of the shape or, for placeholders, the transform inherited from the slide
+/// layout or the slide master. Text is rendered with its formatting resolved through the inheritance chain. Pictures
+/// are inlined as data URIs. Rectangles, rounded rectangles and ellipses are drawn with CSS, other simple preset
+/// geometries are drawn by clipping the fill to a polygon. Any other geometry is drawn as its bounding rectangle.
+pub fn slide_to_html(document: &PPTXDocument, slide_path: &Path, slide_number: usize) -> Result<String> {
+    render_slide(document, slide_path, slide_number, &mut HashMap::new())
+}
+
+fn render_slide(
+    document: &PPTXDocument,
+    slide_path: &Path,
+    slide_number: usize,
+    data_uris: &mut HashMap<PathBuf, String>,
+) -> Result<String> {
+    let context = document
+        .style_context(slide_path)
+        .ok_or_else(|| format!("slide not found: {}", slide_path.display()))?;
+    let slide_size = document.slide_size();
+
+    let mut renderer = HtmlRenderer {
+        context,
+        data_uris,
+        html: String::new(),
+    };
+
+    let background = document
+        .effective_background(renderer.context.slide)
+        .and_then(|background| {
+            renderer
+                .context
+                .resolve_fill(&background.fill, None, &background.source_part)
+        })
+        .map(|paint| renderer.paint_css(&paint))
+        .unwrap_or_default();
+
+    writeln!(
+        renderer.html,
+        "<section class=\"slide\" id=\"slide-{}\" style=\"width: {}; height: {};{}\">",
+        slide_number,
+        px(slide_size.width as f64),
+        px(slide_size.height as f64),
+        background,
+    )?;
+
+    for (part_path, shape_tree) in renderer.context.shape_trees() {
+        let draw_placeholders = part_path == slide_path;
+        renderer.render_group(shape_tree, &part_path, ChildSpace::IDENTITY, draw_placeholders)?;
+    }
+    renderer.html.push_str("</section>\n");
+
+    Ok(renderer.html)
+}
+
+/// Maps the coordinates of a group's child coordinate space into the coordinate space of the group's container.
+#[derive(Debug, Clone, Copy)]
+struct ChildSpace {
+    offset_x: f64,
+    offset_y: f64,
+    scale_x: f64,
+    scale_y: f64,
+}
+
+impl ChildSpace {
+    const IDENTITY: ChildSpace = ChildSpace {
+        offset_x: 0.0,
+        offset_y: 0.0,
+        scale_x: 1.0,
+        scale_y: 1.0,
+    };
+
+    /// Returns the rectangle (left, top, width, height) of the transform in EMUs of the container.
+    fn map_rect(&self, offset_x: i64, offset_y: i64, width: u64, height: u64) -> (f64, f64, f64, f64) {
+        (
+            (offset_x as f64 - self.offset_x) * self.scale_x,
+            (offset_y as f64 - self.offset_y) * self.scale_y,
+            width as f64 * self.scale_x,
+            height as f64 * self.scale_y,
+        )
+    }
+
+    /// Returns the child space of a group that's placed in this space with the given transform.
+    fn group_space(&self, transform: &GroupTransform2D) -> ChildSpace {
+        let extents = transform.extents.unwrap_or_default();
+        let child_offset = transform.child_offset.unwrap_or_default();
+        let child_extents = transform.child_extents.unwrap_or_default();
+        let scale = |extent: u64, child_extent: u64| {
+            if child_extent == 0 {
+                1.0
+            } else {
+                extent as f64 / child_extent as f64
+            }
+        };
+
+        ChildSpace {
+            offset_x: child_offset.x as f64,
+            offset_y: child_offset.y as f64,
+            scale_x: self.scale_x * scale(extents.width, child_extents.width),
+            scale_y: self.scale_y * scale(extents.height, child_extents.height),
+        }
+    }
+}
+
+struct HtmlRenderer<'a, 'b> {
+    context: StyleContext<'a>,
+    data_uris: &'b mut HashMap<PathBuf, String>,
+    html: String,
+}
+
+impl<'a, 'b> HtmlRenderer<'a, 'b> {
+    /// Renders the shapes of a group. `part_path` is the path of the part containing the shapes. Placeholders are
+    /// only rendered if `draw_placeholders` is true, as the placeholders of slide layouts and slide masters are not
+    /// displayed on slides.
+    fn render_group(
+        &mut self,
+        group: &'a GroupShape,
+        part_path: &Path,
+        space: ChildSpace,
+        draw_placeholders: bool,
+    ) -> Result<()> {
+        for shape_group in &group.shape_array {
+            match shape_group {
+                ShapeGroup::Shape(shape) => {
+                    let non_visual_props = &shape.non_visual_props;
+                    if !is_drawn(
+                        &non_visual_props.drawing_props,
+                        &non_visual_props.app_props,
+                        draw_placeholders,
+                    ) {
+                        continue;
+                    }
+                    self.render_shape(shape, part_path, space)?;
+                }
+                ShapeGroup::GroupShape(group_shape) => {
+                    let non_visual_props = &group_shape.non_visual_props;
+                    if !is_drawn(&non_visual_props.drawing_props, &non_visual_props.app_props, true) {
+                        continue;
+                    }
+
+                    let transform = match &group_shape.group_shape_props.transform {
+                        Some(transform) => transform,
+                        None => {
+                            self.render_group(group_shape, part_path, space, draw_placeholders)?;
+                            continue;
+                        }
+                    };
+
+                    let offset = transform.offset.unwrap_or_default();
+                    let extents = transform.extents.unwrap_or_default();
+                    let rect = space.map_rect(offset.x, offset.y, extents.width, extents.height);
+                    writeln!(
+                        self.html,
+                        "<div class=\"shape\" data-id=\"{}\" style=\"{}{}\">",
+                        non_visual_props.drawing_props.id,
+                        rect_css(rect),
+                        transform_css(
+                            transform.rotate_angle,
+                            transform.flip_horizontal,
+                            transform.flip_vertical
+                        ),
+                    )?;
+                    self.render_group(group_shape, part_path, space.group_space(transform), draw_placeholders)?;
+                    self.html.push_str("</div>\n");
+                }
+                ShapeGroup::Picture(picture) => {
+                    let non_visual_props = &picture.non_visual_props;
+                    if !is_drawn(
+                        &non_visual_props.drawing_props,
+                        &non_visual_props.app_props,
+                        draw_placeholders,
+                    ) {
+                        continue;
+                    }
+
+                    let transform = match self
+                        .context
+                        .transform(&picture.shape_props, non_visual_props.app_props.placeholder.as_ref())
+                    {
+                        Some(transform) => transform,
+                        None => continue,
+                    };
+                    let media_path = picture
+                        .blip_fill
+                        .blip
+                        .as_ref()
+                        .and_then(|blip| blip.embed_rel_id.as_ref())
+                        .and_then(|rel_id| self.context.document.relationship_target_path(part_path, rel_id));
+                    let data_uri = match media_path {
+                        Some(media_path) => self.data_uri(&media_path)?,
+                        None => continue,
+                    };
+
+                    let drawing_props = &non_visual_props.drawing_props;
+                    writeln!(
+                        self.html,
+                        "<img class=\"shape\" data-id=\"{}\" alt=\"{}\" src=\"{}\" style=\"{}{}\">",
+                        drawing_props.id,
+                        escape_xml(drawing_props.description.as_ref().unwrap_or(&drawing_props.name)),
+                        data_uri,
+                        rect_css(transform_rect(transform, space)),
+                        transform_css(
+                            transform.rotate_angle,
+                            transform.flip_horizontal,
+                            transform.flip_vertical
+                        ),
+                    )?;
+                }
+                ShapeGroup::Connector(connector) => {
+                    let non_visual_props = &connector.non_visual_props;
+                    if !is_drawn(&non_visual_props.drawing_props, &non_visual_props.app_props, true) {
+                        continue;
+                    }
+
+                    let transform = match &connector.shape_props.transform {
+                        Some(transform) => transform,
+                        None => continue,
+                    };
+                    let stroke = match self.context.stroke(
+                        connector.shape_props.line_properties.as_deref(),
+                        connector.shape_style.as_deref(),
+                    ) {
+                        Some(stroke) => stroke,
+                        None => continue,
+                    };
+
+                    let rect = transform_rect(transform, space);
+                    let geometry = self.geometry_html(
+                        connector.shape_props.geometry.as_ref(),
+                        rect.2 / EMUS_PER_PIXEL,
+                        rect.3 / EMUS_PER_PIXEL,
+                        None,
+                        Some(stroke),
+                    );
+                    writeln!(
+                        self.html,
+                        "<div class=\"shape\" data-id=\"{}\" style=\"{}{}\"><div class=\"geometry\">{}</div></div>",
+                        non_visual_props.drawing_props.id,
+                        rect_css(rect),
+                        transform_css(
+                            transform.rotate_angle,
+                            transform.flip_horizontal,
+                            transform.flip_vertical
+                        ),
+                        geometry,
+                    )?;
+                }
+                ShapeGroup::GraphicFrame(graphic_frame) => {
+                    let non_visual_props = &graphic_frame.non_visual_props;
+                    if !is_drawn(
+                        &non_visual_props.drawing_props,
+                        &non_visual_props.app_props,
+                        draw_placeholders,
+                    ) {
+                        continue;
+                    }
+
+                    if let Some(GraphicalObjectContent::Table(table)) = &graphic_frame.graphic_content {
+                        writeln!(
+                            self.html,
+                            "<div class=\"shape\" data-id=\"{}\" style=\"{}\">\n<table>",
+                            non_visual_props.drawing_props.id,
+                            rect_css(transform_rect(&graphic_frame.transform, space)),
+                        )?;
+                        for row in &table.row_list {
+                            self.html.push_str("<tr>");
+                            for cell in row.cell_list.iter().filter(|cell| !cell.is_merged()) {
+                                let mut attributes = String::new();
+                                if let Some(row_span) = cell.row_span.filter(|span| *span > 1) {
+                                    write!(attributes, " rowspan=\"{}\"", row_span)?;
+                                }
+                                if let Some(grid_span) = cell.grid_span.filter(|span| *span > 1) {
+                                    write!(attributes, " colspan=\"{}\"", grid_span)?;
+                                }
+
+                                write!(self.html, "<td{}>", attributes)?;
+                                if let Some(text_body) = &cell.text_body {
+                                    self.render_paragraphs(None, text_body)?;
+                                }
+                                self.html.push_str("</td>");
+                            }
+                            self.html.push_str("</tr>\n");
+                        }
+                        self.html.push_str("</table>\n</div>\n");
+                    }
+                }
+                ShapeGroup::ContentPart(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_shape(&mut self, shape: &'a Shape, part_path: &Path, space: ChildSpace) -> Result<()> {
+        let placeholder = shape.non_visual_props.app_props.placeholder.as_ref();
+        let transform = match self.context.transform(&shape.shape_props, placeholder) {
+            Some(transform) => transform,
+            None => return Ok(()),
+        };
+
+        let rect = transform_rect(transform, space);
+        writeln!(
+            self.html,
+            "<div class=\"shape\" data-id=\"{}\" style=\"{}{}\">",
+            shape.non_visual_props.drawing_props.id,
+            rect_css(rect),
+            transform_css(transform.rotate_angle, None, None),
+        )?;
+
+        let fill = self.context.shape_fill(shape, part_path);
+        let stroke = self.context.stroke(
+            shape.shape_props.line_properties.as_deref(),
+            shape.shape_style.as_deref(),
+        );
+        if fill.is_some() || stroke.is_some() {
+            let geometry = self.geometry_html(
+                shape.shape_props.geometry.as_ref(),
+                rect.2 / EMUS_PER_PIXEL,
+                rect.3 / EMUS_PER_PIXEL,
+                fill,
+                stroke,
+            );
+            writeln!(
+                self.html,
+                "<div class=\"geometry\" style=\"{}\">{}</div>",
+                transform_css(None, transform.flip_horizontal, transform.flip_vertical),
+                geometry,
+            )?;
+        }
+
+        if let Some(text_body) = &shape.text_body {
+            let body_style = self.context.body_style(shape);
+            let justify_content = match body_style.anchor {
+                TextAnchoringType::Center => "center",
+                TextAnchoringType::Bottom => "flex-end",
+                _ => "flex-start",
+            };
+            writeln!(
+                self.html,
+                "<div class=\"text\" style=\"left: {}; top: {}; right: {}; bottom: {}; justify-content: {};\">",
+                px(body_style.left_inset as f64),
+                px(body_style.top_inset as f64),
+                px(body_style.right_inset as f64),
+                px(body_style.bottom_inset as f64),
+                justify_content,
+            )?;
+            self.render_paragraphs(Some(shape), text_body)?;
+            self.html.push_str("</div>\n");
+        }
+
+        self.html.push_str("</div>\n");
+        Ok(())
+    }
+
+    fn render_paragraphs(&mut self, shape: Option<&'a Shape>, text_body: &TextBody) -> Result<()> {
+        for paragraph in &text_body.paragraph_array {
+            let paragraph_style = self.context.paragraph_style(shape, paragraph);
+            let text_align = match paragraph_style.align {
+                TextAlignType::Center => "center",
+                TextAlignType::Right => "right",
+                TextAlignType::Justified | TextAlignType::JustifiedLow => "justify",
+                _ => "left",
+            };
+            write!(
+                self.html,
+                "<p style=\"text-align: {}; margin-left: {}; text-indent: {};\">",
+                text_align,
+                px(paragraph_style.margin_left as f64),
+                px(paragraph_style.indent as f64),
+            )?;
+
+            if paragraph.text_run_list.is_empty() {
+                let run_style =
+                    self.context
+                        .run_style(shape, paragraph, paragraph.end_paragraph_char_properties.as_deref());
+                write!(
+                    self.html,
+                    "<span style=\"font-size: {}pt;\"><br></span>",
+                    run_style.font_size
+                )?;
+            }
+
+            for text_run in &paragraph.text_run_list {
+                let (char_properties, text) = match text_run {
+                    TextRun::RegularTextRun(run) => (run.char_properties.as_deref(), run.text.as_str()),
+                    TextRun::TextField(field) => (
+                        field.char_properties.as_deref(),
+                        field.text.as_deref().unwrap_or_default(),
+                    ),
+                    TextRun::LineBreak(_) => {
+                        self.html.push_str("<br>");
+                        continue;
+                    }
+                };
+
+                let run_style = self.context.run_style(shape, paragraph, char_properties);
+                let mut css = format!("font-size: {}pt;", run_style.font_size);
+                if let Some(typeface) = &run_style.typeface {
+                    write!(css, " font-family: '{}';", escape_xml(&typeface.replace('\'', "")))?;
+                }
+                if run_style.bold {
+                    css.push_str(" font-weight: bold;");
+                }
+                if run_style.italic {
+                    css.push_str(" font-style: italic;");
+                }
+                match (run_style.underline, run_style.strikethrough) {
+                    (true, true) => css.push_str(" text-decoration: underline line-through;"),
+                    (true, false) => css.push_str(" text-decoration: underline;"),
+                    (false, true) => css.push_str(" text-decoration: line-through;"),
+                    (false, false) => (),
+                }
+                if run_style.all_caps {
+                    css.push_str(" text-transform: uppercase;");
+                }
+                match run_style.color {
+                    Some(color) => write!(css, " color: {};", color.to_css())?,
+                    None => css.push_str(" color: transparent;"),
+                }
+
+                write!(self.html, "<span style=\"{}\">{}</span>", css, escape_text(text))?;
+            }
+
+            self.html.push_str("</p>\n");
+        }
+
+        Ok(())
+    }
+
+    /// Returns the inner html of the geometry element of a shape with the given size in pixels.
+    fn geometry_html(
+        &mut self,
+        geometry: Option<&Geometry>,
+        width: f64,
+        height: f64,
+        fill: Option<Paint>,
+        stroke: Option<Stroke>,
+    ) -> String {
+        let fill_css = fill.as_ref().map(|paint| self.paint_css(paint)).unwrap_or_default();
+        let border_radius = match geometry {
+            None => Some(""),
+            Some(Geometry::Preset(preset)) => match preset.preset {
+                ShapeType::Rect => Some(""),
+                ShapeType::Ellipse => Some(" border-radius: 50%;"),
+                _ => None,
+            },
+            Some(Geometry::Custom(_)) => None,
+        };
+
+        if let Some(border_radius) = border_radius {
+            let mut css = fill_css;
+            css.push_str(border_radius);
+            if let Some(stroke) = &stroke {
+                write!(
+                    css,
+                    " border: {} {} {};",
+                    px(stroke.width as f64),
+                    border_style(stroke.dash),
+                    stroke.color.to_css()
+                )
+                .unwrap();
+            }
+
+            return format!("<div class=\"geometry\" style=\"{}\"></div>", css);
+        }
+
+        let paths = shape_paths(geometry, width, height);
+        let mut html = String::new();
+        let fill_path_data = paths
+            .iter()
+            .filter(|path| path.is_filled())
+            .map(|path| path.to_svg_path_data(1.0))
+            .collect::<Vec<_>>();
+        if fill.is_some() && !fill_path_data.is_empty() {
+            write!(
+                html,
+                "<div class=\"geometry\" style=\"{} clip-path: path('{}');\"></div>",
+                fill_css,
+                fill_path_data.join(" ")
+            )
+            .unwrap();
+        }
+        if let Some(stroke) = &stroke {
+            write!(
+                html,
+                "<svg viewBox=\"0 0 {} {}\">",
+                round(width.max(1.0)),
+                round(height.max(1.0))
+            )
+            .unwrap();
+            for path in paths.iter().filter(|path| path.stroke) {
+                write!(
+                    html,
+                    "<path d=\"{}\" fill=\"none\"{}/>",
+                    path.to_svg_path_data(1.0),
+                    stroke_attributes(stroke)
+                )
+                .unwrap();
+            }
+            html.push_str("</svg>");
+        }
+
+        html
+    }
+
+    /// Returns the CSS background declaration of a paint.
+    fn paint_css(&mut self, paint: &Paint) -> String {
+        match paint {
+            Paint::Solid(color) => format!(" background: {};", color.to_css()),
+            Paint::LinearGradient { angle, stops } => format!(
+                " background: linear-gradient({}deg, {});",
+                round(angle + 90.0),
+                gradient_stops_css(stops)
+            ),
+            Paint::RadialGradient { stops } => {
+                format!(" background: radial-gradient({});", gradient_stops_css(stops))
+            }
+            Paint::Image { media_path } => match self.data_uri(media_path) {
+                Ok(data_uri) => format!(" background: url('{}') center / 100% 100% no-repeat;", data_uri),
+                Err(_) => String::new(),
+            },
+        }
+    }
+
+    /// Returns the data URI of a media part. Data URIs are cached, so media used by multiple slides is only read
+    /// once.
+    fn data_uri(&mut self, media_path: &Path) -> Result<String> {
+        if let Some(data_uri) = self.data_uris.get(media_path) {
+            return Ok(data_uri.clone());
+        }
+
+        let data_uri = self.context.document.part_data_uri(media_path)?;
+        self.data_uris.insert(media_path.to_path_buf(), data_uri.clone());
+        Ok(data_uri)
+    }
+}
+
+fn transform_rect(transform: &Transform2D, space: ChildSpace) -> (f64, f64, f64, f64) {
+    let offset = transform.offset.unwrap_or_default();
+    let extents = transform.extents.unwrap_or_default();
+    space.map_rect(offset.x, offset.y, extents.width, extents.height)
+}
+
+fn rect_css((left, top, width, height): (f64, f64, f64, f64)) -> String {
+    format!(
+        "left: {}; top: {}; width: {}; height: {};",
+        px(left),
+        px(top),
+        px(width),
+        px(height)
+    )
+}
+
+fn transform_css(rotate_angle: Option<i32>, flip_horizontal: Option<bool>, flip_vertical: Option<bool>) -> String {
+    let mut transforms = Vec::new();
+    if let Some(angle) = rotate_angle.filter(|angle| *angle != 0) {
+        transforms.push(format!("rotate({}deg)", round(f64::from(angle) / 60000.0)));
+    }
+    let flip_horizontal = flip_horizontal.unwrap_or(false);
+    let flip_vertical = flip_vertical.unwrap_or(false);
+    if flip_horizontal || flip_vertical {
+        transforms.push(format!(
+            "scale({}, {})",
+            if flip_horizontal { -1 } else { 1 },
+            if flip_vertical { -1 } else { 1 }
+        ));
+    }
+
+    if transforms.is_empty() {
+        String::new()
+    } else {
+        format!(" transform: {};", transforms.join(" "))
+    }
+}
+
+fn stroke_attributes(stroke: &Stroke) -> String {
+    let width = stroke.width as f64 / EMUS_PER_PIXEL;
+    let mut attributes = format!(
+        " stroke=\"{}\" stroke-width=\"{}\"",
+        stroke.color.to_hex(),
+        round(width)
+    );
+    if stroke.color.alpha != 255 {
+        write!(attributes, " stroke-opacity=\"{:.3}\"", stroke.color.opacity()).unwrap();
+    }
+    if let Some(dash_pattern) = stroke.dash_pattern() {
+        let dash_array = dash_pattern
+            .iter()
+            .map(|length| round(length / EMUS_PER_PIXEL).to_string())
+            .collect::<Vec<_>>();
+        write!(attributes, " stroke-dasharray=\"{}\"", dash_array.join(" ")).unwrap();
+    }
+    attributes
+}
+
+fn border_style(dash: Option<PresetLineDashVal>) -> &'static str {
+    match dash {
+        None | Some(PresetLineDashVal::Solid) => "solid",
+        Some(PresetLineDashVal::Dot) | Some(PresetLineDashVal::SystemDot) => "dotted",
+        _ => "dashed",
+    }
+}
+
+fn gradient_stops_css(stops: &[crate::style::GradientStop]) -> String {
+    stops
+        .iter()
+        .map(|stop| format!("{} {}%", stop.color.to_css(), round(stop.position * 100.0)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Escapes text content. Runs of spaces are preserved by replacing every other space with a non-breaking space.
+fn escape_text(text: &str) -> String {
+    escape_xml(text).replace("  ", " &nbsp;")
+}
+
+fn px(emus: f64) -> String {
+    format!("{}px", round(emus / EMUS_PER_PIXEL))
+}
+
+/// Rounds to two decimal places, which is precise enough for CSS pixels and keeps the output short.
+fn round(value: f64) -> f64 {
+    // Adding zero turns negative zero into zero
+    (value * 100.0).round() / 100.0 + 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawingml::color::RgbaColor;
+
+    #[test]
+    pub fn test_css_helpers() {
+        assert_eq!(px(914400.0), "96px");
+        assert_eq!(
+            transform_css(Some(5400000), Some(true), None),
+            " transform: rotate(90deg) scale(-1, 1);"
+        );
+        assert_eq!(transform_css(None, None, None), "");
+
+        let stroke = Stroke {
+            width: 19050,
+            color: RgbaColor::from_rgb(0xff_00_00),
+            dash: Some(PresetLineDashVal::Dash),
+            cap: None,
+            head_end: None,
+            tail_end: None,
+        };
+        assert_eq!(
+            stroke_attributes(&stroke),
+            " stroke=\"#ff0000\" stroke-width=\"2\" stroke-dasharray=\"8 6\""
+        );
+    }
+
+    #[test]
+    pub fn test_html() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let sample_pptx_path = test_dir.join("tests/samplepptx.pptx");
+
+        let document = PPTXDocument::from_file(&sample_pptx_path).unwrap();
+        let html = to_html(&document).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<section class=\"slide\"").count(), 2);
+        assert!(html.contains("id=\"slide-1\" style=\"width: 960px; height: 720px;"));
+        assert!(html.contains(">Sample PowerPoint File</span>"));
+    }
+}
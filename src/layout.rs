@@ -0,0 +1,289 @@
+use crate::pml::slides::Shape;
+use crate::style::{RunStyle, StyleContext};
+use msoffice_shared::drawingml::{
+    core::TextBody,
+    simpletypes::{TextAlignType, TextAnchoringType},
+    text::runformatting::TextRun,
+};
+
+/// The number of EMUs in a point.
+pub const EMUS_PER_POINT: f64 = 12700.0;
+
+/// The height of a line relative to the size of its largest font.
+const LINE_HEIGHT: f64 = 1.2;
+
+/// Measures the width of text. Renderers with access to font files can measure text exactly, others can use
+/// EstimatedTextMeasure.
+pub trait TextMeasure {
+    /// Returns the width of `text` in EMUs when it's drawn with `style`.
+    fn text_width(&self, text: &str, style: &RunStyle) -> f64;
+
+    /// Returns the distance between the top of a line and its baseline relative to the font size.
+    fn ascent(&self, _style: &RunStyle) -> f64 {
+        0.9
+    }
+}
+
+/// Estimates the width of text from the average widths of character classes of a typical proportional font. Good
+/// enough to break lines at roughly the right places when no font files are available.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct EstimatedTextMeasure;
+
+impl TextMeasure for EstimatedTextMeasure {
+    fn text_width(&self, text: &str, style: &RunStyle) -> f64 {
+        let em_width: f64 = text
+            .chars()
+            .map(|c| match c {
+                'i' | 'j' | 'l' | '.' | ',' | '\'' | '|' | '!' | ':' | ';' | 'I' => 0.25,
+                ' ' | 'f' | 't' | 'r' | '(' | ')' | '[' | ']' | '-' => 0.33,
+                'm' | 'w' | 'M' | 'W' => 0.85,
+                'A'..='Z' => 0.67,
+                '0'..='9' => 0.55,
+                _ => 0.5,
+            })
+            .sum();
+        let weight = if style.bold { 1.05 } else { 1.0 };
+
+        em_width * weight * style.font_size * EMUS_PER_POINT
+    }
+}
+
+/// A piece of text with uniform formatting within a line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextFragment {
+    pub text: String,
+    pub style: RunStyle,
+    /// The horizontal position of the fragment relative to the left edge of the text body in EMUs.
+    pub x: f64,
+    /// The width of the fragment in EMUs.
+    pub width: f64,
+}
+
+/// A line of laid out text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+    pub fragments: Vec<TextFragment>,
+    /// The vertical position of the baseline relative to the top edge of the text body in EMUs.
+    pub baseline: f64,
+    /// The height of the line in EMUs.
+    pub height: f64,
+}
+
+/// Lays out a text body within a box of the given size in EMUs, resolving the formatting of the text through the
+/// inheritance chain. `shape` is None for text that isn't contained by a shape, e.g. the text of table cells.
+///
+/// Lines are broken at spaces when the text doesn't fit the width of the box, then the lines are positioned
+/// according to the alignment of the paragraphs and the anchor of the body. Lines that don't fit the height of the box
+/// overflow the box, like they do in PowerPoint.
+pub fn layout_text_body<'a>(
+    context: &StyleContext<'a>,
+    shape: Option<&'a Shape>,
+    text_body: &TextBody,
+    width: f64,
+    height: f64,
+    measure: &dyn TextMeasure,
+) -> Vec<TextLine> {
+    let body_style = shape.map(|shape| context.body_style(shape)).unwrap_or_default();
+    let content_width = (width - (body_style.left_inset + body_style.right_inset) as f64).max(0.0);
+
+    let mut lines = Vec::new();
+    let mut top = 0.0;
+    for paragraph in &text_body.paragraph_array {
+        let paragraph_style = context.paragraph_style(shape, paragraph);
+        let mut words = Vec::new();
+        for text_run in &paragraph.text_run_list {
+            let (char_properties, text) = match text_run {
+                TextRun::RegularTextRun(run) => (run.char_properties.as_deref(), run.text.as_str()),
+                TextRun::TextField(field) => (
+                    field.char_properties.as_deref(),
+                    field.text.as_deref().unwrap_or_default(),
+                ),
+                TextRun::LineBreak(line_break) => {
+                    let style = context.run_style(shape, paragraph, line_break.char_properties.as_deref());
+                    words.push(Word::LineBreak(style));
+                    continue;
+                }
+            };
+
+            let style = context.run_style(shape, paragraph, char_properties);
+            let text = if style.all_caps {
+                text.to_uppercase()
+            } else {
+                text.to_string()
+            };
+            // Split after spaces, so the spaces stay with the preceding word
+            let mut start = 0;
+            for (index, _) in text.match_indices(' ') {
+                words.push(Word::Text(text[start..=index].to_string(), style.clone()));
+                start = index + 1;
+            }
+            if start < text.len() {
+                words.push(Word::Text(text[start..].to_string(), style.clone()));
+            }
+        }
+
+        let empty_paragraph_style =
+            || context.run_style(shape, paragraph, paragraph.end_paragraph_char_properties.as_deref());
+        let mut line_builder = LineBuilder::default();
+        let mut paragraph_lines = Vec::new();
+        for word in words {
+            let first_line = paragraph_lines.is_empty();
+            let left = (paragraph_style.margin_left + if first_line { paragraph_style.indent } else { 0 }) as f64;
+            let available_width = (content_width - left).max(0.0);
+
+            match word {
+                Word::LineBreak(style) => {
+                    line_builder.minimum_font_size = line_builder.minimum_font_size.max(style.font_size);
+                    paragraph_lines.push(line_builder.finish(left));
+                }
+                Word::Text(text, style) => {
+                    let word_width = measure.text_width(&text, &style);
+                    let trimmed_width = measure.text_width(text.trim_end(), &style);
+                    if !line_builder.fragments.is_empty() && line_builder.width + trimmed_width > available_width {
+                        paragraph_lines.push(line_builder.finish(left));
+                    }
+                    line_builder.push(text, style, word_width);
+                }
+            }
+        }
+        if !line_builder.fragments.is_empty() || paragraph_lines.is_empty() {
+            if line_builder.fragments.is_empty() {
+                line_builder.minimum_font_size = empty_paragraph_style().font_size;
+            }
+            let left = (paragraph_style.margin_left
+                + if paragraph_lines.is_empty() {
+                    paragraph_style.indent
+                } else {
+                    0
+                }) as f64;
+            paragraph_lines.push(line_builder.finish(left));
+        }
+
+        for (mut fragments, left, font_size) in paragraph_lines {
+            let line_width = fragments
+                .last()
+                .map(|fragment: &TextFragment| {
+                    fragment.x + measure.text_width(fragment.text.trim_end(), &fragment.style)
+                })
+                .unwrap_or(0.0);
+            let offset = match paragraph_style.align {
+                TextAlignType::Center => left + (content_width - left - line_width) / 2.0,
+                TextAlignType::Right => content_width - line_width,
+                _ => left,
+            };
+            for fragment in &mut fragments {
+                fragment.x += body_style.left_inset as f64 + offset;
+            }
+
+            let ascent = fragments
+                .iter()
+                .map(|fragment| measure.ascent(&fragment.style) * fragment.style.font_size)
+                .fold(0.9 * font_size, f64::max);
+            let line_height = font_size * LINE_HEIGHT * EMUS_PER_POINT;
+            lines.push(TextLine {
+                fragments,
+                baseline: top + (ascent + font_size * (LINE_HEIGHT - 1.0) / 2.0) * EMUS_PER_POINT,
+                height: line_height,
+            });
+            top += line_height;
+        }
+    }
+
+    let content_height = height - (body_style.top_inset + body_style.bottom_inset) as f64;
+    let offset = body_style.top_inset as f64
+        + match body_style.anchor {
+            TextAnchoringType::Center => (content_height - top) / 2.0,
+            TextAnchoringType::Bottom => content_height - top,
+            _ => 0.0,
+        };
+    for line in &mut lines {
+        line.baseline += offset;
+    }
+
+    lines
+}
+
+enum Word {
+    Text(String, RunStyle),
+    LineBreak(RunStyle),
+}
+
+#[derive(Default)]
+struct LineBuilder {
+    fragments: Vec<TextFragment>,
+    width: f64,
+    /// The font size of line breaks and empty paragraphs, which affect the height of the line without adding text.
+    minimum_font_size: f64,
+}
+
+impl LineBuilder {
+    fn push(&mut self, text: String, style: RunStyle, width: f64) {
+        match self.fragments.last_mut() {
+            Some(last) if last.style == style => {
+                last.text.push_str(&text);
+                last.width += width;
+            }
+            _ => self.fragments.push(TextFragment {
+                text,
+                style,
+                x: self.width,
+                width,
+            }),
+        }
+        self.width += width;
+    }
+
+    /// Finishes the line, returning its fragments, its left margin and the largest font size within the line.
+    fn finish(&mut self, left: f64) -> (Vec<TextFragment>, f64, f64) {
+        let font_size = self
+            .fragments
+            .iter()
+            .map(|fragment| fragment.style.font_size)
+            .fold(self.minimum_font_size, f64::max);
+        let fragments = std::mem::take(&mut self.fragments);
+        *self = Default::default();
+
+        (fragments, left, font_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_style(font_size: f64) -> RunStyle {
+        RunStyle {
+            font_size,
+            bold: false,
+            italic: false,
+            underline: false,
+            strikethrough: false,
+            all_caps: false,
+            color: None,
+            typeface: None,
+        }
+    }
+
+    #[test]
+    pub fn test_line_builder() {
+        let mut builder = LineBuilder::default();
+        builder.push(String::from("Hello "), run_style(10.0), 60.0);
+        builder.push(String::from("world"), run_style(10.0), 50.0);
+        builder.push(String::from("!"), run_style(20.0), 10.0);
+
+        let (fragments, left, font_size) = builder.finish(5.0);
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(fragments[0].text, "Hello world");
+        assert_eq!(fragments[1].x, 110.0);
+        assert_eq!((left, font_size), (5.0, 20.0));
+        assert!(builder.fragments.is_empty());
+    }
+
+    #[test]
+    pub fn test_estimated_text_measure() {
+        let measure = EstimatedTextMeasure;
+        assert_eq!(measure.text_width("", &run_style(10.0)), 0.0);
+        assert!(measure.text_width("MMM", &run_style(10.0)) > measure.text_width("iii", &run_style(10.0)));
+        assert_eq!(measure.text_width("aa", &run_style(10.0)), 10.0 * EMUS_PER_POINT);
+    }
+}
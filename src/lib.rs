@@ -1,7 +1,32 @@
 #![forbid(unsafe_code)]
 
+pub mod builder;
+pub mod connection;
 pub mod document;
+pub mod drawingml;
+pub mod edit;
+pub mod graph;
+pub mod html;
+pub mod layout;
+pub mod lookup;
+pub mod markdown;
+pub mod merge;
+pub mod opc;
+pub mod outline;
+pub mod placement;
 pub mod pml;
+pub mod prune;
+#[cfg(feature = "raster")]
+pub mod raster;
+pub mod select;
+pub mod spatial;
+pub mod split;
+pub mod style;
+pub mod svg;
+pub mod template;
+pub mod text;
+pub mod visit;
+pub mod xml;
 
 extern crate strum;
 #[macro_use]
@@ -0,0 +1,239 @@
+use crate::pml::slides::{
+    ApplicationNonVisualDrawingProps, CommonSlideData, GroupShape, PlaceholderType, ShapeGroup, Slide,
+};
+use crate::style::placeholder_type;
+use msoffice_shared::drawingml::{core::NonVisualDrawingProps, simpletypes::DrawingElementId};
+use std::collections::HashMap;
+
+/// A reference to an element of a shape tree along with the chain of groups containing it, starting with the
+/// outermost group. The shape tree itself isn't part of the chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeRef<'a> {
+    pub shape: &'a ShapeGroup,
+    pub parents: Vec<&'a GroupShape>,
+}
+
+impl<'a> ShapeRef<'a> {
+    /// Returns the group directly containing the element, or None if the element is a child of the shape tree.
+    pub fn parent(&self) -> Option<&'a GroupShape> {
+        self.parents.last().copied()
+    }
+}
+
+impl ShapeGroup {
+    /// Returns the non-visual drawing properties of the element, which contain its id and name. Content parts don't
+    /// have any.
+    pub fn drawing_props(&self) -> Option<&NonVisualDrawingProps> {
+        match self {
+            ShapeGroup::Shape(shape) => Some(&shape.non_visual_props.drawing_props),
+            ShapeGroup::GroupShape(group_shape) => Some(&group_shape.non_visual_props.drawing_props),
+            ShapeGroup::GraphicFrame(graphic_frame) => Some(&graphic_frame.non_visual_props.drawing_props),
+            ShapeGroup::Connector(connector) => Some(&connector.non_visual_props.drawing_props),
+            ShapeGroup::Picture(picture) => Some(&picture.non_visual_props.drawing_props),
+            ShapeGroup::ContentPart(_) => None,
+        }
+    }
+
+    /// Returns the application non-visual drawing properties of the element, which contain its placeholder. Content
+    /// parts don't have any.
+    pub fn app_props(&self) -> Option<&ApplicationNonVisualDrawingProps> {
+        match self {
+            ShapeGroup::Shape(shape) => Some(&shape.non_visual_props.app_props),
+            ShapeGroup::GroupShape(group_shape) => Some(&group_shape.non_visual_props.app_props),
+            ShapeGroup::GraphicFrame(graphic_frame) => Some(&graphic_frame.non_visual_props.app_props),
+            ShapeGroup::Connector(connector) => Some(&connector.non_visual_props.app_props),
+            ShapeGroup::Picture(picture) => Some(&picture.non_visual_props.app_props),
+            ShapeGroup::ContentPart(_) => None,
+        }
+    }
+}
+
+/// Returns true if the element is a placeholder of the given type. If `index` is Some, the index of the placeholder
+/// has to match too, otherwise any index matches. A missing type is treated as an object placeholder and a missing
+/// index as 0, as defined by the ECMA specification.
+pub(crate) fn is_placeholder(
+    shape_group: &ShapeGroup,
+    placeholder_type_val: PlaceholderType,
+    index: Option<u32>,
+) -> bool {
+    shape_group
+        .app_props()
+        .and_then(|app_props| app_props.placeholder.as_ref())
+        .filter(|placeholder| {
+            placeholder_type(placeholder) == placeholder_type_val
+                && (index.is_none() || index == Some(placeholder.index.unwrap_or(0)))
+        })
+        .is_some()
+}
+
+impl CommonSlideData {
+    /// Finds the element with the given id in the shape tree, including nested groups. Animations reference shapes by
+    /// their ShapeId, which is the id formatted as a string.
+    pub fn shape_by_id(&self, id: DrawingElementId) -> Option<ShapeRef<'_>> {
+        self.find_shapes(|shape_group| shape_group.drawing_props().filter(|props| props.id == id).is_some())
+            .next()
+    }
+
+    /// Finds the elements with the given name in the shape tree, including nested groups, in document order.
+    pub fn shapes_by_name(&self, name: &str) -> Vec<ShapeRef<'_>> {
+        self.find_shapes(|shape_group| shape_group.drawing_props().filter(|props| props.name == name).is_some())
+            .collect()
+    }
+
+    /// Finds the first placeholder of the given type in the shape tree, including nested groups. If `index` is Some,
+    /// the index of the placeholder has to match too.
+    pub fn placeholder(&self, placeholder_type: PlaceholderType, index: Option<u32>) -> Option<ShapeRef<'_>> {
+        self.find_shapes(|shape_group| is_placeholder(shape_group, placeholder_type, index))
+            .next()
+    }
+
+    /// Builds an index of the shape tree for repeated lookups.
+    pub fn shape_index(&self) -> ShapeIndex<'_> {
+        ShapeIndex::new(&self.shape_tree)
+    }
+
+    fn find_shapes<'a, P>(&'a self, predicate: P) -> impl Iterator<Item = ShapeRef<'a>>
+    where
+        P: Fn(&ShapeGroup) -> bool,
+    {
+        self.iter_shapes()
+            .filter(move |(_, _, shape_group)| predicate(shape_group))
+            .map(|(_, parents, shape)| ShapeRef { shape, parents })
+    }
+}
+
+impl Slide {
+    /// Finds the element with the given id on the slide. See CommonSlideData::shape_by_id.
+    pub fn shape_by_id(&self, id: DrawingElementId) -> Option<ShapeRef<'_>> {
+        self.common_slide_data.shape_by_id(id)
+    }
+
+    /// Finds the elements with the given name on the slide. See CommonSlideData::shapes_by_name.
+    pub fn shapes_by_name(&self, name: &str) -> Vec<ShapeRef<'_>> {
+        self.common_slide_data.shapes_by_name(name)
+    }
+
+    /// Finds the first placeholder of the given type on the slide. See CommonSlideData::placeholder.
+    pub fn placeholder(&self, placeholder_type: PlaceholderType, index: Option<u32>) -> Option<ShapeRef<'_>> {
+        self.common_slide_data.placeholder(placeholder_type, index)
+    }
+}
+
+/// An index of the elements of a shape tree by their ids and names, for repeated lookups.
+#[derive(Debug, Clone)]
+pub struct ShapeIndex<'a> {
+    shapes: Vec<ShapeRef<'a>>,
+    ids: HashMap<DrawingElementId, usize>,
+    names: HashMap<&'a str, Vec<usize>>,
+}
+
+impl<'a> ShapeIndex<'a> {
+    pub fn new(shape_tree: &'a GroupShape) -> Self {
+        let shapes = shape_tree
+            .iter_shapes()
+            .map(|(_, parents, shape)| ShapeRef { shape, parents })
+            .collect::<Vec<_>>();
+        let mut ids = HashMap::new();
+        let mut names: HashMap<&'a str, Vec<usize>> = HashMap::new();
+        for (i, shape_ref) in shapes.iter().enumerate() {
+            if let Some(drawing_props) = shape_ref.shape.drawing_props() {
+                // Ids should be unique, but keep the first element like shape_by_id does if they aren't
+                ids.entry(drawing_props.id).or_insert(i);
+                names.entry(drawing_props.name.as_str()).or_default().push(i);
+            }
+        }
+
+        Self { shapes, ids, names }
+    }
+
+    /// Returns every element of the shape tree in depth-first order.
+    pub fn shapes(&self) -> &[ShapeRef<'a>] {
+        &self.shapes
+    }
+
+    pub fn shape_by_id(&self, id: DrawingElementId) -> Option<&ShapeRef<'a>> {
+        self.ids.get(&id).map(|&i| &self.shapes[i])
+    }
+
+    pub fn shapes_by_name(&self, name: &str) -> Vec<&ShapeRef<'a>> {
+        self.names
+            .get(name)
+            .map(|indices| indices.iter().map(|&i| &self.shapes[i]).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn placeholder(&self, placeholder_type: PlaceholderType, index: Option<u32>) -> Option<&ShapeRef<'a>> {
+        self.shapes
+            .iter()
+            .find(|shape_ref| is_placeholder(shape_ref.shape, placeholder_type, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use msoffice_shared::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_shape_lookup() {
+        let xml = r#"<p:cSld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+                xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+                <p:spTree>
+                    <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                    <p:grpSpPr/>
+                    <p:sp>
+                        <p:nvSpPr><p:cNvPr id="2" name="Title"/><p:cNvSpPr/><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr>
+                        <p:spPr/>
+                    </p:sp>
+                    <p:grpSp>
+                        <p:nvGrpSpPr><p:cNvPr id="3" name="Group"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                        <p:grpSpPr/>
+                        <p:sp>
+                            <p:nvSpPr><p:cNvPr id="4" name="Box"/><p:cNvSpPr/><p:nvPr><p:ph idx="1"/></p:nvPr></p:nvSpPr>
+                            <p:spPr/>
+                        </p:sp>
+                        <p:sp>
+                            <p:nvSpPr><p:cNvPr id="5" name="Box"/><p:cNvSpPr/><p:nvPr><p:ph idx="2"/></p:nvPr></p:nvSpPr>
+                            <p:spPr/>
+                        </p:sp>
+                    </p:grpSp>
+                </p:spTree>
+            </p:cSld>"#;
+        let common_slide_data = CommonSlideData::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        let id = |shape_ref: &ShapeRef| shape_ref.shape.drawing_props().unwrap().id;
+
+        let shape_ref = common_slide_data.shape_by_id(4).unwrap();
+        assert_eq!(id(&shape_ref), 4);
+        assert_eq!(shape_ref.parents.len(), 1);
+        assert_eq!(shape_ref.parent().unwrap().non_visual_props.drawing_props.name, "Group");
+        assert!(common_slide_data.shape_by_id(6).is_none());
+
+        let boxes = common_slide_data.shapes_by_name("Box");
+        assert_eq!(boxes.iter().map(id).collect::<Vec<_>>(), vec![4, 5]);
+
+        let title = common_slide_data.placeholder(PlaceholderType::Title, None).unwrap();
+        assert_eq!(id(&title), 2);
+        assert!(title.parents.is_empty());
+        assert_eq!(
+            id(&common_slide_data.placeholder(PlaceholderType::Title, Some(0)).unwrap()),
+            2
+        );
+        assert_eq!(
+            id(&common_slide_data.placeholder(PlaceholderType::Object, None).unwrap()),
+            4
+        );
+        assert_eq!(
+            id(&common_slide_data.placeholder(PlaceholderType::Object, Some(2)).unwrap()),
+            5
+        );
+        assert!(common_slide_data.placeholder(PlaceholderType::Body, None).is_none());
+
+        let index = common_slide_data.shape_index();
+        assert_eq!(index.shapes().len(), 4);
+        assert_eq!(id(index.shape_by_id(5).unwrap()), 5);
+        assert_eq!(index.shapes_by_name("Box").len(), 2);
+        assert!(index.shapes_by_name("Missing").is_empty());
+        assert_eq!(id(index.placeholder(PlaceholderType::Object, Some(2)).unwrap()), 5);
+    }
+}
@@ -0,0 +1,319 @@
+use crate::document::PPTXDocument;
+use crate::pml::slides::{GraphicalObjectContent, GroupShape, PlaceholderType, ShapeGroup, Slide};
+use crate::text::{diagram_paragraphs, table_cells, text_body_paragraphs, ParagraphText};
+use std::path::Path;
+
+/// Options of the Markdown export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownOptions {
+    /// The directory that image links point to. The media files of the presentation are expected to be extracted into
+    /// this directory with their original file names, e.g. by PPTXDocument::extract_media.
+    ///
+    /// Defaults to "media"
+    pub media_directory: String,
+    /// Specifies whether the speaker notes should be exported as block quotes following the content of the slide.
+    ///
+    /// Defaults to true
+    pub include_notes: bool,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        Self {
+            media_directory: String::from("media"),
+            include_notes: true,
+        }
+    }
+}
+
+/// Renders the whole presentation to Markdown.
+///
+/// The slides are rendered in the order of the presentation's slide id list, separated by empty lines. See
+/// slide_to_markdown for the format of a single slide.
+pub fn to_markdown(document: &PPTXDocument, options: &MarkdownOptions) -> String {
+    document
+        .ordered_slides()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (slide_path, slide))| slide_to_markdown(document, slide_path, slide, index + 1, options))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders a single slide to Markdown.
+///
+/// The slide is rendered as a section starting with a level 1 heading containing the slide's title, or "Slide n" if
+/// the slide has no title. The shapes follow in reading order:
+/// * subtitles are rendered as paragraphs
+/// * any other text is rendered as a bulleted list nested by the indentation levels of the paragraphs
+/// * tables are rendered as GFM tables, using the first row as the header
+/// * pictures are rendered as images linking to options.media_directory
+///
+/// Date, footer, header and slide number placeholders are skipped. The speaker notes, if requested, are rendered as a
+/// block quote at the end of the section.
+pub fn slide_to_markdown(
+    document: &PPTXDocument,
+    slide_path: &Path,
+    slide: &Slide,
+    slide_number: usize,
+    options: &MarkdownOptions,
+) -> String {
+    let mut blocks = Vec::new();
+    let mut title = None;
+    write_group(
+        document,
+        slide_path,
+        &slide.common_slide_data.shape_tree,
+        options,
+        &mut title,
+        &mut blocks,
+    );
+
+    let title = title.unwrap_or_else(|| format!("Slide {}", slide_number));
+    let mut markdown = format!("# {}\n", title);
+    for block in blocks {
+        markdown.push('\n');
+        markdown.push_str(&block);
+    }
+
+    if options.include_notes {
+        let notes_lines = document
+            .notes_slide_path(slide_path)
+            .and_then(|notes_path| document.notes_slide_map.get(&notes_path))
+            .map(|notes_slide| {
+                notes_slide
+                    .text()
+                    .iter()
+                    .flat_map(|shape_text| {
+                        shape_text
+                            .plain_text()
+                            .lines()
+                            .map(|line| format!("> {}\n", escape_markdown(line)))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if !notes_lines.is_empty() {
+            markdown.push('\n');
+            markdown.push_str(&notes_lines.join(">\n"));
+        }
+    }
+
+    markdown
+}
+
+fn write_group(
+    document: &PPTXDocument,
+    slide_path: &Path,
+    group: &GroupShape,
+    options: &MarkdownOptions,
+    title: &mut Option<String>,
+    blocks: &mut Vec<String>,
+) {
+    for shape_group in &group.shape_array {
+        match shape_group {
+            ShapeGroup::Shape(shape) => {
+                let text_body = match &shape.text_body {
+                    Some(text_body) => text_body,
+                    None => continue,
+                };
+
+                let paragraphs = text_body_paragraphs(text_body)
+                    .into_iter()
+                    .filter(|paragraph| !paragraph.text.is_empty())
+                    .collect::<Vec<_>>();
+                if paragraphs.is_empty() {
+                    continue;
+                }
+
+                let placeholder_type = shape
+                    .non_visual_props
+                    .app_props
+                    .placeholder
+                    .as_ref()
+                    .map(|placeholder| placeholder.placeholder_type.unwrap_or(PlaceholderType::Object));
+                match placeholder_type {
+                    Some(PlaceholderType::Title) | Some(PlaceholderType::CenteredTitle) if title.is_none() => {
+                        let text = paragraphs
+                            .iter()
+                            .map(|paragraph| paragraph.text.as_str())
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        *title = Some(escape_markdown(&text.replace('\n', " ")));
+                    }
+                    Some(PlaceholderType::SubTitle) => blocks.push(
+                        paragraphs
+                            .iter()
+                            .map(|paragraph| format!("{}\n", inline_text(&paragraph.text)))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    ),
+                    Some(PlaceholderType::DateTime)
+                    | Some(PlaceholderType::Footer)
+                    | Some(PlaceholderType::Header)
+                    | Some(PlaceholderType::SlideNumber) => (),
+                    _ => blocks.push(list(&paragraphs)),
+                }
+            }
+            ShapeGroup::GroupShape(group_shape) => {
+                write_group(document, slide_path, group_shape, options, title, blocks)
+            }
+            ShapeGroup::GraphicFrame(graphic_frame) => match &graphic_frame.graphic_content {
+                Some(GraphicalObjectContent::Table(table)) => {
+                    let rows = table_cells(table);
+                    if !rows.is_empty() {
+                        blocks.push(gfm_table(&rows));
+                    }
+                }
+                Some(GraphicalObjectContent::Diagram(relationship_ids)) => {
+                    let paragraphs = document
+                        .diagram_data(slide_path, relationship_ids)
+                        .map(diagram_paragraphs)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|paragraph| !paragraph.text.is_empty())
+                        .collect::<Vec<_>>();
+                    if !paragraphs.is_empty() {
+                        blocks.push(list(&paragraphs));
+                    }
+                }
+                None => (),
+            },
+            ShapeGroup::Picture(picture) => {
+                let media_path = picture
+                    .blip_fill
+                    .blip
+                    .as_ref()
+                    .and_then(|blip| blip.embed_rel_id.as_ref())
+                    .and_then(|rel_id| document.relationship_target_path(slide_path, rel_id));
+                let file_name = match media_path.as_ref().and_then(|path| path.file_name()) {
+                    Some(file_name) => file_name.to_string_lossy().into_owned(),
+                    None => continue,
+                };
+
+                let drawing_props = &picture.non_visual_props.drawing_props;
+                let alt_text = drawing_props.description.as_ref().unwrap_or(&drawing_props.name);
+                blocks.push(format!(
+                    "![{}]({}/{})\n",
+                    inline_text(alt_text),
+                    options.media_directory.trim_end_matches('/'),
+                    file_name.replace(' ', "%20"),
+                ));
+            }
+            _ => (),
+        }
+    }
+}
+
+fn list(paragraphs: &[ParagraphText]) -> String {
+    paragraphs
+        .iter()
+        .map(|paragraph| {
+            format!(
+                "{}- {}\n",
+                "  ".repeat(paragraph.level as usize),
+                inline_text(&paragraph.text)
+            )
+        })
+        .collect()
+}
+
+fn gfm_table(rows: &[Vec<Vec<ParagraphText>>]) -> String {
+    let column_count = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    let format_row = |row: &Vec<Vec<ParagraphText>>| {
+        let mut cells = row
+            .iter()
+            .map(|cell| {
+                cell.iter()
+                    .filter(|paragraph| !paragraph.text.is_empty())
+                    .map(|paragraph| inline_text(&paragraph.text))
+                    .collect::<Vec<_>>()
+                    .join("<br>")
+            })
+            .collect::<Vec<_>>();
+        cells.resize(column_count, String::new());
+        format!("| {} |\n", cells.join(" | "))
+    };
+
+    let mut table = format_row(&rows[0]);
+    table.push_str(&format!("|{}\n", " --- |".repeat(column_count)));
+    for row in &rows[1..] {
+        table.push_str(&format_row(row));
+    }
+
+    table
+}
+
+/// Escapes the text of a paragraph so it's rendered literally within a single line of Markdown. Line breaks are
+/// replaced with html line breaks.
+fn inline_text(text: &str) -> String {
+    escape_markdown(text).replace('\n', "<br>")
+}
+
+/// Escapes the characters that would otherwise be interpreted as inline Markdown formatting.
+pub(crate) fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if let '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' = c {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn paragraph(level: u32, text: &str) -> ParagraphText {
+        ParagraphText {
+            level,
+            text: String::from(text),
+        }
+    }
+
+    #[test]
+    pub fn test_list() {
+        let paragraphs = vec![paragraph(0, "First"), paragraph(1, "Nested\nline"), paragraph(0, "a|b")];
+        assert_eq!(list(&paragraphs), "- First\n  - Nested<br>line\n- a\\|b\n");
+    }
+
+    #[test]
+    pub fn test_gfm_table() {
+        let rows = vec![
+            vec![vec![paragraph(0, "Name")], vec![paragraph(0, "Value")]],
+            vec![vec![paragraph(0, "A"), paragraph(0, "B")]],
+        ];
+        assert_eq!(gfm_table(&rows), "| Name | Value |\n| --- | --- |\n| A<br>B |  |\n");
+    }
+
+    #[test]
+    pub fn test_markdown() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let sample_pptx_path = test_dir.join("tests/samplepptx.pptx");
+
+        let document = PPTXDocument::from_file(&sample_pptx_path).unwrap();
+        let slide_paths = document
+            .ordered_slides()
+            .into_iter()
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            slide_paths,
+            vec![
+                PathBuf::from("ppt/slides/slide1.xml"),
+                PathBuf::from("ppt/slides/slide2.xml"),
+            ]
+        );
+
+        let markdown = to_markdown(&document, &Default::default());
+        assert!(markdown
+            .starts_with("# Sample PowerPoint File\n\nSt. Cloud Technical College\n\n# This is a Sample Slide\n"));
+        assert!(markdown.contains("- You can print out PPT files as handouts using the <br>PRINT \\> <br>"));
+    }
+}
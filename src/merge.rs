@@ -0,0 +1,711 @@
+//! Importing slides from one presentation into another.
+//!
+//! Every part an imported slide references is copied into the target document along with the parts it references in
+//! turn, like the slide layout, the slide master, the theme, media files, charts and the notes slide. Copied parts
+//! get unused names in the target, e.g. ppt/media/image1.png may be copied to ppt/media/image4.png, and the
+//! relationships are retargeted accordingly. Each part is copied once per import, so slides that share a slide layout
+//! or a picture keep sharing it.
+
+use crate::document::{
+    relationships_path, relative_relationship_target, resolve_relationship_target, PPTXDocument,
+    NOTES_MASTER_RELATIONSHIP_TYPE, PRESENTATION_PATH, SLIDE_LAYOUT_RELATIONSHIP_TYPE, SLIDE_MASTER_RELATIONSHIP_TYPE,
+    SLIDE_RELATIONSHIP_TYPE,
+};
+use crate::opc::{next_relationship_id, write_relationships};
+use crate::pml::{
+    presentation::{
+        NotesMasterIdListEntry, SlideIdListEntry, SlideLayoutIdList, SlideLayoutIdListEntry, SlideMasterIdListEntry,
+    },
+    slides::{NotesSlide, Slide, SlideLayout, SlideLayoutType, SlideMaster},
+};
+use msoffice_shared::{relationship::Relationship, xml::XmlNode};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// Specifies which slide layouts the imported slides follow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutMapping {
+    /// The slide layout of every imported slide is copied along with its slide master and theme, so the slides keep
+    /// their original formatting. A slide master is copied with all of its slide layouts.
+    KeepSource,
+    /// Imported slides follow the slide layout of the target with the same matching name or, failing that, the same
+    /// type, so they take on the formatting of the target. Custom slide layouts are only matched by name. The slide
+    /// layout is copied like with KeepSource if the target doesn't have an equivalent one.
+    UseTarget,
+}
+
+impl PPTXDocument {
+    /// Imports the slides at `slide_paths` of `source` to the end of the presentation, in the given order. Returns the
+    /// paths of the imported slides.
+    ///
+    /// The imported slides, slide masters and slide layouts get new ids, and the presentation gets new relationships
+    /// to them. Shape ids aren't renumbered on purpose: they only have to be unique within their slide, and every
+    /// imported slide is a part of its own in the target, so they can't collide with the shape ids of the target.
+    /// Keeping them also keeps the references to them valid, like the animation targets and the connections of
+    /// connectors.
+    ///
+    /// # Note
+    ///
+    /// Slides are imported one after the other, so hyperlinks jumping to a slide that isn't imported yet are removed
+    /// along with their relationships. If the target already has a notes master, the imported notes slides use it
+    /// instead of a copy of the source one.
+    pub fn import_slides(
+        &mut self,
+        source: &PPTXDocument,
+        slide_paths: &[PathBuf],
+        layout_mapping: LayoutMapping,
+    ) -> Result<Vec<PathBuf>> {
+        if self.presentation.is_none() {
+            return Err("the document doesn't have a presentation".into());
+        }
+
+        let mut importer = SlideImporter::new(source, self, layout_mapping, true);
+        slide_paths
+            .iter()
+            .map(|slide_path| importer.import_slide(slide_path))
+            .collect()
+    }
+}
+
+/// Copies slides from one document into another along with the parts they reference.
+pub(crate) struct SlideImporter<'a> {
+    source: &'a PPTXDocument,
+    target: &'a mut PPTXDocument,
+    layout_mapping: LayoutMapping,
+    /// Specifies whether slide masters are copied with all of their slide layouts, or only with the ones used by the
+    /// imported slides.
+    include_unused_slide_layouts: bool,
+    /// The paths of the source parts that have been imported, mapped to the paths of their counterparts in the target.
+    part_map: HashMap<PathBuf, PathBuf>,
+    /// The parts of the source package of the target, so the paths of the copies don't collide with them.
+    target_package_part_paths: HashSet<PathBuf>,
+}
+
+impl<'a> SlideImporter<'a> {
+    pub(crate) fn new(
+        source: &'a PPTXDocument,
+        target: &'a mut PPTXDocument,
+        layout_mapping: LayoutMapping,
+        include_unused_slide_layouts: bool,
+    ) -> Self {
+        let target_package_part_paths = target.package_part_paths();
+        Self {
+            source,
+            target,
+            layout_mapping,
+            include_unused_slide_layouts,
+            part_map: HashMap::new(),
+            target_package_part_paths,
+        }
+    }
+
+    /// Returns the target document for modification, e.g. to add parts the imported slides don't reference.
+    pub(crate) fn target_mut(&mut self) -> &mut PPTXDocument {
+        self.target
+    }
+
+    /// Imports the source slide at `slide_path` to the end of the presentation of the target and returns its path.
+    pub(crate) fn import_slide(&mut self, slide_path: &Path) -> Result<PathBuf> {
+        let slide = self
+            .source
+            .slide_map
+            .get(slide_path)
+            .ok_or_else(|| format!("slide not found: {}", slide_path.display()))?
+            .clone();
+        let target_slide_path =
+            self.target
+                .find_unused_part_path(&self.target_package_part_paths, "ppt/slides", "slide", "xml");
+        self.part_map
+            .insert(PathBuf::from(slide_path), target_slide_path.clone());
+        self.target.slide_map.insert(target_slide_path.clone(), slide);
+
+        let relationships = self.copy_relationships(slide_path, &target_slide_path)?;
+        self.target
+            .slide_rels_map
+            .insert(relationships_path(&target_slide_path), relationships);
+
+        let relationship = Relationship {
+            id: next_relationship_id(&self.target.presentation_rels),
+            rel_type: String::from(SLIDE_RELATIONSHIP_TYPE),
+            target: relative_relationship_target(Path::new(PRESENTATION_PATH), &target_slide_path),
+        };
+        let slide_id = self.target.next_slide_id();
+        if let Some(presentation) = self.target.presentation_mut() {
+            presentation.slide_id_list.push(SlideIdListEntry {
+                id: slide_id,
+                relationship_id: relationship.id.clone(),
+                unknown_xml: Default::default(),
+            });
+        }
+        self.target.presentation_rels.push(relationship);
+
+        Ok(target_slide_path)
+    }
+
+    /// Returns the relationships of the source part at `source_part_path` for its counterpart at `target_part_path`,
+    /// importing the parts they reference. Relationships to parts that can't be imported are left out, and so are the
+    /// hyperlinks of the counterpart that use them.
+    fn copy_relationships(&mut self, source_part_path: &Path, target_part_path: &Path) -> Result<Vec<Relationship>> {
+        let is_slide = self.source.slide_map.contains_key(source_part_path);
+        let mut relationships = Vec::new();
+        let mut dropped_relationship_ids = Vec::new();
+        for mut relationship in self.source.read_relationships(source_part_path)? {
+            if self.source.is_external_relationship(source_part_path, &relationship) {
+                self.target
+                    .external_relationships
+                    .insert(&relationships_path(target_part_path), &relationship.id);
+                relationships.push(relationship);
+                continue;
+            }
+
+            let referenced_part_path = resolve_relationship_target(source_part_path, &relationship.target);
+            let target_path = match relationship.rel_type.as_str() {
+                SLIDE_LAYOUT_RELATIONSHIP_TYPE if is_slide => self.import_slide_layout(&referenced_part_path)?,
+                SLIDE_LAYOUT_RELATIONSHIP_TYPE if !self.include_unused_slide_layouts => {
+                    self.part_map.get(&referenced_part_path).cloned()
+                }
+                NOTES_MASTER_RELATIONSHIP_TYPE => self.import_notes_master(&referenced_part_path)?,
+                _ => self.copy_part(&referenced_part_path)?,
+            };
+
+            match target_path {
+                Some(target_path) => {
+                    relationship.target = relative_relationship_target(target_part_path, &target_path);
+                    relationships.push(relationship);
+                }
+                None => dropped_relationship_ids.push(relationship.id),
+            }
+        }
+
+        if !dropped_relationship_ids.is_empty() {
+            self.remove_hyperlinks(target_part_path, &dropped_relationship_ids)?;
+        }
+
+        Ok(relationships)
+    }
+
+    /// Removes the hyperlinks using the relationships with the ids `relationship_ids` from the target part at
+    /// `part_path`, e.g. the ones jumping to a slide that isn't imported.
+    fn remove_hyperlinks(&mut self, part_path: &Path, relationship_ids: &[String]) -> Result<()> {
+        let target = &mut *self.target;
+        if let Some(slide) = target.slide_map.get_mut(part_path) {
+            let xml_node = without_hyperlinks(slide.to_xml_element(), relationship_ids);
+            **slide = Slide::from_xml_element(&xml_node)?;
+        } else if let Some(slide_layout) = target.slide_layout_map.get_mut(part_path) {
+            let xml_node = without_hyperlinks(slide_layout.to_xml_element(), relationship_ids);
+            **slide_layout = SlideLayout::from_xml_element(&xml_node)?;
+        } else if let Some(slide_master) = target.slide_master_map.get_mut(part_path) {
+            let xml_node = without_hyperlinks(slide_master.to_xml_element(), relationship_ids);
+            **slide_master = SlideMaster::from_xml_element(&xml_node)?;
+        } else if let Some(notes_slide) = target.notes_slide_map.get_mut(part_path) {
+            let xml_node = without_hyperlinks(notes_slide.to_xml_element(), relationship_ids);
+            **notes_slide = NotesSlide::from_xml_element(&xml_node)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the path of the slide layout in the target that slides of the source slide layout at
+    /// `slide_layout_path` should follow.
+    fn import_slide_layout(&mut self, slide_layout_path: &Path) -> Result<Option<PathBuf>> {
+        if let Some(target_path) = self.part_map.get(slide_layout_path) {
+            return Ok(Some(target_path.clone()));
+        }
+
+        if self.layout_mapping == LayoutMapping::UseTarget {
+            let equivalent_path = self
+                .source
+                .slide_layout_map
+                .get(slide_layout_path)
+                .and_then(|slide_layout| equivalent_slide_layout_path(self.target, slide_layout));
+            if let Some(equivalent_path) = equivalent_path {
+                self.part_map
+                    .insert(PathBuf::from(slide_layout_path), equivalent_path.clone());
+                return Ok(Some(equivalent_path));
+            }
+        }
+
+        self.copy_part(slide_layout_path)
+    }
+
+    /// Returns the path of the notes master in the target that notes slides of the source notes master at
+    /// `notes_master_path` should use. The notes master is copied if the target doesn't have one yet.
+    fn import_notes_master(&mut self, notes_master_path: &Path) -> Result<Option<PathBuf>> {
+        if let Some(target_path) = self.part_map.get(notes_master_path) {
+            return Ok(Some(target_path.clone()));
+        }
+
+        let presentation_path = Path::new(PRESENTATION_PATH);
+        if let Some(target_path) = self
+            .target
+            .related_part_path(presentation_path, NOTES_MASTER_RELATIONSHIP_TYPE)
+        {
+            self.part_map
+                .insert(PathBuf::from(notes_master_path), target_path.clone());
+            return Ok(Some(target_path));
+        }
+
+        let target_path = match self.copy_part(notes_master_path)? {
+            Some(target_path) => target_path,
+            None => return Ok(None),
+        };
+        let relationship = Relationship {
+            id: next_relationship_id(&self.target.presentation_rels),
+            rel_type: String::from(NOTES_MASTER_RELATIONSHIP_TYPE),
+            target: relative_relationship_target(presentation_path, &target_path),
+        };
+        if let Some(presentation) = self.target.presentation_mut() {
+            presentation.notes_master_id = Some(NotesMasterIdListEntry {
+                relationship_id: relationship.id.clone(),
+            });
+        }
+        self.target.presentation_rels.push(relationship);
+
+        Ok(Some(target_path))
+    }
+
+    /// Copies the source part at `part_path` along with the parts it references, and returns the path of the copy.
+    /// The copy is added to the target before its relationships are followed, so parts referencing each other are
+    /// copied once. Slides that aren't imported and missing parts aren't copied.
+    pub(crate) fn copy_part(&mut self, part_path: &Path) -> Result<Option<PathBuf>> {
+        if let Some(target_path) = self.part_map.get(part_path) {
+            return Ok(Some(target_path.clone()));
+        }
+
+        let source = self.source;
+        if part_path == Path::new(PRESENTATION_PATH) || source.slide_map.contains_key(part_path) {
+            return Ok(None);
+        }
+
+        let target_path = self
+            .target
+            .find_unused_copy_path(&self.target_package_part_paths, part_path);
+        self.part_map.insert(PathBuf::from(part_path), target_path.clone());
+        let target_rels_path = relationships_path(&target_path);
+
+        if let Some(slide_master) = source.slide_master_map.get(part_path) {
+            let slide_master_id = self.target.next_slide_master_id();
+            let mut slide_master = slide_master.clone();
+            if let Some(slide_layout_id_list) = &mut slide_master.slide_layout_id_list {
+                for (i, entry) in slide_layout_id_list.0.iter_mut().enumerate() {
+                    entry.id = Some(slide_master_id + 1 + i as u32);
+                }
+            }
+            self.target.slide_master_map.insert(target_path.clone(), slide_master);
+
+            let relationship = Relationship {
+                id: next_relationship_id(&self.target.presentation_rels),
+                rel_type: String::from(SLIDE_MASTER_RELATIONSHIP_TYPE),
+                target: relative_relationship_target(Path::new(PRESENTATION_PATH), &target_path),
+            };
+            if let Some(presentation) = self.target.presentation_mut() {
+                presentation.slide_master_id_list.push(SlideMasterIdListEntry {
+                    id: Some(slide_master_id),
+                    relationship_id: relationship.id.clone(),
+                    unknown_xml: Default::default(),
+                });
+            }
+            self.target.presentation_rels.push(relationship);
+
+            let relationships = self.copy_relationships(part_path, &target_path)?;
+            if !self.include_unused_slide_layouts {
+                let slide_layout_id_list = self
+                    .target
+                    .slide_master_map
+                    .get_mut(&target_path)
+                    .and_then(|slide_master| slide_master.slide_layout_id_list.as_mut());
+                if let Some(slide_layout_id_list) = slide_layout_id_list {
+                    slide_layout_id_list
+                        .0
+                        .retain(|entry| relationships.iter().any(|rel| rel.id == entry.relationship_id));
+                }
+            }
+            self.target
+                .slide_master_rels_map
+                .insert(target_rels_path, relationships);
+        } else if let Some(slide_layout) = source.slide_layout_map.get(part_path) {
+            self.target
+                .slide_layout_map
+                .insert(target_path.clone(), slide_layout.clone());
+            let relationships = self.copy_relationships(part_path, &target_path)?;
+            self.target
+                .slide_layout_rels_map
+                .insert(target_rels_path, relationships);
+
+            // A slide master that has been copied before only lists the slide layouts that were in use at that time
+            if !self.include_unused_slide_layouts {
+                if let Some(slide_master_path) = self.target.slide_master_path(&target_path) {
+                    self.add_slide_layout_to_master(&slide_master_path, &target_path);
+                }
+            }
+        } else if let Some(notes_slide) = source.notes_slide_map.get(part_path) {
+            self.target
+                .notes_slide_map
+                .insert(target_path.clone(), notes_slide.clone());
+            let relationships = self.copy_relationships(part_path, &target_path)?;
+            self.target.notes_slide_rels_map.insert(target_rels_path, relationships);
+        } else {
+            let data = match source.read_existing_part(part_path)? {
+                Some(data) => data,
+                None => {
+                    self.part_map.remove(part_path);
+                    return Ok(None);
+                }
+            };
+
+            if let Some(theme) = source.theme_map.get(part_path) {
+                self.target.theme_map.insert(target_path.clone(), theme.clone());
+            } else if source.medias.iter().any(|media_path| media_path == part_path) {
+                self.target.medias.push(target_path.clone());
+            } else if let Some(content_type) = source.part_content_type(part_path)? {
+                self.target
+                    .raw_part_content_types
+                    .insert(target_path.clone(), content_type);
+            }
+            if let Some(diagram_data) = source.diagram_data_map.get(part_path) {
+                self.target
+                    .diagram_data_map
+                    .insert(target_path.clone(), diagram_data.clone());
+            }
+            self.target.raw_part_map.insert(target_path.clone(), data);
+
+            let relationships = self.copy_relationships(part_path, &target_path)?;
+            if !relationships.is_empty() {
+                let mut data = Vec::new();
+                write_relationships(
+                    &relationships,
+                    &target_rels_path,
+                    &self.target.external_relationships,
+                    &mut data,
+                )?;
+                self.target.raw_part_map.insert(target_rels_path, data);
+            }
+        }
+
+        Ok(Some(target_path))
+    }
+}
+
+impl<'a> SlideImporter<'a> {
+    /// Adds the slide layout at `slide_layout_path` to the slide layouts of the slide master at `slide_master_path` in
+    /// the target, unless the slide master already lists it.
+    fn add_slide_layout_to_master(&mut self, slide_master_path: &Path, slide_layout_path: &Path) {
+        let slide_layout_id = self.target.next_slide_master_id();
+        let relationships = self
+            .target
+            .slide_master_rels_map
+            .entry(relationships_path(slide_master_path))
+            .or_default();
+        let is_listed = relationships.iter().any(|relationship| {
+            relationship.rel_type == SLIDE_LAYOUT_RELATIONSHIP_TYPE
+                && resolve_relationship_target(slide_master_path, &relationship.target) == slide_layout_path
+        });
+        if is_listed {
+            return;
+        }
+
+        let relationship = Relationship {
+            id: next_relationship_id(relationships),
+            rel_type: String::from(SLIDE_LAYOUT_RELATIONSHIP_TYPE),
+            target: relative_relationship_target(slide_master_path, slide_layout_path),
+        };
+        let entry = SlideLayoutIdListEntry {
+            id: Some(slide_layout_id),
+            relationship_id: relationship.id.clone(),
+            unknown_xml: Default::default(),
+        };
+        relationships.push(relationship);
+        if let Some(slide_master) = self.target.slide_master_map.get_mut(slide_master_path) {
+            slide_master
+                .slide_layout_id_list
+                .get_or_insert_with(|| SlideLayoutIdList(Vec::new()))
+                .0
+                .push(entry);
+        }
+    }
+}
+
+/// Returns `xml_node` without the hyperlink elements, at any depth, that use one of the relationships with the ids
+/// `relationship_ids`.
+fn without_hyperlinks(mut xml_node: XmlNode, relationship_ids: &[String]) -> XmlNode {
+    xml_node.child_nodes = xml_node
+        .child_nodes
+        .into_iter()
+        .filter(|child_node| {
+            let is_hyperlink = matches!(child_node.local_name(), "hlinkClick" | "hlinkHover" | "hlinkMouseOver");
+            match child_node.attributes.get("r:id") {
+                Some(relationship_id) if is_hyperlink => !relationship_ids.contains(relationship_id),
+                _ => true,
+            }
+        })
+        .map(|child_node| without_hyperlinks(child_node, relationship_ids))
+        .collect();
+    xml_node
+}
+
+/// Returns the path of the slide layout of `document` that's equivalent to `slide_layout`, matched by its matching
+/// name or its type. Custom slide layouts are only matched by name. The first match in the order of the slide layout
+/// id lists of the slide masters is returned.
+fn equivalent_slide_layout_path(document: &PPTXDocument, slide_layout: &SlideLayout) -> Option<PathBuf> {
+    let ordered_slide_layouts = document.ordered_slide_layouts();
+    let find_slide_layout = |predicate: &dyn Fn(&SlideLayout) -> bool| {
+        ordered_slide_layouts
+            .iter()
+            .find(|(_, target_slide_layout)| predicate(target_slide_layout))
+            .map(|(slide_layout_path, _)| PathBuf::from(slide_layout_path))
+    };
+
+    let by_matching_name = slide_layout
+        .matching_name
+        .as_deref()
+        .filter(|matching_name| !matching_name.is_empty())
+        .and_then(|matching_name| {
+            find_slide_layout(&|target_slide_layout| {
+                target_slide_layout.matching_name.as_deref() == Some(matching_name)
+            })
+        });
+
+    by_matching_name.or_else(|| match slide_layout.slide_layout_type {
+        Some(SlideLayoutType::Custom) | None => None,
+        Some(slide_layout_type) => {
+            find_slide_layout(&|target_slide_layout| target_slide_layout.slide_layout_type == Some(slide_layout_type))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::PresentationBuilder;
+    use crate::document::{load_saved_document, SavedDocument};
+    use std::io::Cursor;
+
+    fn source_and_target() -> (PPTXDocument, PPTXDocument) {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let source = PPTXDocument::from_file(&test_dir.join("tests/samplepptx.pptx")).unwrap();
+
+        let mut builder = PresentationBuilder::new().unwrap();
+        builder.add_slide(SlideLayoutType::Title).unwrap();
+        (source, builder.build())
+    }
+
+    fn source_slide_paths(source: &PPTXDocument) -> Vec<PathBuf> {
+        source
+            .ordered_slides()
+            .into_iter()
+            .map(|(slide_path, _)| slide_path.clone())
+            .collect()
+    }
+
+    fn hyperlink_relationship_ids(xml_node: &XmlNode) -> Vec<String> {
+        let mut relationship_ids = xml_node
+            .child_nodes
+            .iter()
+            .flat_map(hyperlink_relationship_ids)
+            .collect::<Vec<_>>();
+        if xml_node.local_name().starts_with("hlink") {
+            relationship_ids.extend(xml_node.attributes.get("r:id").cloned());
+        }
+        relationship_ids
+    }
+
+    fn add_hyperlink(xml_node: &mut XmlNode, parent_name: &str, relationship_id: &str) -> bool {
+        if xml_node.local_name() == parent_name {
+            let mut hyperlink = XmlNode::new("a:hlinkClick");
+            hyperlink
+                .attributes
+                .insert(String::from("r:id"), String::from(relationship_id));
+            hyperlink
+                .attributes
+                .insert(String::from("action"), String::from("ppaction://hlinksldjump"));
+            xml_node.child_nodes.push(hyperlink);
+            return true;
+        }
+
+        xml_node
+            .child_nodes
+            .iter_mut()
+            .any(|child_node| add_hyperlink(child_node, parent_name, relationship_id))
+    }
+
+    fn save_and_reload(document: &PPTXDocument) -> SavedDocument {
+        let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+        load_saved_document(&data)
+    }
+
+    #[test]
+    fn test_import_slides_keep_source() {
+        let (source, mut target) = source_and_target();
+        let slide_paths = source_slide_paths(&source);
+
+        let imported_paths = target
+            .import_slides(&source, &slide_paths, LayoutMapping::KeepSource)
+            .unwrap();
+        assert_eq!(
+            imported_paths,
+            vec![
+                PathBuf::from("ppt/slides/slide2.xml"),
+                PathBuf::from("ppt/slides/slide3.xml")
+            ]
+        );
+        assert_eq!(target.slide_master_map.len(), 2);
+        assert_eq!(target.slide_layout_map.len(), 6 + source.slide_layout_map.len());
+        assert_eq!(target.theme_map.len(), 2);
+
+        let presentation = target.presentation.as_ref().unwrap();
+        let slide_ids = presentation
+            .slide_id_list
+            .iter()
+            .map(|entry| entry.id)
+            .collect::<HashSet<_>>();
+        assert_eq!(slide_ids.len(), 3);
+        let mut master_and_layout_ids = presentation
+            .slide_master_id_list
+            .iter()
+            .filter_map(|entry| entry.id)
+            .collect::<Vec<_>>();
+        master_and_layout_ids.extend(
+            target
+                .slide_master_map
+                .values()
+                .flat_map(|slide_master| slide_master.slide_layout_id_list.iter().flat_map(|list| list.0.iter()))
+                .filter_map(|entry| entry.id),
+        );
+        let unique_ids = master_and_layout_ids.iter().collect::<HashSet<_>>();
+        assert_eq!(unique_ids.len(), master_and_layout_ids.len());
+
+        let saved_target = save_and_reload(&target);
+        assert_eq!(saved_target.slide_map, target.slide_map);
+        assert_eq!(saved_target.slide_master_map, target.slide_master_map);
+        assert_eq!(saved_target.slide_layout_map, target.slide_layout_map);
+        assert_eq!(saved_target.theme_map, target.theme_map);
+        for (imported_path, source_path) in imported_paths.iter().zip(&slide_paths) {
+            let slide_layout_path = saved_target.slide_layout_path(imported_path).unwrap();
+            let source_slide_layout_path = source.slide_layout_path(source_path).unwrap();
+            assert_eq!(
+                saved_target.slide_layout_map[&slide_layout_path],
+                source.slide_layout_map[&source_slide_layout_path]
+            );
+
+            let slide_master_path = saved_target.slide_master_path(&slide_layout_path).unwrap();
+            assert_eq!(slide_master_path, PathBuf::from("ppt/slideMasters/slideMaster2.xml"));
+            assert_eq!(
+                saved_target.theme_path(&slide_master_path),
+                Some(PathBuf::from("ppt/theme/theme2.xml"))
+            );
+        }
+
+        // Media files are renamed in the order they're referenced, so they're compared by their content
+        let media_contents = |document: &PPTXDocument| {
+            let mut contents = document
+                .medias
+                .iter()
+                .map(|media_path| document.read_part(media_path).unwrap())
+                .collect::<Vec<_>>();
+            contents.sort();
+            contents
+        };
+        assert_eq!(media_contents(&saved_target), media_contents(&source));
+    }
+
+    #[test]
+    fn test_import_slides_use_target() {
+        let (source, mut target) = source_and_target();
+        let slide_paths = source_slide_paths(&source);
+
+        let imported_paths = target
+            .import_slides(&source, &slide_paths, LayoutMapping::UseTarget)
+            .unwrap();
+        assert_eq!(target.slide_master_map.len(), 1);
+        assert_eq!(target.slide_layout_map.len(), 6);
+
+        let saved_target = save_and_reload(&target);
+        let slide_layout_types = imported_paths
+            .iter()
+            .map(|slide_path| {
+                let slide_layout_path = saved_target.slide_layout_path(slide_path).unwrap();
+                saved_target.slide_layout_map[&slide_layout_path].slide_layout_type
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            slide_layout_types,
+            vec![Some(SlideLayoutType::Title), Some(SlideLayoutType::Object)]
+        );
+        assert_eq!(saved_target.ordered_slides().len(), 3);
+    }
+
+    #[test]
+    fn test_import_slides_external_relationship() {
+        let (mut source, mut target) = source_and_target();
+        let slide_paths = source_slide_paths(&source);
+
+        // A relative target only references a resource outside of the package because of its target mode
+        let slide_rels = source
+            .slide_rels_map
+            .get_mut(&relationships_path(&slide_paths[0]))
+            .unwrap();
+        slide_rels.push(Relationship {
+            id: next_relationship_id(slide_rels),
+            rel_type: String::from("http://schemas.openxmlformats.org/officeDocument/2006/relationships/video"),
+            target: String::from("movies/movie.mp4"),
+        });
+        let relationship_id = slide_rels.last().unwrap().id.clone();
+        source
+            .external_relationships
+            .insert(&relationships_path(&slide_paths[0]), &relationship_id);
+
+        let imported_paths = target
+            .import_slides(&source, &slide_paths[..1], LayoutMapping::KeepSource)
+            .unwrap();
+        let saved_target = save_and_reload(&target);
+        let relationships = saved_target.part_relationships(&imported_paths[0]).unwrap();
+        let relationship = relationships
+            .iter()
+            .find(|relationship| relationship.target == "movies/movie.mp4")
+            .unwrap();
+        assert!(saved_target.is_external_relationship(&imported_paths[0], relationship));
+    }
+
+    #[test]
+    fn test_import_slides_slide_jump_hyperlink() {
+        let (mut source, mut target) = source_and_target();
+        let slide_paths = source_slide_paths(&source);
+
+        // The first slide jumps to the second one from a shape and from a text run
+        let slide_rels = source
+            .slide_rels_map
+            .get_mut(&relationships_path(&slide_paths[0]))
+            .unwrap();
+        slide_rels.push(Relationship {
+            id: next_relationship_id(slide_rels),
+            rel_type: String::from(SLIDE_RELATIONSHIP_TYPE),
+            target: relative_relationship_target(&slide_paths[0], &slide_paths[1]),
+        });
+        let relationship_id = slide_rels.last().unwrap().id.clone();
+        let mut xml_node = source.slide_map[&slide_paths[0]].to_xml_element();
+        assert!(add_hyperlink(&mut xml_node, "cNvPr", &relationship_id));
+        assert!(add_hyperlink(&mut xml_node, "rPr", &relationship_id));
+        let slide = Slide::from_xml_element(&xml_node).unwrap();
+        assert_eq!(
+            hyperlink_relationship_ids(&slide.to_xml_element()),
+            vec![relationship_id.clone(), relationship_id]
+        );
+        source.slide_map.insert(slide_paths[0].clone(), Box::new(slide));
+
+        let imported_paths = target
+            .import_slides(&source, &slide_paths[..1], LayoutMapping::KeepSource)
+            .unwrap();
+        let saved_target = save_and_reload(&target);
+        let relationships = saved_target.part_relationships(&imported_paths[0]).unwrap();
+        assert!(relationships
+            .iter()
+            .all(|relationship| relationship.rel_type != SLIDE_RELATIONSHIP_TYPE));
+        let slide = &saved_target.slide_map[&imported_paths[0]];
+        assert!(hyperlink_relationship_ids(&slide.to_xml_element()).is_empty());
+    }
+}
@@ -0,0 +1,360 @@
+//! Parts of the Open Packaging Conventions container that hold a pptx document together: the content types part
+//! and the relationship parts.
+
+use crate::xml::{write_package_xml, XmlNodeBuilder};
+use msoffice_shared::{error::MissingAttributeError, relationship::Relationship, xml::XmlNode};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use zip::read::ZipFile;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// The path of the part that specifies the content types of every other part of the package.
+pub const CONTENT_TYPES_PATH: &str = "[Content_Types].xml";
+/// The path of the relationship part of the package itself.
+pub const PACKAGE_RELATIONSHIPS_PATH: &str = "_rels/.rels";
+
+pub const CONTENT_TYPES_NAMESPACE: &str = "http://schemas.openxmlformats.org/package/2006/content-types";
+pub const PACKAGE_RELATIONSHIPS_NAMESPACE: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+
+pub const RELATIONSHIPS_CONTENT_TYPE: &str = "application/vnd.openxmlformats-package.relationships+xml";
+pub const XML_CONTENT_TYPE: &str = "application/xml";
+pub const PRESENTATION_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml";
+pub const SLIDE_MASTER_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.presentationml.slideMaster+xml";
+pub const SLIDE_LAYOUT_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.presentationml.slideLayout+xml";
+pub const SLIDE_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.presentationml.slide+xml";
+pub const NOTES_SLIDE_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.presentationml.notesSlide+xml";
+pub const THEME_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.theme+xml";
+
+pub const OFFICE_DOCUMENT_RELATIONSHIP_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument";
+
+/// Specifies the content type of every part with the given extension, unless an override is specified for the part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentTypeDefault {
+    pub extension: String,
+    pub content_type: String,
+}
+
+/// Specifies the content type of a single part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentTypeOverride {
+    /// The path of the part relative to the root of the package, without a leading '/'.
+    pub part_name: PathBuf,
+    pub content_type: String,
+}
+
+/// The content types part ([Content_Types].xml) of a package.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContentTypes {
+    pub defaults: Vec<ContentTypeDefault>,
+    pub overrides: Vec<ContentTypeOverride>,
+}
+
+impl ContentTypes {
+    pub fn from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Self> {
+        let mut xml_string = String::new();
+        zip_file.read_to_string(&mut xml_string)?;
+
+        Self::from_xml_element(&XmlNode::from_str(xml_string.as_str())?)
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+
+        for child_node in &xml_node.child_nodes {
+            let get_attribute = |attr: &'static str| {
+                child_node
+                    .attributes
+                    .get(attr)
+                    .cloned()
+                    .ok_or_else(|| MissingAttributeError::new(child_node.name.clone(), attr))
+            };
+
+            match child_node.local_name() {
+                "Default" => instance.defaults.push(ContentTypeDefault {
+                    extension: get_attribute("Extension")?,
+                    content_type: get_attribute("ContentType")?,
+                }),
+                "Override" => {
+                    let part_name = get_attribute("PartName")?;
+                    instance.overrides.push(ContentTypeOverride {
+                        part_name: PathBuf::from(part_name.trim_start_matches('/')),
+                        content_type: get_attribute("ContentType")?,
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        let defaults = self.defaults.iter().map(|default| {
+            XmlNode::new("Default")
+                .with_attribute("Extension", &default.extension)
+                .with_attribute("ContentType", &default.content_type)
+        });
+        let overrides = self.overrides.iter().map(|content_type_override| {
+            XmlNode::new("Override")
+                .with_attribute("PartName", part_name_uri(&content_type_override.part_name))
+                .with_attribute("ContentType", &content_type_override.content_type)
+        });
+
+        XmlNode::new("Types")
+            .with_attribute("xmlns", CONTENT_TYPES_NAMESPACE)
+            .with_children(defaults)
+            .with_children(overrides)
+    }
+
+    pub fn to_xml<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_package_xml(&self.to_xml_element(), writer)
+    }
+
+    /// Returns the content type of the part at `part_path`. Extensions are compared case-insensitively.
+    pub fn content_type(&self, part_path: &Path) -> Option<&str> {
+        if let Some(content_type_override) = self.overrides.iter().find(|o| o.part_name == part_path) {
+            return Some(&content_type_override.content_type);
+        }
+
+        let extension = part_path.extension()?.to_string_lossy();
+        self.defaults
+            .iter()
+            .find(|default| default.extension.eq_ignore_ascii_case(&extension))
+            .map(|default| default.content_type.as_str())
+    }
+
+    /// Adds a default content type for `extension`, unless one is already specified.
+    pub fn add_default(&mut self, extension: &str, content_type: &str) {
+        if self
+            .defaults
+            .iter()
+            .any(|default| default.extension.eq_ignore_ascii_case(extension))
+        {
+            return;
+        }
+
+        self.defaults.push(ContentTypeDefault {
+            extension: String::from(extension),
+            content_type: String::from(content_type),
+        });
+    }
+
+    /// Sets the content type of the part at `part_path`, replacing the previous override of the part.
+    pub fn set_override(&mut self, part_path: &Path, content_type: &str) {
+        match self.overrides.iter_mut().find(|o| o.part_name == part_path) {
+            Some(content_type_override) => content_type_override.content_type = String::from(content_type),
+            None => self.overrides.push(ContentTypeOverride {
+                part_name: PathBuf::from(part_path),
+                content_type: String::from(content_type),
+            }),
+        }
+    }
+}
+
+/// The relationships of a package that reference resources outside of the package, like the urls of hyperlinks or
+/// linked movies. The target mode belongs to each relationship, so they're identified by the path of their
+/// relationship part and their id rather than by their target, which may look like a part path.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExternalRelationships(HashMap<PathBuf, HashSet<String>>);
+
+impl ExternalRelationships {
+    /// Returns whether the relationship with `relationship_id` of the relationship part at `rels_path` has an external
+    /// target mode.
+    pub fn contains(&self, rels_path: &Path, relationship_id: &str) -> bool {
+        self.0
+            .get(rels_path)
+            .map(|relationship_ids| relationship_ids.contains(relationship_id))
+            .unwrap_or(false)
+    }
+
+    /// Marks the relationship with `relationship_id` of the relationship part at `rels_path` as external.
+    pub fn insert(&mut self, rels_path: &Path, relationship_id: &str) {
+        self.0
+            .entry(PathBuf::from(rels_path))
+            .or_default()
+            .insert(String::from(relationship_id));
+    }
+
+    /// Forgets the relationships of the relationship part at `rels_path`, e.g. because the part has been removed and
+    /// its path may be reused.
+    pub fn remove_part(&mut self, rels_path: &Path) {
+        self.0.remove(rels_path);
+    }
+}
+
+/// Reads the relationships of a relationship part and adds the ones with an external target mode to
+/// `external_relationships`.
+pub fn relationships_from_zip_file(
+    zip_file: &mut ZipFile<'_>,
+    external_relationships: &mut ExternalRelationships,
+) -> Result<Vec<Relationship>> {
+    let rels_path = PathBuf::from(zip_file.name());
+    let mut xml_string = String::new();
+    zip_file.read_to_string(&mut xml_string)?;
+
+    relationships_from_xml_element(
+        &XmlNode::from_str(xml_string.as_str())?,
+        &rels_path,
+        external_relationships,
+    )
+}
+
+/// Returns the relationships listed by the relationship part at `rels_path` and adds the ones with an external target
+/// mode to `external_relationships`.
+pub fn relationships_from_xml_element(
+    xml_node: &XmlNode,
+    rels_path: &Path,
+    external_relationships: &mut ExternalRelationships,
+) -> Result<Vec<Relationship>> {
+    let mut relationships = Vec::new();
+    for child_node in &xml_node.child_nodes {
+        if child_node.local_name() != "Relationship" {
+            continue;
+        }
+
+        let relationship = Relationship::from_xml_element(child_node)?;
+        if child_node.attributes.get("TargetMode").map(String::as_str) == Some("External") {
+            external_relationships.insert(rels_path, &relationship.id);
+        }
+        relationships.push(relationship);
+    }
+
+    Ok(relationships)
+}
+
+/// Returns the relationship part at `rels_path` that lists `relationships`. Relationships listed by
+/// `external_relationships` get an external target mode.
+pub fn relationships_to_xml_element(
+    relationships: &[Relationship],
+    rels_path: &Path,
+    external_relationships: &ExternalRelationships,
+) -> XmlNode {
+    let relationship_nodes = relationships.iter().map(|relationship| {
+        let target_mode = if external_relationships.contains(rels_path, &relationship.id) {
+            Some("External")
+        } else {
+            None
+        };
+
+        XmlNode::new("Relationship")
+            .with_attribute("Id", &relationship.id)
+            .with_attribute("Type", &relationship.rel_type)
+            .with_attribute("Target", &relationship.target)
+            .with_optional_attribute("TargetMode", target_mode)
+    });
+
+    XmlNode::new("Relationships")
+        .with_attribute("xmlns", PACKAGE_RELATIONSHIPS_NAMESPACE)
+        .with_children(relationship_nodes)
+}
+
+/// Writes the relationship part at `rels_path` that lists `relationships`. Relationships listed by
+/// `external_relationships` get an external target mode.
+pub fn write_relationships<W: Write>(
+    relationships: &[Relationship],
+    rels_path: &Path,
+    external_relationships: &ExternalRelationships,
+    writer: &mut W,
+) -> Result<()> {
+    write_package_xml(
+        &relationships_to_xml_element(relationships, rels_path, external_relationships),
+        writer,
+    )
+}
+
+/// Returns a relationship id that isn't used by any of `relationships`. Ids are generated in the rId<n> form used by
+/// PowerPoint, continuing from the highest one in use.
+pub fn next_relationship_id(relationships: &[Relationship]) -> String {
+    let highest_id = relationships
+        .iter()
+        .filter_map(|relationship| relationship.id.strip_prefix("rId")?.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0);
+    format!("rId{}", highest_id + 1)
+}
+
+/// Returns the part name of the part at `part_path`, which is the path prefixed with a '/'.
+fn part_name_uri(part_path: &Path) -> String {
+    format!("/{}", part_path.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_content_types_round_trip() {
+        let xml = r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+            <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+            <Default Extension="JPEG" ContentType="image/jpeg"/>
+            <Override PartName="/ppt/presentation.xml"
+                ContentType="application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml"/>
+        </Types>"#;
+        let content_types = ContentTypes::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(
+            content_types.content_type(Path::new("ppt/presentation.xml")),
+            Some(PRESENTATION_CONTENT_TYPE)
+        );
+        assert_eq!(
+            content_types.content_type(Path::new("ppt/media/image1.jpeg")),
+            Some("image/jpeg")
+        );
+        assert_eq!(content_types.content_type(Path::new("ppt/slides/slide1.xml")), None);
+
+        let mut buffer = Vec::new();
+        content_types.to_xml(&mut buffer).unwrap();
+        let xml_node = XmlNode::from_str(::std::str::from_utf8(&buffer).unwrap()).unwrap();
+        assert_eq!(ContentTypes::from_xml_element(&xml_node).unwrap(), content_types);
+    }
+
+    #[test]
+    pub fn test_relationships_round_trip() {
+        let xml = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image"
+                Target="../media/image1.png"/>
+            <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/video"
+                Target="movies/movie.mp4" TargetMode="External"/>
+            <Relationship Id="rId3"
+                Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"
+                Target="https://github.com/dam4rus/msoffice-pptx-rs" TargetMode="External"/>
+        </Relationships>"#;
+        let rels_path = Path::new("ppt/slides/_rels/slide1.xml.rels");
+        let mut external_relationships = ExternalRelationships::default();
+        let relationships =
+            relationships_from_xml_element(&XmlNode::from_str(xml).unwrap(), rels_path, &mut external_relationships)
+                .unwrap();
+        assert_eq!(relationships.len(), 3);
+        // A relative target is external if the source package says so, regardless of its form
+        assert!(!external_relationships.contains(rels_path, "rId1"));
+        assert!(external_relationships.contains(rels_path, "rId2"));
+        assert!(external_relationships.contains(rels_path, "rId3"));
+        assert!(!external_relationships.contains(Path::new("ppt/slides/_rels/slide2.xml.rels"), "rId2"));
+
+        let mut buffer = Vec::new();
+        write_relationships(&relationships, rels_path, &external_relationships, &mut buffer).unwrap();
+        let xml_node = XmlNode::from_str(::std::str::from_utf8(&buffer).unwrap()).unwrap();
+        let target_modes = xml_node
+            .child_nodes
+            .iter()
+            .map(|child_node| child_node.attributes.get("TargetMode").map(String::as_str))
+            .collect::<Vec<_>>();
+        assert_eq!(target_modes, vec![None, Some("External"), Some("External")]);
+
+        let mut saved_external_relationships = ExternalRelationships::default();
+        let saved_relationships =
+            relationships_from_xml_element(&xml_node, rels_path, &mut saved_external_relationships).unwrap();
+        assert_eq!(saved_relationships, relationships);
+        assert_eq!(saved_external_relationships, external_relationships);
+    }
+}
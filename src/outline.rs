@@ -0,0 +1,316 @@
+use crate::document::PPTXDocument;
+use crate::markdown::escape_markdown;
+use crate::pml::{
+    slides::{GroupShape, PlaceholderType, ShapeGroup, Slide},
+    util::escape_xml,
+};
+use crate::text::{text_body_paragraphs, ParagraphText};
+
+/// A paragraph of a body placeholder along with the paragraphs that are indented below it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineItem {
+    pub text: String,
+    pub children: Vec<OutlineItem>,
+}
+
+/// The outline of a slide, as it is displayed by the outline view of the generating application.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SlideOutline {
+    /// The text of the title or centered title placeholder.
+    pub title: Option<String>,
+    /// The text of the subtitle placeholder.
+    pub subtitle: Option<String>,
+    /// The paragraphs of the body placeholders, arranged into a tree by their indentation levels.
+    pub items: Vec<OutlineItem>,
+}
+
+impl Slide {
+    /// Extracts the outline of the slide.
+    ///
+    /// Only placeholders are part of the outline. The title is taken from the first title or centered title
+    /// placeholder, the subtitle from the first subtitle placeholder. The paragraphs of body and object placeholders
+    /// form the items of the outline. Empty paragraphs are skipped. Line breaks within a paragraph are kept as '\n'.
+    pub fn outline(&self) -> SlideOutline {
+        let mut outline: SlideOutline = Default::default();
+        let mut body_paragraphs = Vec::new();
+        collect_outline(&self.common_slide_data.shape_tree, &mut outline, &mut body_paragraphs);
+        outline.items = build_outline_tree(&body_paragraphs);
+        outline
+    }
+}
+
+impl PPTXDocument {
+    /// Extracts the outline of every slide of the presentation, in presentation order.
+    pub fn outline(&self) -> Vec<SlideOutline> {
+        self.ordered_slides()
+            .into_iter()
+            .map(|(_, slide)| slide.outline())
+            .collect()
+    }
+}
+
+/// Exports the outline of a presentation to Markdown.
+///
+/// Every slide is a level 1 heading containing the title of the slide, or "Slide n" if the slide has no title. The
+/// subtitle follows the heading in italic and the items of the outline are rendered as a nested bulleted list.
+pub fn to_markdown(outlines: &[SlideOutline]) -> String {
+    let mut markdown = String::new();
+    for (index, outline) in outlines.iter().enumerate() {
+        if index > 0 {
+            markdown.push('\n');
+        }
+
+        let title = match &outline.title {
+            Some(title) => escape_markdown(&single_line(title)),
+            None => format!("Slide {}", index + 1),
+        };
+        markdown.push_str(&format!("# {}\n", title));
+
+        if let Some(subtitle) = &outline.subtitle {
+            markdown.push_str(&format!("\n*{}*\n", escape_markdown(&single_line(subtitle))));
+        }
+
+        if !outline.items.is_empty() {
+            markdown.push('\n');
+            write_markdown_items(&outline.items, 0, &mut markdown);
+        }
+    }
+
+    markdown
+}
+
+/// Exports the outline of a presentation to an OPML 2.0 document with the given title.
+///
+/// Every slide is a top level outline element containing the title of the slide, or "Slide n" if the slide has no
+/// title. The subtitle is the first child of the slide's outline element, followed by the items of the outline.
+pub fn to_opml(outlines: &[SlideOutline], title: &str) -> String {
+    let mut opml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n");
+    opml.push_str(&format!(
+        "  <head>\n    <title>{}</title>\n  </head>\n",
+        escape_xml(title)
+    ));
+    opml.push_str("  <body>\n");
+
+    for (index, outline) in outlines.iter().enumerate() {
+        let slide_title = match &outline.title {
+            Some(title) => title.clone(),
+            None => format!("Slide {}", index + 1),
+        };
+
+        if outline.subtitle.is_none() && outline.items.is_empty() {
+            opml.push_str(&format!("    <outline text=\"{}\"/>\n", escape_xml(&slide_title)));
+            continue;
+        }
+
+        opml.push_str(&format!("    <outline text=\"{}\">\n", escape_xml(&slide_title)));
+        if let Some(subtitle) = &outline.subtitle {
+            opml.push_str(&format!("      <outline text=\"{}\"/>\n", escape_xml(subtitle)));
+        }
+        write_opml_items(&outline.items, 3, &mut opml);
+        opml.push_str("    </outline>\n");
+    }
+
+    opml.push_str("  </body>\n</opml>\n");
+    opml
+}
+
+fn collect_outline(group: &GroupShape, outline: &mut SlideOutline, body_paragraphs: &mut Vec<ParagraphText>) {
+    for shape_group in &group.shape_array {
+        let shape = match shape_group {
+            ShapeGroup::Shape(shape) => shape,
+            ShapeGroup::GroupShape(group_shape) => {
+                collect_outline(group_shape, outline, body_paragraphs);
+                continue;
+            }
+            _ => continue,
+        };
+
+        let placeholder = match &shape.non_visual_props.app_props.placeholder {
+            Some(placeholder) => placeholder,
+            None => continue,
+        };
+
+        let paragraphs = match &shape.text_body {
+            Some(text_body) => text_body_paragraphs(text_body)
+                .into_iter()
+                .filter(|paragraph| !paragraph.text.is_empty())
+                .collect::<Vec<_>>(),
+            None => continue,
+        };
+
+        if paragraphs.is_empty() {
+            continue;
+        }
+
+        let joined_text = || {
+            paragraphs
+                .iter()
+                .map(|paragraph| paragraph.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        // A placeholder without a type attribute is an object placeholder
+        match placeholder.placeholder_type.unwrap_or(PlaceholderType::Object) {
+            PlaceholderType::Title | PlaceholderType::CenteredTitle if outline.title.is_none() => {
+                outline.title = Some(joined_text())
+            }
+            PlaceholderType::SubTitle if outline.subtitle.is_none() => outline.subtitle = Some(joined_text()),
+            PlaceholderType::Body | PlaceholderType::Object => body_paragraphs.extend(paragraphs),
+            _ => (),
+        }
+    }
+}
+
+/// Arranges the paragraphs into a tree. A paragraph becomes the child of the closest preceding paragraph with a lower
+/// indentation level. Paragraphs without such a predecessor are top level items.
+fn build_outline_tree(paragraphs: &[ParagraphText]) -> Vec<OutlineItem> {
+    fn build(paragraphs: &[ParagraphText], index: &mut usize, parent_level: Option<u32>) -> Vec<OutlineItem> {
+        let mut items = Vec::new();
+        while let Some(paragraph) = paragraphs.get(*index) {
+            if parent_level.map(|level| paragraph.level <= level).unwrap_or(false) {
+                break;
+            }
+
+            *index += 1;
+            let children = build(paragraphs, index, Some(paragraph.level));
+            items.push(OutlineItem {
+                text: paragraph.text.clone(),
+                children,
+            });
+        }
+
+        items
+    }
+
+    build(paragraphs, &mut 0, None)
+}
+
+fn write_markdown_items(items: &[OutlineItem], depth: usize, markdown: &mut String) {
+    for item in items {
+        markdown.push_str(&format!(
+            "{}- {}\n",
+            "  ".repeat(depth),
+            escape_markdown(&single_line(&item.text))
+        ));
+        write_markdown_items(&item.children, depth + 1, markdown);
+    }
+}
+
+fn write_opml_items(items: &[OutlineItem], depth: usize, opml: &mut String) {
+    for item in items {
+        let indent = "  ".repeat(depth);
+        if item.children.is_empty() {
+            opml.push_str(&format!("{}<outline text=\"{}\"/>\n", indent, escape_xml(&item.text)));
+        } else {
+            opml.push_str(&format!("{}<outline text=\"{}\">\n", indent, escape_xml(&item.text)));
+            write_opml_items(&item.children, depth + 1, opml);
+            opml.push_str(&format!("{}</outline>\n", indent));
+        }
+    }
+}
+
+fn single_line(text: &str) -> String {
+    text.replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn paragraph(level: u32, text: &str) -> ParagraphText {
+        ParagraphText {
+            level,
+            text: String::from(text),
+        }
+    }
+
+    fn item(text: &str, children: Vec<OutlineItem>) -> OutlineItem {
+        OutlineItem {
+            text: String::from(text),
+            children,
+        }
+    }
+
+    #[test]
+    pub fn test_build_outline_tree() {
+        let paragraphs = vec![
+            paragraph(0, "First"),
+            paragraph(1, "First child"),
+            paragraph(3, "Deep child"),
+            paragraph(1, "Second child"),
+            paragraph(0, "Second"),
+        ];
+
+        assert_eq!(
+            build_outline_tree(&paragraphs),
+            vec![
+                item(
+                    "First",
+                    vec![
+                        item("First child", vec![item("Deep child", Vec::new())]),
+                        item("Second child", Vec::new()),
+                    ],
+                ),
+                item("Second", Vec::new()),
+            ],
+        );
+    }
+
+    #[test]
+    pub fn test_outline_exporters() {
+        let outlines = vec![
+            SlideOutline {
+                title: Some(String::from("Intro")),
+                subtitle: Some(String::from("A & B")),
+                items: vec![item("Point *1*", vec![item("Detail", Vec::new())])],
+            },
+            Default::default(),
+        ];
+
+        assert_eq!(
+            to_markdown(&outlines),
+            "# Intro\n\n*A & B*\n\n- Point \\*1\\*\n  - Detail\n\n# Slide 2\n"
+        );
+        assert_eq!(
+            to_opml(&outlines, "Deck"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <head>
+    <title>Deck</title>
+  </head>
+  <body>
+    <outline text="Intro">
+      <outline text="A &amp; B"/>
+      <outline text="Point *1*">
+        <outline text="Detail"/>
+      </outline>
+    </outline>
+    <outline text="Slide 2"/>
+  </body>
+</opml>
+"#
+        );
+    }
+
+    #[test]
+    pub fn test_outline() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let sample_pptx_path = test_dir.join("tests/samplepptx.pptx");
+
+        let mut document = PPTXDocument::from_file(&sample_pptx_path).unwrap();
+        let outlines = document.outline();
+        assert_eq!(outlines.len(), 2);
+        assert_eq!(outlines[0].title.as_ref().unwrap(), "Sample PowerPoint File");
+        assert_eq!(outlines[0].subtitle.as_ref().unwrap(), "St. Cloud Technical College");
+        assert!(outlines[0].items.is_empty());
+
+        // The outline follows the presentation order, regardless of the file names of the slides
+        let first_slide_path = document.slide_path_at(0).unwrap();
+        document.move_slide(&first_slide_path, 1).unwrap();
+        let moved_outlines = document.outline();
+        assert_eq!(moved_outlines[0], outlines[1]);
+        assert_eq!(moved_outlines[1], outlines[0]);
+    }
+}
@@ -0,0 +1,489 @@
+use crate::drawingml::geometry::{Point, Rect};
+use crate::pml::slides::{GroupShape, ShapeGroup};
+use crate::style::StyleContext;
+use msoffice_shared::drawingml::coordsys::{GroupTransform2D, Transform2D};
+
+/// The number of angle units (60000ths of a degree) in a degree.
+const ANGLE_UNITS_PER_DEGREE: f64 = 60000.0;
+
+/// The placement of a shape on the slide after composing the transforms of the groups containing it.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Placement {
+    /// The bounding box of the shape before rotation, in slide EMUs.
+    pub bounds: Rect,
+    /// The clockwise rotation of the shape around the center of its bounds in degrees, in the range of 0 to 360.
+    pub rotation: f64,
+    pub flip_horizontal: bool,
+    pub flip_vertical: bool,
+}
+
+impl Placement {
+    /// Returns the center of the shape, which is the center of the rotation.
+    pub fn center(&self) -> Point {
+        Point::new(
+            (self.bounds.left + self.bounds.right) / 2.0,
+            (self.bounds.top + self.bounds.bottom) / 2.0,
+        )
+    }
+
+    /// Returns the corners of the rotated shape, starting with the top left corner of the unrotated bounds and going
+    /// clockwise.
+    pub fn corners(&self) -> [Point; 4] {
+        let bounds = &self.bounds;
+        let center = self.center();
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let rotate = |x: f64, y: f64| {
+            let (dx, dy) = (x - center.x, y - center.y);
+            Point::new(center.x + dx * cos - dy * sin, center.y + dx * sin + dy * cos)
+        };
+
+        [
+            rotate(bounds.left, bounds.top),
+            rotate(bounds.right, bounds.top),
+            rotate(bounds.right, bounds.bottom),
+            rotate(bounds.left, bounds.bottom),
+        ]
+    }
+
+    /// Maps a point in the coordinate space of the shape, where (0, 0) is the top left corner and (width, height) is
+    /// the bottom right corner of its bounds, to the slide. The point is flipped and rotated with the shape.
+    pub fn map_point(&self, point: Point) -> Point {
+        let bounds = &self.bounds;
+        let x = if self.flip_horizontal {
+            bounds.width() - point.x
+        } else {
+            point.x
+        };
+        let y = if self.flip_vertical {
+            bounds.height() - point.y
+        } else {
+            point.y
+        };
+        let center = self.center();
+        let (dx, dy) = (bounds.left + x - center.x, bounds.top + y - center.y);
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        Point::new(center.x + dx * cos - dy * sin, center.y + dx * sin + dy * cos)
+    }
+
+    /// Returns the axis aligned bounding box of the rotated shape.
+    pub fn rotated_bounds(&self) -> Rect {
+        let corners = self.corners();
+        corners.iter().skip(1).fold(
+            Rect::new(corners[0].x, corners[0].y, corners[0].x, corners[0].y),
+            |bounds, corner| {
+                Rect::new(
+                    bounds.left.min(corner.x),
+                    bounds.top.min(corner.y),
+                    bounds.right.max(corner.x),
+                    bounds.bottom.max(corner.y),
+                )
+            },
+        )
+    }
+}
+
+/// An element of a shape tree with its placement on the slide.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlacedShape<'a> {
+    pub shape: &'a ShapeGroup,
+    pub placement: Placement,
+}
+
+/// Returns every element of a shape tree with its placement on the slide, including the elements of nested groups.
+///
+/// Elements are returned in document order, so a group is followed by its children. The coordinates of the children
+/// of a group are specified in the child coordinate space of the group, which is mapped to the group's bounds by its
+/// child offset and child extents, then flipped and rotated with the group. Elements without a transform are skipped,
+/// see StyleContext::shape_placements for the placements of placeholders that inherit their transform.
+///
+/// If a rotated element is scaled unevenly by a group, its shape is skewed, which can't be described by a placement.
+/// The placement then has the size of the element's sides after scaling.
+pub fn shape_placements(shape_tree: &GroupShape) -> Vec<PlacedShape<'_>> {
+    let mut placed_shapes = Vec::new();
+    collect_placements(shape_tree, Affine::identity(), &|_| None, &mut placed_shapes);
+    placed_shapes
+}
+
+impl<'a> StyleContext<'a> {
+    /// Returns every element of the slide's shape tree with its placement on the slide, like shape_placements does.
+    /// Placeholders without a transform inherit the transform of the matching placeholder of the slide layout or the
+    /// slide master.
+    pub fn shape_placements(&self) -> Vec<PlacedShape<'a>> {
+        let inherited_transform = |shape_group: &'a ShapeGroup| match shape_group {
+            ShapeGroup::Shape(shape) => self.transform(
+                &shape.shape_props,
+                shape.non_visual_props.app_props.placeholder.as_ref(),
+            ),
+            ShapeGroup::Picture(picture) => self.transform(
+                &picture.shape_props,
+                picture.non_visual_props.app_props.placeholder.as_ref(),
+            ),
+            _ => None,
+        };
+
+        let mut placed_shapes = Vec::new();
+        collect_placements(
+            &self.slide.common_slide_data.shape_tree,
+            Affine::identity(),
+            &inherited_transform,
+            &mut placed_shapes,
+        );
+        placed_shapes
+    }
+}
+
+/// Adds the placements of the elements of a group. `transform` maps the child coordinate space of the group to the
+/// slide and `inherited_transform` returns the transform of elements that don't specify one.
+fn collect_placements<'a>(
+    group: &'a GroupShape,
+    transform: Affine,
+    inherited_transform: &dyn Fn(&'a ShapeGroup) -> Option<&'a Transform2D>,
+    placed_shapes: &mut Vec<PlacedShape<'a>>,
+) {
+    for shape_group in &group.shape_array {
+        let shape_transform = match shape_group {
+            ShapeGroup::Shape(shape) => shape.shape_props.transform.as_deref(),
+            ShapeGroup::Connector(connector) => connector.shape_props.transform.as_deref(),
+            ShapeGroup::Picture(picture) => picture.shape_props.transform.as_deref(),
+            ShapeGroup::GraphicFrame(graphic_frame) => Some(&*graphic_frame.transform),
+            ShapeGroup::GroupShape(group_shape) => {
+                let group_transform = group_shape.group_shape_props.transform.as_deref();
+                if let Some(group_transform) = group_transform {
+                    let box_transform = BoxTransform::new(
+                        group_transform.offset.map(|offset| (offset.x, offset.y)),
+                        group_transform.extents.map(|extents| (extents.width, extents.height)),
+                        group_transform.rotate_angle,
+                    );
+                    placed_shapes.push(PlacedShape {
+                        shape: shape_group,
+                        placement: place(
+                            transform,
+                            &box_transform,
+                            group_transform.flip_horizontal.unwrap_or(false),
+                            group_transform.flip_vertical.unwrap_or(false),
+                        ),
+                    });
+                }
+
+                let child_transform = match group_transform {
+                    Some(group_transform) => transform.multiply(&child_space_transform(group_transform)),
+                    None => transform,
+                };
+                collect_placements(group_shape, child_transform, inherited_transform, placed_shapes);
+                continue;
+            }
+            ShapeGroup::ContentPart(_) => None,
+        };
+
+        if let Some(shape_transform) = shape_transform.or_else(|| inherited_transform(shape_group)) {
+            let box_transform = BoxTransform::new(
+                shape_transform.offset.map(|offset| (offset.x, offset.y)),
+                shape_transform.extents.map(|extents| (extents.width, extents.height)),
+                shape_transform.rotate_angle,
+            );
+            placed_shapes.push(PlacedShape {
+                shape: shape_group,
+                placement: place(
+                    transform,
+                    &box_transform,
+                    shape_transform.flip_horizontal.unwrap_or(false),
+                    shape_transform.flip_vertical.unwrap_or(false),
+                ),
+            });
+        }
+    }
+}
+
+/// The bounds and the rotation of an element in the coordinate space of its group.
+struct BoxTransform {
+    bounds: Rect,
+    /// The rotation in degrees.
+    rotation: f64,
+}
+
+impl BoxTransform {
+    fn new(offset: Option<(i64, i64)>, extents: Option<(u64, u64)>, rotate_angle: Option<i32>) -> Self {
+        let (x, y) = offset.unwrap_or_default();
+        let (width, height) = extents.unwrap_or_default();
+        Self {
+            bounds: Rect::new(x as f64, y as f64, x as f64 + width as f64, y as f64 + height as f64),
+            rotation: f64::from(rotate_angle.unwrap_or(0)) / ANGLE_UNITS_PER_DEGREE,
+        }
+    }
+}
+
+/// Maps the bounds of an element from the coordinate space of its group to the slide with `transform`.
+fn place(transform: Affine, box_transform: &BoxTransform, flip_horizontal: bool, flip_vertical: bool) -> Placement {
+    let bounds = &box_transform.bounds;
+    let center = transform.apply(Point::new(
+        (bounds.left + bounds.right) / 2.0,
+        (bounds.top + bounds.bottom) / 2.0,
+    ));
+
+    // The sides of the element are scaled by the length of their directions after the transform
+    let (sin, cos) = box_transform.rotation.to_radians().sin_cos();
+    let width = bounds.width() * length(transform.apply_vector(cos, sin));
+    let height = bounds.height() * length(transform.apply_vector(-sin, cos));
+
+    // A mirroring transform is expressed as an additional horizontal flip, which reverses the rotation of the element
+    let mirrored = transform.determinant() < 0.0;
+    let y_axis = transform.apply_vector(0.0, 1.0);
+    let transform_rotation = y_axis.y.atan2(y_axis.x).to_degrees() - 90.0;
+    let rotation = if mirrored {
+        transform_rotation - box_transform.rotation
+    } else {
+        transform_rotation + box_transform.rotation
+    };
+
+    Placement {
+        bounds: Rect::new(
+            center.x - width / 2.0,
+            center.y - height / 2.0,
+            center.x + width / 2.0,
+            center.y + height / 2.0,
+        ),
+        rotation: normalize_degrees(rotation),
+        flip_horizontal: flip_horizontal != mirrored,
+        flip_vertical,
+    }
+}
+
+/// Returns the transform that maps the child coordinate space of a group to the coordinate space of its parent.
+fn child_space_transform(group_transform: &GroupTransform2D) -> Affine {
+    let offset = group_transform.offset.unwrap_or_default();
+    let extents = group_transform.extents.unwrap_or_default();
+    let child_offset = group_transform.child_offset.unwrap_or_default();
+    let child_extents = group_transform.child_extents.unwrap_or_default();
+    let scale = |extent: u64, child_extent: u64| {
+        if child_extent == 0 {
+            1.0
+        } else {
+            extent as f64 / child_extent as f64
+        }
+    };
+    let flip_scale = |flip: Option<bool>| if flip.unwrap_or(false) { -1.0 } else { 1.0 };
+
+    let center_x = offset.x as f64 + extents.width as f64 / 2.0;
+    let center_y = offset.y as f64 + extents.height as f64 / 2.0;
+    let rotation = f64::from(group_transform.rotate_angle.unwrap_or(0)) / ANGLE_UNITS_PER_DEGREE;
+
+    // Applied from right to left: the child space is scaled into the bounds of the group, then the bounds are flipped
+    // and rotated around their center
+    Affine::translate(center_x, center_y)
+        .multiply(&Affine::rotate(rotation))
+        .multiply(&Affine::scale(
+            flip_scale(group_transform.flip_horizontal),
+            flip_scale(group_transform.flip_vertical),
+        ))
+        .multiply(&Affine::translate(-center_x, -center_y))
+        .multiply(&Affine::translate(offset.x as f64, offset.y as f64))
+        .multiply(&Affine::scale(
+            scale(extents.width, child_extents.width),
+            scale(extents.height, child_extents.height),
+        ))
+        .multiply(&Affine::translate(-child_offset.x as f64, -child_offset.y as f64))
+}
+
+fn normalize_degrees(degrees: f64) -> f64 {
+    let degrees = degrees.rem_euclid(360.0);
+    // Rounding errors shouldn't turn a rotation of 0 into almost 360
+    if (360.0 - degrees) < 1e-9 || degrees < 1e-9 {
+        0.0
+    } else {
+        degrees
+    }
+}
+
+fn length(vector: Point) -> f64 {
+    vector.x.hypot(vector.y)
+}
+
+/// A 2D affine transform, mapping (x, y) to (a * x + c * y + e, b * x + d * y + f).
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Affine {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    e: f64,
+    f: f64,
+}
+
+impl Affine {
+    fn identity() -> Self {
+        Self::scale(1.0, 1.0)
+    }
+
+    fn translate(x: f64, y: f64) -> Self {
+        Self {
+            e: x,
+            f: y,
+            ..Self::identity()
+        }
+    }
+
+    fn scale(x: f64, y: f64) -> Self {
+        Self {
+            a: x,
+            b: 0.0,
+            c: 0.0,
+            d: y,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    /// A clockwise rotation in degrees, as the y axis points down.
+    fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    /// Returns the transform that applies `other` first, then `self`.
+    fn multiply(&self, other: &Affine) -> Self {
+        Self {
+            a: self.a * other.a + self.c * other.b,
+            b: self.b * other.a + self.d * other.b,
+            c: self.a * other.c + self.c * other.d,
+            d: self.b * other.c + self.d * other.d,
+            e: self.a * other.e + self.c * other.f + self.e,
+            f: self.b * other.e + self.d * other.f + self.f,
+        }
+    }
+
+    fn apply(&self, point: Point) -> Point {
+        Point::new(
+            self.a * point.x + self.c * point.y + self.e,
+            self.b * point.x + self.d * point.y + self.f,
+        )
+    }
+
+    fn apply_vector(&self, x: f64, y: f64) -> Point {
+        Point::new(self.a * x + self.c * y, self.b * x + self.d * y)
+    }
+
+    fn determinant(&self) -> f64 {
+        self.a * self.d - self.b * self.c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use msoffice_shared::xml::XmlNode;
+    use std::str::FromStr;
+
+    fn shape_xml(id: u32, x: i64, y: i64, width: u64, height: u64, attributes: &str) -> String {
+        format!(
+            r#"<p:sp>
+                <p:nvSpPr><p:cNvPr id="{0}" name="Shape {0}"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>
+                <p:spPr><a:xfrm{1}><a:off x="{2}" y="{3}"/><a:ext cx="{4}" cy="{5}"/></a:xfrm></p:spPr>
+            </p:sp>"#,
+            id, attributes, x, y, width, height
+        )
+    }
+
+    fn assert_placement(placement: &Placement, bounds: Rect, rotation: f64, flips: (bool, bool)) {
+        let actual = &placement.bounds;
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+        assert!(
+            close(actual.left, bounds.left)
+                && close(actual.top, bounds.top)
+                && close(actual.right, bounds.right)
+                && close(actual.bottom, bounds.bottom),
+            "{:?} != {:?}",
+            actual,
+            bounds
+        );
+        assert!(
+            close(placement.rotation, rotation),
+            "{} != {}",
+            placement.rotation,
+            rotation
+        );
+        assert_eq!((placement.flip_horizontal, placement.flip_vertical), flips);
+    }
+
+    #[test]
+    pub fn test_shape_placements() {
+        let xml = format!(
+            r#"<p:spTree xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+                xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+                <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                <p:grpSpPr/>
+                {}
+                <p:grpSp>
+                    <p:nvGrpSpPr><p:cNvPr id="3" name="Group 3"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                    <p:grpSpPr>
+                        <a:xfrm flipH="1">
+                            <a:off x="1000" y="1000"/><a:ext cx="2000" cy="2000"/>
+                            <a:chOff x="0" y="0"/><a:chExt cx="1000" cy="1000"/>
+                        </a:xfrm>
+                    </p:grpSpPr>
+                    {}
+                    <p:grpSp>
+                        <p:nvGrpSpPr><p:cNvPr id="5" name="Group 5"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                        <p:grpSpPr>
+                            <a:xfrm rot="5400000">
+                                <a:off x="500" y="0"/><a:ext cx="500" cy="500"/>
+                                <a:chOff x="0" y="0"/><a:chExt cx="500" cy="500"/>
+                            </a:xfrm>
+                        </p:grpSpPr>
+                        {}
+                    </p:grpSp>
+                </p:grpSp>
+            </p:spTree>"#,
+            shape_xml(2, 100, 200, 300, 400, r#" rot="2700000""#),
+            shape_xml(4, 0, 0, 500, 500, r#" rot="1800000""#),
+            shape_xml(6, 0, 0, 100, 200, ""),
+        );
+        let shape_tree = GroupShape::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap();
+        let placed_shapes = shape_placements(&shape_tree);
+        assert_eq!(placed_shapes.len(), 5);
+
+        assert_placement(
+            &placed_shapes[0].placement,
+            Rect::new(100.0, 200.0, 400.0, 600.0),
+            45.0,
+            (false, false),
+        );
+        assert_placement(
+            &placed_shapes[1].placement,
+            Rect::new(1000.0, 1000.0, 3000.0, 3000.0),
+            0.0,
+            (true, false),
+        );
+
+        // The group is mirrored and scaled to twice the size, which reverses the rotation of its children
+        assert_placement(
+            &placed_shapes[2].placement,
+            Rect::new(2000.0, 1000.0, 3000.0, 2000.0),
+            330.0,
+            (true, false),
+        );
+        assert_placement(
+            &placed_shapes[3].placement,
+            Rect::new(1000.0, 1000.0, 2000.0, 2000.0),
+            270.0,
+            (true, false),
+        );
+
+        let placement = &placed_shapes[4].placement;
+        assert_placement(
+            placement,
+            Rect::new(1100.0, 900.0, 1300.0, 1300.0),
+            270.0,
+            (true, false),
+        );
+        let rotated_bounds = placement.rotated_bounds();
+        assert!((rotated_bounds.left - 1000.0).abs() < 1e-6 && (rotated_bounds.top - 1000.0).abs() < 1e-6);
+        assert!((rotated_bounds.right - 1400.0).abs() < 1e-6 && (rotated_bounds.bottom - 1200.0).abs() < 1e-6);
+    }
+}
@@ -1,4 +1,5 @@
 use super::util::XmlNodeExt;
+use crate::xml::{write_xml, ToXmlChoice, ToXmlElement, UnknownXml, XmlNodeBuilder};
 use msoffice_shared::{
     drawingml::{
         audiovideo::EmbeddedWAVAudioFile,
@@ -12,7 +13,7 @@ use msoffice_shared::{
     xml::{parse_xml_bool, XmlNode},
     xsdtypes::{XsdChoice, XsdType},
 };
-use std::{error::Error, str::FromStr};
+use std::{error::Error, fmt, io::Write, str::FromStr};
 
 pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
 
@@ -24,7 +25,7 @@ pub type TLSubShapeId = ShapeId;
 
 /// This simple type defines an animation target element that is represented by a subelement of a chart.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLChartSubelementType {
     #[strum(serialize = "gridLegend")]
     GridLegend,
@@ -40,7 +41,7 @@ pub enum TLChartSubelementType {
 
 /// This simple type describes how to build a paragraph.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLParaBuildType {
     /// Specifies to animate all paragraphs at once.
     #[strum(serialize = "allAtOnce")]
@@ -58,7 +59,7 @@ pub enum TLParaBuildType {
 
 /// This simple type specifies the different diagram build types.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLDiagramBuildType {
     #[strum(serialize = "whole")]
     Whole,
@@ -98,7 +99,7 @@ pub enum TLDiagramBuildType {
 
 /// This simple type describes how to build an embedded Chart.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLOleChartBuildType {
     #[strum(serialize = "allAtOnce")]
     AllAtOnce,
@@ -115,7 +116,7 @@ pub enum TLOleChartBuildType {
 /// This simple type specifies the child time node that triggers a time condition. References a child TimeNode or all
 /// child nodes. Order is based on the child's end time.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLTriggerRuntimeNode {
     #[strum(serialize = "first")]
     First,
@@ -127,7 +128,7 @@ pub enum TLTriggerRuntimeNode {
 
 /// This simple type specifies a particular event that causes the time condition to be true.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLTriggerEvent {
     /// Fire trigger at the beginning
     #[strum(serialize = "onBegin")]
@@ -166,7 +167,7 @@ pub enum TLTriggerEvent {
 
 /// This simple type specifies how the animation is applied over subelements of the target element.
 #[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq, EnumString)]
+#[derive(Debug, Copy, Clone, PartialEq, EnumString, Display)]
 pub enum IterateType {
     /// Iterate by element.
     #[strum(serialize = "el")]
@@ -181,7 +182,7 @@ pub enum IterateType {
 
 /// This simple type specifies the class of effect in which this effect belongs.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLTimeNodePresetClassType {
     #[strum(serialize = "entr")]
     Entrance,
@@ -199,7 +200,7 @@ pub enum TLTimeNodePresetClassType {
 
 /// This simple type determines whether an effect can play more than once.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLTimeNodeRestartType {
     /// Always restart node
     #[strum(serialize = "always")]
@@ -215,7 +216,7 @@ pub enum TLTimeNodeRestartType {
 /// This simple type specifies what modifications the effect leaves on the target element's properties when the
 /// effect ends.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLTimeNodeFillType {
     #[strum(serialize = "remove")]
     Remove,
@@ -229,7 +230,7 @@ pub enum TLTimeNodeFillType {
 
 /// This simple type specifies how the time node synchronizes to its group.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLTimeNodeSyncType {
     #[strum(serialize = "canSlip")]
     CanSlip,
@@ -239,7 +240,7 @@ pub enum TLTimeNodeSyncType {
 
 /// This simple type specifies how the time node plays back relative to its master time node.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLTimeNodeMasterRelation {
     #[strum(serialize = "sameClick")]
     SameClick,
@@ -251,7 +252,7 @@ pub enum TLTimeNodeMasterRelation {
 
 /// This simple type specifies time node types.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLTimeNodeType {
     #[strum(serialize = "clickEffect")]
     ClickEffect,
@@ -276,7 +277,7 @@ pub enum TLTimeNodeType {
 /// This simple type specifies what to do when going forward in a sequence. When the value is Seek, it seeks the
 /// current child element to its natural end time before advancing to the next element.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLNextActionType {
     #[strum(serialize = "none")]
     None,
@@ -288,7 +289,7 @@ pub enum TLNextActionType {
 /// sequence continues to go backwards until it reaches a sequence element that was defined to being only on a
 /// "next" event.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLPreviousActionType {
     #[strum(serialize = "none")]
     None,
@@ -298,7 +299,7 @@ pub enum TLPreviousActionType {
 
 /// This simple type specifies how the animation flows from point to point.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLAnimateBehaviorCalcMode {
     #[strum(serialize = "discrete")]
     Discrete,
@@ -310,7 +311,7 @@ pub enum TLAnimateBehaviorCalcMode {
 
 /// This simple type specifies the type of property value.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLAnimateBehaviorValueType {
     #[strum(serialize = "clr")]
     Color,
@@ -322,7 +323,7 @@ pub enum TLAnimateBehaviorValueType {
 
 /// This simple type specifies how to apply the animation values to the original value for the property.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLBehaviorAdditiveType {
     #[strum(serialize = "base")]
     Base,
@@ -338,7 +339,7 @@ pub enum TLBehaviorAdditiveType {
 
 /// This simple type makes a repeating animation build with each iteration when set to "always."
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLBehaviorAccumulateType {
     #[strum(serialize = "none")]
     None,
@@ -348,7 +349,7 @@ pub enum TLBehaviorAccumulateType {
 
 /// This simple type specifies how the behavior animates the target element.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLBehaviorTransformType {
     #[strum(serialize = "pt")]
     Point,
@@ -359,7 +360,7 @@ pub enum TLBehaviorTransformType {
 /// This simple type specifies how a behavior should override values of the attribute being animated on the target
 /// element. The ChildStyle clears the attributes on the children contained inside the target element.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLBehaviorOverrideType {
     #[strum(serialize = "normal")]
     Normal,
@@ -369,7 +370,7 @@ pub enum TLBehaviorOverrideType {
 
 /// This simple type specifies the color space of the animation.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLAnimateColorSpace {
     #[strum(serialize = "rgb")]
     Rgb,
@@ -379,7 +380,7 @@ pub enum TLAnimateColorSpace {
 
 /// This simple type specifies the direction in which to interpolate the animation (clockwise or counterclockwise).
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLAnimateColorDirection {
     #[strum(serialize = "cw")]
     Clockwise,
@@ -389,7 +390,7 @@ pub enum TLAnimateColorDirection {
 
 /// This simple type specifies whether the effect is a transition in, transition out, or neither.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLAnimateEffectTransition {
     #[strum(serialize = "in")]
     In,
@@ -401,7 +402,7 @@ pub enum TLAnimateEffectTransition {
 
 /// This simple type specifies what the origin of the motion path is relative to.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLAnimateMotionBehaviorOrigin {
     #[strum(serialize = "parent")]
     Parent,
@@ -411,7 +412,7 @@ pub enum TLAnimateMotionBehaviorOrigin {
 
 /// This simple type specifies how the motion path moves when the target element is moved.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLAnimateMotionPathEditMode {
     #[strum(serialize = "relative")]
     Relative,
@@ -421,7 +422,7 @@ pub enum TLAnimateMotionPathEditMode {
 
 /// This simple type specifies a command type.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TLCommandType {
     #[strum(serialize = "evt")]
     Event,
@@ -431,35 +432,52 @@ pub enum TLCommandType {
     Verb,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct IndexRange {
     /// This attribute defines the start of the index range.
     pub start: Index,
     /// This attribute defines the end of the index range.
     pub end: Index,
+    pub unknown_xml: UnknownXml,
 }
 
 impl IndexRange {
     pub fn new(start: Index, end: Index) -> Self {
-        Self { start, end }
+        Self {
+            start,
+            end,
+            unknown_xml: Default::default(),
+        }
     }
 
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut start = None;
         let mut end = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "st" => start = Some(value.parse()?),
                 "end" => end = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
         let start = start.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "st"))?;
         let end = end.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "end"))?;
 
-        Ok(Self { start, end })
+        Ok(Self {
+            start,
+            end,
+            unknown_xml,
+        })
+    }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("st", self.start)
+            .with_attribute("end", self.end)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -759,18 +777,31 @@ pub enum TimeNodeGroup {
     Video(Box<TLMediaNodeVideo>),
 }
 
+// The common time node data of par and excl is stored in their cTn child element. Nodes without a cTn child are read as
+// if the node itself was the cTn element
+fn parse_container_time_node_data(xml_node: &XmlNode) -> Result<TLCommonTimeNodeData> {
+    match xml_node
+        .child_nodes
+        .iter()
+        .find(|child_node| child_node.local_name() == "cTn")
+    {
+        Some(common_time_node) => TLCommonTimeNodeData::from_xml_element(common_time_node),
+        None => TLCommonTimeNodeData::from_xml_element(xml_node),
+    }
+}
+
 impl XsdType for TimeNodeGroup {
     fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         match xml_node.local_name() {
-            "par" => Ok(TimeNodeGroup::Parallel(Box::new(
-                TLCommonTimeNodeData::from_xml_element(xml_node)?,
-            ))),
+            "par" => Ok(TimeNodeGroup::Parallel(Box::new(parse_container_time_node_data(
+                xml_node,
+            )?))),
             "seq" => Ok(TimeNodeGroup::Sequence(Box::new(TLTimeNodeSequence::from_xml_element(
                 xml_node,
             )?))),
-            "excl" => Ok(TimeNodeGroup::Exclusive(Box::new(
-                TLCommonTimeNodeData::from_xml_element(xml_node)?,
-            ))),
+            "excl" => Ok(TimeNodeGroup::Exclusive(Box::new(parse_container_time_node_data(
+                xml_node,
+            )?))),
             "anim" => Ok(TimeNodeGroup::Animate(Box::new(TLAnimateBehavior::from_xml_element(
                 xml_node,
             )?))),
@@ -820,8 +851,37 @@ impl XsdChoice for TimeNodeGroup {
     }
 }
 
+impl ToXmlChoice for TimeNodeGroup {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TimeNodeGroup::Parallel(data) => XmlNode::new("p:par").with_child(data.to_xml_element("p:cTn")),
+            TimeNodeGroup::Sequence(sequence) => sequence.to_xml_element(),
+            TimeNodeGroup::Exclusive(data) => XmlNode::new("p:excl").with_child(data.to_xml_element("p:cTn")),
+            TimeNodeGroup::Animate(behavior) => behavior.to_xml_element(),
+            TimeNodeGroup::AnimateColor(behavior) => behavior.to_xml_element(),
+            TimeNodeGroup::AnimateEffect(behavior) => behavior.to_xml_element(),
+            TimeNodeGroup::AnimateMotion(behavior) => behavior.to_xml_element(),
+            TimeNodeGroup::AnimateRotation(behavior) => behavior.to_xml_element(),
+            TimeNodeGroup::AnimateScale(behavior) => behavior.to_xml_element(),
+            TimeNodeGroup::Command(behavior) => behavior.to_xml_element(),
+            TimeNodeGroup::Set(behavior) => behavior.to_xml_element(),
+            TimeNodeGroup::Audio(media_node) => media_node.to_xml_element(),
+            TimeNodeGroup::Video(media_node) => media_node.to_xml_element(),
+        }
+    }
+}
+
+impl TimeNodeGroup {
+    /// Writes the time node as a standalone xml document.
+    pub fn to_xml<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_xml(&ToXmlChoice::to_xml_element(self), writer)
+    }
+}
+
+/// A list of time nodes. The child elements that aren't time nodes, like the mc:AlternateContent blocks written by
+/// PowerPoint for newer effects, are kept in the unknown xml of the list.
 #[derive(Debug, Clone, PartialEq, Default)]
-pub struct TLTimeNodeList(pub Vec<TimeNodeGroup>);
+pub struct TLTimeNodeList(pub Vec<TimeNodeGroup>, pub UnknownXml);
 
 impl TLTimeNodeList {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
@@ -830,8 +890,16 @@ impl TLTimeNodeList {
             .iter()
             .filter_map(TimeNodeGroup::try_from_xml_element)
             .collect::<Result<Vec<_>>>()?;
+        let unknown_xml =
+            UnknownXml::from_xml_element(xml_node, &[], |local_name| TimeNodeGroup::is_choice_member(local_name));
 
-        Ok(Self(vec))
+        Ok(Self(vec, unknown_xml))
+    }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_children(self.0.iter().map(ToXmlChoice::to_xml_element))
+            .with_unknown_xml(&self.1)
     }
 }
 
@@ -1024,6 +1092,7 @@ pub struct TLCommonBehaviorData {
     /// </p:anim>
     /// ```
     pub attr_name_list: Option<Vec<String>>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLCommonBehaviorData {
@@ -1036,6 +1105,7 @@ impl TLCommonBehaviorData {
         let mut by = None;
         let mut runtime_context = None;
         let mut override_type = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
@@ -1047,7 +1117,7 @@ impl TLCommonBehaviorData {
                 "by" => by = Some(value.clone()),
                 "rctx" => runtime_context = Some(value.clone()),
                 "override" => override_type = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -1055,7 +1125,7 @@ impl TLCommonBehaviorData {
         let mut target_element = None;
         let mut attr_name_list = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cTn" => common_time_node_data = Some(Box::new(TLCommonTimeNodeData::from_xml_element(child_node)?)),
                 "tgtEl" => {
@@ -1089,7 +1159,7 @@ impl TLCommonBehaviorData {
 
                     attr_name_list = Some(vec);
                 }
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1110,8 +1180,33 @@ impl TLCommonBehaviorData {
             common_time_node_data,
             target_element,
             attr_name_list,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        let attr_name_list = self.attr_name_list.as_ref().map(|attr_names| {
+            XmlNode::new("p:attrNameLst").with_children(
+                attr_names
+                    .iter()
+                    .map(|attr_name| XmlNode::new("p:attrName").with_text(attr_name.as_str())),
+            )
+        });
+
+        XmlNode::new(node_name)
+            .with_optional_attribute("additive", self.additive)
+            .with_optional_attribute("accumulate", self.accumulate)
+            .with_optional_attribute("xfrmType", self.transform_type)
+            .with_optional_attribute("from", self.from.as_ref())
+            .with_optional_attribute("to", self.to.as_ref())
+            .with_optional_attribute("by", self.by.as_ref())
+            .with_optional_attribute("rctx", self.runtime_context.as_ref())
+            .with_optional_attribute("override", self.override_type)
+            .with_child(self.common_time_node_data.to_xml_element("p:cTn"))
+            .with_child(XmlNode::new("p:tgtEl").with_child(self.target_element.to_xml_element()))
+            .with_optional_child(attr_name_list)
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 /// This element is used to describe behavior of media elements, such as sound or movies, in an animation.
@@ -1149,6 +1244,7 @@ pub struct TLCommonMediaNodeData {
     pub show_when_stopped: Option<bool>,
     pub common_time_node_data: Box<TLCommonTimeNodeData>,
     pub target_element: TLTimeTargetElement,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLCommonMediaNodeData {
@@ -1157,6 +1253,7 @@ impl TLCommonMediaNodeData {
         let mut mute = None;
         let mut number_of_slides = None;
         let mut show_when_stopped = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
@@ -1164,14 +1261,14 @@ impl TLCommonMediaNodeData {
                 "mute" => mute = Some(parse_xml_bool(value)?),
                 "numSld" => number_of_slides = Some(value.parse()?),
                 "showWhenStopped" => show_when_stopped = Some(parse_xml_bool(value)?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
         let mut common_time_node_data = None;
         let mut target_element = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cTn" => common_time_node_data = Some(Box::new(TLCommonTimeNodeData::from_xml_element(child_node)?)),
                 "tgtEl" => {
@@ -1185,7 +1282,7 @@ impl TLCommonMediaNodeData {
                             .and_then(TLTimeTargetElement::from_xml_element)?,
                     )
                 }
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1201,8 +1298,20 @@ impl TLCommonMediaNodeData {
             show_when_stopped,
             common_time_node_data,
             target_element,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("vol", self.volume)
+            .with_optional_attribute("mute", self.mute)
+            .with_optional_attribute("numSld", self.number_of_slides)
+            .with_optional_attribute("showWhenStopped", self.show_when_stopped)
+            .with_child(self.common_time_node_data.to_xml_element("p:cTn"))
+            .with_child(XmlNode::new("p:tgtEl").with_child(self.target_element.to_xml_element()))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1241,6 +1350,7 @@ pub struct TLBuildParagraph {
     /// Defaults to TLTime::Indefinite
     pub auto_advance_time: Option<TLTime>,
     pub template_list: Option<TLTemplateList>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLBuildParagraph {
@@ -1254,6 +1364,7 @@ impl TLBuildParagraph {
         let mut auto_update_anim_bg = None;
         let mut reverse = None;
         let mut auto_advance_time = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
@@ -1266,7 +1377,7 @@ impl TLBuildParagraph {
                 "autoUpdateAnimBg" => auto_update_anim_bg = Some(parse_xml_bool(value)?),
                 "rev" => reverse = Some(parse_xml_bool(value)?),
                 "advAuto" => auto_advance_time = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -1280,6 +1391,12 @@ impl TLBuildParagraph {
             .map(TLTemplateList::from_xml_element)
             .transpose()?;
 
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            if child_node.local_name() != "tmplLst" {
+                unknown_xml.push_child(position, child_node);
+            }
+        }
+
         Ok(Self {
             build_common: TLBuildCommonAttributes {
                 shape_id,
@@ -1293,8 +1410,22 @@ impl TLBuildParagraph {
             reverse,
             auto_advance_time,
             template_list,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        self.build_common
+            .to_xml_element("p:bldP")
+            .with_optional_attribute("build", self.build_type)
+            .with_optional_attribute("bldLvl", self.build_level)
+            .with_optional_attribute("animBg", self.animate_bg)
+            .with_optional_attribute("autoUpdateAnimBg", self.auto_update_anim_bg)
+            .with_optional_attribute("rev", self.reverse)
+            .with_optional_attribute("advAuto", self.auto_advance_time.as_ref())
+            .with_optional_child(self.template_list.as_ref().map(|list| list.to_xml_element("p:tmplLst")))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1303,25 +1434,34 @@ pub struct TLPoint {
     pub x: Percentage,
     /// This attribute describes the Y coordinate.
     pub y: Percentage,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLPoint {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut x = None;
         let mut y = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "x" => x = Some(value.parse()?),
                 "y" => y = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
         let x = x.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "x"))?;
         let y = y.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "y"))?;
 
-        Ok(Self { x, y })
+        Ok(Self { x, y, unknown_xml })
+    }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("x", self.x)
+            .with_attribute("y", self.y)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -1342,6 +1482,15 @@ impl FromStr for TLTime {
     }
 }
 
+impl fmt::Display for TLTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TLTime::TimePoint(time_point) => write!(f, "{}", time_point),
+            TLTime::Indefinite => f.write_str("indefinite"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct TLTemplate {
     /// This attribute describes the paragraph indent level to which this template effect applies.
@@ -1364,6 +1513,12 @@ impl TLTemplate {
 
         Ok(Self { level, time_node_list })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:tmpl")
+            .with_optional_attribute("lvl", self.level)
+            .with_child(self.time_node_list.to_xml_element("p:tnLst"))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -1389,6 +1544,10 @@ impl TLTemplateList {
             ))),
         }
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_children(self.0.iter().map(TLTemplate::to_xml_element))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1407,6 +1566,16 @@ pub struct TLBuildCommonAttributes {
     pub ui_expand: Option<bool>,
 }
 
+impl TLBuildCommonAttributes {
+    /// Creates the element of a build with these attributes set.
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_attribute("spid", self.shape_id)
+            .with_attribute("grpId", self.group_id)
+            .with_optional_attribute("uiExpand", self.ui_expand)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TLBuildDiagram {
     pub build_common: TLBuildCommonAttributes,
@@ -1415,6 +1584,7 @@ pub struct TLBuildDiagram {
     ///
     /// Defaults to TLDiagramBuildType::Whole
     pub build_type: Option<TLDiagramBuildType>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLBuildDiagram {
@@ -1423,6 +1593,7 @@ impl TLBuildDiagram {
         let mut group_id = None;
         let mut ui_expand = None;
         let mut build_type = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
@@ -1430,7 +1601,7 @@ impl TLBuildDiagram {
                 "grpId" => group_id = Some(value.parse()?),
                 "uiExpand" => ui_expand = Some(parse_xml_bool(value)?),
                 "bld" => build_type = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -1444,8 +1615,16 @@ impl TLBuildDiagram {
                 ui_expand,
             },
             build_type,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        self.build_common
+            .to_xml_element("p:bldDgm")
+            .with_optional_attribute("bld", self.build_type)
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1460,6 +1639,7 @@ pub struct TLOleBuildChart {
     ///
     /// Defaults to true
     pub animate_bg: Option<bool>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLOleBuildChart {
@@ -1469,6 +1649,7 @@ impl TLOleBuildChart {
         let mut ui_expand = None;
         let mut build_type = None;
         let mut animate_bg = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
@@ -1477,7 +1658,7 @@ impl TLOleBuildChart {
                 "uiExpand" => ui_expand = Some(parse_xml_bool(value)?),
                 "bld" => build_type = Some(value.parse()?),
                 "animBg" => animate_bg = Some(parse_xml_bool(value)?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -1492,14 +1673,24 @@ impl TLOleBuildChart {
             },
             build_type,
             animate_bg,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        self.build_common
+            .to_xml_element("p:bldOleChart")
+            .with_optional_attribute("bld", self.build_type)
+            .with_optional_attribute("animBg", self.animate_bg)
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TLGraphicalObjectBuild {
     pub build_common: TLBuildCommonAttributes,
     pub build_choice: TLGraphicalObjectBuildChoice,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLGraphicalObjectBuild {
@@ -1507,13 +1698,14 @@ impl TLGraphicalObjectBuild {
         let mut shape_id = None;
         let mut group_id = None;
         let mut ui_expand = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "spid" => shape_id = Some(value.parse()?),
                 "grpId" => group_id = Some(value.parse()?),
                 "uiExpand" => ui_expand = Some(parse_xml_bool(value)?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -1524,6 +1716,12 @@ impl TLGraphicalObjectBuild {
             .transpose()?
             .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "TLGraphicalObjectBuildChoice"))?;
 
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            if !TLGraphicalObjectBuildChoice::is_choice_member(child_node.local_name()) {
+                unknown_xml.push_child(position, child_node);
+            }
+        }
+
         let shape_id = shape_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "spid"))?;
         let group_id = group_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "grpId"))?;
 
@@ -1534,8 +1732,16 @@ impl TLGraphicalObjectBuild {
                 ui_expand,
             },
             build_choice,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        self.build_common
+            .to_xml_element("p:bldGraphic")
+            .with_child(self.build_choice.to_xml_element())
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1600,6 +1806,17 @@ impl XsdChoice for TLGraphicalObjectBuildChoice {
     }
 }
 
+impl ToXmlChoice for TLGraphicalObjectBuildChoice {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TLGraphicalObjectBuildChoice::BuildAsOne => XmlNode::new("p:bldAsOne"),
+            TLGraphicalObjectBuildChoice::BuildSubElements(build_properties) => {
+                XmlNode::new("p:bldSub").with_child(build_properties.to_xml_element())
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TLTimeNodeSequence {
     /// This attribute specifies if concurrency is enabled or disabled. By default this attribute has
@@ -1668,6 +1885,7 @@ pub struct TLTimeNodeSequence {
     /// </p:seq>
     /// ```
     pub next_condition_list: Option<TLTimeConditionList>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLTimeNodeSequence {
@@ -1675,13 +1893,14 @@ impl TLTimeNodeSequence {
         let mut concurrent = None;
         let mut prev_action_type = None;
         let mut next_action_type = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "concurrent" => concurrent = Some(parse_xml_bool(value)?),
                 "prevAc" => prev_action_type = Some(value.parse()?),
                 "nextAc" => next_action_type = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -1689,12 +1908,12 @@ impl TLTimeNodeSequence {
         let mut prev_condition_list = None;
         let mut next_condition_list = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cTn" => common_time_node_data = Some(Box::new(TLCommonTimeNodeData::from_xml_element(child_node)?)),
                 "prevCondLst" => prev_condition_list = Some(TLTimeConditionList::from_xml_element(child_node)?),
                 "nextCondLst" => next_condition_list = Some(TLTimeConditionList::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1708,8 +1927,28 @@ impl TLTimeNodeSequence {
             common_time_node_data,
             prev_condition_list,
             next_condition_list,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:seq")
+            .with_optional_attribute("concurrent", self.concurrent)
+            .with_optional_attribute("prevAc", self.prev_action_type)
+            .with_optional_attribute("nextAc", self.next_action_type)
+            .with_child(self.common_time_node_data.to_xml_element("p:cTn"))
+            .with_optional_child(
+                self.prev_condition_list
+                    .as_ref()
+                    .map(|list| list.to_xml_element("p:prevCondLst")),
+            )
+            .with_optional_child(
+                self.next_condition_list
+                    .as_ref()
+                    .map(|list| list.to_xml_element("p:nextCondLst")),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1746,6 +1985,7 @@ pub struct TLAnimateBehavior {
     /// </p:anim>
     /// ```
     pub time_animate_value_list: Option<TLTimeAnimateValueList>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLAnimateBehavior {
@@ -1755,6 +1995,7 @@ impl TLAnimateBehavior {
         let mut to = None;
         let mut calc_mode = None;
         let mut value_type = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
@@ -1763,18 +2004,18 @@ impl TLAnimateBehavior {
                 "to" => to = Some(value.clone()),
                 "calcmode" => calc_mode = Some(value.parse()?),
                 "valueType" => value_type = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
         let mut common_behavior_data = None;
         let mut time_animate_value_list = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cBhvr" => common_behavior_data = Some(Box::new(TLCommonBehaviorData::from_xml_element(child_node)?)),
                 "tavLst" => time_animate_value_list = Some(TLTimeAnimateValueList::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1789,8 +2030,25 @@ impl TLAnimateBehavior {
             value_type,
             common_behavior_data,
             time_animate_value_list,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:anim")
+            .with_optional_attribute("by", self.by.as_ref())
+            .with_optional_attribute("from", self.from.as_ref())
+            .with_optional_attribute("to", self.to.as_ref())
+            .with_optional_attribute("calcmode", self.calc_mode)
+            .with_optional_attribute("valueType", self.value_type)
+            .with_child(self.common_behavior_data.to_xml_element("p:cBhvr"))
+            .with_optional_child(
+                self.time_animate_value_list
+                    .as_ref()
+                    .map(|list| list.to_xml_element("p:tavLst")),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1870,18 +2128,20 @@ pub struct TLAnimateColorBehavior {
     /// </p:childTnLst>
     /// ```
     pub to: Option<Color>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLAnimateColorBehavior {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut color_space = None;
         let mut direction = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "clrSpc" => color_space = Some(value.parse()?),
                 "dir" => direction = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -1890,7 +2150,7 @@ impl TLAnimateColorBehavior {
         let mut from = None;
         let mut to = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cBhvr" => common_behavior_data = Some(Box::new(TLCommonBehaviorData::from_xml_element(child_node)?)),
                 "by" => {
@@ -1925,7 +2185,7 @@ impl TLAnimateColorBehavior {
                             .ok_or_else(|| MissingChildNodeError::new(child_node.name.clone(), "EG_Color"))?,
                     )
                 }
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1939,8 +2199,32 @@ impl TLAnimateColorBehavior {
             by,
             from,
             to,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:animClr")
+            .with_optional_attribute("clrSpc", self.color_space)
+            .with_optional_attribute("dir", self.direction)
+            .with_child(self.common_behavior_data.to_xml_element("p:cBhvr"))
+            .with_optional_child(
+                self.by
+                    .as_ref()
+                    .map(|by| XmlNode::new("p:by").with_child(by.to_xml_element())),
+            )
+            .with_optional_child(
+                self.from
+                    .as_ref()
+                    .map(|color| XmlNode::new("p:from").with_child(color.to_xml_element())),
+            )
+            .with_optional_child(
+                self.to
+                    .as_ref()
+                    .map(|color| XmlNode::new("p:to").with_child(color.to_xml_element())),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2019,6 +2303,7 @@ pub struct TLAnimateEffectBehavior {
     /// the progress attribute, you can use the tmFilter (time filter), which is a base attribute of any effect/timenode, to
     /// specify the way that progress through an effect should be performed dynamically.
     pub progress: Option<TLAnimVariant>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLAnimateEffectBehavior {
@@ -2026,20 +2311,21 @@ impl TLAnimateEffectBehavior {
         let mut transition = None;
         let mut filter = None;
         let mut property_list = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "transition" => transition = Some(value.parse()?),
                 "filter" => filter = Some(value.clone()),
                 "prLst" => property_list = Some(value.clone()),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
         let mut common_behavior_data = None;
         let mut progress = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cBhvr" => common_behavior_data = Some(Box::new(TLCommonBehaviorData::from_xml_element(child_node)?)),
                 "progress" => {
@@ -2052,7 +2338,7 @@ impl TLAnimateEffectBehavior {
                             .ok_or_else(|| MissingChildNodeError::new(child_node.name.clone(), "CT_TLAnimVariant"))?,
                     )
                 }
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -2065,8 +2351,23 @@ impl TLAnimateEffectBehavior {
             property_list,
             common_behavior_data,
             progress,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:animEffect")
+            .with_optional_attribute("transition", self.transition)
+            .with_optional_attribute("filter", self.filter.as_ref())
+            .with_optional_attribute("prLst", self.property_list.as_ref())
+            .with_child(self.common_behavior_data.to_xml_element("p:cBhvr"))
+            .with_optional_child(
+                self.progress
+                    .as_ref()
+                    .map(|progress| XmlNode::new("p:progress").with_child(progress.to_xml_element())),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2158,6 +2459,7 @@ pub struct TLAnimateMotionBehavior {
     /// </p:animMotion>
     /// ```
     pub rotation_center: Option<TLPoint>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLAnimateMotionBehavior {
@@ -2167,6 +2469,7 @@ impl TLAnimateMotionBehavior {
         let mut path_edit_mode = None;
         let mut rotate_angle = None;
         let mut points_types = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
@@ -2175,7 +2478,7 @@ impl TLAnimateMotionBehavior {
                 "pathEditMode" => path_edit_mode = Some(value.parse()?),
                 "rAng" => rotate_angle = Some(value.parse()?),
                 "ptsTypes" => points_types = Some(value.clone()),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -2185,14 +2488,14 @@ impl TLAnimateMotionBehavior {
         let mut to = None;
         let mut rotation_center = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cBhvr" => common_behavior_data = Some(Box::new(TLCommonBehaviorData::from_xml_element(child_node)?)),
                 "by" => by = Some(TLPoint::from_xml_element(child_node)?),
                 "from" => from = Some(TLPoint::from_xml_element(child_node)?),
                 "to" => to = Some(TLPoint::from_xml_element(child_node)?),
                 "rCtr" => rotation_center = Some(TLPoint::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -2210,8 +2513,28 @@ impl TLAnimateMotionBehavior {
             from,
             to,
             rotation_center,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:animMotion")
+            .with_optional_attribute("origin", self.origin)
+            .with_optional_attribute("path", self.path.as_ref())
+            .with_optional_attribute("pathEditMode", self.path_edit_mode)
+            .with_optional_attribute("rAng", self.rotate_angle)
+            .with_optional_attribute("ptsTypes", self.points_types.as_ref())
+            .with_child(self.common_behavior_data.to_xml_element("p:cBhvr"))
+            .with_optional_child(self.by.as_ref().map(|point| point.to_xml_element("p:by")))
+            .with_optional_child(self.from.as_ref().map(|point| point.to_xml_element("p:from")))
+            .with_optional_child(self.to.as_ref().map(|point| point.to_xml_element("p:to")))
+            .with_optional_child(
+                self.rotation_center
+                    .as_ref()
+                    .map(|point| point.to_xml_element("p:rCtr")),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2223,6 +2546,7 @@ pub struct TLAnimateRotationBehavior {
     /// This attribute describes the ending value for the animation.
     pub to: Option<Angle>,
     pub common_behavior_data: Box<TLCommonBehaviorData>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLAnimateRotationBehavior {
@@ -2230,13 +2554,14 @@ impl TLAnimateRotationBehavior {
         let mut by = None;
         let mut from = None;
         let mut to = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "by" => by = Some(value.parse()?),
                 "from" => from = Some(value.parse()?),
                 "to" => to = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -2248,13 +2573,29 @@ impl TLAnimateRotationBehavior {
             .and_then(TLCommonBehaviorData::from_xml_element)?
             .into();
 
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            if child_node.local_name() != "cBhvr" {
+                unknown_xml.push_child(position, child_node);
+            }
+        }
+
         Ok(Self {
             by,
             from,
             to,
             common_behavior_data,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:animRot")
+            .with_optional_attribute("by", self.by)
+            .with_optional_attribute("from", self.from)
+            .with_optional_attribute("to", self.to)
+            .with_child(self.common_behavior_data.to_xml_element("p:cBhvr"))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2318,6 +2659,7 @@ pub struct TLAnimateScaleBehavior {
     /// </p:animScale>
     /// ```
     pub to: Option<TLPoint>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLAnimateScaleBehavior {
@@ -2327,19 +2669,20 @@ impl TLAnimateScaleBehavior {
             .get("zoomContents")
             .map(parse_xml_bool)
             .transpose()?;
+        let mut unknown_xml = UnknownXml::from_xml_element(xml_node, &["zoomContents"], |_| true);
 
         let mut common_behavior_data = None;
         let mut by = None;
         let mut from = None;
         let mut to = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cBhvr" => common_behavior_data = Some(Box::new(TLCommonBehaviorData::from_xml_element(child_node)?)),
                 "by" => by = Some(TLPoint::from_xml_element(child_node)?),
                 "from" => from = Some(TLPoint::from_xml_element(child_node)?),
                 "to" => to = Some(TLPoint::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -2352,8 +2695,19 @@ impl TLAnimateScaleBehavior {
             by,
             from,
             to,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:animScale")
+            .with_optional_attribute("zoomContents", self.zoom_contents)
+            .with_child(self.common_behavior_data.to_xml_element("p:cBhvr"))
+            .with_optional_child(self.by.as_ref().map(|point| point.to_xml_element("p:by")))
+            .with_optional_child(self.from.as_ref().map(|point| point.to_xml_element("p:from")))
+            .with_optional_child(self.to.as_ref().map(|point| point.to_xml_element("p:to")))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2423,18 +2777,20 @@ pub struct TLCommandBehavior {
     /// particular animation is reached in the timeline.
     pub command: Option<String>,
     pub common_behavior_data: Box<TLCommonBehaviorData>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLCommandBehavior {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut command_type = None;
         let mut command = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "type" => command_type = Some(value.parse()?),
                 "cmd" => command = Some(value.clone()),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -2446,12 +2802,27 @@ impl TLCommandBehavior {
             .and_then(TLCommonBehaviorData::from_xml_element)?
             .into();
 
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            if child_node.local_name() != "cBhvr" {
+                unknown_xml.push_child(position, child_node);
+            }
+        }
+
         Ok(Self {
             command_type,
             command,
             common_behavior_data,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:cmd")
+            .with_optional_attribute("type", self.command_type)
+            .with_optional_attribute("cmd", self.command.as_ref())
+            .with_child(self.common_behavior_data.to_xml_element("p:cBhvr"))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2475,14 +2846,16 @@ pub struct TLSetBehavior {
     /// </p:childTnLst>
     /// ```
     pub to: Option<TLAnimVariant>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLSetBehavior {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut common_behavior_data = None;
         let mut to = None;
+        let mut unknown_xml = UnknownXml::from_xml_element(xml_node, &[], |_| true);
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cBhvr" => common_behavior_data = Some(Box::new(TLCommonBehaviorData::from_xml_element(child_node)?)),
                 "to" => {
@@ -2495,7 +2868,7 @@ impl TLSetBehavior {
                             .ok_or_else(|| MissingChildNodeError::new(child_node.name.clone(), "CT_TLAnimVariant"))?,
                     )
                 }
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -2505,8 +2878,20 @@ impl TLSetBehavior {
         Ok(Self {
             common_behavior_data,
             to,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:set")
+            .with_child(self.common_behavior_data.to_xml_element("p:cBhvr"))
+            .with_optional_child(
+                self.to
+                    .as_ref()
+                    .map(|to| XmlNode::new("p:to").with_child(to.to_xml_element())),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2535,6 +2920,12 @@ impl TLMediaNodeAudio {
             common_media_node_data,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:audio")
+            .with_optional_attribute("isNarration", self.is_narration)
+            .with_child(self.common_media_node_data.to_xml_element("p:cMediaNode"))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2563,6 +2954,12 @@ impl TLMediaNodeVideo {
             common_media_node_data,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:video")
+            .with_optional_attribute("fullScrn", self.fullscreen)
+            .with_child(self.common_media_node_data.to_xml_element("p:cMediaNode"))
+    }
 }
 
 /// This element defines a "keypoint" in animation interpolation.
@@ -2772,6 +3169,7 @@ pub struct TLTimeAnimateValue {
     /// </p:anim>
     /// ```
     pub value: Option<TLAnimVariant>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLTimeAnimateValue {
@@ -2783,12 +3181,18 @@ impl TLTimeAnimateValue {
                 match attr.as_ref() {
                     "tm" => instance.time = Some(value.parse()?),
                     "fmla" => instance.formula = Some(value.clone()),
-                    _ => (),
+                    _ => instance.unknown_xml.push_attribute(attr, value),
                 }
 
                 Ok(instance)
             })
-            .and_then(|instance| {
+            .and_then(|mut instance| {
+                for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+                    if child_node.local_name() != "val" {
+                        instance.unknown_xml.push_child(position, child_node);
+                    }
+                }
+
                 let value = xml_node
                     .child_nodes
                     .iter()
@@ -2806,6 +3210,18 @@ impl TLTimeAnimateValue {
                 Ok(Self { value, ..instance })
             })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:tav")
+            .with_optional_attribute("tm", self.time.as_ref())
+            .with_optional_attribute("fmla", self.formula.as_ref())
+            .with_optional_child(
+                self.value
+                    .as_ref()
+                    .map(|value| XmlNode::new("p:val").with_child(value.to_xml_element())),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -2822,6 +3238,10 @@ impl TLTimeAnimateValueList {
 
         Ok(Self(vec))
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_children(self.0.iter().map(TLTimeAnimateValue::to_xml_element))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2843,6 +3263,15 @@ impl FromStr for TLTimeAnimateValueTime {
     }
 }
 
+impl fmt::Display for TLTimeAnimateValueTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TLTimeAnimateValueTime::Percentage(percentage) => write!(f, "{}", percentage),
+            TLTimeAnimateValueTime::Indefinite => f.write_str("indefinite"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TLAnimVariant {
     /// This element specifies a boolean value to be used for evaluation by a parent element. The exact meaning of the
@@ -2914,6 +3343,18 @@ impl XsdChoice for TLAnimVariant {
     }
 }
 
+impl ToXmlChoice for TLAnimVariant {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TLAnimVariant::Bool(value) => XmlNode::new("p:boolVal").with_attribute("val", value),
+            TLAnimVariant::Int(value) => XmlNode::new("p:intVal").with_attribute("val", value),
+            TLAnimVariant::Float(value) => XmlNode::new("p:fltVal").with_attribute("val", value),
+            TLAnimVariant::String(value) => XmlNode::new("p:strVal").with_attribute("val", value),
+            TLAnimVariant::Color(color) => XmlNode::new("p:clrVal").with_child(color.to_xml_element()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TLTimeConditionTriggerGroup {
     TargetElement(TLTimeTargetElement),
@@ -2998,6 +3439,18 @@ impl XsdChoice for TLTimeConditionTriggerGroup {
     }
 }
 
+impl ToXmlChoice for TLTimeConditionTriggerGroup {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TLTimeConditionTriggerGroup::TargetElement(target_element) => {
+                XmlNode::new("p:tgtEl").with_child(target_element.to_xml_element())
+            }
+            TLTimeConditionTriggerGroup::TimeNode(id) => XmlNode::new("p:tn").with_attribute("val", id),
+            TLTimeConditionTriggerGroup::RuntimeNode(node) => XmlNode::new("p:rtn").with_attribute("val", node),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TLTimeTargetElement {
     /// This element specifies the slide as the target element.
@@ -3111,6 +3564,17 @@ impl XsdChoice for TLTimeTargetElement {
     }
 }
 
+impl ToXmlChoice for TLTimeTargetElement {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TLTimeTargetElement::SlideTarget => XmlNode::new("p:sldTgt"),
+            TLTimeTargetElement::SoundTarget(audio_file) => audio_file.to_xml_element("p:sndTgt"),
+            TLTimeTargetElement::ShapeTarget(shape_target) => shape_target.to_xml_element(),
+            TLTimeTargetElement::InkTarget(spid) => XmlNode::new("p:inkTgt").with_attribute("spid", spid),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TLShapeTargetElement {
     /// This attribute specifies the shape identifier.
@@ -3138,6 +3602,12 @@ impl TLShapeTargetElement {
 
         Ok(Self { shape_id, target })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:spTgt")
+            .with_attribute("spid", self.shape_id)
+            .with_optional_child(self.target.as_ref().map(ToXmlChoice::to_xml_element))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -3286,6 +3756,22 @@ impl XsdChoice for TLShapeTargetElementGroup {
     }
 }
 
+impl ToXmlChoice for TLShapeTargetElementGroup {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TLShapeTargetElementGroup::Background => XmlNode::new("p:bg"),
+            TLShapeTargetElementGroup::SubShape(spid) => XmlNode::new("p:subSp").with_attribute("spid", spid),
+            TLShapeTargetElementGroup::OleChartElement(chart_element) => chart_element.to_xml_element(),
+            TLShapeTargetElementGroup::TextElement(text_element) => {
+                XmlNode::new("p:txEl").with_optional_child(text_element.as_ref().map(ToXmlChoice::to_xml_element))
+            }
+            TLShapeTargetElementGroup::GraphicElement(animation_element) => {
+                XmlNode::new("p:graphicEl").with_child(animation_element.to_xml_element())
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TLOleChartTargetElement {
     /// This attribute specifies how to chart should be built during its animation.
@@ -3294,24 +3780,37 @@ pub struct TLOleChartTargetElement {
     ///
     /// Defaults to 0
     pub level: Option<u32>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLOleChartTargetElement {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut element_type = None;
         let mut level = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "type" => element_type = Some(value.parse()?),
                 "lvl" => level = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
         let element_type = element_type.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "type"))?;
 
-        Ok(Self { element_type, level })
+        Ok(Self {
+            element_type,
+            level,
+            unknown_xml,
+        })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:oleChartEl")
+            .with_attribute("type", self.element_type)
+            .with_optional_attribute("lvl", self.level)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -3390,6 +3889,15 @@ impl XsdChoice for TLTextTargetElement {
     }
 }
 
+impl ToXmlChoice for TLTextTargetElement {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TLTextTargetElement::CharRange(range) => range.to_xml_element("p:charRg"),
+            TLTextTargetElement::ParagraphRange(range) => range.to_xml_element("p:pRg"),
+        }
+    }
+}
+
 /// This element specifies conditions on time nodes in a timeline. It is used within a list of start condition or list of
 /// end condition elements.
 ///
@@ -3420,6 +3928,7 @@ pub struct TLTimeCondition {
     /// This attribute describes the delay after an animation is triggered.
     pub delay: Option<TLTime>,
     pub trigger: Option<TLTimeConditionTriggerGroup>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLTimeCondition {
@@ -3431,12 +3940,18 @@ impl TLTimeCondition {
                 match attr.as_str() {
                     "evt" => instance.trigger_event = Some(value.parse()?),
                     "delay" => instance.delay = Some(value.parse()?),
-                    _ => (),
+                    _ => instance.unknown_xml.push_attribute(attr, value),
                 }
 
                 Ok(instance)
             })
-            .and_then(|instance| {
+            .and_then(|mut instance| {
+                for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+                    if !TLTimeConditionTriggerGroup::is_choice_member(child_node.local_name()) {
+                        instance.unknown_xml.push_child(position, child_node);
+                    }
+                }
+
                 let trigger = xml_node
                     .child_nodes
                     .iter()
@@ -3446,6 +3961,14 @@ impl TLTimeCondition {
                 Ok(Self { trigger, ..instance })
             })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("evt", self.trigger_event)
+            .with_optional_attribute("delay", self.delay.as_ref())
+            .with_optional_child(self.trigger.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -3472,6 +3995,10 @@ impl TLTimeConditionList {
             Ok(Self(list))
         }
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_children(self.0.iter().map(|condition| condition.to_xml_element("p:cond")))
+    }
 }
 
 /// This element describes the properties that are common for time nodes.
@@ -3658,6 +4185,7 @@ pub struct TLCommonTimeNodeData {
     /// </p:par>
     /// ```
     pub sub_time_node_list: Option<TLTimeNodeList>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLCommonTimeNodeData {
@@ -3690,7 +4218,7 @@ impl TLCommonTimeNodeData {
                     "afterEffect" => instance.after_effect = Some(parse_xml_bool(value)?),
                     "nodeType" => instance.node_type = Some(value.parse()?),
                     "nodePh" => instance.node_placeholder = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    _ => instance.unknown_xml.push_attribute(attr, value),
                 }
 
                 Ok(instance)
@@ -3699,7 +4227,8 @@ impl TLCommonTimeNodeData {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
                         match child_node.local_name() {
                             "stCondLst" => {
                                 instance.start_condition_list = Some(TLTimeConditionList::from_xml_element(child_node)?)
@@ -3715,13 +4244,67 @@ impl TLCommonTimeNodeData {
                             "subTnLst" => {
                                 instance.sub_time_node_list = Some(TLTimeNodeList::from_xml_element(child_node)?)
                             }
-                            _ => (),
+                            _ => instance.unknown_xml.push_child(position, child_node),
                         }
 
                         Ok(instance)
                     })
             })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("id", self.id)
+            .with_optional_attribute("presetID", self.preset_id)
+            .with_optional_attribute("presetClass", self.preset_class)
+            .with_optional_attribute("presetSubtype", self.preset_subtype)
+            .with_optional_attribute("dur", self.duration.as_ref())
+            .with_optional_attribute("repeatCount", self.repeat_count.as_ref())
+            .with_optional_attribute("repeatDur", self.repeat_duration.as_ref())
+            .with_optional_attribute("spd", self.speed)
+            .with_optional_attribute("accel", self.acceleration)
+            .with_optional_attribute("decel", self.deceleration)
+            .with_optional_attribute("autoRev", self.auto_reverse)
+            .with_optional_attribute("restart", self.restart_type)
+            .with_optional_attribute("fill", self.fill_type)
+            .with_optional_attribute("syncBehavior", self.sync_behavior)
+            .with_optional_attribute("tmFilter", self.time_filter.as_ref())
+            .with_optional_attribute("evtFilter", self.event_filter.as_ref())
+            .with_optional_attribute("display", self.display)
+            .with_optional_attribute("masterRel", self.master_relationship)
+            .with_optional_attribute("bldLvl", self.build_level)
+            .with_optional_attribute("grpId", self.group_id)
+            .with_optional_attribute("afterEffect", self.after_effect)
+            .with_optional_attribute("nodeType", self.node_type)
+            .with_optional_attribute("nodePh", self.node_placeholder)
+            .with_optional_child(
+                self.start_condition_list
+                    .as_ref()
+                    .map(|list| list.to_xml_element("p:stCondLst")),
+            )
+            .with_optional_child(
+                self.end_condition_list
+                    .as_ref()
+                    .map(|list| list.to_xml_element("p:endCondLst")),
+            )
+            .with_optional_child(
+                self.end_sync
+                    .as_ref()
+                    .map(|end_sync| end_sync.to_xml_element("p:endSync")),
+            )
+            .with_optional_child(self.iterate.as_ref().map(TLIterateData::to_xml_element))
+            .with_optional_child(
+                self.child_time_node_list
+                    .as_ref()
+                    .map(|list| list.to_xml_element("p:childTnLst")),
+            )
+            .with_optional_child(
+                self.sub_time_node_list
+                    .as_ref()
+                    .map(|list| list.to_xml_element("p:subTnLst")),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -3789,6 +4372,15 @@ impl XsdChoice for TLIterateDataChoice {
     }
 }
 
+impl ToXmlChoice for TLIterateDataChoice {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TLIterateDataChoice::Absolute(time) => XmlNode::new("p:tmAbs").with_attribute("val", time),
+            TLIterateDataChoice::Percent(percent) => XmlNode::new("p:tmPct").with_attribute("val", percent),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TLIterateData {
     /// This attribute specifies the iteration behavior and applies it to each letter, word or shape
@@ -3805,33 +4397,51 @@ pub struct TLIterateData {
     /// Defaults to false
     pub backwards: Option<bool>,
     pub interval: TLIterateDataChoice,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLIterateData {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut iterate_type = None;
         let mut backwards = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "type" => iterate_type = Some(value.parse()?),
                 "backwards" => backwards = Some(parse_xml_bool(value)?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
         let interval = xml_node
             .child_nodes
-            .first()
-            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "TLIterateDataChoice").into())
-            .and_then(TLIterateDataChoice::from_xml_element)?;
+            .iter()
+            .find_map(TLIterateDataChoice::try_from_xml_element)
+            .transpose()?
+            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "TLIterateDataChoice"))?;
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            if !TLIterateDataChoice::is_choice_member(child_node.local_name()) {
+                unknown_xml.push_child(position, child_node);
+            }
+        }
 
         Ok(Self {
             iterate_type,
             backwards,
             interval,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:iterate")
+            .with_optional_attribute("type", self.iterate_type)
+            .with_optional_attribute("backwards", self.backwards)
+            .with_child(self.interval.to_xml_element())
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -3909,6 +4519,15 @@ impl XsdChoice for TLByAnimateColorTransform {
     }
 }
 
+impl ToXmlChoice for TLByAnimateColorTransform {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TLByAnimateColorTransform::Rgb(transform) => transform.to_xml_element(),
+            TLByAnimateColorTransform::Hsl(transform) => transform.to_xml_element(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TLByRgbColorTransform {
     /// This attribute specifies a red component luminance as a percentage. Values are in the range [-100%, 100%].
@@ -3917,6 +4536,7 @@ pub struct TLByRgbColorTransform {
     pub g: FixedPercentage,
     /// This attribute specifies a blue component luminance as a percentage. Values are in the range [-100%, 100%].
     pub b: FixedPercentage,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLByRgbColorTransform {
@@ -3924,13 +4544,14 @@ impl TLByRgbColorTransform {
         let mut r = None;
         let mut g = None;
         let mut b = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "r" => r = Some(value.parse()?),
                 "g" => g = Some(value.parse()?),
                 "b" => b = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -3938,7 +4559,15 @@ impl TLByRgbColorTransform {
         let g = g.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "g"))?;
         let b = b.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "b"))?;
 
-        Ok(Self { r, g, b })
+        Ok(Self { r, g, b, unknown_xml })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:rgb")
+            .with_attribute("r", self.r)
+            .with_attribute("g", self.g)
+            .with_attribute("b", self.b)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -3950,6 +4579,7 @@ pub struct TLByHslColorTransform {
     pub s: FixedPercentage,
     /// Specifies a saturation as a percentage. The values are in the range [-100%, 100%].
     pub l: FixedPercentage,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TLByHslColorTransform {
@@ -3957,13 +4587,14 @@ impl TLByHslColorTransform {
         let mut h = None;
         let mut s = None;
         let mut l = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "h" => h = Some(value.parse()?),
                 "s" => s = Some(value.parse()?),
                 "l" => l = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -3971,7 +4602,15 @@ impl TLByHslColorTransform {
         let s = s.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "s"))?;
         let l = l.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "l"))?;
 
-        Ok(Self { h, s, l })
+        Ok(Self { h, s, l, unknown_xml })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:hsl")
+            .with_attribute("h", self.h)
+            .with_attribute("s", self.s)
+            .with_attribute("l", self.l)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -4070,9 +4709,28 @@ impl XsdChoice for Build {
     }
 }
 
+impl ToXmlChoice for Build {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            Build::Paragraph(build) => build.to_xml_element(),
+            Build::Diagram(build) => build.to_xml_element(),
+            Build::OleChart(build) => build.to_xml_element(),
+            Build::Graphic(build) => build.to_xml_element(),
+        }
+    }
+}
+
+impl Build {
+    /// Writes the build as a standalone xml document.
+    pub fn to_xml<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_xml(&ToXmlChoice::to_xml_element(self), writer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::xml::parse_part_xml;
 
     impl IndexRange {
         pub fn test_xml(node_name: &'static str) -> String {
@@ -4080,7 +4738,7 @@ mod tests {
         }
 
         pub fn test_instance() -> Self {
-            Self { start: 0, end: 5 }
+            Self::new(0, 5)
         }
     }
 
@@ -4124,6 +4782,7 @@ mod tests {
                 common_time_node_data: Box::new(TLCommonTimeNodeData::test_instance_non_recursive()),
                 target_element: TLTimeTargetElement::SlideTarget,
                 attr_name_list: Some(vec![String::from("style.fontSize")]),
+                unknown_xml: Default::default(),
             }
         }
     }
@@ -4159,6 +4818,7 @@ mod tests {
                 show_when_stopped: Some(true),
                 common_time_node_data: Box::new(TLCommonTimeNodeData::test_instance_non_recursive()),
                 target_element: TLTimeTargetElement::SlideTarget,
+                unknown_xml: Default::default(),
             }
         }
     }
@@ -4184,6 +4844,7 @@ mod tests {
             Self {
                 x: 100_000.0,
                 y: 100_000.0,
+                unknown_xml: Default::default(),
             }
         }
     }
@@ -4216,6 +4877,7 @@ mod tests {
                 trigger: Some(TLTimeConditionTriggerGroup::TargetElement(
                     TLTimeTargetElement::SlideTarget,
                 )),
+                unknown_xml: Default::default(),
             }
         }
     }
@@ -4266,10 +4928,13 @@ mod tests {
         }
 
         pub fn test_instance() -> Self {
-            Self(vec![
-                TimeNodeGroup::Parallel(Box::new(TLCommonTimeNodeData::test_instance_non_recursive())),
-                TimeNodeGroup::Sequence(Box::new(TLTimeNodeSequence::test_instance())),
-            ])
+            Self(
+                vec![
+                    TimeNodeGroup::Parallel(Box::new(TLCommonTimeNodeData::test_instance_non_recursive())),
+                    TimeNodeGroup::Sequence(Box::new(TLTimeNodeSequence::test_instance())),
+                ],
+                Default::default(),
+            )
         }
     }
 
@@ -4391,6 +5056,7 @@ mod tests {
                 common_time_node_data: Box::new(TLCommonTimeNodeData::test_instance_non_recursive()),
                 prev_condition_list: Some(TLTimeConditionList::test_instance()),
                 next_condition_list: Some(TLTimeConditionList::test_instance()),
+                unknown_xml: Default::default(),
             }
         }
     }
@@ -4419,6 +5085,7 @@ mod tests {
                 iterate_type: Some(IterateType::Element),
                 backwards: Some(false),
                 interval: TLIterateDataChoice::Absolute(TLTime::TimePoint(10_000)),
+                unknown_xml: Default::default(),
             }
         }
     }
@@ -4514,6 +5181,7 @@ mod tests {
                 reverse: Some(false),
                 auto_advance_time: Some(TLTime::TimePoint(1000)),
                 template_list: Some(TLTemplateList::test_instance()),
+                unknown_xml: Default::default(),
             }
         }
     }
@@ -4540,6 +5208,7 @@ mod tests {
             Self {
                 build_common: TLBuildCommonAttributes::test_instance(),
                 build_type: Some(TLDiagramBuildType::Whole),
+                unknown_xml: Default::default(),
             }
         }
     }
@@ -4567,6 +5236,7 @@ mod tests {
                 build_common: TLBuildCommonAttributes::test_instance(),
                 build_type: Some(TLOleChartBuildType::AllAtOnce),
                 animate_bg: Some(true),
+                unknown_xml: Default::default(),
             }
         }
     }
@@ -4595,6 +5265,7 @@ mod tests {
             Self {
                 build_common: TLBuildCommonAttributes::test_instance(),
                 build_choice: TLGraphicalObjectBuildChoice::BuildAsOne,
+                unknown_xml: Default::default(),
             }
         }
     }
@@ -4608,6 +5279,145 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_time_node_round_trip() {
+        let common_behavior_data = TLCommonBehaviorData {
+            target_element: TLTimeTargetElement::ShapeTarget(TLShapeTargetElement::new(
+                4,
+                Some(TLShapeTargetElementGroup::TextElement(Some(
+                    TLTextTargetElement::ParagraphRange(IndexRange::new(0, 2)),
+                ))),
+            )),
+            ..TLCommonBehaviorData::test_instance()
+        };
+        let time_animate_value_list = TLTimeAnimateValueList(vec![
+            TLTimeAnimateValue {
+                time: Some(TLTimeAnimateValueTime::Percentage(0.0)),
+                formula: None,
+                value: Some(TLAnimVariant::String(String::from("#ppt_x"))),
+                unknown_xml: Default::default(),
+            },
+            TLTimeAnimateValue {
+                time: Some(TLTimeAnimateValueTime::Indefinite),
+                formula: Some(String::from("#ppt_x+1")),
+                value: Some(TLAnimVariant::Float(0.5)),
+                unknown_xml: Default::default(),
+            },
+        ]);
+        let child_time_node_list = TLTimeNodeList(
+            vec![
+                TimeNodeGroup::Set(Box::new(TLSetBehavior {
+                    common_behavior_data: Box::new(common_behavior_data.clone()),
+                    to: Some(TLAnimVariant::String(String::from("visible"))),
+                    unknown_xml: Default::default(),
+                })),
+                TimeNodeGroup::Animate(Box::new(TLAnimateBehavior {
+                    by: None,
+                    from: None,
+                    to: None,
+                    calc_mode: Some(TLAnimateBehaviorCalcMode::Linear),
+                    value_type: Some(TLAnimateBehaviorValueType::Number),
+                    common_behavior_data: Box::new(common_behavior_data.clone()),
+                    time_animate_value_list: Some(time_animate_value_list),
+                    unknown_xml: Default::default(),
+                })),
+                TimeNodeGroup::AnimateScale(Box::new(TLAnimateScaleBehavior {
+                    zoom_contents: Some(true),
+                    common_behavior_data: Box::new(common_behavior_data),
+                    by: Some(TLPoint::test_instance()),
+                    from: None,
+                    to: None,
+                    unknown_xml: Default::default(),
+                })),
+                TimeNodeGroup::Audio(Box::new(TLMediaNodeAudio {
+                    is_narration: Some(true),
+                    common_media_node_data: Box::new(TLCommonMediaNodeData::test_instance()),
+                })),
+            ],
+            Default::default(),
+        );
+        let time_node = TimeNodeGroup::Parallel(Box::new(TLCommonTimeNodeData {
+            child_time_node_list: Some(child_time_node_list),
+            ..TLCommonTimeNodeData::test_instance()
+        }));
+
+        let mut xml = Vec::new();
+        time_node.to_xml(&mut xml).unwrap();
+        let xml_node = XmlNode::from_str(std::str::from_utf8(&xml).unwrap()).unwrap();
+        assert_eq!(TimeNodeGroup::from_xml_element(&xml_node).unwrap(), time_node);
+    }
+
+    #[test]
+    pub fn test_time_node_unknown_xml_round_trip() {
+        let xml = r##"<p:par xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+            xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006"
+            xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main">
+            <p:cTn id="1" p14:presetBounceEnd="50000">
+                <p:childTnLst>
+                    <mc:AlternateContent>
+                        <mc:Choice Requires="p14"><p:par><p:cTn id="2" p14:presetBounceEnd="50000"/></p:par></mc:Choice>
+                        <mc:Fallback><p:par><p:cTn id="2"/></p:par></mc:Fallback>
+                    </mc:AlternateContent>
+                    <p:anim calcmode="lin" p14:unknownAttr="1">
+                        <p:cBhvr>
+                            <p:cTn id="3" dur="500"/>
+                            <p:tgtEl>
+                                <p:spTgt spid="4"><p:txEl><p:pRg st="0" end="2" p14:unknownAttr="1"/></p:txEl></p:spTgt>
+                            </p:tgtEl>
+                            <p:attrNameLst><p:attrName>ppt_x</p:attrName></p:attrNameLst>
+                        </p:cBhvr>
+                        <p:tavLst>
+                            <p:tav tm="indefinite"><p:val><p:strVal val="#ppt_x"/></p:val><p14:unknownChild/></p:tav>
+                        </p:tavLst>
+                        <p:extLst><p:ext uri="{1}"/></p:extLst>
+                    </p:anim>
+                </p:childTnLst>
+            </p:cTn>
+        </p:par>"##;
+        let time_node = TimeNodeGroup::from_xml_element(&parse_part_xml(xml).unwrap()).unwrap();
+
+        let common_time_node_data = match &time_node {
+            TimeNodeGroup::Parallel(common_time_node_data) => common_time_node_data,
+            _ => panic!("expected a parallel time node"),
+        };
+        let child_time_node_list = common_time_node_data.child_time_node_list.as_ref().unwrap();
+        assert_eq!(child_time_node_list.0.len(), 1);
+        assert_eq!(child_time_node_list.1.child_nodes[0].1.name, "mc:AlternateContent");
+
+        let mut written_xml = Vec::new();
+        time_node.to_xml(&mut written_xml).unwrap();
+        let xml_node = parse_part_xml(std::str::from_utf8(&written_xml).unwrap()).unwrap();
+        let child_names = xml_node.child_nodes[0].child_nodes[0]
+            .child_nodes
+            .iter()
+            .map(|child_node| child_node.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(child_names, vec!["mc:AlternateContent", "p:anim"]);
+        assert_eq!(TimeNodeGroup::from_xml_element(&xml_node).unwrap(), time_node);
+    }
+
+    #[test]
+    pub fn test_build_round_trip() {
+        let builds = vec![
+            Build::Paragraph(Box::new(TLBuildParagraph::test_instance())),
+            Build::Diagram(Box::new(TLBuildDiagram::test_instance())),
+            Build::OleChart(Box::new(TLOleBuildChart::test_instance())),
+            Build::Graphic(Box::new(TLGraphicalObjectBuild {
+                build_choice: TLGraphicalObjectBuildChoice::BuildSubElements(
+                    AnimationGraphicalObjectBuildProperties::BuildChart,
+                ),
+                ..TLGraphicalObjectBuild::test_instance()
+            })),
+        ];
+
+        for build in builds {
+            let mut xml = Vec::new();
+            build.to_xml(&mut xml).unwrap();
+            let xml_node = XmlNode::from_str(std::str::from_utf8(&xml).unwrap()).unwrap();
+            assert_eq!(Build::from_xml_element(&xml_node).unwrap(), build);
+        }
+    }
+
     // impl TLAnimateBehavior {
     //     pub fn test_xml(node_name: &'static str) -> String {
     //         format!(r#"<{node_name} by="Example" from="Example" to="Example" calcmode="fmla" valueType="str">
@@ -11,10 +11,11 @@ use msoffice_shared::{
 };
 use std::{
     error::Error,
-    io::{Read, Seek},
-    str::FromStr,
+    io::{Read, Seek, Write},
 };
 
+use crate::xml::{parse_part_xml, write_xml, SchemaValue, ToXmlElement, UnknownXml, XmlNodeBuilder};
+
 pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
 
 /// This simple type specifies the allowed numbering for the slide identifier.
@@ -42,7 +43,7 @@ pub type SlideSizeCoordinate = PositiveCoordinate32;
 pub type Name = String;
 
 /// This simple type specifies the kind of slide size that the slide should be optimized for.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum SlideSizeType {
     /// Slide size should be optimized for 35mm film output
     #[strum(serialize = "mm35")]
@@ -96,7 +97,7 @@ pub enum SlideSizeType {
 
 /// This simple type specifies the values for photo layouts within a photo album presentation.
 /// See Fundamentals And Markup Language Reference for examples
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum PhotoAlbumLayout {
     /// Fit Photos to Slide
     #[strum(serialize = "fitToSlide")]
@@ -123,7 +124,7 @@ pub enum PhotoAlbumLayout {
 
 /// This simple type specifies the values for photo frame types within a photo album presentation.
 /// See Fundamentals And Markup Language Reference for examples
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum PhotoAlbumFrameShape {
     /// Rectangle Photo Frame
     #[strum(serialize = "frameStyle1")]
@@ -149,7 +150,7 @@ pub enum PhotoAlbumFrameShape {
 }
 
 /// This simple type determines if the Embedded object is re-colored to reflect changes to the color schemes.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum OleObjectFollowColorScheme {
     /// Setting this enumeration causes the Embedded object to not respond to changes in the color scheme in the
     /// presentation.
@@ -173,14 +174,14 @@ pub struct CustomerDataList {
     /// information, this tag mainly focuses on referencing to other parts of the presentation document. This is
     /// accomplished via the relationship identification attribute that is required for all specified tags.
     pub tags: Option<RelationshipId>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl CustomerDataList {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
+        xml_node.child_nodes.iter().enumerate().try_fold(
+            Default::default(),
+            |mut instance: Self, (position, child_node)| {
                 match child_node.local_name() {
                     "custData" => {
                         let id = child_node
@@ -198,11 +199,27 @@ impl CustomerDataList {
                             .clone();
                         instance.tags = Some(id);
                     }
-                    _ => (),
+                    _ => instance.unknown_xml.push_child(position, child_node),
                 }
 
                 Ok(instance)
-            })
+            },
+        )
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:custDataLst")
+            .with_children(
+                self.customer_data_list
+                    .iter()
+                    .map(|id| XmlNode::new("p:custData").with_attribute("r:id", id)),
+            )
+            .with_optional_child(
+                self.tags
+                    .as_ref()
+                    .map(|id| XmlNode::new("p:tags").with_attribute("r:id", id)),
+            )
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -229,6 +246,7 @@ pub struct SlideSize {
     /// Specifies the kind of slide size that should be used. This identifies in particular the
     /// expected delivery platform for this presentation.
     pub size_type: Option<SlideSizeType>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl SlideSize {
@@ -236,13 +254,14 @@ impl SlideSize {
         let mut width = None;
         let mut height = None;
         let mut size_type = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "cx" => width = Some(value.parse()?),
                 "cy" => height = Some(value.parse()?),
                 "type" => size_type = Some(value.parse()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -253,8 +272,17 @@ impl SlideSize {
             width,
             height,
             size_type,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:sldSz")
+            .with_attribute("cx", self.width)
+            .with_attribute("cy", self.height)
+            .with_optional_attribute("type", self.size_type)
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -265,26 +293,44 @@ pub struct SlideIdListEntry {
     /// relationship file to resolve the location within a presentation of the sld element defining
     /// this slide.
     pub relationship_id: RelationshipId,
+    pub unknown_xml: UnknownXml,
 }
 
 impl SlideIdListEntry {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut id = None;
         let mut relationship_id = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "id" => id = Some(value.parse()?),
                 "r:id" => relationship_id = Some(value.clone()),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
+        // The only child element of an id list entry is an extension list
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            unknown_xml.push_child(position, child_node);
+        }
+
         let id = id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "id"))?;
         let relationship_id =
             relationship_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:id"))?;
 
-        Ok(Self { id, relationship_id })
+        Ok(Self {
+            id,
+            relationship_id,
+            unknown_xml,
+        })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:sldId")
+            .with_attribute("id", self.id)
+            .with_attribute("r:id", &self.relationship_id)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -298,24 +344,42 @@ pub struct SlideLayoutIdListEntry {
     /// the relationship file for the master slide to expose the location of the corresponding
     /// layout file within the presentation.
     pub relationship_id: RelationshipId,
+    pub unknown_xml: UnknownXml,
 }
 
 impl SlideLayoutIdListEntry {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut id = None;
         let mut relationship_id = None;
+        let mut unknown_xml: UnknownXml = Default::default();
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "id" => id = Some(value.parse()?),
                 "r:id" => relationship_id = Some(value.clone()),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
+        // The only child element of an id list entry is an extension list
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            unknown_xml.push_child(position, child_node);
+        }
+
         let relationship_id =
             relationship_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:id"))?;
 
-        Ok(Self { id, relationship_id })
+        Ok(Self {
+            id,
+            relationship_id,
+            unknown_xml,
+        })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:sldLayoutId")
+            .with_optional_attribute("id", self.id)
+            .with_attribute("r:id", &self.relationship_id)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -333,6 +397,10 @@ impl SlideLayoutIdList {
 
         Ok(Self(id_list))
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:sldLayoutIdLst").with_children(self.0.iter().map(SlideLayoutIdListEntry::to_xml_element))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -344,25 +412,43 @@ pub struct SlideMasterIdListEntry {
     /// relationship file to resolve the location within a presentation of the sldMaster element
     /// defining this slide master.
     pub relationship_id: RelationshipId,
+    pub unknown_xml: UnknownXml,
 }
 
 impl SlideMasterIdListEntry {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut id = None;
         let mut relationship_id = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "id" => id = Some(value.parse()?),
                 "r:id" => relationship_id = Some(value.clone()),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
+        // The only child element of an id list entry is an extension list
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            unknown_xml.push_child(position, child_node);
+        }
+
         let relationship_id =
             relationship_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:id"))?;
 
-        Ok(Self { id, relationship_id })
+        Ok(Self {
+            id,
+            relationship_id,
+            unknown_xml,
+        })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:sldMasterId")
+            .with_optional_attribute("id", self.id)
+            .with_attribute("r:id", &self.relationship_id)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -384,6 +470,10 @@ impl NotesMasterIdListEntry {
 
         Ok(Self { relationship_id })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:notesMasterId").with_attribute("r:id", &self.relationship_id)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -404,6 +494,10 @@ impl HandoutMasterIdListEntry {
 
         Ok(Self { relationship_id })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:handoutMasterId").with_attribute("r:id", &self.relationship_id)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -511,6 +605,7 @@ pub struct EmbeddedFontListEntry {
     /// Not all characters for a typeface must be stored. It is up to the generating application to determine
     /// which characters are to be stored in the corresponding font data files.
     pub bold_italic: Option<RelationshipId>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl EmbeddedFontListEntry {
@@ -520,8 +615,9 @@ impl EmbeddedFontListEntry {
         let mut bold = None;
         let mut italic = None;
         let mut bold_italic = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "font" => font = Some(TextFont::from_xml_element(child_node)?),
                 "regular" => {
@@ -556,7 +652,7 @@ impl EmbeddedFontListEntry {
                         .clone();
                     bold_italic = Some(id);
                 }
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -568,8 +664,23 @@ impl EmbeddedFontListEntry {
             bold,
             italic,
             bold_italic,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        let font_style = |node_name: &str, id: Option<&RelationshipId>| {
+            id.map(|id| XmlNode::new(node_name).with_attribute("r:id", id))
+        };
+
+        XmlNode::new("p:embeddedFont")
+            .with_child(self.font.to_xml_element("p:font"))
+            .with_optional_child(font_style("p:regular", self.regular.as_ref()))
+            .with_optional_child(font_style("p:bold", self.bold.as_ref()))
+            .with_optional_child(font_style("p:italic", self.italic.as_ref()))
+            .with_optional_child(font_style("p:boldItalic", self.bold_italic.as_ref()))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -591,6 +702,10 @@ impl SlideRelationshipList {
 
         Ok(Self(relationship_ids))
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:sldLst").with_children(self.0.iter().map(|id| XmlNode::new("p:sld").with_attribute("r:id", id)))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -601,18 +716,26 @@ pub struct CustomShow {
     /// all the custom shows within the corresponding presentation.
     pub id: u32,
     pub slides: SlideRelationshipList,
+    pub unknown_xml: UnknownXml,
 }
 
 impl CustomShow {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<CustomShow> {
         let mut name = None;
         let mut id = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "name" => name = Some(value.clone()),
                 "id" => id = Some(value.parse::<u32>()?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            if child_node.local_name() != "sldLst" {
+                unknown_xml.push_child(position, child_node);
             }
         }
 
@@ -626,7 +749,20 @@ impl CustomShow {
             .ok_or_else(|| Box::<dyn Error>::from(MissingChildNodeError::new(xml_node.name.clone(), "sldLst")))
             .and_then(SlideRelationshipList::from_xml_element)?;
 
-        Ok(Self { name, id, slides })
+        Ok(Self {
+            name,
+            id,
+            slides,
+            unknown_xml,
+        })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:custShow")
+            .with_attribute("name", &self.name)
+            .with_attribute("id", self.id)
+            .with_child(self.slides.to_xml_element())
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -650,6 +786,7 @@ pub struct PhotoAlbum {
     ///
     /// Defaults to PhotoAlbumFrameShape::FrameStyle1
     pub frame: Option<PhotoAlbumFrameShape>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl PhotoAlbum {
@@ -663,11 +800,28 @@ impl PhotoAlbum {
                     "showCaptions" => instance.show_captions = Some(parse_xml_bool(value)?),
                     "layout" => instance.layout = Some(value.parse()?),
                     "frame" => instance.frame = Some(value.parse()?),
-                    _ => (),
+                    _ => instance.unknown_xml.push_attribute(attr, value),
                 }
 
                 Ok(instance)
             })
+            .map(|mut instance| {
+                // The only child element of a photo album is an extension list
+                for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+                    instance.unknown_xml.push_child(position, child_node);
+                }
+
+                instance
+            })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:photoAlbum")
+            .with_optional_attribute("bw", self.black_and_white)
+            .with_optional_attribute("showCaptions", self.show_captions)
+            .with_optional_attribute("layout", self.layout)
+            .with_optional_attribute("frame", self.frame)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -679,6 +833,7 @@ pub struct Kinsoku {
     pub invalid_start_chars: String,
     /// Specifies the characters that cannot end a line of text.
     pub invalid_end_chars: String,
+    pub unknown_xml: UnknownXml,
 }
 
 impl Kinsoku {
@@ -686,13 +841,14 @@ impl Kinsoku {
         let mut language = None;
         let mut invalid_start_chars = None;
         let mut invalid_end_chars = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "lang" => language = Some(value.clone()),
                 "invalStChars" => invalid_start_chars = Some(value.clone()),
                 "invalEndChars" => invalid_end_chars = Some(value.clone()),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -705,8 +861,17 @@ impl Kinsoku {
             language,
             invalid_start_chars,
             invalid_end_chars,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:kinsoku")
+            .with_optional_attribute("lang", self.language.as_ref())
+            .with_attribute("invalStChars", &self.invalid_start_chars)
+            .with_attribute("invalEndChars", &self.invalid_end_chars)
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -811,6 +976,7 @@ pub struct ModifyVerifier {
     /// one hundred thousand times to generate a hash value for comparison with the
     /// hashValue attribute.
     pub spin_value: Option<u32>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl ModifyVerifier {
@@ -823,12 +989,21 @@ impl ModifyVerifier {
                 "hashValue" => instance.hash_value = Some(value.clone()),
                 "saltValue" => instance.salt_value = Some(value.clone()),
                 "spinValue" => instance.spin_value = Some(value.parse()?),
-                _ => (),
+                _ => instance.unknown_xml.push_attribute(attr, value),
             }
         }
 
         Ok(instance)
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:modifyVerifier")
+            .with_optional_attribute("algorithmName", self.algorithm_name.as_ref())
+            .with_optional_attribute("hashValue", self.hash_value.as_ref())
+            .with_optional_attribute("saltValue", self.salt_value.as_ref())
+            .with_optional_attribute("spinValue", self.spin_value)
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 /// This element specifies within it fundamental presentation-wide properties.
@@ -1148,6 +1323,7 @@ pub struct Presentation {
     /// algorithm attributes and compare to the value of the hashValue attribute ( 9oN7nWkCAyEZib1RomSJTjmPpCY= ).
     /// If the two values matched, the file would be opened in an editable state.
     pub modify_verifier: Option<Box<ModifyVerifier>>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl Presentation {
@@ -1159,7 +1335,7 @@ impl Presentation {
         let mut xml_string = String::new();
         presentation_file.read_to_string(&mut xml_string)?;
 
-        let root = XmlNode::from_str(xml_string.as_str())?;
+        let root = parse_part_xml(xml_string.as_str())?;
         Self::from_xml_element(&root)
     }
 
@@ -1183,7 +1359,7 @@ impl Presentation {
                     "autoCompressPictures" => instance.auto_compress_pictures = Some(parse_xml_bool(value)?),
                     "bookmarkIdSeed" => instance.bookmark_id_seed = Some(value.parse()?),
                     "conformance" => instance.conformance = Some(value.parse()?),
-                    _ => (),
+                    _ => instance.unknown_xml.push_attribute(attr, value),
                 }
 
                 Ok(instance)
@@ -1192,7 +1368,8 @@ impl Presentation {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
                         match child_node.local_name() {
                             "sldMasterIdLst" => {
                                 instance.slide_master_id_list = child_node
@@ -1267,11 +1444,87 @@ impl Presentation {
                             "modifyVerifier" => {
                                 instance.modify_verifier = Some(Box::new(ModifyVerifier::from_xml_element(child_node)?))
                             }
-                            _ => (),
+                            _ => instance.unknown_xml.push_child(position, child_node),
                         }
 
                         Ok(instance)
                     })
             })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        // The master id lists are required by the schema even if they are empty, unlike the other lists
+        let slide_master_id_list = XmlNode::new("p:sldMasterIdLst").with_children(
+            self.slide_master_id_list
+                .iter()
+                .map(SlideMasterIdListEntry::to_xml_element),
+        );
+        let notes_master_id_list = self
+            .notes_master_id
+            .as_ref()
+            .map(|id| XmlNode::new("p:notesMasterIdLst").with_child(id.to_xml_element()));
+        let handout_master_id_list = self
+            .handout_master_id
+            .as_ref()
+            .map(|id| XmlNode::new("p:handoutMasterIdLst").with_child(id.to_xml_element()));
+        let slide_id_list = Some(&self.slide_id_list)
+            .filter(|list| !list.is_empty())
+            .map(|list| XmlNode::new("p:sldIdLst").with_children(list.iter().map(SlideIdListEntry::to_xml_element)));
+        let embedded_font_list = Some(&self.embedded_font_list)
+            .filter(|list| !list.is_empty())
+            .map(|list| {
+                XmlNode::new("p:embeddedFontLst").with_children(list.iter().map(EmbeddedFontListEntry::to_xml_element))
+            });
+        let custom_show_list = Some(&self.custom_show_list)
+            .filter(|list| !list.is_empty())
+            .map(|list| XmlNode::new("p:custShowLst").with_children(list.iter().map(CustomShow::to_xml_element)));
+
+        XmlNode::new("p:presentation")
+            .with_optional_attribute("serverZoom", self.server_zoom)
+            .with_optional_attribute("firstSlideNum", self.first_slide_num)
+            .with_optional_attribute(
+                "showSpecialPlsOnTitleSld",
+                self.show_special_placeholders_on_title_slide,
+            )
+            .with_optional_attribute("rtl", self.rtl)
+            .with_optional_attribute("removePersonalInfoOnSave", self.remove_personal_info_on_save)
+            .with_optional_attribute("compatMode", self.compatibility_mode)
+            .with_optional_attribute("strictFirstAndLastChars", self.strict_first_and_last_chars)
+            .with_optional_attribute("embedTrueTypeFonts", self.embed_true_type_fonts)
+            .with_optional_attribute("saveSubsetFonts", self.save_subset_fonts)
+            .with_optional_attribute("autoCompressPictures", self.auto_compress_pictures)
+            .with_optional_attribute("bookmarkIdSeed", self.bookmark_id_seed)
+            .with_optional_attribute(
+                "conformance",
+                self.conformance.map(|conformance| conformance.schema_value()),
+            )
+            .with_child(slide_master_id_list)
+            .with_optional_child(notes_master_id_list)
+            .with_optional_child(handout_master_id_list)
+            .with_optional_child(slide_id_list)
+            .with_optional_child(self.slide_size.as_ref().map(SlideSize::to_xml_element))
+            .with_optional_child(self.notes_size.as_ref().map(|size| size.to_xml_element("p:notesSz")))
+            .with_optional_child(
+                self.smart_tags
+                    .as_ref()
+                    .map(|id| XmlNode::new("p:smartTags").with_attribute("r:id", id)),
+            )
+            .with_optional_child(embedded_font_list)
+            .with_optional_child(custom_show_list)
+            .with_optional_child(self.photo_album.as_ref().map(PhotoAlbum::to_xml_element))
+            .with_optional_child(self.customer_data_list.as_ref().map(CustomerDataList::to_xml_element))
+            .with_optional_child(self.kinsoku.as_ref().map(|kinsoku| kinsoku.to_xml_element()))
+            .with_optional_child(
+                self.default_text_style
+                    .as_ref()
+                    .map(|style| style.to_xml_element("p:defaultTextStyle")),
+            )
+            .with_optional_child(self.modify_verifier.as_ref().map(|verifier| verifier.to_xml_element()))
+            .with_unknown_xml(&self.unknown_xml)
+    }
+
+    /// Writes the presentation as a standalone xml document, like the content of the ppt/presentation.xml part.
+    pub fn to_xml<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_xml(&self.to_xml_element(), writer)
+    }
 }
@@ -1,3 +1,4 @@
+use log::warn;
 use msoffice_shared::{
     drawingml::{
         audiovideo::{EmbeddedWAVAudioFile, Media},
@@ -19,18 +20,28 @@ use msoffice_shared::{
     xml::{parse_xml_bool, XmlNode},
     xsdtypes::{XsdChoice, XsdType},
 };
-use std::{error::Error, io::Read, str::FromStr};
+use std::{
+    error::Error,
+    io::{Read, Write},
+};
 use zip::read::ZipFile;
 
 use super::{
     animation::{Build, TimeNodeGroup},
     presentation::{CustomerDataList, SlideLayoutIdList},
 };
+use crate::{
+    drawingml::{
+        diagram::{DiagramRelationshipIds, DIAGRAM_GRAPHIC_DATA_URI},
+        table::{Table, TABLE_GRAPHIC_DATA_URI},
+    },
+    xml::{parse_part_xml, write_xml, SchemaValue, ToXmlChoice, ToXmlElement, UnknownXml, XmlNodeBuilder},
+};
 
 pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
 
 /// This simple type facilitates the storing of the content type a placeholder should contain.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, AsRefStr, Display)]
 pub enum PlaceholderType {
     /// Contains a slide title. Allowed for Slide, Slide Layout and Slide Master. Can be horizontal or vertical on Slide
     /// and Slide Layout.
@@ -87,7 +98,7 @@ pub enum PlaceholderType {
 }
 
 /// This simple type defines a direction of either horizontal or vertical.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum Direction {
     /// Defines a horizontal direction.
     #[strum(serialize = "horz")]
@@ -99,7 +110,7 @@ pub enum Direction {
 
 /// This simple type facilitates the storing of the size of the placeholder. This size is described relative to the body
 /// placeholder on the master.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum PlaceholderSize {
     /// Specifies that the placeholder should take the full size of the body placeholder on the master.
     #[strum(serialize = "full")]
@@ -115,7 +126,7 @@ pub enum PlaceholderSize {
 }
 
 /// This simple type defines a set of slide transition directions.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TransitionSideDirectionType {
     /// Specifies that the transition direction is left
     #[strum(serialize = "l")]
@@ -132,7 +143,7 @@ pub enum TransitionSideDirectionType {
 }
 
 /// This simple type specifies diagonal directions for slide transitions.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TransitionCornerDirectionType {
     /// Specifies the slide transition direction of left-up
     #[strum(serialize = "lu")]
@@ -149,7 +160,7 @@ pub enum TransitionCornerDirectionType {
 }
 
 /// This simple type specifies the direction of an animation.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TransitionEightDirectionType {
     /// Specifies that the transition direction is left
     #[strum(serialize = "l")]
@@ -178,7 +189,7 @@ pub enum TransitionEightDirectionType {
 }
 
 /// This simple type specifies if a slide transition should go in or out.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TransitionInOutDirectionType {
     /// Specifies the slide transition should go in
     #[strum(serialize = "in")]
@@ -189,7 +200,7 @@ pub enum TransitionInOutDirectionType {
 }
 
 /// This simple type defines the allowed transition speeds for transitioning from the current slide to the next.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum TransitionSpeed {
     /// Slow slide transition.
     #[strum(serialize = "slow")]
@@ -210,7 +221,7 @@ pub enum TransitionSpeed {
 /// Each layout contains zero or more placeholders, each with a specific content type. An "object" placeholder can
 /// contain any kind of data. Media placeholders are intended to hold video or audio clips. The enumeration value
 /// descriptions include illustrations of sample layouts for each value of the simple type.
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
 pub enum SlideLayoutType {
     /// Blank
     #[strum(serialize = "blank")]
@@ -378,6 +389,7 @@ pub struct SlideMaster {
     /// text, the body text and other slide text as well. This element is only for use within the Slide Master and thus sets
     /// the text styles for the corresponding presentation slides.
     pub text_styles: Option<SlideMasterTextStyles>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl SlideMaster {
@@ -385,11 +397,19 @@ impl SlideMaster {
         let mut xml_string = String::new();
         zip_file.read_to_string(&mut xml_string)?;
 
-        Self::from_xml_element(&XmlNode::from_str(xml_string.as_str())?)
+        Self::from_xml_element(&parse_part_xml(xml_string.as_str())?)
     }
 
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let preserve = xml_node.attributes.get("preserve").map(parse_xml_bool).transpose()?;
+        let mut preserve = None;
+        let mut unknown_xml: UnknownXml = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "preserve" => preserve = Some(parse_xml_bool(value)?),
+                _ => unknown_xml.push_attribute(attr, value),
+            }
+        }
 
         let mut common_slide_data = None;
         let mut color_mapping = None;
@@ -399,16 +419,22 @@ impl SlideMaster {
         let mut header_footer = None;
         let mut text_styles = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cSld" => common_slide_data = Some(Box::new(CommonSlideData::from_xml_element(child_node)?)),
-                "clrMap" => color_mapping = Some(Box::new(ColorMapping::from_xml_element(child_node)?)),
+                "clrMap" => {
+                    color_mapping = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        ColorMapping::from_xml_element,
+                    )?))
+                }
                 "sldLayoutIdLst" => slide_layout_id_list = Some(SlideLayoutIdList::from_xml_element(child_node)?),
                 "transition" => transition = Some(Box::new(SlideTransition::from_xml_element(child_node)?)),
                 "timing" => timing = Some(SlideTiming::from_xml_element(child_node)?),
                 "hf" => header_footer = Some(HeaderFooter::from_xml_element(child_node)?),
                 "txStyles" => text_styles = Some(SlideMasterTextStyles::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -425,8 +451,35 @@ impl SlideMaster {
             header_footer,
             text_styles,
             preserve,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:sldMaster")
+            .with_optional_attribute("preserve", self.preserve)
+            .with_child(self.common_slide_data.to_xml_element())
+            .with_child(self.color_mapping.to_xml_element("p:clrMap"))
+            .with_optional_child(
+                self.slide_layout_id_list
+                    .as_ref()
+                    .map(SlideLayoutIdList::to_xml_element),
+            )
+            .with_optional_child(self.transition.as_ref().map(|transition| transition.to_xml_element()))
+            .with_optional_child(self.timing.as_ref().map(SlideTiming::to_xml_element))
+            .with_optional_child(self.header_footer.as_ref().map(HeaderFooter::to_xml_element))
+            .with_optional_child(self.text_styles.as_ref().map(SlideMasterTextStyles::to_xml_element))
+            .with_unknown_xml(&self.unknown_xml)
+    }
+
+    /// Writes the slide master as a standalone xml document, like the content of a slide master part.
+    pub fn to_xml<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_xml(&self.to_xml_element(), writer)
+    }
+}
+
+fn color_mapping_override_element(color_mapping_override: &ColorMappingOverride) -> XmlNode {
+    XmlNode::new("p:clrMapOvr").with_child(color_mapping_override.to_xml_element())
 }
 
 /// This element specifies an instance of a slide layout. The slide layout contains in essence a template slide design
@@ -473,6 +526,7 @@ pub struct SlideLayout {
     /// the PresentationML framework.
     pub timing: Option<SlideTiming>,
     pub header_footer: Option<HeaderFooter>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl SlideLayout {
@@ -480,7 +534,7 @@ impl SlideLayout {
         let mut xml_string = String::new();
         zip_file.read_to_string(&mut xml_string)?;
 
-        Self::from_xml_element(&XmlNode::from_str(xml_string.as_str())?)
+        Self::from_xml_element(&parse_part_xml(xml_string.as_str())?)
     }
 
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
@@ -490,6 +544,7 @@ impl SlideLayout {
         let mut is_user_drawn = None;
         let mut show_master_shapes = None;
         let mut show_master_placeholder_animations = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
@@ -499,7 +554,7 @@ impl SlideLayout {
                 "userDrawn" => is_user_drawn = Some(parse_xml_bool(value)?),
                 "showMasterSp" => show_master_shapes = Some(parse_xml_bool(value)?),
                 "showMasterPhAnim" => show_master_placeholder_animations = Some(parse_xml_bool(value)?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -509,7 +564,7 @@ impl SlideLayout {
         let mut timing = None;
         let mut header_footer = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cSld" => common_slide_data = Some(Box::new(CommonSlideData::from_xml_element(child_node)?)),
                 "clrMapOvr" => {
@@ -530,7 +585,7 @@ impl SlideLayout {
                 "transition" => transition = Some(Box::new(SlideTransition::from_xml_element(child_node)?)),
                 "timing" => timing = Some(SlideTiming::from_xml_element(child_node)?),
                 "hf" => header_footer = Some(HeaderFooter::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -549,8 +604,30 @@ impl SlideLayout {
             transition,
             timing,
             header_footer,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:sldLayout")
+            .with_optional_attribute("matchingName", self.matching_name.as_ref())
+            .with_optional_attribute("type", self.slide_layout_type)
+            .with_optional_attribute("preserve", self.preserve)
+            .with_optional_attribute("userDrawn", self.is_user_drawn)
+            .with_optional_attribute("showMasterSp", self.show_master_shapes)
+            .with_optional_attribute("showMasterPhAnim", self.show_master_placeholder_animations)
+            .with_child(self.common_slide_data.to_xml_element())
+            .with_optional_child(self.color_mapping_override.as_ref().map(color_mapping_override_element))
+            .with_optional_child(self.transition.as_ref().map(|transition| transition.to_xml_element()))
+            .with_optional_child(self.timing.as_ref().map(SlideTiming::to_xml_element))
+            .with_optional_child(self.header_footer.as_ref().map(HeaderFooter::to_xml_element))
+            .with_unknown_xml(&self.unknown_xml)
+    }
+
+    /// Writes the slide layout as a standalone xml document, like the content of a slide layout part.
+    pub fn to_xml<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_xml(&self.to_xml_element(), writer)
+    }
 }
 
 /// This element specifies a slide within a slide list. The slide list is used to specify an ordering of slides.
@@ -601,6 +678,7 @@ pub struct Slide {
     /// the specifics of these time nodes and how they are to be defined can be found within the Animation section of
     /// the PresentationML framework.
     pub timing: Option<SlideTiming>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl Slide {
@@ -608,20 +686,21 @@ impl Slide {
         let mut xml_string = String::new();
         zip_file.read_to_string(&mut xml_string)?;
 
-        Self::from_xml_element(&XmlNode::from_str(xml_string.as_str())?)
+        Self::from_xml_element(&parse_part_xml(xml_string.as_str())?)
     }
 
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut show = None;
         let mut show_master_shapes = None;
         let mut show_master_placeholder_animations = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
         for (attr, value) in &xml_node.attributes {
             match attr.as_str() {
                 "show" => show = Some(parse_xml_bool(value)?),
                 "showMasterSp" => show_master_shapes = Some(parse_xml_bool(value)?),
                 "showMasterPhAnim" => show_master_placeholder_animations = Some(parse_xml_bool(value)?),
-                _ => (),
+                _ => unknown_xml.push_attribute(attr, value),
             }
         }
 
@@ -630,7 +709,7 @@ impl Slide {
         let mut transition = None;
         let mut timing = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "cSld" => common_slide_data = Some(Box::new(CommonSlideData::from_xml_element(child_node)?)),
                 "clrMapOvr" => {
@@ -650,7 +729,7 @@ impl Slide {
                 }
                 "transition" => transition = Some(Box::new(SlideTransition::from_xml_element(child_node)?)),
                 "timing" => timing = Some(SlideTiming::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -665,8 +744,133 @@ impl Slide {
             color_mapping_override,
             transition,
             timing,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:sld")
+            .with_optional_attribute("show", self.show)
+            .with_optional_attribute("showMasterSp", self.show_master_shapes)
+            .with_optional_attribute("showMasterPhAnim", self.show_master_placeholder_animations)
+            .with_child(self.common_slide_data.to_xml_element())
+            .with_optional_child(self.color_mapping_override.as_ref().map(color_mapping_override_element))
+            .with_optional_child(self.transition.as_ref().map(|transition| transition.to_xml_element()))
+            .with_optional_child(self.timing.as_ref().map(SlideTiming::to_xml_element))
+            .with_unknown_xml(&self.unknown_xml)
+    }
+
+    /// Writes the slide as a standalone xml document, like the content of a slide part.
+    pub fn to_xml<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_xml(&self.to_xml_element(), writer)
+    }
+}
+
+/// This element specifies the existence of a notes slide along with its corresponding data. Contained within a notes
+/// slide are all the common slide elements along with additional properties that are specific to the notes element.
+///
+/// # Xml example
+///
+/// ```xml
+/// <p:notes>
+///   <p:cSld>
+///     ...
+///   </p:cSld>
+///   <p:clrMapOvr>
+///     <a:masterClrMapping/>
+///   </p:clrMapOvr>
+/// </p:notes>
+/// ```
+///
+/// In the above example a notes slide is specified that uses the color mapping of the notes master.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotesSlide {
+    /// Specifies if shapes on the master slide should be shown on slides or not.
+    ///
+    /// Defaults to true
+    pub show_master_shapes: Option<bool>,
+    /// Specifies whether or not to display animations on placeholders from the master slide.
+    ///
+    /// Defaults to true
+    pub show_master_placeholder_animations: Option<bool>,
+    pub common_slide_data: Box<CommonSlideData>,
+    /// This element provides a mechanism with which to override the color schemes listed within the
+    /// NotesMaster::color_mapping element.
+    pub color_mapping_override: Option<ColorMappingOverride>,
+    pub unknown_xml: UnknownXml,
+}
+
+impl NotesSlide {
+    pub fn from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Self> {
+        let mut xml_string = String::new();
+        zip_file.read_to_string(&mut xml_string)?;
+
+        Self::from_xml_element(&parse_part_xml(xml_string.as_str())?)
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut show_master_shapes = None;
+        let mut show_master_placeholder_animations = None;
+        let mut unknown_xml: UnknownXml = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "showMasterSp" => show_master_shapes = Some(parse_xml_bool(value)?),
+                "showMasterPhAnim" => show_master_placeholder_animations = Some(parse_xml_bool(value)?),
+                _ => unknown_xml.push_attribute(attr, value),
+            }
+        }
+
+        let mut common_slide_data = None;
+        let mut color_mapping_override = None;
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            match child_node.local_name() {
+                "cSld" => common_slide_data = Some(Box::new(CommonSlideData::from_xml_element(child_node)?)),
+                "clrMapOvr" => {
+                    color_mapping_override = Some(
+                        child_node
+                            .child_nodes
+                            .iter()
+                            .find_map(ColorMappingOverride::try_from_xml_element)
+                            .transpose()?
+                            .ok_or_else(|| {
+                                MissingChildNodeError::new(
+                                    child_node.name.clone(),
+                                    "masterClrMapping|overrideClrMapping",
+                                )
+                            })?,
+                    );
+                }
+                _ => unknown_xml.push_child(position, child_node),
+            }
+        }
+
+        let common_slide_data =
+            common_slide_data.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "cSld"))?;
+
+        Ok(Self {
+            show_master_shapes,
+            show_master_placeholder_animations,
+            common_slide_data,
+            color_mapping_override,
+            unknown_xml,
+        })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:notes")
+            .with_optional_attribute("showMasterSp", self.show_master_shapes)
+            .with_optional_attribute("showMasterPhAnim", self.show_master_placeholder_animations)
+            .with_child(self.common_slide_data.to_xml_element())
+            .with_optional_child(self.color_mapping_override.as_ref().map(color_mapping_override_element))
+            .with_unknown_xml(&self.unknown_xml)
+    }
+
+    /// Writes the notes slide as a standalone xml document, like the content of a notes slide part.
+    pub fn to_xml<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_xml(&self.to_xml_element(), writer)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -679,24 +883,31 @@ pub struct BackgroundProperties {
     pub shade_to_title: Option<bool>,
     pub fill: FillProperties,
     pub effect: Option<EffectProperties>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl BackgroundProperties {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let shade_to_title = xml_node
-            .attributes
-            .get("shadeToTitle")
-            .map(parse_xml_bool)
-            .transpose()?;
+        let mut shade_to_title = None;
+        let mut unknown_xml: UnknownXml = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "shadeToTitle" => shade_to_title = Some(parse_xml_bool(value)?),
+                _ => unknown_xml.push_attribute(attr, value),
+            }
+        }
 
         let mut fill = None;
         let mut effect = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             if FillProperties::is_choice_member(child_node.local_name()) {
                 fill = Some(FillProperties::from_xml_element(child_node)?);
             } else if EffectProperties::is_choice_member(child_node.local_name()) {
                 effect = Some(EffectProperties::from_xml_element(child_node)?);
+            } else {
+                unknown_xml.push_child(position, child_node);
             }
         }
 
@@ -706,8 +917,17 @@ impl BackgroundProperties {
             shade_to_title,
             fill,
             effect,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:bgPr")
+            .with_optional_attribute("shadeToTitle", self.shade_to_title)
+            .with_child(self.fill.to_xml_element())
+            .with_optional_child(self.effect.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -768,6 +988,15 @@ impl XsdChoice for BackgroundGroup {
     }
 }
 
+impl ToXmlChoice for BackgroundGroup {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            BackgroundGroup::Properties(properties) => properties.to_xml_element(),
+            BackgroundGroup::Reference(reference) => reference.to_xml_element("p:bgRef"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Background {
     /// Specifies that the background should be rendered using only black and white coloring.
@@ -780,6 +1009,7 @@ pub struct Background {
     /// white.
     pub black_and_white_mode: Option<BlackWhiteMode>, // white
     pub background: BackgroundGroup,
+    pub unknown_xml: UnknownXml,
 }
 
 impl Background {
@@ -792,12 +1022,23 @@ impl Background {
             .find_map(BackgroundGroup::try_from_xml_element)
             .transpose()?
             .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_Background"))?;
+        let unknown_xml = UnknownXml::from_xml_element(xml_node, &["bwMode"], |local_name| {
+            BackgroundGroup::is_choice_member(local_name)
+        });
 
         Ok(Self {
             background,
             black_and_white_mode,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:bg")
+            .with_optional_attribute("bwMode", self.black_and_white_mode.map(|mode| mode.schema_value()))
+            .with_child(self.background.to_xml_element())
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -813,25 +1054,43 @@ pub struct Placeholder {
     pub index: Option<u32>,
     /// Specifies whether the corresponding placeholder should have a custom prompt or not.
     pub has_custom_prompt: Option<bool>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl Placeholder {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .attributes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
+        let mut instance = xml_node.attributes.iter().try_fold(
+            Default::default(),
+            |mut instance: Self, (attr, value)| -> Result<Self> {
                 match attr.as_ref() {
                     "type" => instance.placeholder_type = Some(value.parse()?),
                     "orient" => instance.orientation = Some(value.parse()?),
                     "sz" => instance.size = Some(value.parse()?),
                     "idx" => instance.index = Some(value.parse()?),
                     "hasCustomPrompt" => instance.has_custom_prompt = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    _ => instance.unknown_xml.push_attribute(attr, value),
                 }
 
                 Ok(instance)
-            })
+            },
+        )?;
+
+        // The only child element of a placeholder is an extension list
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            instance.unknown_xml.push_child(position, child_node);
+        }
+
+        Ok(instance)
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:ph")
+            .with_optional_attribute("type", self.placeholder_type)
+            .with_optional_attribute("orient", self.orientation)
+            .with_optional_attribute("sz", self.size)
+            .with_optional_attribute("idx", self.index)
+            .with_optional_attribute("hasCustomPrompt", self.has_custom_prompt)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -852,6 +1111,7 @@ pub struct ApplicationNonVisualDrawingProps {
     pub placeholder: Option<Placeholder>,
     pub media: Option<Media>,
     pub customer_data_list: Option<CustomerDataList>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl ApplicationNonVisualDrawingProps {
@@ -863,7 +1123,7 @@ impl ApplicationNonVisualDrawingProps {
                 match attr.as_ref() {
                     "isPhoto" => instance.is_photo = Some(parse_xml_bool(value)?),
                     "userDrawn" => instance.is_user_drawn = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    _ => instance.unknown_xml.push_attribute(attr, value),
                 }
 
                 Ok(instance)
@@ -872,7 +1132,8 @@ impl ApplicationNonVisualDrawingProps {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
                         match child_node.local_name() {
                             "ph" => instance.placeholder = Some(Placeholder::from_xml_element(child_node)?),
                             "custDataLst" => {
@@ -881,13 +1142,23 @@ impl ApplicationNonVisualDrawingProps {
                             local_name if Media::is_choice_member(local_name) => {
                                 instance.media = Some(Media::from_xml_element(child_node)?)
                             }
-                            _ => (),
+                            _ => instance.unknown_xml.push_child(position, child_node),
                         }
 
                         Ok(instance)
                     })
             })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:nvPr")
+            .with_optional_attribute("isPhoto", self.is_photo)
+            .with_optional_attribute("userDrawn", self.is_user_drawn)
+            .with_optional_child(self.placeholder.as_ref().map(Placeholder::to_xml_element))
+            .with_optional_child(self.media.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(self.customer_data_list.as_ref().map(CustomerDataList::to_xml_element))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1066,6 +1337,19 @@ impl XsdChoice for ShapeGroup {
     }
 }
 
+impl ToXmlChoice for ShapeGroup {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            ShapeGroup::Shape(shape) => shape.to_xml_element(),
+            ShapeGroup::GroupShape(group_shape) => group_shape.to_xml_element("p:grpSp"),
+            ShapeGroup::GraphicFrame(graphic_frame) => graphic_frame.to_xml_element(),
+            ShapeGroup::Connector(connector) => connector.to_xml_element(),
+            ShapeGroup::Picture(picture) => picture.to_xml_element(),
+            ShapeGroup::ContentPart(rel_id) => XmlNode::new("p:contentPart").with_attribute("r:id", rel_id),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Shape {
     /// Specifies that the shape fill should be set to that of the slide background surface.
@@ -1112,24 +1396,47 @@ pub struct Shape {
     /// visible text related properties are contained within this element. There can be multiple paragraphs and within
     /// paragraphs multiple runs of text.
     pub text_body: Option<TextBody>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl Shape {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let use_bg_fill = xml_node.attributes.get("useBgFill").map(parse_xml_bool).transpose()?;
+        let mut use_bg_fill = None;
+        let mut unknown_xml: UnknownXml = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "useBgFill" => use_bg_fill = Some(parse_xml_bool(value)?),
+                _ => unknown_xml.push_attribute(attr, value),
+            }
+        }
 
         let mut non_visual_props = None;
         let mut shape_props = None;
         let mut shape_style = None;
         let mut text_body = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "nvSpPr" => non_visual_props = Some(Box::new(ShapeNonVisual::from_xml_element(child_node)?)),
-                "spPr" => shape_props = Some(Box::new(ShapeProperties::from_xml_element(child_node)?)),
-                "style" => shape_style = Some(Box::new(ShapeStyle::from_xml_element(child_node)?)),
-                "txBody" => text_body = Some(TextBody::from_xml_element(child_node)?),
-                _ => (),
+                "spPr" => {
+                    shape_props = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        ShapeProperties::from_xml_element,
+                    )?))
+                }
+                "style" => {
+                    shape_style = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        ShapeStyle::from_xml_element,
+                    )?))
+                }
+                "txBody" => {
+                    text_body = Some(unknown_xml.parse_known_child(xml_node, position, TextBody::from_xml_element)?)
+                }
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1143,8 +1450,23 @@ impl Shape {
             shape_props,
             shape_style,
             text_body,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:sp")
+            .with_optional_attribute("useBgFill", self.use_bg_fill)
+            .with_child(self.non_visual_props.to_xml_element())
+            .with_child(self.shape_props.to_xml_element("p:spPr"))
+            .with_optional_child(self.shape_style.as_ref().map(|style| style.to_xml_element("p:style")))
+            .with_optional_child(
+                self.text_body
+                    .as_ref()
+                    .map(|text_body| text_body.to_xml_element("p:txBody")),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1170,6 +1492,7 @@ pub struct ShapeNonVisual {
     /// This shape lock is stored within the non-visual drawing properties for this shape.
     pub shape_drawing_props: NonVisualDrawingShapeProps,
     pub app_props: ApplicationNonVisualDrawingProps,
+    pub unknown_xml: UnknownXml,
 }
 
 impl ShapeNonVisual {
@@ -1177,13 +1500,26 @@ impl ShapeNonVisual {
         let mut drawing_props = None;
         let mut shape_drawing_props = None;
         let mut app_props = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
-                "cNvPr" => drawing_props = Some(Box::new(NonVisualDrawingProps::from_xml_element(child_node)?)),
-                "cNvSpPr" => shape_drawing_props = Some(NonVisualDrawingShapeProps::from_xml_element(child_node)?),
+                "cNvPr" => {
+                    drawing_props = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        NonVisualDrawingProps::from_xml_element,
+                    )?))
+                }
+                "cNvSpPr" => {
+                    shape_drawing_props = Some(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        NonVisualDrawingShapeProps::from_xml_element,
+                    )?)
+                }
                 "nvPr" => app_props = Some(ApplicationNonVisualDrawingProps::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1196,8 +1532,17 @@ impl ShapeNonVisual {
             drawing_props,
             shape_drawing_props,
             app_props,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:nvSpPr")
+            .with_child(self.drawing_props.to_xml_element("p:cNvPr"))
+            .with_child(self.shape_drawing_props.to_xml_element("p:cNvSpPr"))
+            .with_child(self.app_props.to_xml_element())
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1212,6 +1557,7 @@ pub struct GroupShape {
     /// properties then the individual shape properties should take precedence.
     pub group_shape_props: GroupShapeProperties,
     pub shape_array: Vec<ShapeGroup>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl GroupShape {
@@ -1219,15 +1565,22 @@ impl GroupShape {
         let mut non_visual_props = None;
         let mut group_shape_props = None;
         let mut shape_array = Vec::new();
+        let mut unknown_xml: UnknownXml = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "nvGrpSpPr" => non_visual_props = Some(Box::new(GroupShapeNonVisual::from_xml_element(child_node)?)),
-                "grpSpPr" => group_shape_props = Some(GroupShapeProperties::from_xml_element(child_node)?),
+                "grpSpPr" => {
+                    group_shape_props = Some(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        GroupShapeProperties::from_xml_element,
+                    )?)
+                }
                 local_name if ShapeGroup::is_choice_member(local_name) => {
                     shape_array.push(ShapeGroup::from_xml_element(child_node)?)
                 }
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1240,8 +1593,17 @@ impl GroupShape {
             non_visual_props,
             group_shape_props,
             shape_array,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_child(self.non_visual_props.to_xml_element())
+            .with_child(self.group_shape_props.to_xml_element("p:grpSpPr"))
+            .with_children(self.shape_array.iter().map(ToXmlChoice::to_xml_element))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1251,6 +1613,7 @@ pub struct GroupShapeNonVisual {
     /// properties that the generating application would utilize when rendering the slide surface.
     pub group_drawing_props: NonVisualGroupDrawingShapeProps,
     pub app_props: ApplicationNonVisualDrawingProps,
+    pub unknown_xml: UnknownXml,
 }
 
 impl GroupShapeNonVisual {
@@ -1258,15 +1621,26 @@ impl GroupShapeNonVisual {
         let mut drawing_props = None;
         let mut group_drawing_props = None;
         let mut app_props = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
-                "cNvPr" => drawing_props = Some(Box::new(NonVisualDrawingProps::from_xml_element(child_node)?)),
+                "cNvPr" => {
+                    drawing_props = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        NonVisualDrawingProps::from_xml_element,
+                    )?))
+                }
                 "cNvGrpSpPr" => {
-                    group_drawing_props = Some(NonVisualGroupDrawingShapeProps::from_xml_element(child_node)?)
+                    group_drawing_props = Some(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        NonVisualGroupDrawingShapeProps::from_xml_element,
+                    )?)
                 }
                 "nvPr" => app_props = Some(ApplicationNonVisualDrawingProps::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1279,8 +1653,17 @@ impl GroupShapeNonVisual {
             drawing_props,
             group_drawing_props,
             app_props,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:nvGrpSpPr")
+            .with_child(self.drawing_props.to_xml_element("p:cNvPr"))
+            .with_child(self.group_drawing_props.to_xml_element("p:cNvGrpSpPr"))
+            .with_child(self.app_props.to_xml_element())
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1303,28 +1686,78 @@ pub struct GraphicalObjectFrame {
     /// applied to the graphic frame just as it would be for a shape or group shape.
     pub transform: Box<Transform2D>,
     pub graphic: GraphicalObject,
+    /// The content of the graphic, if it's a kind of graphical object that is understood by this crate and it can be
+    /// parsed.
+    pub graphic_content: Option<GraphicalObjectContent>,
+    /// The content of the graphicData element that isn't represented by graphic_content, like the reference of a
+    /// chart or an embedded ole object.
+    pub unknown_graphic_data: UnknownXml,
+    pub unknown_xml: UnknownXml,
 }
 
 impl GraphicalObjectFrame {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let black_white_mode = xml_node
-            .attributes
-            .get("bwMode")
-            .map(|value| value.parse())
-            .transpose()?;
+        let mut black_white_mode = None;
+        let mut unknown_xml: UnknownXml = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "bwMode" => black_white_mode = Some(value.parse()?),
+                _ => unknown_xml.push_attribute(attr, value),
+            }
+        }
 
         let mut non_visual_props = None;
         let mut transform = None;
         let mut graphic = None;
+        let mut graphic_content = None;
+        let mut unknown_graphic_data: UnknownXml = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "nvGraphicFramePr" => {
                     non_visual_props = Some(Box::new(GraphicalObjectFrameNonVisual::from_xml_element(child_node)?))
                 }
-                "xfrm" => transform = Some(Box::new(Transform2D::from_xml_element(child_node)?)),
-                "graphic" => graphic = Some(GraphicalObject::from_xml_element(child_node)?),
-                _ => (),
+                "xfrm" => {
+                    transform = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        Transform2D::from_xml_element,
+                    )?))
+                }
+                "graphic" => {
+                    graphic = Some(GraphicalObject::from_xml_element(child_node)?);
+                    // Content that can't be parsed, like a table without a grid, is kept as unknown xml instead
+                    graphic_content = GraphicalObjectContent::from_graphic_element(child_node).unwrap_or_else(|err| {
+                        warn!("failed to parse the content of a graphic frame: {}", err);
+                        None
+                    });
+
+                    let graphic_data_node = child_node
+                        .child_nodes
+                        .iter()
+                        .find(|graphic_child_node| graphic_child_node.local_name() == "graphicData");
+                    if let Some(graphic_data_node) = graphic_data_node {
+                        let content_name = graphic_content.as_ref().map(GraphicalObjectContent::local_name);
+                        unknown_graphic_data =
+                            UnknownXml::from_xml_element(graphic_data_node, &["uri"], |local_name| {
+                                Some(local_name) == content_name
+                            });
+
+                        let content_position = graphic_data_node
+                            .child_nodes
+                            .iter()
+                            .position(|content_node| Some(content_node.local_name()) == content_name);
+                        if let (Some(content), Some(content_position)) = (&graphic_content, content_position) {
+                            unknown_graphic_data.push_known_child(
+                                graphic_data_node,
+                                content_position,
+                                &content.to_xml_element(),
+                            );
+                        }
+                    }
+                }
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1338,8 +1771,89 @@ impl GraphicalObjectFrame {
             non_visual_props,
             transform,
             graphic,
+            graphic_content,
+            unknown_graphic_data,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        let graphic_data = XmlNode::new("a:graphicData")
+            .with_attribute("uri", &self.graphic.graphic_data.uri)
+            .with_optional_child(
+                self.graphic_content
+                    .as_ref()
+                    .map(GraphicalObjectContent::to_xml_element),
+            )
+            .with_unknown_xml(&self.unknown_graphic_data);
+
+        XmlNode::new("p:graphicFrame")
+            .with_optional_attribute("bwMode", self.black_white_mode.map(|mode| mode.schema_value()))
+            .with_child(self.non_visual_props.to_xml_element())
+            .with_child(self.transform.to_xml_element("p:xfrm"))
+            .with_child(XmlNode::new("a:graphic").with_child(graphic_data))
+            .with_unknown_xml(&self.unknown_xml)
+    }
+}
+
+/// The content of a graphic frame's graphicData element, parsed according to the uri of the graphicData element.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphicalObjectContent {
+    /// The graphic frame contains a table.
+    Table(Box<Table>),
+    /// The graphic frame contains a diagram (SmartArt). The content of the diagram is stored in separate parts that are
+    /// referenced by the relationship ids.
+    Diagram(DiagramRelationshipIds),
+}
+
+impl GraphicalObjectContent {
+    /// Parses the content of the graphicData child of the given graphic element. Returns None if the graphic element
+    /// has no graphicData or if the kind of the graphical object is not supported.
+    pub fn from_graphic_element(xml_node: &XmlNode) -> Result<Option<Self>> {
+        let graphic_data_node = match xml_node
+            .child_nodes
+            .iter()
+            .find(|child_node| child_node.local_name() == "graphicData")
+        {
+            Some(node) => node,
+            None => return Ok(None),
+        };
+
+        let uri = graphic_data_node.attributes.get("uri").map(String::as_str);
+        for child_node in &graphic_data_node.child_nodes {
+            match (uri, child_node.local_name()) {
+                (Some(TABLE_GRAPHIC_DATA_URI), "tbl") => {
+                    return Ok(Some(GraphicalObjectContent::Table(Box::new(Table::from_xml_element(
+                        child_node,
+                    )?))))
+                }
+                (Some(DIAGRAM_GRAPHIC_DATA_URI), "relIds") => {
+                    return Ok(Some(GraphicalObjectContent::Diagram(
+                        DiagramRelationshipIds::from_xml_element(child_node)?,
+                    )))
+                }
+                _ => (),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the local name of the child element of the graphicData element that contains this content.
+    pub fn local_name(&self) -> &'static str {
+        match self {
+            GraphicalObjectContent::Table(_) => "tbl",
+            GraphicalObjectContent::Diagram(_) => "relIds",
+        }
+    }
+
+    /// Returns the child element of the graphicData element that contains this content.
+    pub fn to_xml_element(&self) -> XmlNode {
+        match self {
+            GraphicalObjectContent::Table(table) => table.to_xml_element(),
+            GraphicalObjectContent::Diagram(relationship_ids) => relationship_ids.to_xml_element(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1349,6 +1863,7 @@ pub struct GraphicalObjectFrameNonVisual {
     /// properties that the generating application would utilize when rendering the slide surface.
     pub graphic_frame_props: NonVisualGraphicFrameProperties,
     pub app_props: ApplicationNonVisualDrawingProps,
+    pub unknown_xml: UnknownXml,
 }
 
 impl GraphicalObjectFrameNonVisual {
@@ -1356,15 +1871,26 @@ impl GraphicalObjectFrameNonVisual {
         let mut drawing_props = None;
         let mut graphic_frame_props = None;
         let mut app_props = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
-                "cNvPr" => drawing_props = Some(Box::new(NonVisualDrawingProps::from_xml_element(child_node)?)),
+                "cNvPr" => {
+                    drawing_props = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        NonVisualDrawingProps::from_xml_element,
+                    )?))
+                }
                 "cNvGraphicFramePr" => {
-                    graphic_frame_props = Some(NonVisualGraphicFrameProperties::from_xml_element(child_node)?)
+                    graphic_frame_props = Some(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        NonVisualGraphicFrameProperties::from_xml_element,
+                    )?)
                 }
                 "nvPr" => app_props = Some(ApplicationNonVisualDrawingProps::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1377,8 +1903,17 @@ impl GraphicalObjectFrameNonVisual {
             drawing_props,
             graphic_frame_props,
             app_props,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:nvGraphicFramePr")
+            .with_child(self.drawing_props.to_xml_element("p:cNvPr"))
+            .with_child(self.graphic_frame_props.to_xml_element("p:cNvGraphicFramePr"))
+            .with_child(self.app_props.to_xml_element())
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1417,6 +1952,7 @@ pub struct Connector {
     /// the first fill defined by the scheme, and be rendered with the first effect defined by the theme. Text inside the
     /// shape is to use the minor font defined by the theme.
     pub shape_style: Option<Box<ShapeStyle>>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl Connector {
@@ -1424,13 +1960,26 @@ impl Connector {
         let mut non_visual_props = None;
         let mut shape_props = None;
         let mut shape_style = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "nvCxnSpPr" => non_visual_props = Some(Box::new(ConnectorNonVisual::from_xml_element(child_node)?)),
-                "spPr" => shape_props = Some(Box::new(ShapeProperties::from_xml_element(child_node)?)),
-                "style" => shape_style = Some(Box::new(ShapeStyle::from_xml_element(child_node)?)),
-                _ => (),
+                "spPr" => {
+                    shape_props = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        ShapeProperties::from_xml_element,
+                    )?))
+                }
+                "style" => {
+                    shape_style = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        ShapeStyle::from_xml_element,
+                    )?))
+                }
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1442,8 +1991,17 @@ impl Connector {
             non_visual_props,
             shape_props,
             shape_style,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:cxnSp")
+            .with_child(self.non_visual_props.to_xml_element())
+            .with_child(self.shape_props.to_xml_element("p:spPr"))
+            .with_optional_child(self.shape_style.as_ref().map(|style| style.to_xml_element("p:style")))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1453,6 +2011,7 @@ pub struct ConnectorNonVisual {
     /// information specifying the shapes to which the connector shape is connected.
     pub connector_props: NonVisualConnectorProperties,
     pub app_props: ApplicationNonVisualDrawingProps,
+    pub unknown_xml: UnknownXml,
 }
 
 impl ConnectorNonVisual {
@@ -1460,13 +2019,26 @@ impl ConnectorNonVisual {
         let mut drawing_props = None;
         let mut connector_props = None;
         let mut app_props = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
-                "cNvPr" => drawing_props = Some(Box::new(NonVisualDrawingProps::from_xml_element(child_node)?)),
-                "cNvCxnSpPr" => connector_props = Some(NonVisualConnectorProperties::from_xml_element(child_node)?),
+                "cNvPr" => {
+                    drawing_props = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        NonVisualDrawingProps::from_xml_element,
+                    )?))
+                }
+                "cNvCxnSpPr" => {
+                    connector_props = Some(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        NonVisualConnectorProperties::from_xml_element,
+                    )?)
+                }
                 "nvPr" => app_props = Some(ApplicationNonVisualDrawingProps::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1479,8 +2051,17 @@ impl ConnectorNonVisual {
             drawing_props,
             connector_props,
             app_props,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:nvCxnSpPr")
+            .with_child(self.drawing_props.to_xml_element("p:cNvPr"))
+            .with_child(self.connector_props.to_xml_element("p:cNvCxnSpPr"))
+            .with_child(self.app_props.to_xml_element())
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1529,6 +2110,7 @@ pub struct Picture {
     /// the first fill defined by the scheme, and be rendered with the first effect defined by the theme. Text inside the
     /// shape is to use the minor font defined by the theme.
     pub shape_style: Option<Box<ShapeStyle>>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl Picture {
@@ -1537,14 +2119,33 @@ impl Picture {
         let mut blip_fill = None;
         let mut shape_props = None;
         let mut shape_style = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "nvPicPr" => non_visual_props = Some(Box::new(PictureNonVisual::from_xml_element(child_node)?)),
-                "blipFill" => blip_fill = Some(Box::new(BlipFillProperties::from_xml_element(child_node)?)),
-                "spPr" => shape_props = Some(Box::new(ShapeProperties::from_xml_element(child_node)?)),
-                "style" => shape_style = Some(Box::new(ShapeStyle::from_xml_element(child_node)?)),
-                _ => (),
+                "blipFill" => {
+                    blip_fill = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        BlipFillProperties::from_xml_element,
+                    )?))
+                }
+                "spPr" => {
+                    shape_props = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        ShapeProperties::from_xml_element,
+                    )?))
+                }
+                "style" => {
+                    shape_style = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        ShapeStyle::from_xml_element,
+                    )?))
+                }
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1558,8 +2159,18 @@ impl Picture {
             blip_fill,
             shape_props,
             shape_style,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:pic")
+            .with_child(self.non_visual_props.to_xml_element())
+            .with_child(self.blip_fill.to_xml_element("p:blipFill"))
+            .with_child(self.shape_props.to_xml_element("p:spPr"))
+            .with_optional_child(self.shape_style.as_ref().map(|style| style.to_xml_element("p:style")))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1585,6 +2196,7 @@ pub struct PictureNonVisual {
     /// ```
     pub picture_props: NonVisualPictureProperties,
     pub app_props: ApplicationNonVisualDrawingProps,
+    pub unknown_xml: UnknownXml,
 }
 
 impl PictureNonVisual {
@@ -1592,13 +2204,26 @@ impl PictureNonVisual {
         let mut drawing_props = None;
         let mut picture_props = None;
         let mut app_props = None;
+        let mut unknown_xml: UnknownXml = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
-                "cNvPr" => drawing_props = Some(Box::new(NonVisualDrawingProps::from_xml_element(child_node)?)),
-                "cNvPicPr" => picture_props = Some(NonVisualPictureProperties::from_xml_element(child_node)?),
+                "cNvPr" => {
+                    drawing_props = Some(Box::new(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        NonVisualDrawingProps::from_xml_element,
+                    )?))
+                }
+                "cNvPicPr" => {
+                    picture_props = Some(unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        NonVisualPictureProperties::from_xml_element,
+                    )?)
+                }
                 "nvPr" => app_props = Some(ApplicationNonVisualDrawingProps::from_xml_element(child_node)?),
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1611,8 +2236,17 @@ impl PictureNonVisual {
             drawing_props,
             picture_props,
             app_props,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:nvPicPr")
+            .with_child(self.drawing_props.to_xml_element("p:cNvPr"))
+            .with_child(self.picture_props.to_xml_element("p:cNvPicPr"))
+            .with_child(self.app_props.to_xml_element())
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 /// This element specifies a container for slide information that is relevant to all of the slide types.
@@ -1684,21 +2318,31 @@ pub struct CommonSlideData {
     /// This element specifies a list of embedded controls for the corresponding slide. Custom embedded controls can
     /// be embedded on slides.
     pub control_list: Option<Vec<Control>>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl CommonSlideData {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let name = xml_node.attributes.get("name").cloned();
+        let mut name = None;
+        let mut unknown_xml: UnknownXml = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_str() {
+                "name" => name = Some(value.clone()),
+                _ => unknown_xml.push_attribute(attr, value),
+            }
+        }
+
         let mut background = None;
         let mut shape_tree = None;
         let mut customer_data_list = None;
         let mut control_list = None;
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
                 "bg" => background = Some(Box::new(Background::from_xml_element(child_node)?)),
                 "spTree" => shape_tree = Some(Box::new(GroupShape::from_xml_element(child_node)?)),
-                "custDataList" => customer_data_list = Some(CustomerDataList::from_xml_element(child_node)?),
+                "custDataLst" => customer_data_list = Some(CustomerDataList::from_xml_element(child_node)?),
                 "controls" => {
                     control_list = Some(
                         child_node
@@ -1709,7 +2353,7 @@ impl CommonSlideData {
                             .collect::<Result<Vec<_>>>()?,
                     );
                 }
-                _ => (),
+                _ => unknown_xml.push_child(position, child_node),
             }
         }
 
@@ -1721,8 +2365,24 @@ impl CommonSlideData {
             shape_tree,
             customer_data_list,
             control_list,
+            unknown_xml,
         })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        let control_list = self
+            .control_list
+            .as_ref()
+            .map(|controls| XmlNode::new("p:controls").with_children(controls.iter().map(Control::to_xml_element)));
+
+        XmlNode::new("p:cSld")
+            .with_optional_attribute("name", self.name.as_ref())
+            .with_optional_child(self.background.as_ref().map(|background| background.to_xml_element()))
+            .with_child(self.shape_tree.to_xml_element("p:spTree"))
+            .with_optional_child(self.customer_data_list.as_ref().map(CustomerDataList::to_xml_element))
+            .with_optional_child(control_list)
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -1750,23 +2410,62 @@ pub struct SlideMasterTextStyles {
     /// The other_styles element is to be used for specifying the text formatting of text within a slide shape but
     /// not within a text box. Text box styling is handled from within the body_styles element.
     pub other_styles: Option<Box<TextListStyle>>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl SlideMasterTextStyles {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         let mut instance: Self = Default::default();
 
-        for child_node in &xml_node.child_nodes {
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
             match child_node.local_name() {
-                "titleStyle" => instance.title_styles = Some(Box::new(TextListStyle::from_xml_element(child_node)?)),
-                "bodyStyle" => instance.body_styles = Some(Box::new(TextListStyle::from_xml_element(child_node)?)),
-                "otherStyle" => instance.other_styles = Some(Box::new(TextListStyle::from_xml_element(child_node)?)),
-                _ => (),
+                "titleStyle" => {
+                    instance.title_styles = Some(Box::new(instance.unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        TextListStyle::from_xml_element,
+                    )?))
+                }
+                "bodyStyle" => {
+                    instance.body_styles = Some(Box::new(instance.unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        TextListStyle::from_xml_element,
+                    )?))
+                }
+                "otherStyle" => {
+                    instance.other_styles = Some(Box::new(instance.unknown_xml.parse_known_child(
+                        xml_node,
+                        position,
+                        TextListStyle::from_xml_element,
+                    )?))
+                }
+                _ => instance.unknown_xml.push_child(position, child_node),
             }
         }
 
         Ok(instance)
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:txStyles")
+            .with_optional_child(
+                self.title_styles
+                    .as_ref()
+                    .map(|style| style.to_xml_element("p:titleStyle")),
+            )
+            .with_optional_child(
+                self.body_styles
+                    .as_ref()
+                    .map(|style| style.to_xml_element("p:bodyStyle")),
+            )
+            .with_optional_child(
+                self.other_styles
+                    .as_ref()
+                    .map(|style| style.to_xml_element("p:otherStyle")),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -1783,6 +2482,10 @@ impl OrientationTransition {
 
         Ok(Self { direction })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_optional_attribute("dir", self.direction)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -1799,6 +2502,10 @@ impl EightDirectionTransition {
 
         Ok(Self { direction })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_optional_attribute("dir", self.direction)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -1816,6 +2523,10 @@ impl OptionalBlackTransition {
 
         Ok(Self { through_black })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_optional_attribute("thruBlk", self.through_black)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -1832,6 +2543,10 @@ impl SideDirectionTransition {
 
         Ok(Self { direction })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_optional_attribute("dir", self.direction)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -1861,6 +2576,12 @@ impl SplitTransition {
                 Ok(instance)
             })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name)
+            .with_optional_attribute("orient", self.orientation)
+            .with_optional_attribute("dir", self.direction)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -1877,6 +2598,10 @@ impl CornerDirectionTransition {
 
         Ok(Self { direction })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_optional_attribute("dir", self.direction)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -1897,6 +2622,10 @@ impl WheelTransition {
 
         Ok(Self { spokes })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_optional_attribute("spokes", self.spokes)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -1913,6 +2642,10 @@ impl InOutTransition {
 
         Ok(Self { direction })
     }
+
+    pub fn to_xml_element(&self, node_name: &str) -> XmlNode {
+        XmlNode::new(node_name).with_optional_attribute("dir", self.direction)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2253,6 +2986,34 @@ impl XsdChoice for SlideTransitionGroup {
     }
 }
 
+impl ToXmlChoice for SlideTransitionGroup {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            SlideTransitionGroup::Blinds(transition) => transition.to_xml_element("p:blinds"),
+            SlideTransitionGroup::Checker(transition) => transition.to_xml_element("p:checker"),
+            SlideTransitionGroup::Circle => XmlNode::new("p:circle"),
+            SlideTransitionGroup::Dissolve => XmlNode::new("p:dissolve"),
+            SlideTransitionGroup::Comb(transition) => transition.to_xml_element("p:comb"),
+            SlideTransitionGroup::Cover(transition) => transition.to_xml_element("p:cover"),
+            SlideTransitionGroup::Cut(transition) => transition.to_xml_element("p:cut"),
+            SlideTransitionGroup::Diamond => XmlNode::new("p:diamond"),
+            SlideTransitionGroup::Fade(transition) => transition.to_xml_element("p:fade"),
+            SlideTransitionGroup::Newsflash => XmlNode::new("p:newsflash"),
+            SlideTransitionGroup::Plus => XmlNode::new("p:plus"),
+            SlideTransitionGroup::Pull(transition) => transition.to_xml_element("p:pull"),
+            SlideTransitionGroup::Push(transition) => transition.to_xml_element("p:push"),
+            SlideTransitionGroup::Random => XmlNode::new("p:random"),
+            SlideTransitionGroup::RandomBar(transition) => transition.to_xml_element("p:randomBar"),
+            SlideTransitionGroup::Split(transition) => transition.to_xml_element("p:split"),
+            SlideTransitionGroup::Strips(transition) => transition.to_xml_element("p:strips"),
+            SlideTransitionGroup::Wedge => XmlNode::new("p:wedge"),
+            SlideTransitionGroup::Wheel(transition) => transition.to_xml_element("p:wheel"),
+            SlideTransitionGroup::Wipe(transition) => transition.to_xml_element("p:wipe"),
+            SlideTransitionGroup::Zoom(transition) => transition.to_xml_element("p:zoom"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TransitionStartSoundAction {
     /// This attribute specifies if the sound loops until the next sound event occurs in slideshow.
@@ -2274,6 +3035,7 @@ pub struct TransitionStartSoundAction {
     /// </p:transition>
     /// ```
     pub sound_file: EmbeddedWAVAudioFile,
+    pub unknown_xml: UnknownXml,
 }
 
 impl TransitionStartSoundAction {
@@ -2287,8 +3049,20 @@ impl TransitionStartSoundAction {
             .map(EmbeddedWAVAudioFile::from_xml_element)
             .transpose()?
             .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "snd"))?;
+        let unknown_xml = UnknownXml::from_xml_element(xml_node, &["loop"], |local_name| local_name == "snd");
 
-        Ok(Self { is_looping, sound_file })
+        Ok(Self {
+            is_looping,
+            sound_file,
+            unknown_xml,
+        })
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:stSnd")
+            .with_optional_attribute("loop", self.is_looping)
+            .with_child(self.sound_file.to_xml_element("p:snd"))
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -2346,6 +3120,15 @@ impl XsdChoice for TransitionSoundAction {
     }
 }
 
+impl ToXmlChoice for TransitionSoundAction {
+    fn to_xml_element(&self) -> XmlNode {
+        match self {
+            TransitionSoundAction::StartSound(start_sound) => start_sound.to_xml_element(),
+            TransitionSoundAction::EndSound => XmlNode::new("p:endSnd"),
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct SlideTransition {
     /// Specifies the transition speed that is to be used when transitioning from the current slide
@@ -2380,6 +3163,7 @@ pub struct SlideTransition {
     /// </p:transition>
     /// ```
     pub sound_action: Option<TransitionSoundAction>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl SlideTransition {
@@ -2392,7 +3176,7 @@ impl SlideTransition {
                     "spd" => instance.speed = Some(value.parse()?),
                     "advClick" => instance.advance_on_click = Some(value.parse()?),
                     "advTm" => instance.advance_on_time = Some(value.parse()?),
-                    _ => (),
+                    _ => instance.unknown_xml.push_attribute(attr, value),
                 }
 
                 Ok(instance)
@@ -2401,21 +3185,40 @@ impl SlideTransition {
                 xml_node
                     .child_nodes
                     .iter()
-                    .try_fold(instance, |mut instance, child_node| {
+                    .enumerate()
+                    .try_fold(instance, |mut instance, (position, child_node)| {
                         match child_node.local_name() {
                             "sndAc" => {
-                                instance.sound_action = Some(TransitionSoundAction::from_xml_element(child_node)?)
+                                instance.sound_action = child_node
+                                    .child_nodes
+                                    .iter()
+                                    .find_map(TransitionSoundAction::try_from_xml_element)
+                                    .transpose()?
                             }
                             local_name if SlideTransitionGroup::is_choice_member(local_name) => {
                                 instance.transition_type = Some(SlideTransitionGroup::from_xml_element(child_node)?)
                             }
-                            _ => (),
+                            _ => instance.unknown_xml.push_child(position, child_node),
                         }
 
                         Ok(instance)
                     })
             })
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:transition")
+            .with_optional_attribute("spd", self.speed)
+            .with_optional_attribute("advClick", self.advance_on_click)
+            .with_optional_attribute("advTm", self.advance_on_time)
+            .with_optional_child(self.transition_type.as_ref().map(ToXmlChoice::to_xml_element))
+            .with_optional_child(
+                self.sound_action
+                    .as_ref()
+                    .map(|sound_action| XmlNode::new("p:sndAc").with_child(sound_action.to_xml_element())),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -2448,21 +3251,20 @@ pub struct SlideTiming {
     /// </p:bldLst>
     /// ```
     pub build_list: Option<Vec<Build>>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl SlideTiming {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
+        xml_node.child_nodes.iter().enumerate().try_fold(
+            Default::default(),
+            |mut instance: Self, (position, child_node)| {
                 match child_node.local_name() {
                     "tnLst" => {
                         let vec = child_node
                             .child_nodes
                             .iter()
-                            .filter(|tn_node| tn_node.local_name() == "tn")
-                            .map(TimeNodeGroup::from_xml_element)
+                            .filter_map(TimeNodeGroup::try_from_xml_element)
                             .collect::<Result<Vec<_>>>()?;
 
                         instance.time_node_list = if !vec.is_empty() {
@@ -2470,7 +3272,7 @@ impl SlideTiming {
                         } else {
                             return Err(Box::<dyn Error>::from(MissingChildNodeError::new(
                                 child_node.name.clone(),
-                                "tn",
+                                "TimeNodeGroup",
                             )));
                         }
                     }
@@ -2478,8 +3280,7 @@ impl SlideTiming {
                         let vec = child_node
                             .child_nodes
                             .iter()
-                            .filter(|bld_node| bld_node.local_name() == "bld")
-                            .map(Build::from_xml_element)
+                            .filter_map(Build::try_from_xml_element)
                             .collect::<Result<Vec<_>>>()?;
 
                         instance.build_list = if !vec.is_empty() {
@@ -2487,15 +3288,36 @@ impl SlideTiming {
                         } else {
                             return Err(Box::<dyn Error>::from(MissingChildNodeError::new(
                                 child_node.name.clone(),
-                                "bld",
+                                "Build",
                             )));
                         }
                     }
-                    _ => (),
+                    _ => instance.unknown_xml.push_child(position, child_node),
                 }
 
                 Ok(instance)
-            })
+            },
+        )
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:timing")
+            .with_optional_child(
+                self.time_node_list
+                    .as_ref()
+                    .map(|list| XmlNode::new("p:tnLst").with_children(list.iter().map(ToXmlChoice::to_xml_element))),
+            )
+            .with_optional_child(
+                self.build_list
+                    .as_ref()
+                    .map(|list| XmlNode::new("p:bldLst").with_children(list.iter().map(ToXmlChoice::to_xml_element))),
+            )
+            .with_unknown_xml(&self.unknown_xml)
+    }
+
+    /// Writes the timing information as a standalone xml document.
+    pub fn to_xml<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_xml(&self.to_xml_element(), writer)
     }
 }
 
@@ -2513,24 +3335,41 @@ pub struct HeaderFooter {
     /// Specifies whether the Date/Time placeholder is enabled for this master. If this attribute is
     /// not specified, a value of true should be assumed by the generating application.
     pub date_time_enabled: Option<bool>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl HeaderFooter {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        xml_node
-            .attributes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
+        let mut instance = xml_node.attributes.iter().try_fold(
+            Default::default(),
+            |mut instance: Self, (attr, value)| -> Result<Self> {
                 match attr.as_ref() {
                     "sldNum" => instance.slide_number_enabled = Some(parse_xml_bool(value)?),
                     "hdr" => instance.header_enabled = Some(parse_xml_bool(value)?),
                     "ftr" => instance.footer_enabled = Some(parse_xml_bool(value)?),
                     "dt" => instance.date_time_enabled = Some(parse_xml_bool(value)?),
-                    _ => (),
+                    _ => instance.unknown_xml.push_attribute(attr, value),
                 }
 
                 Ok(instance)
-            })
+            },
+        )?;
+
+        // The only child element of a header footer is an extension list
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            instance.unknown_xml.push_child(position, child_node);
+        }
+
+        Ok(instance)
+    }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        XmlNode::new("p:hf")
+            .with_optional_attribute("sldNum", self.slide_number_enabled)
+            .with_optional_attribute("hdr", self.header_enabled)
+            .with_optional_attribute("ftr", self.footer_enabled)
+            .with_optional_attribute("dt", self.date_time_enabled)
+            .with_unknown_xml(&self.unknown_xml)
     }
 }
 
@@ -2538,6 +3377,7 @@ impl HeaderFooter {
 pub struct Control {
     pub picture: Option<Box<Picture>>,
     pub ole_attributes: Box<OleAttributes>,
+    pub unknown_xml: UnknownXml,
 }
 
 impl Control {
@@ -2555,9 +3395,24 @@ impl Control {
             .map(Picture::from_xml_element)
             .transpose()?
             .map(Box::new);
+        instance.unknown_xml =
+            UnknownXml::from_xml_element(xml_node, &OleAttributes::NAMES, |local_name| local_name == "pic");
 
         Ok(instance)
     }
+
+    pub fn to_xml_element(&self) -> XmlNode {
+        let ole_attributes = &self.ole_attributes;
+        XmlNode::new("p:control")
+            .with_optional_attribute("spid", ole_attributes.shape_id.as_ref())
+            .with_optional_attribute("name", ole_attributes.name.as_ref())
+            .with_optional_attribute("showAsIcon", ole_attributes.show_as_icon)
+            .with_optional_attribute("r:id", ole_attributes.id.as_ref())
+            .with_optional_attribute("imgW", ole_attributes.image_width)
+            .with_optional_attribute("imgH", ole_attributes.image_height)
+            .with_optional_child(self.picture.as_ref().map(|picture| picture.to_xml_element()))
+            .with_unknown_xml(&self.unknown_xml)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -2579,6 +3434,9 @@ pub struct OleAttributes {
 }
 
 impl OleAttributes {
+    /// The names of the attributes that are parsed by try_attribute_parse.
+    pub const NAMES: [&'static str; 6] = ["spid", "name", "showAsIcon", "r:id", "imgW", "imgH"];
+
     pub fn try_attribute_parse<T: AsRef<str>>(&mut self, attr: T, value: T) -> Result<()> {
         match attr.as_ref() {
             "spid" => self.shape_id = Some(value.as_ref().parse()?),
@@ -2593,3 +3451,34 @@ impl OleAttributes {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphic_frame_with_invalid_table() {
+        let xml = r#"<p:graphicFrame xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+            xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+            <p:nvGraphicFramePr><p:cNvPr id="2" name="Table 1"/><p:cNvGraphicFramePr/><p:nvPr/></p:nvGraphicFramePr>
+            <p:xfrm><a:off x="0" y="0"/><a:ext cx="100" cy="100"/></p:xfrm>
+            <a:graphic>
+                <a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/table">
+                    <a:tbl><a:tr h="100"><a:tc><a:txBody><a:bodyPr/><a:p/></a:txBody></a:tc></a:tr></a:tbl>
+                </a:graphicData>
+            </a:graphic>
+        </p:graphicFrame>"#;
+        let xml_node = parse_part_xml(xml).unwrap();
+        let graphic_frame = GraphicalObjectFrame::from_xml_element(&xml_node).unwrap();
+        assert!(graphic_frame.graphic_content.is_none());
+
+        let written_node = graphic_frame.to_xml_element();
+        let graphic_data_node = &written_node.child_nodes[2].child_nodes[0];
+        assert_eq!(graphic_data_node.child_nodes.len(), 1);
+        assert_eq!(graphic_data_node.child_nodes[0].name, "a:tbl");
+        assert_eq!(
+            GraphicalObjectFrame::from_xml_element(&written_node).unwrap(),
+            graphic_frame
+        );
+    }
+}
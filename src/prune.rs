@@ -0,0 +1,357 @@
+//! Finding and removing the parts of a presentation that are no longer in use.
+//!
+//! Decks that went through many revisions tend to carry slide layouts that no slide follows, spare slide masters and
+//! media files that nothing refers to. A part is in use if it can be reached from the package by following
+//! relationships, which leads to the presentation and the document properties, and from there to the slides and
+//! everything they reference. The relationships the presentation lists its slide masters with and a slide master lists
+//! its slide layouts with aren't followed, so slide masters and slide layouts are only in use if a slide follows them.
+//! A presentation without slides keeps all of its slide masters and slide layouts.
+
+use crate::document::{
+    relationships_path, resolve_relationship_target, PPTXDocument, PRESENTATION_PATH, SLIDE_LAYOUT_RELATIONSHIP_TYPE,
+    SLIDE_MASTER_RELATIONSHIP_TYPE,
+};
+use crate::opc::ExternalRelationships;
+use msoffice_shared::relationship::Relationship;
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs::File,
+    path::{Path, PathBuf},
+};
+use zip::{result::ZipError, ZipArchive};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// A part of the document that can't be reached from the package by following relationships.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnreachablePart {
+    pub path: PathBuf,
+    /// The size of the part in bytes. Parts represented by the model are measured by serializing them.
+    pub size: u64,
+}
+
+impl PPTXDocument {
+    /// Returns the paths of the parts that can be reached from the package by following relationships. See the module
+    /// documentation for the relationships that aren't followed.
+    pub fn reachable_parts(&self) -> Result<HashSet<PathBuf>> {
+        let follow_listings = match &self.presentation {
+            Some(presentation) => presentation.slide_id_list.is_empty(),
+            None => true,
+        };
+
+        // The package itself is the source of the relationships of the package, like the one to the presentation
+        let package_path = PathBuf::new();
+        let mut reachable_parts = HashSet::new();
+        let mut part_paths = vec![package_path.clone(), PathBuf::from(PRESENTATION_PATH)];
+        while let Some(part_path) = part_paths.pop() {
+            if !reachable_parts.insert(part_path.clone()) {
+                continue;
+            }
+
+            for relationship in self.read_relationships(&part_path)? {
+                if self.is_external_relationship(&part_path, &relationship)
+                    || (!follow_listings && self.is_listing_relationship(&part_path, &relationship))
+                {
+                    continue;
+                }
+
+                part_paths.push(resolve_relationship_target(&part_path, &relationship.target));
+            }
+        }
+
+        reachable_parts.remove(&package_path);
+        Ok(reachable_parts)
+    }
+
+    /// Returns the parts of the document that can't be reached from the package, sorted by their paths.
+    pub fn unreachable_parts(&self) -> Result<Vec<UnreachablePart>> {
+        let reachable_parts = self.reachable_parts()?;
+        let mut zipper = if self.file_path.is_file() {
+            Some(ZipArchive::new(File::open(&self.file_path)?)?)
+        } else {
+            None
+        };
+
+        self.part_paths()?
+            .into_iter()
+            .filter(|part_path| !reachable_parts.contains(part_path))
+            .map(|part_path| {
+                let size = self.part_size(&part_path, zipper.as_mut())?;
+                Ok(UnreachablePart { path: part_path, size })
+            })
+            .collect()
+    }
+
+    /// Removes the parts that can't be reached from the package, so they are left out when the document is saved, and
+    /// returns them. Slide masters and slide layouts are removed from the lists of the presentation and the slide
+    /// masters as well.
+    pub fn remove_unreachable_parts(&mut self) -> Result<Vec<UnreachablePart>> {
+        let unreachable_parts = self.unreachable_parts()?;
+        for unreachable_part in &unreachable_parts {
+            self.remove_part(&unreachable_part.path);
+        }
+
+        Ok(unreachable_parts)
+    }
+
+    /// Returns whether `relationship` of the part at `part_path` only lists a slide master of the presentation or a
+    /// slide layout of a slide master.
+    fn is_listing_relationship(&self, part_path: &Path, relationship: &Relationship) -> bool {
+        match relationship.rel_type.as_str() {
+            SLIDE_MASTER_RELATIONSHIP_TYPE => part_path == Path::new(PRESENTATION_PATH),
+            SLIDE_LAYOUT_RELATIONSHIP_TYPE => self.slide_master_map.contains_key(part_path),
+            _ => false,
+        }
+    }
+
+    /// Returns the size of the part at `part_path` in bytes. Parts that aren't represented by the model or
+    /// raw_part_map are measured in the source package opened by `zipper`.
+    fn part_size(&self, part_path: &Path, zipper: Option<&mut ZipArchive<File>>) -> Result<u64> {
+        if let Some(data) = self.raw_part_map.get(part_path) {
+            return Ok(data.len() as u64);
+        }
+
+        let mut data = Vec::new();
+        if let Some(slide_master) = self.slide_master_map.get(part_path) {
+            slide_master.to_xml(&mut data)?;
+        } else if let Some(slide_layout) = self.slide_layout_map.get(part_path) {
+            slide_layout.to_xml(&mut data)?;
+        } else if let Some(slide) = self.slide_map.get(part_path) {
+            slide.to_xml(&mut data)?;
+        } else if let Some(notes_slide) = self.notes_slide_map.get(part_path) {
+            notes_slide.to_xml(&mut data)?;
+        } else if let Some(zipper) = zipper {
+            return match zipper.by_name(&part_path.to_string_lossy()) {
+                Ok(zip_file) => Ok(zip_file.size()),
+                Err(ZipError::FileNotFound) => Ok(0),
+                Err(error) => Err(error.into()),
+            };
+        }
+
+        Ok(data.len() as u64)
+    }
+
+    /// Removes the part at `part_path` from the document along with its relationships, and removes the entries of the
+    /// presentation and the slide masters that list it.
+    fn remove_part(&mut self, part_path: &Path) {
+        let rels_path = relationships_path(part_path);
+        self.external_relationships.remove_part(&rels_path);
+        if self.slide_master_map.remove(part_path).is_some() {
+            self.slide_master_rels_map.remove(&rels_path);
+
+            let presentation_path = Path::new(PRESENTATION_PATH);
+            let relationship_ids = listing_relationship_ids(
+                presentation_path,
+                &self.presentation_rels,
+                &self.external_relationships,
+                part_path,
+            );
+            self.presentation_rels
+                .retain(|relationship| !relationship_ids.contains(&relationship.id));
+            if let Some(presentation) = self.presentation_mut() {
+                presentation
+                    .slide_master_id_list
+                    .retain(|entry| !relationship_ids.contains(&entry.relationship_id));
+            }
+        } else if self.slide_layout_map.remove(part_path).is_some() {
+            self.slide_layout_rels_map.remove(&rels_path);
+
+            let slide_master_paths = self.slide_master_map.keys().cloned().collect::<Vec<_>>();
+            for slide_master_path in slide_master_paths {
+                let slide_master_rels_path = relationships_path(&slide_master_path);
+                let relationship_ids = match self.slide_master_rels_map.get_mut(&slide_master_rels_path) {
+                    Some(relationships) => {
+                        let relationship_ids = listing_relationship_ids(
+                            &slide_master_path,
+                            relationships,
+                            &self.external_relationships,
+                            part_path,
+                        );
+                        relationships.retain(|relationship| !relationship_ids.contains(&relationship.id));
+                        relationship_ids
+                    }
+                    None => continue,
+                };
+                if relationship_ids.is_empty() {
+                    continue;
+                }
+
+                let slide_layout_id_list = self
+                    .slide_master_mut(&slide_master_path)
+                    .and_then(|slide_master| slide_master.slide_layout_id_list.as_mut());
+                if let Some(slide_layout_id_list) = slide_layout_id_list {
+                    slide_layout_id_list
+                        .0
+                        .retain(|entry| !relationship_ids.contains(&entry.relationship_id));
+                }
+            }
+        } else if self.slide_map.remove(part_path).is_some() {
+            self.slide_rels_map.remove(&rels_path);
+        } else if self.notes_slide_map.remove(part_path).is_some() {
+            self.notes_slide_rels_map.remove(&rels_path);
+        } else {
+            self.theme_map.remove(part_path);
+            self.medias.retain(|media_path| media_path != part_path);
+            self.diagram_data_map.remove(part_path);
+            self.raw_part_map.remove(part_path);
+            self.raw_part_map.remove(&rels_path);
+            self.raw_part_content_types.remove(part_path);
+            self.removed_parts.insert(PathBuf::from(part_path));
+        }
+    }
+}
+
+/// Returns the ids of `relationships` of the part at `source_path` that target the part at `part_path`. Relationships
+/// listed by `external_relationships` reference resources outside of the package.
+fn listing_relationship_ids(
+    source_path: &Path,
+    relationships: &[Relationship],
+    external_relationships: &ExternalRelationships,
+    part_path: &Path,
+) -> Vec<String> {
+    let rels_path = relationships_path(source_path);
+    relationships
+        .iter()
+        .filter(|relationship| {
+            !external_relationships.contains(&rels_path, &relationship.id)
+                && resolve_relationship_target(source_path, &relationship.target) == part_path
+        })
+        .map(|relationship| relationship.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::load_saved_document;
+    use std::io::Cursor;
+
+    const IMAGE_DATA: [u8; 4] = [0x89, b'P', b'N', b'G'];
+
+    #[test]
+    fn test_remove_unreachable_parts() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let mut document = PPTXDocument::from_file(&test_dir.join("tests/samplepptx.pptx")).unwrap();
+        let orphan_media_path = PathBuf::from("ppt/media/image9.png");
+        document
+            .raw_part_map
+            .insert(orphan_media_path.clone(), IMAGE_DATA.to_vec());
+        document.medias.push(orphan_media_path.clone());
+
+        let reachable_parts = document.reachable_parts().unwrap();
+        assert!(reachable_parts.contains(Path::new("ppt/slideLayouts/slideLayout1.xml")));
+        assert!(reachable_parts.contains(Path::new("ppt/slideMasters/slideMaster1.xml")));
+        assert!(reachable_parts.contains(Path::new("ppt/presProps.xml")));
+        assert!(reachable_parts.contains(Path::new("docProps/thumbnail.jpeg")));
+        assert!(!reachable_parts.contains(Path::new("ppt/slideLayouts/slideLayout3.xml")));
+
+        let unreachable_parts = document.unreachable_parts().unwrap();
+        let mut expected_paths = (3..=11)
+            .map(|n| PathBuf::from(format!("ppt/slideLayouts/slideLayout{}.xml", n)))
+            .collect::<Vec<_>>();
+        expected_paths.push(orphan_media_path.clone());
+        expected_paths.sort();
+        assert_eq!(
+            unreachable_parts
+                .iter()
+                .map(|unreachable_part| unreachable_part.path.clone())
+                .collect::<Vec<_>>(),
+            expected_paths
+        );
+        assert!(unreachable_parts
+            .iter()
+            .all(|unreachable_part| unreachable_part.size > 0));
+        let orphan_media = unreachable_parts
+            .iter()
+            .find(|unreachable_part| unreachable_part.path == orphan_media_path)
+            .unwrap();
+        assert_eq!(orphan_media.size, IMAGE_DATA.len() as u64);
+
+        assert_eq!(document.remove_unreachable_parts().unwrap(), unreachable_parts);
+        assert_eq!(document.slide_layout_map.len(), 2);
+        assert!(!document.medias.contains(&orphan_media_path));
+        let slide_master = &document.slide_master_map[Path::new("ppt/slideMasters/slideMaster1.xml")];
+        assert_eq!(slide_master.slide_layout_id_list.as_ref().unwrap().0.len(), 2);
+
+        let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+        let saved_document = load_saved_document(&data);
+        assert_eq!(saved_document.slide_map, document.slide_map);
+        assert_eq!(saved_document.slide_layout_map, document.slide_layout_map);
+        assert_eq!(saved_document.slide_master_map, document.slide_master_map);
+        assert_eq!(saved_document.medias.len(), 2);
+        assert!(saved_document.unreachable_parts().unwrap().is_empty());
+
+        let mut zipper = ZipArchive::new(Cursor::new(data)).unwrap();
+        assert!(zipper.by_name("ppt/slideLayouts/slideLayout3.xml").is_err());
+        assert!(zipper.by_name("ppt/slideLayouts/_rels/slideLayout3.xml.rels").is_err());
+        assert!(zipper.by_name(&orphan_media_path.to_string_lossy()).is_err());
+    }
+
+    #[test]
+    fn test_removed_raw_part_is_left_out() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let mut document = PPTXDocument::from_file(&test_dir.join("tests/samplepptx.pptx")).unwrap();
+        let tags_path = PathBuf::from("ppt/tags/tag1.xml");
+        let relationship_ids = listing_relationship_ids(
+            Path::new(PRESENTATION_PATH),
+            &document.presentation_rels,
+            &document.external_relationships,
+            &tags_path,
+        );
+        document
+            .presentation_rels
+            .retain(|relationship| !relationship_ids.contains(&relationship.id));
+        document.presentation_mut().unwrap().customer_data_list = None;
+
+        let unreachable_parts = document.remove_unreachable_parts().unwrap();
+        assert!(unreachable_parts
+            .iter()
+            .any(|unreachable_part| unreachable_part.path == tags_path && unreachable_part.size == 1309));
+        assert!(document.removed_parts.contains(&tags_path));
+
+        let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+        let mut zipper = ZipArchive::new(Cursor::new(data)).unwrap();
+        assert!(zipper.by_name(&tags_path.to_string_lossy()).is_err());
+        assert!(zipper.by_name("ppt/presProps.xml").is_ok());
+    }
+
+    #[test]
+    fn test_external_relationship_sharing_internal_target() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let mut document = PPTXDocument::from_file(&test_dir.join("tests/samplepptx.pptx")).unwrap();
+        let slide_paths = document
+            .ordered_slides()
+            .into_iter()
+            .map(|(slide_path, _)| PathBuf::from(slide_path))
+            .collect::<Vec<_>>();
+        let slide_layout_relationship = document
+            .part_relationships(&slide_paths[0])
+            .unwrap()
+            .iter()
+            .find(|relationship| relationship.rel_type == SLIDE_LAYOUT_RELATIONSHIP_TYPE)
+            .unwrap()
+            .clone();
+        let slide_layout_path = resolve_relationship_target(&slide_paths[0], &slide_layout_relationship.target);
+
+        // The hyperlink of the other slide has the same target string, but it's external because of its target mode
+        let slide_rels_path = relationships_path(&slide_paths[1]);
+        let slide_rels = document.slide_rels_map.get_mut(&slide_rels_path).unwrap();
+        let hyperlink = Relationship {
+            id: String::from("rId99"),
+            rel_type: String::from("http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"),
+            target: slide_layout_relationship.target.clone(),
+        };
+        slide_rels.push(hyperlink.clone());
+        document.external_relationships.insert(&slide_rels_path, &hyperlink.id);
+
+        assert!(!document.is_external_relationship(&slide_paths[0], &slide_layout_relationship));
+        assert!(document.is_external_relationship(&slide_paths[1], &hyperlink));
+        assert!(document.reachable_parts().unwrap().contains(&slide_layout_path));
+
+        let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+        let saved_document = load_saved_document(&data);
+        assert!(!saved_document.is_external_relationship(&slide_paths[0], &slide_layout_relationship));
+        assert!(saved_document.is_external_relationship(&slide_paths[1], &hyperlink));
+    }
+}
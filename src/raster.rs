@@ -0,0 +1,1051 @@
+use crate::document::{PPTXDocument, PRESENTATION_PATH};
+use crate::drawingml::geometry::{shape_paths, PathSegment, ShapePath};
+use crate::layout::{layout_text_body, EstimatedTextMeasure, TextLine, TextMeasure, EMUS_PER_POINT};
+use crate::pml::slides::{GraphicalObjectContent, GroupShape, Shape, ShapeGroup};
+use crate::style::{is_drawn, Paint as ShapePaint, RunStyle, Stroke as ShapeStroke, StyleContext};
+use ab_glyph::{Font, FontArc, FontVec, OutlineCurve};
+use log::warn;
+use msoffice_shared::drawingml::{
+    coordsys::{GroupTransform2D, Transform2D},
+    shapeprops::LineEndProperties,
+    simpletypes::{LineCap as ShapeLineCap, LineEndLength, LineEndType, LineEndWidth, PathFillMode},
+};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use tiny_skia::{
+    Color, FillRule, FilterQuality, GradientStop, IntSize, LineCap, LinearGradient, Paint, PathBuilder, Pattern,
+    Pixmap, Point, RadialGradient, Rect, Shader, SpreadMode, Stroke, StrokeDash, Transform,
+};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// A collection of fonts used to draw text. Fonts are looked up by their typeface name, so no system fonts are needed.
+#[derive(Debug, Clone, Default)]
+pub struct FontBook {
+    faces: Vec<FontFace>,
+    fallback: Option<FontArc>,
+}
+
+#[derive(Debug, Clone)]
+struct FontFace {
+    typeface: String,
+    bold: bool,
+    italic: bool,
+    font: FontArc,
+}
+
+impl FontBook {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a font book containing the fonts embedded into the presentation.
+    ///
+    /// Embedded fonts that can't be read are skipped. Embedded fonts compressed with MicroType Express are not
+    /// supported.
+    pub fn from_document(document: &PPTXDocument) -> Self {
+        let mut font_book = Self::new();
+        let embedded_fonts = document
+            .presentation
+            .as_ref()
+            .map(|presentation| presentation.embedded_font_list.as_slice())
+            .unwrap_or_default();
+
+        for embedded_font in embedded_fonts {
+            let variants = [
+                (&embedded_font.regular, false, false),
+                (&embedded_font.bold, true, false),
+                (&embedded_font.italic, false, true),
+                (&embedded_font.bold_italic, true, true),
+            ];
+            for (relationship_id, bold, italic) in variants.iter() {
+                let font_path = match relationship_id
+                    .as_ref()
+                    .and_then(|rel_id| document.relationship_target_path(Path::new(PRESENTATION_PATH), rel_id))
+                {
+                    Some(font_path) => font_path,
+                    None => continue,
+                };
+
+                let result = document
+                    .read_part(&font_path)
+                    .and_then(|data| font_book.add_font(&embedded_font.font.typeface, *bold, *italic, data));
+                if let Err(err) = result {
+                    warn!("failed to load embedded font {}: {}", font_path.display(), err);
+                }
+            }
+        }
+
+        font_book
+    }
+
+    /// Adds a font for the given typeface and style. `data` is the content of a TrueType or OpenType font file, or an
+    /// embedded font of a presentation.
+    pub fn add_font(&mut self, typeface: &str, bold: bool, italic: bool, data: Vec<u8>) -> Result<()> {
+        self.faces.push(FontFace {
+            typeface: typeface.to_lowercase(),
+            bold,
+            italic,
+            font: parse_font(data)?,
+        });
+        Ok(())
+    }
+
+    /// Sets the font that is used for typefaces that are not in the font book.
+    pub fn set_fallback_font(&mut self, data: Vec<u8>) -> Result<()> {
+        self.fallback = Some(parse_font(data)?);
+        Ok(())
+    }
+
+    /// Returns the font to draw text with the given style. Prefers the font of the exact style of the typeface, then
+    /// the regular font of the typeface, then any font of the typeface, then the fallback font.
+    pub fn font(&self, style: &RunStyle) -> Option<&FontArc> {
+        let typeface = style.typeface.as_ref().map(|typeface| typeface.to_lowercase());
+        let faces = self
+            .faces
+            .iter()
+            .filter(|face| Some(&face.typeface) == typeface.as_ref())
+            .collect::<Vec<_>>();
+
+        faces
+            .iter()
+            .find(|face| face.bold == style.bold && face.italic == style.italic)
+            .or_else(|| faces.iter().find(|face| !face.bold && !face.italic))
+            .or_else(|| faces.first())
+            .map(|face| &face.font)
+            .or(self.fallback.as_ref())
+    }
+}
+
+impl TextMeasure for FontBook {
+    fn text_width(&self, text: &str, style: &RunStyle) -> f64 {
+        let font = match self.font(style) {
+            Some(font) => font,
+            None => return EstimatedTextMeasure.text_width(text, style),
+        };
+
+        let units_per_em = f64::from(font.units_per_em().unwrap_or(1000.0));
+        let mut width = 0.0;
+        let mut previous = None;
+        for c in text.chars() {
+            let glyph_id = font.glyph_id(c);
+            if let Some(previous) = previous {
+                width += f64::from(font.kern_unscaled(previous, glyph_id));
+            }
+            width += f64::from(font.h_advance_unscaled(glyph_id));
+            previous = Some(glyph_id);
+        }
+
+        width / units_per_em * style.font_size * EMUS_PER_POINT
+    }
+
+    fn ascent(&self, style: &RunStyle) -> f64 {
+        match self.font(style) {
+            Some(font) => f64::from(font.ascent_unscaled() / font.units_per_em().unwrap_or(1000.0)),
+            None => EstimatedTextMeasure.ascent(style),
+        }
+    }
+}
+
+/// Renders a slide into a PNG image of the given width in pixels, using the fonts embedded into the presentation. The
+/// height of the image follows the aspect ratio of the slides. Text with typefaces that are not embedded is not
+/// drawn, see render_slide_png_with_fonts to supply fonts.
+pub fn render_slide_png(document: &PPTXDocument, slide_path: &Path, width: u32) -> Result<Vec<u8>> {
+    render_slide_png_with_fonts(document, slide_path, width, &FontBook::from_document(document))
+}
+
+/// Renders a slide into a PNG image of the given width in pixels, drawing text with the fonts of `fonts`.
+pub fn render_slide_png_with_fonts(
+    document: &PPTXDocument,
+    slide_path: &Path,
+    width: u32,
+    fonts: &FontBook,
+) -> Result<Vec<u8>> {
+    Ok(render_slide(document, slide_path, width, fonts)?.encode_png()?)
+}
+
+/// Renders a slide into a pixmap of the given width in pixels.
+///
+/// The background of the slide is drawn first, followed by the shapes of the slide master and the slide layout, if the
+/// slide shows master shapes, and finally the shapes of the slide. Geometries are filled with solid, gradient or
+/// picture fills and outlined with dashes and arrowheads. PNG and JPEG pictures are drawn, other picture formats are
+/// skipped. Text is laid out and drawn with the fonts of `fonts`.
+pub fn render_slide(document: &PPTXDocument, slide_path: &Path, width: u32, fonts: &FontBook) -> Result<Pixmap> {
+    let context = document
+        .style_context(slide_path)
+        .ok_or_else(|| format!("slide not found: {}", slide_path.display()))?;
+    let slide_size = document.slide_size();
+    if slide_size.width == 0 || slide_size.height == 0 {
+        return Err("the slide size is empty".into());
+    }
+
+    let scale = width as f32 / slide_size.width as f32;
+    let height = (slide_size.height as f32 * scale).round().max(1.0) as u32;
+    let pixmap = Pixmap::new(width, height).ok_or("invalid image size")?;
+
+    let mut renderer = RasterRenderer {
+        context,
+        fonts,
+        pixmap,
+        images: HashMap::new(),
+    };
+
+    let transform = Transform::from_scale(scale, scale);
+    let background = document
+        .effective_background(renderer.context.slide)
+        .and_then(|background| {
+            renderer
+                .context
+                .resolve_fill(&background.fill, None, &background.source_part)
+        })
+        .unwrap_or_else(|| ShapePaint::Solid(crate::drawingml::color::RgbaColor::new(255, 255, 255, 255)));
+    let background_path = rectangle_path(slide_size.width as f32, slide_size.height as f32);
+    if let Some(background_path) = background_path {
+        renderer.fill_path(
+            &background_path,
+            &background,
+            slide_size.width as f32,
+            slide_size.height as f32,
+            transform,
+        );
+    }
+
+    for (part_path, shape_tree) in renderer.context.shape_trees() {
+        let draw_placeholders = part_path == slide_path;
+        renderer.render_group(shape_tree, &part_path, transform, draw_placeholders)?;
+    }
+
+    Ok(renderer.pixmap)
+}
+
+struct RasterRenderer<'a, 'b> {
+    context: StyleContext<'a>,
+    fonts: &'b FontBook,
+    pixmap: Pixmap,
+    /// Decoded pictures by the path of their media part. None if the picture couldn't be decoded.
+    images: HashMap<PathBuf, Option<Pixmap>>,
+}
+
+impl<'a, 'b> RasterRenderer<'a, 'b> {
+    /// Renders the shapes of a group. `part_path` is the path of the part containing the shapes and `transform` maps
+    /// the coordinate space of the group into the pixmap. Placeholders are only drawn if `draw_placeholders` is true.
+    fn render_group(
+        &mut self,
+        group: &'a GroupShape,
+        part_path: &Path,
+        transform: Transform,
+        draw_placeholders: bool,
+    ) -> Result<()> {
+        for shape_group in &group.shape_array {
+            match shape_group {
+                ShapeGroup::Shape(shape) => {
+                    let non_visual_props = &shape.non_visual_props;
+                    if is_drawn(
+                        &non_visual_props.drawing_props,
+                        &non_visual_props.app_props,
+                        draw_placeholders,
+                    ) {
+                        self.render_shape(shape, part_path, transform)?;
+                    }
+                }
+                ShapeGroup::GroupShape(group_shape) => {
+                    let non_visual_props = &group_shape.non_visual_props;
+                    if !is_drawn(&non_visual_props.drawing_props, &non_visual_props.app_props, true) {
+                        continue;
+                    }
+
+                    let group_transform = match &group_shape.group_shape_props.transform {
+                        Some(group_transform) => child_space_transform(transform, group_transform),
+                        None => transform,
+                    };
+                    self.render_group(group_shape, part_path, group_transform, draw_placeholders)?;
+                }
+                ShapeGroup::Picture(picture) => {
+                    let non_visual_props = &picture.non_visual_props;
+                    if !is_drawn(
+                        &non_visual_props.drawing_props,
+                        &non_visual_props.app_props,
+                        draw_placeholders,
+                    ) {
+                        continue;
+                    }
+
+                    let picture_transform = match self
+                        .context
+                        .transform(&picture.shape_props, non_visual_props.app_props.placeholder.as_ref())
+                    {
+                        Some(picture_transform) => picture_transform,
+                        None => continue,
+                    };
+                    let (width, height) = extents(picture_transform);
+                    let shape_transform = shape_transform(transform, picture_transform);
+
+                    let media_path = picture
+                        .blip_fill
+                        .blip
+                        .as_ref()
+                        .and_then(|blip| blip.embed_rel_id.as_ref())
+                        .and_then(|rel_id| self.context.document.relationship_target_path(part_path, rel_id));
+                    if let Some(media_path) = media_path {
+                        let paint = ShapePaint::Image { media_path };
+                        if let Some(path) = rectangle_path(width, height) {
+                            self.fill_path(&path, &paint, width, height, shape_transform);
+                        }
+                    }
+
+                    let stroke = self.context.stroke(
+                        picture.shape_props.line_properties.as_deref(),
+                        picture.shape_style.as_deref(),
+                    );
+                    if let Some(stroke) = stroke {
+                        let paths = shape_paths(picture.shape_props.geometry.as_ref(), width.into(), height.into());
+                        self.render_paths(&paths, None, Some(&stroke), width, height, shape_transform);
+                    }
+                }
+                ShapeGroup::Connector(connector) => {
+                    let non_visual_props = &connector.non_visual_props;
+                    if !is_drawn(&non_visual_props.drawing_props, &non_visual_props.app_props, true) {
+                        continue;
+                    }
+
+                    let connector_transform = match &connector.shape_props.transform {
+                        Some(connector_transform) => connector_transform,
+                        None => continue,
+                    };
+                    let stroke = self.context.stroke(
+                        connector.shape_props.line_properties.as_deref(),
+                        connector.shape_style.as_deref(),
+                    );
+                    if let Some(stroke) = stroke {
+                        let (width, height) = extents(connector_transform);
+                        let paths = shape_paths(connector.shape_props.geometry.as_ref(), width.into(), height.into());
+                        let shape_transform = shape_transform(transform, connector_transform);
+                        self.render_paths(&paths, None, Some(&stroke), width, height, shape_transform);
+                    }
+                }
+                ShapeGroup::GraphicFrame(graphic_frame) => {
+                    let non_visual_props = &graphic_frame.non_visual_props;
+                    if !is_drawn(
+                        &non_visual_props.drawing_props,
+                        &non_visual_props.app_props,
+                        draw_placeholders,
+                    ) {
+                        continue;
+                    }
+
+                    if let Some(GraphicalObjectContent::Table(table)) = &graphic_frame.graphic_content {
+                        let frame_transform = shape_transform(transform, &graphic_frame.transform);
+                        let border_paint = solid_paint(Color::BLACK);
+                        let border = Stroke {
+                            width: 9525.0,
+                            ..Default::default()
+                        };
+
+                        let column_widths = table
+                            .grid_column_list
+                            .iter()
+                            .map(|column| column.width as f32)
+                            .collect::<Vec<_>>();
+                        let mut top = 0.0;
+                        for row in &table.row_list {
+                            let row_height = row.height as f32;
+                            let mut left = 0.0;
+                            for (column, cell) in row.cell_list.iter().enumerate() {
+                                let column_width = column_widths.get(column).copied().unwrap_or(0.0);
+                                if !cell.is_merged() {
+                                    let span = cell.grid_span.unwrap_or(1).max(1) as usize;
+                                    let cell_width = column_widths.iter().skip(column).take(span).sum::<f32>();
+                                    let cell_transform = frame_transform.pre_translate(left, top);
+                                    if let Some(path) = rectangle_path(cell_width, row_height) {
+                                        self.pixmap
+                                            .stroke_path(&path, &border_paint, &border, cell_transform, None);
+                                    }
+                                    if let Some(text_body) = &cell.text_body {
+                                        let lines = layout_text_body(
+                                            &self.context,
+                                            None,
+                                            text_body,
+                                            cell_width.into(),
+                                            row_height.into(),
+                                            self.fonts,
+                                        );
+                                        self.render_text(&lines, cell_transform);
+                                    }
+                                }
+                                left += column_width;
+                            }
+                            top += row.height as f32;
+                        }
+                    }
+                }
+                ShapeGroup::ContentPart(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_shape(&mut self, shape: &'a Shape, part_path: &Path, transform: Transform) -> Result<()> {
+        let placeholder = shape.non_visual_props.app_props.placeholder.as_ref();
+        let shape_transform2d = match self.context.transform(&shape.shape_props, placeholder) {
+            Some(shape_transform2d) => shape_transform2d,
+            None => return Ok(()),
+        };
+
+        let (width, height) = extents(shape_transform2d);
+        let fill = self.context.shape_fill(shape, part_path);
+        let stroke = self.context.stroke(
+            shape.shape_props.line_properties.as_deref(),
+            shape.shape_style.as_deref(),
+        );
+        if fill.is_some() || stroke.is_some() {
+            let paths = shape_paths(shape.shape_props.geometry.as_ref(), width.into(), height.into());
+            self.render_paths(
+                &paths,
+                fill.as_ref(),
+                stroke.as_ref(),
+                width,
+                height,
+                shape_transform(transform, shape_transform2d),
+            );
+        }
+
+        // Text isn't mirrored by flips, so it's drawn with the rotation of the shape only
+        if let Some(text_body) = &shape.text_body {
+            let lines = layout_text_body(
+                &self.context,
+                Some(shape),
+                text_body,
+                width.into(),
+                height.into(),
+                self.fonts,
+            );
+            let text_transform = shape_transform(
+                transform,
+                &Transform2D {
+                    flip_horizontal: None,
+                    flip_vertical: None,
+                    ..shape_transform2d.clone()
+                },
+            );
+            self.render_text(&lines, text_transform);
+        }
+
+        Ok(())
+    }
+
+    /// Fills and strokes the paths of a geometry of a shape with the given size. `transform` maps the coordinate space
+    /// of the shape into the pixmap.
+    fn render_paths(
+        &mut self,
+        paths: &[ShapePath],
+        fill: Option<&ShapePaint>,
+        stroke: Option<&ShapeStroke>,
+        width: f32,
+        height: f32,
+        transform: Transform,
+    ) {
+        for shape_path in paths {
+            let path = match to_path(shape_path) {
+                Some(path) => path,
+                None => continue,
+            };
+
+            if let (Some(fill), true) = (fill, shape_path.is_filled()) {
+                self.fill_path(&path, fill, width, height, transform);
+
+                // Lighten and darken modes are approximated with a translucent white or black layer
+                let overlay = match shape_path.fill_mode {
+                    PathFillMode::Lighten => Some(Color::from_rgba8(255, 255, 255, 102)),
+                    PathFillMode::LightenLess => Some(Color::from_rgba8(255, 255, 255, 51)),
+                    PathFillMode::Darken => Some(Color::from_rgba8(0, 0, 0, 102)),
+                    PathFillMode::DarkenLess => Some(Color::from_rgba8(0, 0, 0, 51)),
+                    _ => None,
+                };
+                if let Some(overlay) = overlay {
+                    self.pixmap
+                        .fill_path(&path, &solid_paint(overlay), FillRule::Winding, transform, None);
+                }
+            }
+
+            if let (Some(stroke), true) = (stroke, shape_path.stroke) {
+                let paint = solid_paint(to_color(stroke.color));
+                let line_cap = match stroke.cap {
+                    Some(ShapeLineCap::Round) => LineCap::Round,
+                    Some(ShapeLineCap::Square) => LineCap::Square,
+                    _ => LineCap::Butt,
+                };
+                let dash = stroke.dash_pattern().and_then(|pattern| {
+                    StrokeDash::new(pattern.into_iter().map(|length| length as f32).collect(), 0.0)
+                });
+                let line = Stroke {
+                    width: stroke.width as f32,
+                    line_cap,
+                    dash,
+                    ..Default::default()
+                };
+                self.pixmap.stroke_path(&path, &paint, &line, transform, None);
+
+                if !shape_path.is_closed() {
+                    let ends = line_ends(shape_path);
+                    if let (Some(head_end), Some((tip, direction))) = (&stroke.head_end, ends.0) {
+                        self.render_line_end(head_end, stroke, tip, direction, transform);
+                    }
+                    if let (Some(tail_end), Some((tip, direction))) = (&stroke.tail_end, ends.1) {
+                        self.render_line_end(tail_end, stroke, tip, direction, transform);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws the decoration of a line end pointing in `direction` (in radians) with its tip at `tip`.
+    fn render_line_end(
+        &mut self,
+        line_end: &LineEndProperties,
+        stroke: &ShapeStroke,
+        tip: Point,
+        direction: f32,
+        transform: Transform,
+    ) {
+        let line_width = (stroke.width as f32).max(9525.0);
+        let width = line_width
+            * match line_end.width {
+                Some(LineEndWidth::Small) => 2.0,
+                Some(LineEndWidth::Large) => 5.0,
+                _ => 3.0,
+            };
+        let length = line_width
+            * match line_end.length {
+                Some(LineEndLength::Small) => 2.0,
+                Some(LineEndLength::Large) => 5.0,
+                _ => 3.0,
+            };
+
+        // The decoration is built pointing to the right with its tip at the origin
+        let mut builder = PathBuilder::new();
+        let filled = match line_end.end_type {
+            Some(LineEndType::Triangle) => {
+                builder.move_to(0.0, 0.0);
+                builder.line_to(-length, -width / 2.0);
+                builder.line_to(-length, width / 2.0);
+                builder.close();
+                true
+            }
+            Some(LineEndType::Stealth) => {
+                builder.move_to(0.0, 0.0);
+                builder.line_to(-length, -width / 2.0);
+                builder.line_to(-length * 0.7, 0.0);
+                builder.line_to(-length, width / 2.0);
+                builder.close();
+                true
+            }
+            Some(LineEndType::Diamond) => {
+                builder.move_to(length / 2.0, 0.0);
+                builder.line_to(0.0, -width / 2.0);
+                builder.line_to(-length / 2.0, 0.0);
+                builder.line_to(0.0, width / 2.0);
+                builder.close();
+                true
+            }
+            Some(LineEndType::Oval) => {
+                if let Some(oval) = Rect::from_xywh(-length / 2.0, -width / 2.0, length, width) {
+                    builder.push_oval(oval);
+                }
+                true
+            }
+            Some(LineEndType::Arrow) => {
+                builder.move_to(-length, -width / 2.0);
+                builder.line_to(0.0, 0.0);
+                builder.line_to(-length, width / 2.0);
+                false
+            }
+            Some(LineEndType::None) | None => return,
+        };
+        let path = match builder.finish() {
+            Some(path) => path,
+            None => return,
+        };
+
+        let paint = solid_paint(to_color(stroke.color));
+        let end_transform = transform.pre_translate(tip.x, tip.y).pre_rotate(direction.to_degrees());
+        if filled {
+            self.pixmap
+                .fill_path(&path, &paint, FillRule::Winding, end_transform, None);
+        } else {
+            let line = Stroke {
+                width: line_width,
+                ..Default::default()
+            };
+            self.pixmap.stroke_path(&path, &paint, &line, end_transform, None);
+        }
+    }
+
+    /// Draws laid out text. `transform` maps the coordinate space of the text body into the pixmap.
+    fn render_text(&mut self, lines: &[TextLine], transform: Transform) {
+        for line in lines {
+            for fragment in &line.fragments {
+                let style = &fragment.style;
+                let color = match style.color {
+                    Some(color) => color,
+                    None => continue,
+                };
+                let font = match self.fonts.font(style) {
+                    Some(font) => font,
+                    None => continue,
+                };
+
+                let font_size = (style.font_size * EMUS_PER_POINT) as f32;
+                let units_per_em = font.units_per_em().unwrap_or(1000.0);
+                let scale = font_size / units_per_em;
+                let paint = solid_paint(to_color(color));
+                let fragment_transform = transform.pre_translate(fragment.x as f32, line.baseline as f32);
+
+                // Glyph outlines are in font units with the y axis pointing up
+                let mut builder = PathBuilder::new();
+                let mut cursor = 0.0;
+                let mut previous = None;
+                for c in fragment.text.chars() {
+                    let glyph_id = font.glyph_id(c);
+                    if let Some(previous) = previous {
+                        cursor += font.kern_unscaled(previous, glyph_id);
+                    }
+                    if let Some(outline) = font.outline(glyph_id) {
+                        push_outline(&mut builder, &outline.curves, cursor);
+                    }
+                    cursor += font.h_advance_unscaled(glyph_id);
+                    previous = Some(glyph_id);
+                }
+                if let Some(path) = builder.finish() {
+                    self.pixmap.fill_path(
+                        &path,
+                        &paint,
+                        FillRule::Winding,
+                        fragment_transform.pre_scale(scale, -scale),
+                        None,
+                    );
+                }
+
+                let decoration_width = cursor * scale;
+                let thickness = font_size / 18.0;
+                let mut decorations = Vec::new();
+                if style.underline {
+                    decorations.push(font_size * 0.1);
+                }
+                if style.strikethrough {
+                    decorations.push(-font_size * 0.3);
+                }
+                for offset in decorations {
+                    if let Some(rect) = Rect::from_xywh(0.0, offset - thickness / 2.0, decoration_width, thickness) {
+                        self.pixmap.fill_rect(rect, &paint, fragment_transform, None);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fills a path of a shape with the given size with a paint.
+    fn fill_path(&mut self, path: &tiny_skia::Path, paint: &ShapePaint, width: f32, height: f32, transform: Transform) {
+        if let ShapePaint::Image { media_path } = paint {
+            if !self.images.contains_key(media_path) {
+                let image = self
+                    .context
+                    .document
+                    .read_part(media_path)
+                    .ok()
+                    .and_then(|data| decode_image(&data));
+                self.images.insert(media_path.clone(), image);
+            }
+        }
+
+        if let Some(shader) = shader(paint, width, height, &self.images) {
+            let paint = Paint {
+                shader,
+                anti_alias: true,
+                ..Default::default()
+            };
+            self.pixmap.fill_path(path, &paint, FillRule::Winding, transform, None);
+        }
+    }
+}
+
+/// Returns the shader of a paint for a shape with the given size.
+fn shader<'p>(
+    paint: &ShapePaint,
+    width: f32,
+    height: f32,
+    images: &'p HashMap<PathBuf, Option<Pixmap>>,
+) -> Option<Shader<'p>> {
+    let gradient_stops = |stops: &[crate::style::GradientStop]| {
+        stops
+            .iter()
+            .map(|stop| GradientStop::new(stop.position as f32, to_color(stop.color)))
+            .collect::<Vec<_>>()
+    };
+
+    match paint {
+        ShapePaint::Solid(color) => Some(Shader::SolidColor(to_color(*color))),
+        ShapePaint::LinearGradient { angle, stops } => {
+            // The gradient line goes through the center of the shape and is long enough to cover the whole shape
+            let (sin, cos) = (*angle as f32).to_radians().sin_cos();
+            let half_length = ((width * cos).abs() + (height * sin).abs()) / 2.0;
+            let center = Point::from_xy(width / 2.0, height / 2.0);
+            LinearGradient::new(
+                Point::from_xy(center.x - cos * half_length, center.y - sin * half_length),
+                Point::from_xy(center.x + cos * half_length, center.y + sin * half_length),
+                gradient_stops(stops),
+                SpreadMode::Pad,
+                Transform::identity(),
+            )
+        }
+        ShapePaint::RadialGradient { stops } => {
+            let center = Point::from_xy(width / 2.0, height / 2.0);
+            RadialGradient::new(
+                center,
+                center,
+                (width * width + height * height).sqrt() / 2.0,
+                gradient_stops(stops),
+                SpreadMode::Pad,
+                Transform::identity(),
+            )
+        }
+        ShapePaint::Image { media_path } => {
+            let image = images.get(media_path)?.as_ref()?;
+            Some(Pattern::new(
+                image.as_ref(),
+                SpreadMode::Pad,
+                FilterQuality::Bicubic,
+                1.0,
+                Transform::from_scale(width / image.width() as f32, height / image.height() as f32),
+            ))
+        }
+    }
+}
+
+/// Returns the transform that maps the coordinate space of a shape into the pixmap, given the transform of the
+/// coordinate space of its group.
+fn shape_transform(transform: Transform, shape_transform: &Transform2D) -> Transform {
+    let offset = shape_transform.offset.unwrap_or_default();
+    let (width, height) = extents(shape_transform);
+    placement_transform(
+        transform,
+        offset.x as f32,
+        offset.y as f32,
+        width,
+        height,
+        shape_transform.rotate_angle,
+        shape_transform.flip_horizontal,
+        shape_transform.flip_vertical,
+    )
+}
+
+/// Returns the transform that maps the child coordinate space of a group into the pixmap, given the transform of the
+/// coordinate space of its parent.
+fn child_space_transform(transform: Transform, group_transform: &GroupTransform2D) -> Transform {
+    let offset = group_transform.offset.unwrap_or_default();
+    let extents = group_transform.extents.unwrap_or_default();
+    let child_offset = group_transform.child_offset.unwrap_or_default();
+    let child_extents = group_transform.child_extents.unwrap_or_default();
+    let scale = |extent: u64, child_extent: u64| {
+        if child_extent == 0 {
+            1.0
+        } else {
+            extent as f32 / child_extent as f32
+        }
+    };
+
+    placement_transform(
+        transform,
+        offset.x as f32,
+        offset.y as f32,
+        extents.width as f32,
+        extents.height as f32,
+        group_transform.rotate_angle,
+        group_transform.flip_horizontal,
+        group_transform.flip_vertical,
+    )
+    .pre_scale(
+        scale(extents.width, child_extents.width),
+        scale(extents.height, child_extents.height),
+    )
+    .pre_translate(-child_offset.x as f32, -child_offset.y as f32)
+}
+
+/// Places a box of the given size at (x, y), rotated and flipped around its center.
+#[allow(clippy::too_many_arguments)]
+fn placement_transform(
+    transform: Transform,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    rotate_angle: Option<i32>,
+    flip_horizontal: Option<bool>,
+    flip_vertical: Option<bool>,
+) -> Transform {
+    let mut transform = transform.pre_translate(x, y);
+    if let Some(angle) = rotate_angle.filter(|angle| *angle != 0) {
+        transform = transform.pre_rotate_at(angle as f32 / 60000.0, width / 2.0, height / 2.0);
+    }
+    if flip_horizontal.unwrap_or(false) {
+        transform = transform.pre_translate(width, 0.0).pre_scale(-1.0, 1.0);
+    }
+    if flip_vertical.unwrap_or(false) {
+        transform = transform.pre_translate(0.0, height).pre_scale(1.0, -1.0);
+    }
+    transform
+}
+
+/// Returns the width and height of a transform in EMUs.
+fn extents(transform: &Transform2D) -> (f32, f32) {
+    let extents = transform.extents.unwrap_or_default();
+    (extents.width as f32, extents.height as f32)
+}
+
+fn to_path(shape_path: &ShapePath) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+    for segment in &shape_path.segments {
+        match segment {
+            PathSegment::MoveTo(to) => builder.move_to(to.x as f32, to.y as f32),
+            PathSegment::LineTo(to) => builder.line_to(to.x as f32, to.y as f32),
+            PathSegment::QuadraticBezierTo(control, to) => {
+                builder.quad_to(control.x as f32, control.y as f32, to.x as f32, to.y as f32)
+            }
+            PathSegment::CubicBezierTo(control1, control2, to) => builder.cubic_to(
+                control1.x as f32,
+                control1.y as f32,
+                control2.x as f32,
+                control2.y as f32,
+                to.x as f32,
+                to.y as f32,
+            ),
+            PathSegment::Close => builder.close(),
+        }
+    }
+    builder.finish()
+}
+
+fn rectangle_path(width: f32, height: f32) -> Option<tiny_skia::Path> {
+    Rect::from_xywh(0.0, 0.0, width, height).map(PathBuilder::from_rect)
+}
+
+/// Returns the tips and the directions of the start and the end of an open path. The direction of the start points
+/// away from the path, so decorations at both ends can be drawn the same way.
+#[allow(clippy::type_complexity)]
+fn line_ends(shape_path: &ShapePath) -> (Option<(Point, f32)>, Option<(Point, f32)>) {
+    let mut points = Vec::new();
+    for segment in &shape_path.segments {
+        match segment {
+            PathSegment::MoveTo(to) | PathSegment::LineTo(to) => points.push(*to),
+            PathSegment::QuadraticBezierTo(control, to) => points.extend(&[*control, *to]),
+            PathSegment::CubicBezierTo(control1, control2, to) => points.extend(&[*control1, *control2, *to]),
+            PathSegment::Close => (),
+        }
+    }
+
+    let direction = |from: &crate::drawingml::geometry::Point, to: &crate::drawingml::geometry::Point| {
+        (
+            Point::from_xy(to.x as f32, to.y as f32),
+            ((to.y - from.y) as f32).atan2((to.x - from.x) as f32),
+        )
+    };
+    let first = points.first();
+    let start = first.and_then(|first| {
+        points
+            .iter()
+            .find(|point| *point != first)
+            .map(|next| direction(next, first))
+    });
+    let last = points.last();
+    let end = last.and_then(|last| {
+        points
+            .iter()
+            .rev()
+            .find(|point| *point != last)
+            .map(|previous| direction(previous, last))
+    });
+
+    (start, end)
+}
+
+/// Adds the outline of a glyph to a path. `offset` is the horizontal position of the glyph in font units.
+fn push_outline(builder: &mut PathBuilder, curves: &[OutlineCurve], offset: f32) {
+    let mut last = None;
+    for curve in curves {
+        let (start, end) = match curve {
+            OutlineCurve::Line(start, end) => (*start, *end),
+            OutlineCurve::Quad(start, _, end) => (*start, *end),
+            OutlineCurve::Cubic(start, _, _, end) => (*start, *end),
+        };
+        // Curves of a contour are connected, so a gap starts a new contour
+        if last != Some(start) {
+            if last.is_some() {
+                builder.close();
+            }
+            builder.move_to(start.x + offset, start.y);
+        }
+
+        match curve {
+            OutlineCurve::Line(_, end) => builder.line_to(end.x + offset, end.y),
+            OutlineCurve::Quad(_, control, end) => {
+                builder.quad_to(control.x + offset, control.y, end.x + offset, end.y)
+            }
+            OutlineCurve::Cubic(_, control1, control2, end) => builder.cubic_to(
+                control1.x + offset,
+                control1.y,
+                control2.x + offset,
+                control2.y,
+                end.x + offset,
+                end.y,
+            ),
+        }
+        last = Some(end);
+    }
+    if last.is_some() {
+        builder.close();
+    }
+}
+
+fn solid_paint(color: Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(color);
+    paint.anti_alias = true;
+    paint
+}
+
+fn to_color(color: crate::drawingml::color::RgbaColor) -> Color {
+    Color::from_rgba8(color.red, color.green, color.blue, color.alpha)
+}
+
+/// Parses a font file. Embedded fonts of presentations are stored in the Embedded OpenType format, which wraps the
+/// font data with a header and optionally obfuscates it.
+fn parse_font(data: Vec<u8>) -> Result<FontArc> {
+    const EOT_MAGIC_NUMBER: u16 = 0x504c;
+    const EOT_COMPRESSED: u32 = 0x4;
+    const EOT_XOR_ENCRYPTED: u32 = 0x1000_0000;
+
+    let read_u32 = |offset: usize| {
+        data.get(offset..offset + 4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+    let magic_number = data.get(34..36).map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]));
+
+    let font_data = match (read_u32(0), read_u32(4), read_u32(12), magic_number) {
+        (Some(eot_size), Some(font_data_size), Some(flags), Some(EOT_MAGIC_NUMBER))
+            if eot_size as usize <= data.len() && font_data_size <= eot_size =>
+        {
+            if flags & EOT_COMPRESSED != 0 {
+                return Err("compressed embedded fonts are not supported".into());
+            }
+
+            // The font data is the last field of the header
+            let mut font_data = data[(eot_size - font_data_size) as usize..eot_size as usize].to_vec();
+            if flags & EOT_XOR_ENCRYPTED != 0 {
+                font_data.iter_mut().for_each(|byte| *byte ^= 0x50);
+            }
+            font_data
+        }
+        _ => data,
+    };
+
+    Ok(FontArc::new(FontVec::try_from_vec(font_data)?))
+}
+
+/// Decodes a PNG or JPEG picture into a pixmap. Returns None for other formats.
+fn decode_image(data: &[u8]) -> Option<Pixmap> {
+    if data.starts_with(b"\x89PNG") {
+        return Pixmap::decode_png(data).ok();
+    }
+    if !data.starts_with(&[0xff, 0xd8]) {
+        return None;
+    }
+
+    let mut decoder = jpeg_decoder::Decoder::new(data);
+    let pixels = decoder.decode().ok()?;
+    let info = decoder.info()?;
+    let rgba = match info.pixel_format {
+        jpeg_decoder::PixelFormat::L8 => pixels.iter().flat_map(|&l| vec![l, l, l, 255]).collect(),
+        jpeg_decoder::PixelFormat::L16 => pixels
+            .chunks_exact(2)
+            .flat_map(|l| vec![l[0], l[0], l[0], 255])
+            .collect(),
+        jpeg_decoder::PixelFormat::RGB24 => pixels
+            .chunks_exact(3)
+            .flat_map(|rgb| vec![rgb[0], rgb[1], rgb[2], 255])
+            .collect(),
+        jpeg_decoder::PixelFormat::CMYK32 => pixels
+            .chunks_exact(4)
+            .flat_map(|cmyk| {
+                let k = 255 - u16::from(cmyk[3]);
+                let channel = |value: u8| ((255 - u16::from(value)) * k / 255) as u8;
+                vec![channel(cmyk[0]), channel(cmyk[1]), channel(cmyk[2]), 255]
+            })
+            .collect(),
+    };
+
+    Pixmap::from_vec(rgba, IntSize::from_wh(u32::from(info.width), u32::from(info.height))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawingml::geometry::Point as ShapePoint;
+
+    #[test]
+    pub fn test_placement_transform() {
+        let transform = placement_transform(
+            Transform::identity(),
+            10.0,
+            20.0,
+            100.0,
+            50.0,
+            Some(10_800_000),
+            Some(true),
+            None,
+        );
+        let mut points = [Point::from_xy(0.0, 0.0), Point::from_xy(100.0, 0.0)];
+        transform.map_points(&mut points);
+        // Rotating by 180 degrees and flipping horizontally is a vertical flip
+        assert!((points[0].x - 10.0).abs() < 1e-3 && (points[0].y - 70.0).abs() < 1e-3);
+        assert!((points[1].x - 110.0).abs() < 1e-3 && (points[1].y - 70.0).abs() < 1e-3);
+    }
+
+    #[test]
+    pub fn test_line_ends() {
+        let shape_path = ShapePath {
+            segments: vec![
+                PathSegment::MoveTo(ShapePoint::new(0.0, 0.0)),
+                PathSegment::LineTo(ShapePoint::new(100.0, 0.0)),
+                PathSegment::LineTo(ShapePoint::new(100.0, 100.0)),
+            ],
+            fill_mode: PathFillMode::None,
+            stroke: true,
+        };
+        let (start, end) = line_ends(&shape_path);
+        let (start_tip, start_direction) = start.unwrap();
+        let (end_tip, end_direction) = end.unwrap();
+        assert_eq!(start_tip, Point::from_xy(0.0, 0.0));
+        assert!((start_direction.abs() - std::f32::consts::PI).abs() < 1e-6);
+        assert_eq!(end_tip, Point::from_xy(100.0, 100.0));
+        assert!((end_direction - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    pub fn test_parse_font() {
+        assert!(parse_font(vec![0; 64]).is_err());
+    }
+
+    #[test]
+    pub fn test_render_slide_png() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let sample_pptx_path = test_dir.join("tests/samplepptx.pptx");
+
+        let document = PPTXDocument::from_file(&sample_pptx_path).unwrap();
+        let png = render_slide_png(&document, Path::new("ppt/slides/slide2.xml"), 480).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        // The width and the height are the first fields of the IHDR chunk
+        assert_eq!(&png[16..24], &[0, 0, 1, 224, 0, 0, 1, 104]);
+        assert!(render_slide_png(&document, Path::new("ppt/slides/slide3.xml"), 480).is_err());
+    }
+}
@@ -0,0 +1,814 @@
+use crate::document::PPTXDocument;
+use crate::lookup::ShapeRef;
+use crate::pml::slides::{CommonSlideData, GroupShape, ShapeGroup};
+use crate::style::placeholder_type;
+use std::{error::Error, fmt, path::Path, str::FromStr};
+
+pub const CHART_GRAPHIC_DATA_URI: &str = "http://schemas.openxmlformats.org/drawingml/2006/chart";
+
+/// An error of parsing a selector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectorParseError {
+    /// The byte offset of the error in the selector.
+    pub position: usize,
+    pub message: String,
+}
+
+impl SelectorParseError {
+    pub fn new<T: Into<String>>(position: usize, message: T) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SelectorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid selector at position {}: {}", self.position, self.message)
+    }
+}
+
+impl Error for SelectorParseError {}
+
+/// The kinds of parts that are searched by selectors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PartKind {
+    Slide,
+    SlideLayout,
+    SlideMaster,
+}
+
+/// An element selected by a selector. If `shape` is None, the part itself has been selected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectedElement<'a> {
+    pub part_kind: PartKind,
+    pub part_path: &'a Path,
+    pub shape: Option<ShapeRef<'a>>,
+}
+
+/// A parsed CSS-like selector, selecting elements of a presentation.
+///
+/// A selector is a sequence of compound selectors separated by combinators. Whitespace selects descendants and `>`
+/// selects direct children. Several selectors can be separated by commas. A compound selector consists of an
+/// optional element type followed by any number of attribute selectors and pseudo-classes:
+///
+/// * Element types are the parts `slide`, `layout` and `master`, the shape tree elements `sp`, `grpSp`,
+///   `graphicFrame`, `cxnSp`, `pic` and `contentPart`, or `*` for any element.
+/// * `[3]` selects the third element of its type among its siblings, e.g. `slide[3]` is the third slide of the
+///   presentation.
+/// * `[attr]` selects elements having the attribute. `[attr=value]`, `[attr!=value]`, `[attr^=value]`,
+///   `[attr$=value]` and `[attr*=value]` compare its value for equality, inequality, prefix, suffix and substring. A
+///   missing attribute compares as an empty string, so `pic[descr=""]` selects pictures without alternative text.
+///   Values can be quoted with double or single quotes.
+/// * `:table`, `:diagram` and `:chart` select graphic frames by their content, `:placeholder` selects placeholders and
+///   `:hidden` selects hidden elements.
+///
+/// The attributes of shape tree elements are `id`, `name`, `descr`, `title` and `hidden` from their non-visual drawing
+/// properties, `ph` (the placeholder type, e.g. `title`) and `idx` (the placeholder index). The attributes of parts are
+/// `name` (the name of the common slide data) and `path` (the path of the part within the package).
+///
+/// ```ignore
+/// let selector: Selector = "slide[3] sp[ph=title], grpSp > cxnSp".parse()?;
+/// for selected in document.select(&selector) { ... }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    alternatives: Vec<ComplexSelector>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ComplexSelector {
+    /// The compound selectors from left to right. The combinator of the first one is ignored.
+    compounds: Vec<(Combinator, CompoundSelector)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct CompoundSelector {
+    element_type: Option<ElementType>,
+    position: Option<usize>,
+    attributes: Vec<AttributeSelector>,
+    pseudo_classes: Vec<PseudoClass>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ElementType {
+    Slide,
+    SlideLayout,
+    SlideMaster,
+    Shape,
+    GroupShape,
+    GraphicFrame,
+    Connector,
+    Picture,
+    ContentPart,
+}
+
+impl ElementType {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "slide" | "sld" => Some(ElementType::Slide),
+            "layout" | "sldLayout" => Some(ElementType::SlideLayout),
+            "master" | "sldMaster" => Some(ElementType::SlideMaster),
+            "sp" => Some(ElementType::Shape),
+            "grpSp" => Some(ElementType::GroupShape),
+            "graphicFrame" => Some(ElementType::GraphicFrame),
+            "cxnSp" => Some(ElementType::Connector),
+            "pic" => Some(ElementType::Picture),
+            "contentPart" => Some(ElementType::ContentPart),
+            _ => None,
+        }
+    }
+
+    fn of_shape_group(shape_group: &ShapeGroup) -> Self {
+        match shape_group {
+            ShapeGroup::Shape(_) => ElementType::Shape,
+            ShapeGroup::GroupShape(_) => ElementType::GroupShape,
+            ShapeGroup::GraphicFrame(_) => ElementType::GraphicFrame,
+            ShapeGroup::Connector(_) => ElementType::Connector,
+            ShapeGroup::Picture(_) => ElementType::Picture,
+            ShapeGroup::ContentPart(_) => ElementType::ContentPart,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct AttributeSelector {
+    name: Attribute,
+    /// None only checks the presence of the attribute.
+    condition: Option<(Operator, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Attribute {
+    Id,
+    Name,
+    Description,
+    Title,
+    Hidden,
+    PlaceholderType,
+    PlaceholderIndex,
+    Path,
+}
+
+impl Attribute {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "id" => Some(Attribute::Id),
+            "name" => Some(Attribute::Name),
+            "descr" => Some(Attribute::Description),
+            "title" => Some(Attribute::Title),
+            "hidden" => Some(Attribute::Hidden),
+            "ph" => Some(Attribute::PlaceholderType),
+            "idx" => Some(Attribute::PlaceholderIndex),
+            "path" => Some(Attribute::Path),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Prefix,
+    Suffix,
+    Contains,
+}
+
+impl Operator {
+    fn matches(self, actual: &str, expected: &str) -> bool {
+        match self {
+            Operator::Equal => actual == expected,
+            Operator::NotEqual => actual != expected,
+            Operator::Prefix => actual.starts_with(expected),
+            Operator::Suffix => actual.ends_with(expected),
+            Operator::Contains => actual.contains(expected),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PseudoClass {
+    Table,
+    Diagram,
+    Chart,
+    Placeholder,
+    Hidden,
+}
+
+impl PseudoClass {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "table" => Some(PseudoClass::Table),
+            "diagram" => Some(PseudoClass::Diagram),
+            "chart" => Some(PseudoClass::Chart),
+            "placeholder" => Some(PseudoClass::Placeholder),
+            "hidden" => Some(PseudoClass::Hidden),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser { input: s, position: 0 }.parse_selector()
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn peek_is<P: Fn(char) -> bool>(&self, predicate: P) -> bool {
+        self.peek().filter(|&c| predicate(c)).is_some()
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.position;
+        while self.peek_is(char::is_whitespace) {
+            self.advance();
+        }
+        self.position != start
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), SelectorParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", c)))
+        }
+    }
+
+    fn error<T: Into<String>>(&self, message: T) -> SelectorParseError {
+        SelectorParseError::new(self.position, message)
+    }
+
+    fn identifier(&mut self) -> &'a str {
+        let start = self.position;
+        while self.peek_is(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            self.advance();
+        }
+        &self.input[start..self.position]
+    }
+
+    fn parse_selector(&mut self) -> Result<Selector, SelectorParseError> {
+        let mut alternatives = vec![self.parse_complex_selector()?];
+        while self.eat(',') {
+            alternatives.push(self.parse_complex_selector()?);
+        }
+
+        match self.peek() {
+            Some(c) => Err(self.error(format!("unexpected '{}'", c))),
+            None => Ok(Selector { alternatives }),
+        }
+    }
+
+    fn parse_complex_selector(&mut self) -> Result<ComplexSelector, SelectorParseError> {
+        self.skip_whitespace();
+        let mut compounds = vec![(Combinator::Descendant, self.parse_compound_selector()?)];
+        loop {
+            let had_whitespace = self.skip_whitespace();
+            let combinator = if self.eat('>') {
+                self.skip_whitespace();
+                Combinator::Child
+            } else if had_whitespace && self.peek_is(|c| c != ',') {
+                Combinator::Descendant
+            } else {
+                break;
+            };
+
+            compounds.push((combinator, self.parse_compound_selector()?));
+        }
+
+        Ok(ComplexSelector { compounds })
+    }
+
+    fn parse_compound_selector(&mut self) -> Result<CompoundSelector, SelectorParseError> {
+        let start = self.position;
+        let mut compound: CompoundSelector = Default::default();
+        if !self.eat('*') {
+            let name = self.identifier();
+            if !name.is_empty() {
+                compound.element_type = Some(
+                    ElementType::from_name(name)
+                        .ok_or_else(|| SelectorParseError::new(start, format!("unknown element type '{}'", name)))?,
+                );
+            }
+        }
+
+        loop {
+            if self.eat('[') {
+                self.parse_attribute_selector(&mut compound)?;
+            } else if self.eat(':') {
+                let name_position = self.position;
+                let name = self.identifier();
+                let pseudo_class = PseudoClass::from_name(name).ok_or_else(|| {
+                    SelectorParseError::new(name_position, format!("unknown pseudo-class '{}'", name))
+                })?;
+                compound.pseudo_classes.push(pseudo_class);
+            } else {
+                break;
+            }
+        }
+
+        if self.position == start {
+            return Err(self.error("expected a selector"));
+        }
+
+        Ok(compound)
+    }
+
+    fn parse_attribute_selector(&mut self, compound: &mut CompoundSelector) -> Result<(), SelectorParseError> {
+        self.skip_whitespace();
+        let name_position = self.position;
+        let name = self.identifier();
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_digit()) {
+            let position = name
+                .parse::<usize>()
+                .ok()
+                .filter(|&position| position > 0)
+                .ok_or_else(|| SelectorParseError::new(name_position, "positions start at 1"))?;
+            compound.position = Some(position);
+            self.skip_whitespace();
+            return self.expect(']');
+        }
+
+        let name = Attribute::from_name(name)
+            .ok_or_else(|| SelectorParseError::new(name_position, format!("unknown attribute '{}'", name)))?;
+        self.skip_whitespace();
+        let operator = match self.peek() {
+            Some('=') => Some(Operator::Equal),
+            Some('!') => Some(Operator::NotEqual),
+            Some('^') => Some(Operator::Prefix),
+            Some('$') => Some(Operator::Suffix),
+            Some('*') => Some(Operator::Contains),
+            _ => None,
+        };
+
+        let condition = match operator {
+            Some(operator) => {
+                if operator != Operator::Equal {
+                    self.advance();
+                }
+                self.expect('=')?;
+                self.skip_whitespace();
+                Some((operator, self.parse_value()?))
+            }
+            None => None,
+        };
+
+        self.skip_whitespace();
+        self.expect(']')?;
+        compound.attributes.push(AttributeSelector { name, condition });
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<String, SelectorParseError> {
+        let quote = match self.peek() {
+            Some(c @ '"') | Some(c @ '\'') => c,
+            _ => {
+                let start = self.position;
+                while self.peek_is(|c| !c.is_whitespace() && c != ']') {
+                    self.advance();
+                }
+                return Ok(String::from(&self.input[start..self.position]));
+            }
+        };
+
+        self.advance();
+        let mut value = String::new();
+        loop {
+            match self.advance() {
+                Some('\\') => value.extend(self.advance()),
+                Some(c) if c == quote => return Ok(value),
+                Some(c) => value.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+}
+
+/// An element of the chain from a part down to a selected element.
+#[derive(Clone, Copy)]
+enum Node<'a> {
+    Part {
+        kind: PartKind,
+        path: &'a Path,
+        /// The 1-based position of the part among the parts of the same kind.
+        position: usize,
+        common_slide_data: &'a CommonSlideData,
+    },
+    Shape {
+        shape_group: &'a ShapeGroup,
+        siblings: &'a [ShapeGroup],
+    },
+}
+
+impl<'a> Node<'a> {
+    fn element_type(&self) -> ElementType {
+        match self {
+            Node::Part { kind, .. } => match kind {
+                PartKind::Slide => ElementType::Slide,
+                PartKind::SlideLayout => ElementType::SlideLayout,
+                PartKind::SlideMaster => ElementType::SlideMaster,
+            },
+            Node::Shape { shape_group, .. } => ElementType::of_shape_group(shape_group),
+        }
+    }
+
+    fn position(&self) -> usize {
+        match self {
+            Node::Part { position, .. } => *position,
+            Node::Shape { shape_group, siblings } => {
+                let element_type = ElementType::of_shape_group(shape_group);
+                siblings
+                    .iter()
+                    .filter(|sibling| ElementType::of_shape_group(sibling) == element_type)
+                    .position(|sibling| std::ptr::eq(sibling, *shape_group))
+                    .map_or(0, |position| position + 1)
+            }
+        }
+    }
+
+    fn attribute(&self, attribute: Attribute) -> Option<String> {
+        match self {
+            Node::Part {
+                path,
+                common_slide_data,
+                ..
+            } => match attribute {
+                Attribute::Name => common_slide_data.name.clone(),
+                Attribute::Path => path.to_str().map(String::from),
+                _ => None,
+            },
+            Node::Shape { shape_group, .. } => {
+                let placeholder = shape_group
+                    .app_props()
+                    .and_then(|app_props| app_props.placeholder.as_ref());
+                let drawing_props = shape_group.drawing_props()?;
+                match attribute {
+                    Attribute::Id => Some(drawing_props.id.to_string()),
+                    Attribute::Name => Some(drawing_props.name.clone()),
+                    Attribute::Description => drawing_props.description.clone(),
+                    Attribute::Title => drawing_props.title.clone(),
+                    Attribute::Hidden => drawing_props.hidden.map(|hidden| hidden.to_string()),
+                    Attribute::PlaceholderType => {
+                        placeholder.map(|placeholder| String::from(placeholder_type(placeholder).as_ref()))
+                    }
+                    Attribute::PlaceholderIndex => {
+                        placeholder.map(|placeholder| placeholder.index.unwrap_or(0).to_string())
+                    }
+                    Attribute::Path => None,
+                }
+            }
+        }
+    }
+
+    fn has_pseudo_class(&self, pseudo_class: PseudoClass) -> bool {
+        let shape_group = match self {
+            Node::Part { .. } => return false,
+            Node::Shape { shape_group, .. } => shape_group,
+        };
+
+        let graphic_data_uri = match shape_group {
+            ShapeGroup::GraphicFrame(graphic_frame) => Some(graphic_frame.graphic.graphic_data.uri.as_str()),
+            _ => None,
+        };
+        match pseudo_class {
+            PseudoClass::Table => graphic_data_uri == Some(crate::drawingml::table::TABLE_GRAPHIC_DATA_URI),
+            PseudoClass::Diagram => graphic_data_uri == Some(crate::drawingml::diagram::DIAGRAM_GRAPHIC_DATA_URI),
+            PseudoClass::Chart => graphic_data_uri == Some(CHART_GRAPHIC_DATA_URI),
+            PseudoClass::Placeholder => shape_group
+                .app_props()
+                .and_then(|app_props| app_props.placeholder.as_ref())
+                .is_some(),
+            PseudoClass::Hidden => shape_group
+                .drawing_props()
+                .and_then(|drawing_props| drawing_props.hidden)
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl CompoundSelector {
+    fn matches(&self, node: &Node) -> bool {
+        self.element_type
+            .iter()
+            .all(|&element_type| element_type == node.element_type())
+            && self.position.iter().all(|&position| position == node.position())
+            && self.attributes.iter().all(|attribute| {
+                let value = node.attribute(attribute.name);
+                match &attribute.condition {
+                    Some((operator, expected)) => operator.matches(value.as_deref().unwrap_or(""), expected),
+                    None => value.is_some(),
+                }
+            })
+            && self
+                .pseudo_classes
+                .iter()
+                .all(|&pseudo_class| node.has_pseudo_class(pseudo_class))
+    }
+}
+
+impl ComplexSelector {
+    /// Returns true if the last node of `chain` is selected.
+    fn matches(&self, chain: &[Node]) -> bool {
+        match chain.split_last() {
+            Some((node, ancestors)) => match self.compounds.split_last() {
+                Some(((combinator, compound), rest)) => {
+                    compound.matches(node) && matches_ancestors(rest, *combinator, ancestors)
+                }
+                None => false,
+            },
+            None => false,
+        }
+    }
+}
+
+/// Matches the remaining compound selectors against the ancestors of a matched node. `combinator` is the combinator
+/// between the last remaining compound selector and the matched node.
+fn matches_ancestors(compounds: &[(Combinator, CompoundSelector)], combinator: Combinator, ancestors: &[Node]) -> bool {
+    let ((next_combinator, compound), rest) = match compounds.split_last() {
+        Some(last) => last,
+        None => return true,
+    };
+
+    match combinator {
+        Combinator::Child => match ancestors.split_last() {
+            Some((parent, ancestors)) => {
+                compound.matches(parent) && matches_ancestors(rest, *next_combinator, ancestors)
+            }
+            None => false,
+        },
+        Combinator::Descendant => (0..ancestors.len())
+            .rev()
+            .any(|i| compound.matches(&ancestors[i]) && matches_ancestors(rest, *next_combinator, &ancestors[..i])),
+    }
+}
+
+impl Selector {
+    fn matches(&self, chain: &[Node]) -> bool {
+        self.alternatives.iter().any(|alternative| alternative.matches(chain))
+    }
+
+    /// Returns the elements of the shape tree of a part selected by the selector, and the part itself if it's
+    /// selected, in document order.
+    fn select_in_part<'a>(
+        &self,
+        kind: PartKind,
+        path: &'a Path,
+        position: usize,
+        common_slide_data: &'a CommonSlideData,
+        selected: &mut Vec<SelectedElement<'a>>,
+    ) {
+        let part_node = Node::Part {
+            kind,
+            path,
+            position,
+            common_slide_data,
+        };
+        if self.matches(&[part_node]) {
+            selected.push(SelectedElement {
+                part_kind: kind,
+                part_path: path,
+                shape: None,
+            });
+        }
+
+        let shape_tree = &common_slide_data.shape_tree;
+        for (_, parents, shape_group) in shape_tree.iter_shapes() {
+            let mut chain = vec![part_node];
+            let mut siblings = shape_tree.shape_array.as_slice();
+            for parent in &parents {
+                chain.push(Node::Shape {
+                    shape_group: find_group(siblings, parent),
+                    siblings,
+                });
+                siblings = &parent.shape_array;
+            }
+            chain.push(Node::Shape { shape_group, siblings });
+
+            if self.matches(&chain) {
+                selected.push(SelectedElement {
+                    part_kind: kind,
+                    part_path: path,
+                    shape: Some(ShapeRef {
+                        shape: shape_group,
+                        parents,
+                    }),
+                });
+            }
+        }
+    }
+}
+
+/// Returns the element of `siblings` that wraps `group_shape`.
+fn find_group<'a>(siblings: &'a [ShapeGroup], group_shape: &GroupShape) -> &'a ShapeGroup {
+    siblings
+        .iter()
+        .find(
+            |sibling| matches!(sibling, ShapeGroup::GroupShape(sibling) if std::ptr::eq(sibling.as_ref(), group_shape)),
+        )
+        .expect("the parent chain of a shape should only contain groups of the shape tree")
+}
+
+/// Sorts part paths by the number in their file names, so slideLayout10.xml comes after slideLayout9.xml.
+fn sorted_part_paths<'a, T>(paths: impl Iterator<Item = (&'a std::path::PathBuf, &'a T)>) -> Vec<(&'a Path, &'a T)>
+where
+    T: 'a,
+{
+    let part_number = |path: &Path| {
+        path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| {
+                stem.trim_start_matches(|c: char| !c.is_ascii_digit())
+                    .parse::<u32>()
+                    .ok()
+            })
+            .unwrap_or(0)
+    };
+    let mut paths = paths.map(|(path, part)| (path.as_path(), part)).collect::<Vec<_>>();
+    paths.sort_by(|(a, _), (b, _)| (a.parent(), part_number(a), *a).cmp(&(b.parent(), part_number(b), *b)));
+    paths
+}
+
+impl PPTXDocument {
+    /// Returns the elements selected by a selector. Slides are searched in presentation order, then slide layouts and
+    /// slide masters in the order of their file names. Within a part, elements are returned in document order.
+    pub fn select(&self, selector: &Selector) -> Vec<SelectedElement<'_>> {
+        let mut selected = Vec::new();
+        for (i, (path, slide)) in self.ordered_slides().into_iter().enumerate() {
+            selector.select_in_part(PartKind::Slide, path, i + 1, &slide.common_slide_data, &mut selected);
+        }
+
+        for (i, (path, layout)) in sorted_part_paths(self.slide_layout_map.iter()).into_iter().enumerate() {
+            selector.select_in_part(
+                PartKind::SlideLayout,
+                path,
+                i + 1,
+                &layout.common_slide_data,
+                &mut selected,
+            );
+        }
+
+        for (i, (path, master)) in sorted_part_paths(self.slide_master_map.iter()).into_iter().enumerate() {
+            selector.select_in_part(
+                PartKind::SlideMaster,
+                path,
+                i + 1,
+                &master.common_slide_data,
+                &mut selected,
+            );
+        }
+
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use msoffice_shared::xml::XmlNode;
+
+    fn common_slide_data() -> CommonSlideData {
+        let xml = r#"<p:cSld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+                xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+                <p:spTree>
+                    <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                    <p:grpSpPr/>
+                    <p:sp>
+                        <p:nvSpPr><p:cNvPr id="2" name="Title 1"/><p:cNvSpPr/><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr>
+                        <p:spPr/>
+                    </p:sp>
+                    <p:pic>
+                        <p:nvPicPr><p:cNvPr id="3" name="Logo" descr="Company logo"/><p:cNvPicPr/><p:nvPr/></p:nvPicPr>
+                        <p:blipFill/>
+                        <p:spPr/>
+                    </p:pic>
+                    <p:grpSp>
+                        <p:nvGrpSpPr><p:cNvPr id="4" name="Flow"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                        <p:grpSpPr/>
+                        <p:pic>
+                            <p:nvPicPr><p:cNvPr id="5" name="Icon"/><p:cNvPicPr/><p:nvPr/></p:nvPicPr>
+                            <p:blipFill/>
+                            <p:spPr/>
+                        </p:pic>
+                        <p:cxnSp>
+                            <p:nvCxnSpPr><p:cNvPr id="6" name="Arrow"/><p:cNvCxnSpPr/><p:nvPr/></p:nvCxnSpPr>
+                            <p:spPr/>
+                        </p:cxnSp>
+                        <p:grpSp>
+                            <p:nvGrpSpPr><p:cNvPr id="7" name="Inner" hidden="1"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                            <p:grpSpPr/>
+                            <p:cxnSp>
+                                <p:nvCxnSpPr><p:cNvPr id="8" name="Inner arrow"/><p:cNvCxnSpPr/><p:nvPr/></p:nvCxnSpPr>
+                                <p:spPr/>
+                            </p:cxnSp>
+                        </p:grpSp>
+                    </p:grpSp>
+                    <p:graphicFrame>
+                        <p:nvGraphicFramePr>
+                            <p:cNvPr id="9" name="Table"/><p:cNvGraphicFramePr/><p:nvPr/>
+                        </p:nvGraphicFramePr>
+                        <p:xfrm><a:off x="0" y="0"/><a:ext cx="100" cy="100"/></p:xfrm>
+                        <a:graphic>
+                            <a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/table"/>
+                        </a:graphic>
+                    </p:graphicFrame>
+                </p:spTree>
+            </p:cSld>"#;
+        CommonSlideData::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    fn select_ids(selector: &str, common_slide_data: &CommonSlideData) -> Vec<u32> {
+        let selector: Selector = selector.parse().unwrap();
+        let mut selected = Vec::new();
+        selector.select_in_part(
+            PartKind::Slide,
+            Path::new("ppt/slides/slide1.xml"),
+            1,
+            common_slide_data,
+            &mut selected,
+        );
+        selected
+            .iter()
+            .map(|element| match &element.shape {
+                Some(shape_ref) => shape_ref.shape.drawing_props().unwrap().id,
+                None => 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn test_select() {
+        let common_slide_data = common_slide_data();
+        let ids = |selector| select_ids(selector, &common_slide_data);
+
+        assert_eq!(ids("slide[1] sp[ph=title]"), vec![2]);
+        assert_eq!(ids("slide[2] sp"), Vec::<u32>::new());
+        assert_eq!(ids("slide"), vec![0]);
+        assert_eq!(ids(r#"pic[descr=""]"#), vec![5]);
+        assert_eq!(ids("pic[descr]"), vec![3]);
+        assert_eq!(ids("grpSp > cxnSp"), vec![6, 8]);
+        assert_eq!(ids("slide > grpSp > cxnSp"), vec![6]);
+        assert_eq!(ids("grpSp grpSp cxnSp"), vec![8]);
+        assert_eq!(ids("graphicFrame:table, grpSp:hidden"), vec![7, 9]);
+        assert_eq!(ids("*:placeholder"), vec![2]);
+        assert_eq!(ids("[name^='In']"), vec![7, 8]);
+        assert_eq!(ids("grpSp[1] > *[2]"), vec![]);
+        assert_eq!(ids("grpSp[1] > cxnSp[1]"), vec![6, 8]);
+        assert_eq!(ids("[id=\"5\"], [name*=ow]"), vec![4, 5, 6, 8]);
+    }
+
+    #[test]
+    pub fn test_parse_selector_errors() {
+        let error = |selector: &str| selector.parse::<Selector>().unwrap_err();
+
+        assert_eq!(error("sp[ph=title"), SelectorParseError::new(11, "expected ']'"));
+        assert_eq!(
+            error("shape"),
+            SelectorParseError::new(0, "unknown element type 'shape'")
+        );
+        assert_eq!(
+            error("sp[color=red]"),
+            SelectorParseError::new(3, "unknown attribute 'color'")
+        );
+        assert_eq!(
+            error("graphicFrame:video"),
+            SelectorParseError::new(13, "unknown pseudo-class 'video'")
+        );
+        assert_eq!(error("slide[0]"), SelectorParseError::new(6, "positions start at 1"));
+        assert_eq!(error("grpSp >"), SelectorParseError::new(7, "expected a selector"));
+        assert_eq!(error("sp, "), SelectorParseError::new(4, "expected a selector"));
+    }
+}
@@ -0,0 +1,288 @@
+use crate::drawingml::geometry::{Point, Rect};
+use crate::placement::{shape_placements, PlacedShape, Placement};
+use crate::pml::slides::{ShapeGroup, Slide};
+use msoffice_shared::drawingml::coordsys::PositiveSize2D;
+
+/// Tolerance for comparing coordinates in EMUs, so shapes that only touch don't overlap.
+const EPSILON: f64 = 1e-6;
+
+/// Distance in EMUs from a zero-width or zero-height element, like a straight line, that still hits it. This is half
+/// of a 1pt outline.
+const STROKE_TOLERANCE: f64 = 6350.0;
+
+impl Placement {
+    /// Returns true if the point, in slide EMUs, is inside the rotated shape or on its border. Zero-width and
+    /// zero-height elements, like straight lines, are hit within half a 1pt stroke.
+    pub fn contains_point(&self, point: Point) -> bool {
+        // Rotate the point back around the center of the shape, so it can be compared with the unrotated bounds
+        let center = self.center();
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (dx, dy) = (point.x - center.x, point.y - center.y);
+        let x = center.x + dx * cos + dy * sin;
+        let y = center.y - dx * sin + dy * cos;
+        let bounds = &self.bounds;
+        let tolerance = |extent: f64| {
+            if extent.abs() < EPSILON {
+                STROKE_TOLERANCE
+            } else {
+                EPSILON
+            }
+        };
+        let (x_tolerance, y_tolerance) = (tolerance(bounds.width()), tolerance(bounds.height()));
+        x >= bounds.left - x_tolerance
+            && x <= bounds.right + x_tolerance
+            && y >= bounds.top - y_tolerance
+            && y <= bounds.bottom + y_tolerance
+    }
+
+    /// Returns true if the rotated shapes overlap. Shapes that only touch each other don't overlap.
+    pub fn intersects(&self, other: &Placement) -> bool {
+        let corners = self.corners();
+        let other_corners = other.corners();
+
+        // Separating axis theorem: two convex polygons don't overlap if there is an axis perpendicular to one of their
+        // edges on which their projections don't overlap
+        let (axes, other_axes) = (separating_axes(&corners), separating_axes(&other_corners));
+        axes.iter().chain(other_axes.iter()).all(|axis| {
+            let (min, max) = project(&corners, *axis);
+            let (other_min, other_max) = project(&other_corners, *axis);
+            max > other_min + EPSILON && other_max > min + EPSILON
+        })
+    }
+
+    /// Returns true if the rotated shape lies completely inside the rectangle.
+    pub fn is_inside(&self, rect: &Rect) -> bool {
+        let bounds = self.rotated_bounds();
+        bounds.left >= rect.left - EPSILON
+            && bounds.top >= rect.top - EPSILON
+            && bounds.right <= rect.right + EPSILON
+            && bounds.bottom <= rect.bottom + EPSILON
+    }
+}
+
+/// Returns the axes perpendicular to the edges of a rotated shape. A zero-width or zero-height element is a line, so
+/// the direction of the line replaces the normal of its missing edge. An element without any extent uses the x and y
+/// axes.
+fn separating_axes(corners: &[Point; 4]) -> [Point; 2] {
+    let is_degenerate = |axis: Point| axis.x.abs() < EPSILON && axis.y.abs() < EPSILON;
+    let first = edge_normal(corners[0], corners[1]);
+    let second = edge_normal(corners[1], corners[2]);
+    match (is_degenerate(first), is_degenerate(second)) {
+        (false, false) => [first, second],
+        (true, false) => [second, perpendicular(second)],
+        (false, true) => [first, perpendicular(first)],
+        (true, true) => [Point::new(1.0, 0.0), Point::new(0.0, 1.0)],
+    }
+}
+
+fn edge_normal(start: Point, end: Point) -> Point {
+    Point::new(start.y - end.y, end.x - start.x)
+}
+
+fn perpendicular(axis: Point) -> Point {
+    Point::new(axis.y, -axis.x)
+}
+
+fn project(corners: &[Point; 4], axis: Point) -> (f64, f64) {
+    corners
+        .iter()
+        .map(|corner| corner.x * axis.x + corner.y * axis.y)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        })
+}
+
+/// Spatial queries over the elements of a slide, like hit testing and finding overlapping elements.
+///
+/// Queries only consider the shapes, pictures, connectors, graphic frames and content parts of the slide. Groups are
+/// represented by their elements, as the bounds of a group generally cover more than its elements do.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpatialQuery<'a> {
+    /// The elements of the slide in z-order, from the back to the front.
+    shapes: Vec<PlacedShape<'a>>,
+}
+
+impl<'a> SpatialQuery<'a> {
+    /// Creates a query over the placements of the elements of a slide, as returned by shape_placements or
+    /// StyleContext::shape_placements.
+    pub fn new(placed_shapes: Vec<PlacedShape<'a>>) -> Self {
+        // Document order is z-order, as the elements of a group are drawn in place of the group
+        let shapes = placed_shapes
+            .into_iter()
+            .filter(|placed_shape| !matches!(placed_shape.shape, ShapeGroup::GroupShape(_)))
+            .collect();
+        Self { shapes }
+    }
+
+    /// Returns the elements in z-order, from the back to the front.
+    pub fn shapes(&self) -> &[PlacedShape<'a>] {
+        &self.shapes
+    }
+
+    /// Returns the elements under a point in slide EMUs, starting with the topmost one.
+    pub fn hit_test(&self, point: Point) -> Vec<PlacedShape<'a>> {
+        self.shapes
+            .iter()
+            .rev()
+            .filter(|placed_shape| placed_shape.placement.contains_point(point))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the elements overlapping a rectangle in slide EMUs, starting with the topmost one.
+    pub fn shapes_in_rect(&self, rect: Rect) -> Vec<PlacedShape<'a>> {
+        let rect_placement = Placement {
+            bounds: rect,
+            ..Default::default()
+        };
+        self.shapes
+            .iter()
+            .rev()
+            .filter(|placed_shape| placed_shape.placement.intersects(&rect_placement))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the pairs of overlapping elements. The first element of each pair is below the second one.
+    pub fn overlapping_pairs(&self) -> Vec<(PlacedShape<'a>, PlacedShape<'a>)> {
+        let mut pairs = Vec::new();
+        for (i, placed_shape) in self.shapes.iter().enumerate() {
+            for other in &self.shapes[i + 1..] {
+                if placed_shape.placement.intersects(&other.placement) {
+                    pairs.push((*placed_shape, *other));
+                }
+            }
+        }
+
+        pairs
+    }
+
+    /// Returns the elements that aren't completely inside the slide, in z-order.
+    pub fn outside_slide(&self, slide_size: &PositiveSize2D) -> Vec<PlacedShape<'a>> {
+        let slide_rect = Rect::new(0.0, 0.0, slide_size.width as f64, slide_size.height as f64);
+        self.shapes
+            .iter()
+            .filter(|placed_shape| !placed_shape.placement.is_inside(&slide_rect))
+            .copied()
+            .collect()
+    }
+}
+
+impl Slide {
+    /// Returns spatial queries over the elements of the slide. Placeholders without a transform are skipped, use
+    /// SpatialQuery::new with StyleContext::shape_placements to include them.
+    pub fn spatial_query(&self) -> SpatialQuery<'_> {
+        SpatialQuery::new(shape_placements(&self.common_slide_data.shape_tree))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pml::slides::GroupShape;
+    use msoffice_shared::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_spatial_query() {
+        let shape = |id: u32, x: i64, y: i64, width: u64, height: u64, rotation: i32| {
+            format!(
+                r#"<p:sp>
+                    <p:nvSpPr><p:cNvPr id="{}" name="Shape {0}"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>
+                    <p:spPr><a:xfrm rot="{}"><a:off x="{}" y="{}"/><a:ext cx="{}" cy="{}"/></a:xfrm></p:spPr>
+                </p:sp>"#,
+                id, rotation, x, y, width, height
+            )
+        };
+        let xml = format!(
+            r#"<p:spTree xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+                xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+                <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                <p:grpSpPr/>
+                {}{}
+                <p:grpSp>
+                    <p:nvGrpSpPr><p:cNvPr id="4" name="Group"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                    <p:grpSpPr>
+                        <a:xfrm>
+                            <a:off x="500" y="0"/><a:ext cx="200" cy="200"/>
+                            <a:chOff x="0" y="0"/><a:chExt cx="100" cy="100"/>
+                        </a:xfrm>
+                    </p:grpSpPr>
+                    {}
+                </p:grpSp>
+            </p:spTree>"#,
+            // A square, and a diamond overlapping its right corner
+            shape(2, 0, 0, 200, 200, 0),
+            shape(3, 200, 50, 100, 100, 2_700_000),
+            // A shape scaled into slide coordinates (500, 0) - (700, 200) and hanging off the slide on the right
+            shape(5, 0, 0, 100, 100, 0),
+        );
+        let shape_tree = GroupShape::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap();
+        let query = SpatialQuery::new(shape_placements(&shape_tree));
+        let ids = |shapes: Vec<PlacedShape>| {
+            shapes
+                .iter()
+                .map(|placed_shape| placed_shape.shape.drawing_props().unwrap().id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(query.shapes().len(), 3);
+        assert_eq!(ids(query.hit_test(Point::new(190.0, 100.0))), vec![3, 2]);
+        assert_eq!(ids(query.hit_test(Point::new(10.0, 10.0))), vec![2]);
+        // The corner of the diamond's bounding box isn't part of the diamond
+        assert!(query.hit_test(Point::new(215.0, 55.0)).is_empty());
+        assert_eq!(ids(query.hit_test(Point::new(650.0, 150.0))), vec![5]);
+
+        assert_eq!(ids(query.shapes_in_rect(Rect::new(0.0, 0.0, 100.0, 100.0))), vec![2]);
+        assert_eq!(
+            ids(query.shapes_in_rect(Rect::new(150.0, 0.0, 600.0, 10.0))),
+            vec![5, 2]
+        );
+        assert!(query.shapes_in_rect(Rect::new(200.0, 210.0, 500.0, 300.0)).is_empty());
+
+        let pairs = query
+            .overlapping_pairs()
+            .iter()
+            .map(|(below, above)| {
+                (
+                    below.shape.drawing_props().unwrap().id,
+                    above.shape.drawing_props().unwrap().id,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, vec![(2, 3)]);
+
+        let slide_size = PositiveSize2D::new(600, 400);
+        assert_eq!(ids(query.outside_slide(&slide_size)), vec![5]);
+    }
+
+    #[test]
+    pub fn test_degenerate_placements() {
+        let placement = |left: f64, top: f64, right: f64, bottom: f64, rotation: f64| Placement {
+            bounds: Rect::new(left, top, right, bottom),
+            rotation,
+            ..Default::default()
+        };
+        let square = placement(0.0, 0.0, 100_000.0, 100_000.0, 0.0);
+        let horizontal_line = placement(-50_000.0, 50_000.0, 150_000.0, 50_000.0, 0.0);
+        let vertical_line = placement(50_000.0, -50_000.0, 50_000.0, 150_000.0, 0.0);
+        let diagonal_line = placement(-50_000.0, 50_000.0, 150_000.0, 50_000.0, 45.0);
+        let distant_line = placement(200_000.0, 0.0, 200_000.0, 100_000.0, 0.0);
+        let dot = placement(50_000.0, 50_000.0, 50_000.0, 50_000.0, 0.0);
+
+        assert!(horizontal_line.intersects(&square));
+        assert!(square.intersects(&vertical_line));
+        assert!(horizontal_line.intersects(&vertical_line));
+        assert!(diagonal_line.intersects(&square));
+        assert!(dot.intersects(&square));
+        assert!(!distant_line.intersects(&square));
+        assert!(!distant_line.intersects(&horizontal_line));
+
+        // Lines are hit within half a stroke, while shapes with an area aren't
+        assert!(horizontal_line.contains_point(Point::new(0.0, 53_000.0)));
+        assert!(!horizontal_line.contains_point(Point::new(0.0, 60_000.0)));
+        assert!(vertical_line.contains_point(Point::new(47_000.0, 140_000.0)));
+        assert!(diagonal_line.contains_point(Point::new(80_000.0, 80_000.0)));
+        assert!(!square.contains_point(Point::new(103_000.0, 50_000.0)));
+    }
+}
@@ -0,0 +1,267 @@
+//! Splitting a presentation into standalone documents, one per slide.
+//!
+//! An extracted slide is copied into an empty document by following its relationships, so the new document only
+//! contains the parts the slide depends on: its slide layout, the slide master of the layout with the layouts that
+//! are in use, the themes, media files, charts and the notes slide with its notes master. Presentation level parts
+//! like the presentation properties and the table styles are copied as well.
+
+use crate::document::{
+    relative_relationship_target, resolve_relationship_target, PPTXDocument, NOTES_MASTER_RELATIONSHIP_TYPE,
+    PRESENTATION_PATH, PRESENTATION_RELATIONSHIPS_PATH, SLIDE_MASTER_RELATIONSHIP_TYPE, SLIDE_RELATIONSHIP_TYPE,
+};
+use crate::merge::{LayoutMapping, SlideImporter};
+use crate::xml::UnknownXml;
+use msoffice_shared::xml::XmlNode;
+use std::{error::Error, path::Path};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+const HANDOUT_MASTER_RELATIONSHIP_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/handoutMaster";
+
+impl PPTXDocument {
+    /// Returns a new document that only contains the slide at `slide_path` and the parts it depends on. The slide
+    /// keeps its slide layout, slide master, theme, media and notes slide, while slide layouts that aren't used by it
+    /// are left out.
+    ///
+    /// The document doesn't have a source package, so it can be saved anywhere. Document properties, the handout
+    /// master, custom shows and sections aren't copied, since they describe the whole presentation. Hyperlinks jumping
+    /// to other slides are removed along with their relationships.
+    pub fn extract_slide(&self, slide_path: &Path) -> Result<PPTXDocument> {
+        if !self.slide_map.contains_key(slide_path) {
+            return Err(format!("slide not found: {}", slide_path.display()).into());
+        }
+
+        let mut presentation = self
+            .presentation
+            .clone()
+            .ok_or("the document doesn't have a presentation")?;
+        presentation.slide_master_id_list.clear();
+        presentation.notes_master_id = None;
+        presentation.handout_master_id = None;
+        presentation.slide_id_list.clear();
+        presentation.custom_show_list.clear();
+        remove_section_list(&mut presentation.unknown_xml);
+
+        let mut document = PPTXDocument {
+            presentation: Some(presentation),
+            ..Default::default()
+        };
+
+        let presentation_path = Path::new(PRESENTATION_PATH);
+        let mut importer = SlideImporter::new(self, &mut document, LayoutMapping::KeepSource, false);
+        let mut presentation_rels = Vec::new();
+        for mut relationship in self.presentation_rels.iter().cloned() {
+            match relationship.rel_type.as_str() {
+                SLIDE_RELATIONSHIP_TYPE
+                | SLIDE_MASTER_RELATIONSHIP_TYPE
+                | NOTES_MASTER_RELATIONSHIP_TYPE
+                | HANDOUT_MASTER_RELATIONSHIP_TYPE => continue,
+                _ if self.is_external_relationship(presentation_path, &relationship) => {
+                    importer
+                        .target_mut()
+                        .external_relationships
+                        .insert(Path::new(PRESENTATION_RELATIONSHIPS_PATH), &relationship.id);
+                    presentation_rels.push(relationship)
+                }
+                _ => {
+                    let part_path = resolve_relationship_target(presentation_path, &relationship.target);
+                    if let Some(target_path) = importer.copy_part(&part_path)? {
+                        relationship.target = relative_relationship_target(presentation_path, &target_path);
+                        presentation_rels.push(relationship);
+                    }
+                }
+            }
+        }
+
+        // Relationship ids are kept, since parts like the customer data list of the presentation refer to them
+        importer.target_mut().presentation_rels.extend(presentation_rels);
+        importer.import_slide(slide_path)?;
+
+        Ok(document)
+    }
+
+    /// Returns a standalone document for every slide of the presentation, in the order of the slide id list. See
+    /// `extract_slide` for the content of the documents.
+    pub fn split_slides(&self) -> Result<Vec<PPTXDocument>> {
+        let presentation = self
+            .presentation
+            .as_ref()
+            .ok_or("the document doesn't have a presentation")?;
+        (0..presentation.slide_id_list.len())
+            .filter_map(|position| self.slide_path_at(position))
+            .map(|slide_path| self.extract_slide(&slide_path))
+            .collect()
+    }
+}
+
+/// Removes the sections of the presentation, which are stored in an extension of the extension list since they were
+/// added to the format later on. Sections list slides by their ids, so they don't apply to an extracted slide.
+fn remove_section_list(unknown_xml: &mut UnknownXml) {
+    for (_, child_node) in &mut unknown_xml.child_nodes {
+        if child_node.local_name() == "extLst" {
+            child_node
+                .child_nodes
+                .retain(|extension| !extension.child_nodes.iter().any(is_section_list));
+        }
+    }
+
+    unknown_xml
+        .child_nodes
+        .retain(|(_, child_node)| child_node.local_name() != "extLst" || !child_node.child_nodes.is_empty());
+}
+
+fn is_section_list(xml_node: &XmlNode) -> bool {
+    xml_node.local_name() == "sectionLst"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{load_saved_document, relationships_path};
+    use crate::opc::next_relationship_id;
+    use crate::pml::slides::Slide;
+    use msoffice_shared::relationship::Relationship;
+    use std::{
+        io::{Cursor, Read},
+        path::PathBuf,
+        str::FromStr,
+    };
+    use zip::ZipArchive;
+
+    fn sample_document() -> PPTXDocument {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        PPTXDocument::from_file(&test_dir.join("tests/samplepptx.pptx")).unwrap()
+    }
+
+    #[test]
+    fn test_split_slides() {
+        let source = sample_document();
+        let documents = source.split_slides().unwrap();
+        assert_eq!(documents.len(), 2);
+
+        let mut media_counts = Vec::new();
+        for (position, document) in documents.iter().enumerate() {
+            let source_slide_path = source.slide_path_at(position).unwrap();
+            let slide_path = document.slide_path_at(0).unwrap();
+            assert_eq!(document.slide_map.len(), 1);
+            assert_eq!(document.slide_map[&slide_path], source.slide_map[&source_slide_path]);
+            assert_eq!(document.slide_master_map.len(), 1);
+            assert_eq!(document.slide_layout_map.len(), 1);
+            assert_eq!(document.theme_map.len(), 1);
+
+            let slide_layout_path = document.slide_layout_path(&slide_path).unwrap();
+            let source_slide_layout_path = source.slide_layout_path(&source_slide_path).unwrap();
+            assert_eq!(
+                document.slide_layout_map[&slide_layout_path],
+                source.slide_layout_map[&source_slide_layout_path]
+            );
+            let slide_master = document.slide_master_map.values().next().unwrap();
+            assert_eq!(slide_master.slide_layout_id_list.as_ref().unwrap().0.len(), 1);
+
+            let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+            let saved_document = load_saved_document(&data);
+            assert_eq!(saved_document.slide_map, document.slide_map);
+            assert_eq!(saved_document.slide_layout_map, document.slide_layout_map);
+            assert_eq!(saved_document.slide_master_map, document.slide_master_map);
+            assert_eq!(saved_document.presentation, document.presentation);
+
+            let zipper = ZipArchive::new(Cursor::new(data)).unwrap();
+            let file_names = zipper.file_names().collect::<Vec<_>>();
+            assert!(file_names.contains(&"ppt/presProps.xml"));
+            assert!(file_names.contains(&"ppt/tags/tag1.xml"));
+            assert!(!file_names.iter().any(|file_name| file_name.starts_with("docProps")));
+            let slide_layout_count = file_names
+                .iter()
+                .filter(|file_name| file_name.starts_with("ppt/slideLayouts/slideLayout"))
+                .count();
+            assert_eq!(slide_layout_count, 1);
+            media_counts.push(saved_document.medias.len());
+        }
+
+        // The title slide layout of the first slide uses a picture besides the one of the slide master
+        assert_eq!(media_counts, vec![2, 1]);
+        assert!(source.extract_slide(Path::new("ppt/slides/slide3.xml")).is_err());
+    }
+
+    #[test]
+    fn test_extract_slide_with_slide_jump_hyperlink() {
+        let mut source = sample_document();
+        let slide_path = source.slide_path_at(0).unwrap();
+        let other_slide_path = source.slide_path_at(1).unwrap();
+
+        let slide_rels = source.slide_rels_map.get_mut(&relationships_path(&slide_path)).unwrap();
+        slide_rels.push(Relationship {
+            id: next_relationship_id(slide_rels),
+            rel_type: String::from(SLIDE_RELATIONSHIP_TYPE),
+            target: relative_relationship_target(&slide_path, &other_slide_path),
+        });
+        let hyperlink_xml = format!(
+            r#"<a:hlinkClick xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+                xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"
+                r:id="{}" action="ppaction://hlinksldjump"/>"#,
+            slide_rels.last().unwrap().id
+        );
+        let mut xml_node = source.slide_map[&slide_path].to_xml_element();
+        let shape_properties = xml_node
+            .child_nodes
+            .iter_mut()
+            .find(|child_node| child_node.local_name() == "cSld")
+            .and_then(|common_slide_data| {
+                common_slide_data
+                    .child_nodes
+                    .iter_mut()
+                    .find(|child_node| child_node.local_name() == "spTree")
+            })
+            .and_then(|shape_tree| {
+                shape_tree
+                    .child_nodes
+                    .iter_mut()
+                    .find(|child_node| child_node.local_name() == "sp")
+            })
+            .and_then(|shape| shape.child_nodes.first_mut())
+            .and_then(|non_visual_properties| non_visual_properties.child_nodes.first_mut())
+            .unwrap();
+        assert_eq!(shape_properties.local_name(), "cNvPr");
+        shape_properties
+            .child_nodes
+            .push(XmlNode::from_str(&hyperlink_xml).unwrap());
+        let slide = Slide::from_xml_element(&xml_node).unwrap();
+        source.slide_map.insert(slide_path.clone(), Box::new(slide));
+
+        let document = source.extract_slide(&slide_path).unwrap();
+        let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+        let saved_document = load_saved_document(&data);
+        let extracted_slide_path = saved_document.slide_path_at(0).unwrap();
+        let relationships = saved_document.part_relationships(&extracted_slide_path).unwrap();
+        assert!(relationships
+            .iter()
+            .all(|relationship| relationship.rel_type != SLIDE_RELATIONSHIP_TYPE));
+
+        let mut zipper = ZipArchive::new(Cursor::new(data)).unwrap();
+        let mut slide_xml = String::new();
+        zipper
+            .by_name(&extracted_slide_path.to_string_lossy())
+            .unwrap()
+            .read_to_string(&mut slide_xml)
+            .unwrap();
+        assert!(!slide_xml.contains("hlinkClick"));
+    }
+
+    #[test]
+    fn test_remove_section_list() {
+        let xml = r#"<p:presentation xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+            <p:extLst>
+                <p:ext uri="{521415D9-36F7-43E2-AB2F-B90AF26B5E84}">
+                    <p14:sectionLst xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main"/>
+                </p:ext>
+            </p:extLst>
+        </p:presentation>"#;
+        let xml_node = XmlNode::from_str(xml).unwrap();
+        let mut unknown_xml = UnknownXml::from_xml_element(&xml_node, &[], |_| false);
+        assert_eq!(unknown_xml.child_nodes.len(), 1);
+
+        remove_section_list(&mut unknown_xml);
+        assert!(unknown_xml.child_nodes.is_empty());
+    }
+}
@@ -0,0 +1,807 @@
+use crate::document::PPTXDocument;
+use crate::drawingml::color::{resolve_color, RgbaColor};
+use crate::pml::slides::{
+    ApplicationNonVisualDrawingProps, GroupShape, Placeholder, PlaceholderType, Shape, ShapeGroup, Slide, SlideLayout,
+    SlideMaster,
+};
+use msoffice_shared::drawingml::{
+    colors::{Color, ColorMappingOverride, SchemeColor},
+    coordsys::Transform2D,
+    core::{NonVisualDrawingProps, ShapeProperties, ShapeStyle},
+    shapeprops::{
+        FillProperties, LineDashProperties, LineEndProperties, LineFillProperties, LineProperties, ShadeProperties,
+    },
+    sharedstylesheet::{ColorMapping, ColorScheme, OfficeStyleSheet},
+    simpletypes::{
+        Coordinate, FontCollectionIndex, LineCap, LineEndType, PresetLineDashVal, SchemeColorVal, TextAlignType,
+        TextAnchoringType, TextCapsType, TextStrikeType, TextUnderlineType,
+    },
+    text::{
+        bodyformatting::TextBodyProperties,
+        bullet::TextListStyle,
+        paragraphs::{TextParagraph, TextParagraphProperties},
+        runformatting::TextCharacterProperties,
+    },
+};
+use std::path::{Path, PathBuf};
+
+/// The font size in points that is used if no size is specified anywhere in the inheritance chain.
+pub const DEFAULT_FONT_SIZE: f64 = 18.0;
+/// The width of a line in EMUs that is used if the line properties don't specify a width.
+pub const DEFAULT_LINE_WIDTH: Coordinate = 9525;
+
+/// A resolved fill that can be drawn without further lookups into the theme.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Solid(RgbaColor),
+    /// A linear gradient. The angle is the direction of the color change in degrees, measured clockwise starting from
+    /// the horizontal left to right direction.
+    LinearGradient {
+        angle: f64,
+        stops: Vec<GradientStop>,
+    },
+    /// A gradient that follows a path from the center of the shape outwards.
+    RadialGradient {
+        stops: Vec<GradientStop>,
+    },
+    /// An image fill. The path is the path of the image part within the package.
+    Image {
+        media_path: PathBuf,
+    },
+}
+
+impl Paint {
+    /// Returns a single color that approximates the paint, e.g. for renderers that can't draw gradients or images.
+    pub fn average_color(&self) -> Option<RgbaColor> {
+        match self {
+            Paint::Solid(color) => Some(*color),
+            Paint::LinearGradient { stops, .. } | Paint::RadialGradient { stops } => {
+                let count = stops.len() as u32;
+                if count == 0 {
+                    return None;
+                }
+
+                let sum = |channel: fn(&RgbaColor) -> u8| {
+                    (stops.iter().map(|stop| u32::from(channel(&stop.color))).sum::<u32>() / count) as u8
+                };
+                Some(RgbaColor::new(
+                    sum(|color| color.red),
+                    sum(|color| color.green),
+                    sum(|color| color.blue),
+                    sum(|color| color.alpha),
+                ))
+            }
+            Paint::Image { .. } => None,
+        }
+    }
+}
+
+/// A resolved stop of a gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+    /// The position of the stop along the gradient in the range of 0.0 to 1.0.
+    pub position: f64,
+    pub color: RgbaColor,
+}
+
+/// A resolved outline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    /// The width of the line in EMUs.
+    pub width: Coordinate,
+    pub color: RgbaColor,
+    pub dash: Option<PresetLineDashVal>,
+    pub cap: Option<LineCap>,
+    /// The decoration at the start of the line.
+    pub head_end: Option<LineEndProperties>,
+    /// The decoration at the end of the line.
+    pub tail_end: Option<LineEndProperties>,
+}
+
+impl Stroke {
+    /// Returns the lengths of the alternating dashes and gaps of a dashed line in EMUs, or None if the line is solid.
+    /// The lengths of preset dashes are relative to the width of the line, which is at least one pixel for this
+    /// purpose, so dashes of hairlines stay visible.
+    pub fn dash_pattern(&self) -> Option<Vec<f64>> {
+        let pattern: &[f64] = match self.dash? {
+            PresetLineDashVal::Solid => return None,
+            PresetLineDashVal::Dot => &[1.0, 3.0],
+            PresetLineDashVal::Dash => &[4.0, 3.0],
+            PresetLineDashVal::LargeDash => &[8.0, 3.0],
+            PresetLineDashVal::DashDot => &[4.0, 3.0, 1.0, 3.0],
+            PresetLineDashVal::LargeDashDot => &[8.0, 3.0, 1.0, 3.0],
+            PresetLineDashVal::LargeDashDotDot => &[8.0, 3.0, 1.0, 3.0, 1.0, 3.0],
+            PresetLineDashVal::SystemDash => &[3.0, 1.0],
+            PresetLineDashVal::SystemDot => &[1.0, 1.0],
+            PresetLineDashVal::SystemDashDot => &[3.0, 1.0, 1.0, 1.0],
+            PresetLineDashVal::SystemDashDotDot => &[3.0, 1.0, 1.0, 1.0, 1.0, 1.0],
+        };
+
+        let width = self.width.max(DEFAULT_LINE_WIDTH) as f64;
+        Some(pattern.iter().map(|length| length * width).collect())
+    }
+}
+
+/// The formatting of a text body after resolving the inheritance chain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BodyStyle {
+    /// The left inset of the text in EMUs.
+    pub left_inset: Coordinate,
+    /// The top inset of the text in EMUs.
+    pub top_inset: Coordinate,
+    /// The right inset of the text in EMUs.
+    pub right_inset: Coordinate,
+    /// The bottom inset of the text in EMUs.
+    pub bottom_inset: Coordinate,
+    pub anchor: TextAnchoringType,
+}
+
+impl Default for BodyStyle {
+    fn default() -> Self {
+        Self {
+            left_inset: 91440,
+            top_inset: 45720,
+            right_inset: 91440,
+            bottom_inset: 45720,
+            anchor: TextAnchoringType::Top,
+        }
+    }
+}
+
+/// The formatting of a paragraph after resolving the inheritance chain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParagraphStyle {
+    /// The indentation level of the paragraph. Top level paragraphs have a level of 0.
+    pub level: u32,
+    pub align: TextAlignType,
+    /// The left margin of the paragraph in EMUs.
+    pub margin_left: Coordinate,
+    /// The indentation of the first line of the paragraph relative to the left margin in EMUs.
+    pub indent: Coordinate,
+}
+
+/// The formatting of a text run after resolving the inheritance chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunStyle {
+    /// The size of the font in points.
+    pub font_size: f64,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+    pub all_caps: bool,
+    /// The color of the text. None if the text has no fill.
+    pub color: Option<RgbaColor>,
+    /// The typeface of the latin font. Theme font references (+mj-lt, +mn-lt) are already resolved.
+    pub typeface: Option<String>,
+}
+
+/// The chain of parts that a slide inherits its formatting from: the slide itself, its slide layout, its slide master
+/// and the theme of the master.
+#[derive(Debug, Clone)]
+pub struct StyleContext<'a> {
+    pub document: &'a PPTXDocument,
+    pub slide_path: PathBuf,
+    pub slide: &'a Slide,
+    pub slide_layout_path: Option<PathBuf>,
+    pub slide_layout: Option<&'a SlideLayout>,
+    pub slide_master_path: Option<PathBuf>,
+    pub slide_master: Option<&'a SlideMaster>,
+    pub theme: Option<&'a OfficeStyleSheet>,
+}
+
+impl PPTXDocument {
+    /// Returns the style context of the slide at `slide_path`, which is used to resolve inherited formatting. Returns
+    /// None if there's no slide at the given path.
+    pub fn style_context(&self, slide_path: &Path) -> Option<StyleContext<'_>> {
+        let slide = self.slide_map.get(slide_path)?;
+        let slide_layout_path = self.slide_layout_path(slide_path);
+        let slide_master_path = slide_layout_path
+            .as_ref()
+            .and_then(|layout_path| self.slide_master_path(layout_path));
+        let theme = slide_master_path
+            .as_ref()
+            .and_then(|master_path| self.theme_path(master_path))
+            .and_then(|theme_path| self.theme_map.get(&theme_path))
+            .map(Box::as_ref);
+
+        Some(StyleContext {
+            document: self,
+            slide_path: slide_path.to_path_buf(),
+            slide,
+            slide_layout: slide_layout_path
+                .as_ref()
+                .and_then(|path| self.slide_layout_map.get(path))
+                .map(Box::as_ref),
+            slide_layout_path,
+            slide_master: slide_master_path
+                .as_ref()
+                .and_then(|path| self.slide_master_map.get(path))
+                .map(Box::as_ref),
+            slide_master_path,
+            theme,
+        })
+    }
+}
+
+impl<'a> StyleContext<'a> {
+    /// Returns the shape trees that are drawn on the slide from back to front, together with the path of the part
+    /// that contains them.
+    ///
+    /// The shapes of the slide master and the slide layout are only included if the slide and the slide layout show
+    /// master shapes. Placeholders of the slide layout and the slide master are never drawn on the slide, so callers
+    /// should only draw placeholders from the tree of the slide itself.
+    pub fn shape_trees(&self) -> Vec<(PathBuf, &'a GroupShape)> {
+        let show_master_shapes = self.slide.show_master_shapes.unwrap_or(true);
+        let show_layout_master_shapes = self
+            .slide_layout
+            .and_then(|layout| layout.show_master_shapes)
+            .unwrap_or(true);
+
+        let mut shape_trees = Vec::new();
+        if show_master_shapes && show_layout_master_shapes {
+            if let (Some(master_path), Some(master)) = (&self.slide_master_path, self.slide_master) {
+                shape_trees.push((master_path.clone(), &*master.common_slide_data.shape_tree));
+            }
+        }
+        if show_master_shapes {
+            if let (Some(layout_path), Some(layout)) = (&self.slide_layout_path, self.slide_layout) {
+                shape_trees.push((layout_path.clone(), &*layout.common_slide_data.shape_tree));
+            }
+        }
+        shape_trees.push((self.slide_path.clone(), &*self.slide.common_slide_data.shape_tree));
+
+        shape_trees
+    }
+
+    /// Returns the color scheme of the theme.
+    pub fn color_scheme(&self) -> Option<&'a ColorScheme> {
+        self.theme.map(|theme| &*theme.theme_elements.color_scheme)
+    }
+
+    /// Returns the color mapping that is in effect for the slide. Overrides of the slide and the slide layout take
+    /// precedence over the mapping of the slide master.
+    pub fn color_mapping(&self) -> Option<&'a ColorMapping> {
+        let override_mapping = |color_mapping_override: Option<&'a ColorMappingOverride>| match color_mapping_override {
+            Some(ColorMappingOverride::OverrideColorMapping(mapping)) => Some(&**mapping),
+            _ => None,
+        };
+
+        override_mapping(self.slide.color_mapping_override.as_ref())
+            .or_else(|| {
+                self.slide_layout
+                    .and_then(|layout| override_mapping(layout.color_mapping_override.as_ref()))
+            })
+            .or_else(|| self.slide_master.map(|master| &*master.color_mapping))
+    }
+
+    /// Resolves a color using the color scheme of the theme and the color mapping of the slide. The placeholder color
+    /// (phClr) is substituted with `placeholder_color`.
+    pub fn resolve_color(&self, color: &Color, placeholder_color: Option<&Color>) -> Option<RgbaColor> {
+        resolve_color(color, self.color_scheme(), self.color_mapping(), placeholder_color)
+    }
+
+    /// Returns the shapes of the slide layout and the slide master that the given placeholder inherits its properties
+    /// from, in this order.
+    ///
+    /// On the slide layout the placeholder is matched by its index first, then by its type. On the slide master only
+    /// the type is considered, where centered titles match titles and any other content placeholder matches the body.
+    pub fn inherited_placeholders(&self, placeholder: &Placeholder) -> Vec<&'a Shape> {
+        self.inherited_placeholder_parts(placeholder)
+            .into_iter()
+            .map(|(_, shape)| shape)
+            .collect()
+    }
+
+    /// Same as inherited_placeholders, but each shape is paired with the path of the part that contains it.
+    pub fn inherited_placeholder_parts(&self, placeholder: &Placeholder) -> Vec<(&Path, &'a Shape)> {
+        let mut shapes = Vec::new();
+        if let (Some(layout_path), Some(layout)) = (&self.slide_layout_path, self.slide_layout) {
+            let shape_tree = &layout.common_slide_data.shape_tree;
+            let layout_shape = placeholder
+                .index
+                .and_then(|index| find_placeholder(shape_tree, &|other| other.index == Some(index)))
+                .or_else(|| {
+                    find_placeholder(shape_tree, &|other| {
+                        placeholder_type(other) == placeholder_type(placeholder)
+                    })
+                });
+            shapes.extend(layout_shape.map(|shape| (layout_path.as_path(), shape)));
+        }
+
+        if let (Some(master_path), Some(master)) = (&self.slide_master_path, self.slide_master) {
+            let master_type = master_placeholder_type(placeholder_type(placeholder));
+            let master_shape = find_placeholder(&master.common_slide_data.shape_tree, &|other| {
+                master_placeholder_type(placeholder_type(other)) == master_type
+            });
+            shapes.extend(master_shape.map(|shape| (master_path.as_path(), shape)));
+        }
+
+        shapes
+    }
+
+    /// Returns the transform of a shape. Placeholders without a transform inherit the transform of the matching
+    /// placeholder of the slide layout or the slide master.
+    pub fn transform(
+        &self,
+        shape_props: &'a ShapeProperties,
+        placeholder: Option<&Placeholder>,
+    ) -> Option<&'a Transform2D> {
+        shape_props.transform.as_deref().or_else(|| {
+            placeholder.and_then(|placeholder| {
+                self.inherited_placeholders(placeholder)
+                    .into_iter()
+                    .find_map(|shape| shape.shape_props.transform.as_deref())
+            })
+        })
+    }
+
+    /// Resolves a fill. Relationship ids of image fills are resolved using the relationships of `part_path`. Returns
+    /// None for no fill and for fills that can't be resolved.
+    pub fn resolve_fill(
+        &self,
+        fill: &FillProperties,
+        placeholder_color: Option<&Color>,
+        part_path: &Path,
+    ) -> Option<Paint> {
+        match fill {
+            FillProperties::NoFill | FillProperties::GroupFill => None,
+            FillProperties::SolidFill(color) => self.resolve_color(color, placeholder_color).map(Paint::Solid),
+            FillProperties::GradientFill(gradient) => {
+                let mut stops = gradient
+                    .gradient_stop_list
+                    .as_ref()?
+                    .iter()
+                    .filter_map(|stop| {
+                        Some(GradientStop {
+                            position: f64::from(stop.position) / 100000.0,
+                            color: self.resolve_color(&stop.color, placeholder_color)?,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                stops.sort_by(|lhs, rhs| lhs.position.partial_cmp(&rhs.position).unwrap());
+
+                match &gradient.shade_properties {
+                    Some(ShadeProperties::Path(_)) => Some(Paint::RadialGradient { stops }),
+                    Some(ShadeProperties::Linear(linear)) => Some(Paint::LinearGradient {
+                        angle: f64::from(linear.angle.unwrap_or(0)) / 60000.0,
+                        stops,
+                    }),
+                    None => Some(Paint::LinearGradient { angle: 90.0, stops }),
+                }
+            }
+            FillProperties::BlipFill(blip_fill) => {
+                let rel_id = blip_fill.blip.as_ref()?.embed_rel_id.as_ref()?;
+                let media_path = self.document.relationship_target_path(part_path, rel_id)?;
+                Some(Paint::Image { media_path })
+            }
+            FillProperties::PatternFill(pattern) => pattern
+                .fg_color
+                .as_ref()
+                .and_then(|color| self.resolve_color(color, placeholder_color))
+                .map(Paint::Solid),
+        }
+    }
+
+    /// Resolves the fill of a shape that's defined in the part at `part_path`. The fill is taken from the shape
+    /// properties, then from the inherited placeholders and finally from the fill style of the theme referenced by the
+    /// shape style.
+    pub fn shape_fill(&self, shape: &'a Shape, part_path: &Path) -> Option<Paint> {
+        if let Some(fill) = &shape.shape_props.fill_properties {
+            return self.resolve_fill(fill, None, part_path);
+        }
+
+        if let Some(placeholder) = &shape.non_visual_props.app_props.placeholder {
+            let inherited = self
+                .inherited_placeholder_parts(placeholder)
+                .into_iter()
+                .find_map(|(part_path, shape)| {
+                    shape.shape_props.fill_properties.as_ref().map(|fill| (fill, part_path))
+                });
+            if let Some((fill, part_path)) = inherited {
+                return self.resolve_fill(fill, None, part_path);
+            }
+        }
+
+        self.style_fill(shape.shape_style.as_deref(), part_path)
+    }
+
+    /// Resolves the fill that's referenced by the fill reference of a shape style.
+    pub fn style_fill(&self, shape_style: Option<&ShapeStyle>, part_path: &Path) -> Option<Paint> {
+        let reference = &shape_style?.fill_reference;
+        let format_scheme = &self.theme?.theme_elements.format_scheme;
+        let fill = match reference.index as usize {
+            index @ 1..=999 => format_scheme.fill_style_list.get(index - 1),
+            index if index > 1000 => format_scheme.bg_fill_style_list.get(index - 1001),
+            _ => None,
+        }?;
+
+        self.resolve_fill(fill, reference.color.as_ref(), part_path)
+    }
+
+    /// Resolves the outline of a shape. The line style of the theme referenced by the shape style serves as the base,
+    /// which is overridden by the line properties of the shape. Returns None if the shape has no outline.
+    pub fn stroke(&self, line_properties: Option<&LineProperties>, shape_style: Option<&ShapeStyle>) -> Option<Stroke> {
+        let reference = shape_style.map(|style| &style.line_reference);
+        let style_line = reference.and_then(|reference| {
+            let index = reference.index as usize;
+            if index == 0 {
+                return None;
+            }
+
+            self.theme?
+                .theme_elements
+                .format_scheme
+                .line_style_list
+                .get(index - 1)
+                .map(Box::as_ref)
+        });
+        let placeholder_color = reference.and_then(|reference| reference.color.as_ref());
+
+        let line_fill = line_properties
+            .and_then(|line| line.fill_properties.as_ref())
+            .or_else(|| style_line.and_then(|line| line.fill_properties.as_ref()))?;
+        let color = match line_fill {
+            LineFillProperties::NoFill => return None,
+            LineFillProperties::SolidFill(color) => self.resolve_color(color, placeholder_color)?,
+            LineFillProperties::GradientFill(gradient) => {
+                self.resolve_color(&gradient.gradient_stop_list.as_ref()?.first()?.color, placeholder_color)?
+            }
+            LineFillProperties::PatternFill(pattern) => {
+                self.resolve_color(pattern.fg_color.as_ref()?, placeholder_color)?
+            }
+        };
+
+        let width = line_properties
+            .and_then(|line| line.width)
+            .or_else(|| style_line.and_then(|line| line.width))
+            .map(Coordinate::from)
+            .unwrap_or(DEFAULT_LINE_WIDTH);
+        let dash = line_properties
+            .and_then(|line| line.dash_properties.as_ref())
+            .or_else(|| style_line.and_then(|line| line.dash_properties.as_ref()))
+            .and_then(|dash| match dash {
+                LineDashProperties::PresetDash(preset) => Some(*preset),
+                _ => None,
+            });
+
+        let cap = line_properties
+            .and_then(|line| line.cap)
+            .or_else(|| style_line.and_then(|line| line.cap));
+        let line_end = |get: fn(&LineProperties) -> Option<&LineEndProperties>| {
+            line_properties
+                .and_then(get)
+                .or_else(|| style_line.and_then(get))
+                .filter(|line_end| line_end.end_type.unwrap_or(LineEndType::None) != LineEndType::None)
+                .cloned()
+        };
+
+        Some(Stroke {
+            width,
+            color,
+            dash,
+            cap,
+            head_end: line_end(|line| line.head_end.as_ref()),
+            tail_end: line_end(|line| line.tail_end.as_ref()),
+        })
+    }
+
+    /// Resolves the body properties of a shape's text body.
+    pub fn body_style(&self, shape: &'a Shape) -> BodyStyle {
+        let mut body_properties = Vec::new();
+        body_properties.extend(shape.text_body.as_ref().map(|text_body| &*text_body.body_properties));
+        if let Some(placeholder) = &shape.non_visual_props.app_props.placeholder {
+            body_properties.extend(
+                self.inherited_placeholders(placeholder)
+                    .into_iter()
+                    .filter_map(|shape| shape.text_body.as_ref())
+                    .map(|text_body| &*text_body.body_properties),
+            );
+        }
+
+        let defaults = BodyStyle::default();
+        let inset = |get: fn(&TextBodyProperties) -> Option<i32>, default: Coordinate| {
+            body_properties
+                .iter()
+                .find_map(|properties| get(properties))
+                .map(Coordinate::from)
+                .unwrap_or(default)
+        };
+
+        BodyStyle {
+            left_inset: inset(|properties| properties.left_inset, defaults.left_inset),
+            top_inset: inset(|properties| properties.top_inset, defaults.top_inset),
+            right_inset: inset(|properties| properties.right_inset, defaults.right_inset),
+            bottom_inset: inset(|properties| properties.bottom_inset, defaults.bottom_inset),
+            anchor: body_properties
+                .iter()
+                .find_map(|properties| properties.anchor)
+                .unwrap_or(defaults.anchor),
+        }
+    }
+
+    /// Resolves the formatting of a paragraph of a shape's text body. `shape` is None for text that isn't contained by
+    /// a shape, e.g. the text of table cells.
+    pub fn paragraph_style(&self, shape: Option<&'a Shape>, paragraph: &TextParagraph) -> ParagraphStyle {
+        let level = paragraph_level(paragraph);
+        let chain = self.paragraph_properties_chain(shape, paragraph, level);
+
+        ParagraphStyle {
+            level,
+            align: chain
+                .iter()
+                .find_map(|properties| properties.align)
+                .unwrap_or(TextAlignType::Left),
+            margin_left: chain
+                .iter()
+                .find_map(|properties| properties.margin_left)
+                .map(Coordinate::from)
+                .unwrap_or(0),
+            indent: chain
+                .iter()
+                .find_map(|properties| properties.indent)
+                .map(Coordinate::from)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Resolves the formatting of a text run within a paragraph of a shape's text body. `shape` is None for text that
+    /// isn't contained by a shape, e.g. the text of table cells.
+    ///
+    /// The properties are looked up on the run first, then on the list styles of the shape and the inherited
+    /// placeholders, then on the font reference of the shape style and finally on the text styles of the slide master
+    /// and the default text style of the presentation.
+    pub fn run_style(
+        &self,
+        shape: Option<&'a Shape>,
+        paragraph: &TextParagraph,
+        run_properties: Option<&TextCharacterProperties>,
+    ) -> RunStyle {
+        let level = paragraph_level(paragraph);
+        let local_chain = self.local_list_styles(shape);
+        let global_chain = self.global_list_styles(shape);
+
+        let mut chain: Vec<&TextCharacterProperties> = Vec::new();
+        chain.extend(run_properties);
+        chain.extend(
+            paragraph
+                .properties
+                .as_ref()
+                .and_then(|properties| properties.default_run_properties.as_deref()),
+        );
+        let list_chain = |list_styles: &[&'a TextListStyle]| {
+            list_styles
+                .iter()
+                .flat_map(|list_style| list_style_properties(list_style, level))
+                .filter_map(|properties| properties.default_run_properties.as_deref())
+                .collect::<Vec<_>>()
+        };
+        chain.extend(list_chain(&local_chain));
+        let global_start = chain.len();
+        chain.extend(list_chain(&global_chain));
+
+        let font_reference = shape
+            .and_then(|shape| shape.shape_style.as_ref())
+            .map(|style| &style.font_reference);
+        let fill = chain[..global_start]
+            .iter()
+            .find_map(|properties| properties.fill_properties.as_ref());
+        let color = match fill {
+            Some(fill) => self.text_fill_color(fill),
+            None => match font_reference.and_then(|reference| reference.color.as_ref()) {
+                Some(color) => self.resolve_color(color, None),
+                None => match chain[global_start..]
+                    .iter()
+                    .find_map(|properties| properties.fill_properties.as_ref())
+                {
+                    Some(fill) => self.text_fill_color(fill),
+                    None => self.resolve_color(&text1_color(), None),
+                },
+            },
+        };
+
+        let typeface = chain
+            .iter()
+            .find_map(|properties| properties.latin_font.as_ref())
+            .map(|font| font.typeface.clone())
+            .or_else(|| {
+                font_reference.and_then(|reference| match reference.index {
+                    FontCollectionIndex::Major => Some(String::from("+mj-lt")),
+                    FontCollectionIndex::Minor => Some(String::from("+mn-lt")),
+                    _ => None,
+                })
+            })
+            .and_then(|typeface| self.resolve_typeface(&typeface));
+
+        RunStyle {
+            font_size: chain
+                .iter()
+                .find_map(|properties| properties.font_size)
+                .map(|size| f64::from(size) / 100.0)
+                .unwrap_or(DEFAULT_FONT_SIZE),
+            bold: chain.iter().find_map(|properties| properties.bold).unwrap_or(false),
+            italic: chain.iter().find_map(|properties| properties.italic).unwrap_or(false),
+            underline: chain
+                .iter()
+                .find_map(|properties| properties.underline)
+                .map(|underline| underline != TextUnderlineType::None)
+                .unwrap_or(false),
+            strikethrough: chain
+                .iter()
+                .find_map(|properties| properties.strikethrough)
+                .map(|strike| strike != TextStrikeType::NoStrike)
+                .unwrap_or(false),
+            all_caps: chain
+                .iter()
+                .find_map(|properties| properties.capitalization)
+                .map(|caps| caps == TextCapsType::All)
+                .unwrap_or(false),
+            color,
+            typeface,
+        }
+    }
+
+    /// Resolves the theme font references +mj-lt and +mn-lt into the latin font of the major and minor font of the
+    /// theme. Other typefaces are returned as is.
+    pub fn resolve_typeface(&self, typeface: &str) -> Option<String> {
+        let font_scheme = self.theme.map(|theme| &theme.theme_elements.font_scheme);
+        match typeface {
+            "+mj-lt" => font_scheme?.major_font.latin.as_ref().map(|font| font.typeface.clone()),
+            "+mn-lt" => font_scheme?.minor_font.latin.as_ref().map(|font| font.typeface.clone()),
+            "" => None,
+            _ => Some(String::from(typeface)),
+        }
+    }
+
+    fn text_fill_color(&self, fill: &FillProperties) -> Option<RgbaColor> {
+        self.resolve_fill(fill, None, &self.slide_path)
+            .and_then(|paint| paint.average_color())
+    }
+
+    fn paragraph_properties_chain<'b>(
+        &self,
+        shape: Option<&'a Shape>,
+        paragraph: &'b TextParagraph,
+        level: u32,
+    ) -> Vec<&'b TextParagraphProperties>
+    where
+        'a: 'b,
+    {
+        let mut chain = Vec::new();
+        chain.extend(paragraph.properties.as_deref());
+        for list_style in self
+            .local_list_styles(shape)
+            .into_iter()
+            .chain(self.global_list_styles(shape))
+        {
+            chain.extend(list_style_properties(list_style, level));
+        }
+        chain
+    }
+
+    /// The list styles of the shape and the placeholders it inherits from.
+    fn local_list_styles(&self, shape: Option<&'a Shape>) -> Vec<&'a TextListStyle> {
+        let shape = match shape {
+            Some(shape) => shape,
+            None => return Vec::new(),
+        };
+
+        let mut list_styles = Vec::new();
+        list_styles.extend(
+            shape
+                .text_body
+                .as_ref()
+                .and_then(|text_body| text_body.list_style.as_deref()),
+        );
+        if let Some(placeholder) = &shape.non_visual_props.app_props.placeholder {
+            list_styles.extend(
+                self.inherited_placeholders(placeholder)
+                    .into_iter()
+                    .filter_map(|shape| shape.text_body.as_ref())
+                    .filter_map(|text_body| text_body.list_style.as_deref()),
+            );
+        }
+        list_styles
+    }
+
+    /// The text styles of the slide master and the presentation that apply to the shape.
+    fn global_list_styles(&self, shape: Option<&'a Shape>) -> Vec<&'a TextListStyle> {
+        let text_styles = self.slide_master.and_then(|master| master.text_styles.as_ref());
+        let mut list_styles = Vec::new();
+        match shape.and_then(|shape| shape.non_visual_props.app_props.placeholder.as_ref()) {
+            Some(placeholder) => match placeholder_type(placeholder) {
+                PlaceholderType::Title | PlaceholderType::CenteredTitle => {
+                    list_styles.extend(text_styles.and_then(|styles| styles.title_styles.as_deref()))
+                }
+                _ => list_styles.extend(text_styles.and_then(|styles| styles.body_styles.as_deref())),
+            },
+            None => {
+                list_styles.extend(text_styles.and_then(|styles| styles.other_styles.as_deref()));
+                list_styles.extend(
+                    self.document
+                        .presentation
+                        .as_ref()
+                        .and_then(|presentation| presentation.default_text_style.as_deref()),
+                );
+            }
+        }
+        list_styles
+    }
+}
+
+/// Returns the paragraph properties of the given indentation level followed by the default paragraph properties of
+/// the list style.
+pub fn list_style_properties(list_style: &TextListStyle, level: u32) -> Vec<&TextParagraphProperties> {
+    let level_properties = match level {
+        0 => &list_style.lvl1_paragraph_props,
+        1 => &list_style.lvl2_paragraph_props,
+        2 => &list_style.lvl3_paragraph_props,
+        3 => &list_style.lvl4_paragraph_props,
+        4 => &list_style.lvl5_paragraph_props,
+        5 => &list_style.lvl6_paragraph_props,
+        6 => &list_style.lvl7_paragraph_props,
+        7 => &list_style.lvl8_paragraph_props,
+        _ => &list_style.lvl9_paragraph_props,
+    };
+
+    level_properties
+        .iter()
+        .chain(list_style.def_paragraph_props.iter())
+        .map(Box::as_ref)
+        .collect()
+}
+
+/// Returns whether a shape is drawn. Hidden shapes and, if `draw_placeholders` is false, placeholders are not drawn.
+pub fn is_drawn(
+    drawing_props: &NonVisualDrawingProps,
+    app_props: &ApplicationNonVisualDrawingProps,
+    draw_placeholders: bool,
+) -> bool {
+    !drawing_props.hidden.unwrap_or(false) && (draw_placeholders || app_props.placeholder.is_none())
+}
+
+/// Returns the type of a placeholder. A placeholder without a type attribute is an object placeholder.
+pub fn placeholder_type(placeholder: &Placeholder) -> PlaceholderType {
+    placeholder.placeholder_type.unwrap_or(PlaceholderType::Object)
+}
+
+fn master_placeholder_type(placeholder_type: PlaceholderType) -> PlaceholderType {
+    match placeholder_type {
+        PlaceholderType::Title | PlaceholderType::CenteredTitle => PlaceholderType::Title,
+        PlaceholderType::DateTime
+        | PlaceholderType::SlideNumber
+        | PlaceholderType::Footer
+        | PlaceholderType::Header
+        | PlaceholderType::SlideImage => placeholder_type,
+        _ => PlaceholderType::Body,
+    }
+}
+
+fn paragraph_level(paragraph: &TextParagraph) -> u32 {
+    paragraph
+        .properties
+        .as_ref()
+        .and_then(|properties| properties.level)
+        .unwrap_or(0) as u32
+}
+
+fn text1_color() -> Color {
+    Color::SchemeColor(Box::new(SchemeColor {
+        value: SchemeColorVal::Text1,
+        color_transforms: Vec::new(),
+    }))
+}
+
+fn find_placeholder<'a>(group: &'a GroupShape, predicate: &dyn Fn(&Placeholder) -> bool) -> Option<&'a Shape> {
+    group.shape_array.iter().find_map(|shape_group| match shape_group {
+        ShapeGroup::Shape(shape) => shape
+            .non_visual_props
+            .app_props
+            .placeholder
+            .as_ref()
+            .filter(|placeholder| predicate(placeholder))
+            .map(|_| &**shape),
+        ShapeGroup::GroupShape(group_shape) => find_placeholder(group_shape, predicate),
+        _ => None,
+    })
+}
@@ -0,0 +1,753 @@
+use crate::document::PPTXDocument;
+use crate::drawingml::geometry::{shape_paths, ShapePath};
+use crate::layout::{layout_text_body, EstimatedTextMeasure, TextLine, TextMeasure, EMUS_PER_POINT};
+use crate::pml::{
+    slides::{GraphicalObjectContent, GroupShape, Shape, ShapeGroup},
+    util::escape_xml,
+};
+use crate::style::{is_drawn, GradientStop, Paint, Stroke, StyleContext};
+use msoffice_shared::drawingml::{
+    coordsys::{GroupTransform2D, Transform2D},
+    shapeprops::LineEndProperties,
+    simpletypes::{LineCap, LineEndLength, LineEndType, LineEndWidth, PathFillMode},
+};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// The number of EMUs in an SVG user unit. User units are CSS pixels (1/96 inch).
+pub const EMUS_PER_PIXEL: f64 = 9525.0;
+
+/// Renders a slide into a standalone SVG document.
+///
+/// The document has the size of the presentation's slides in pixels. The background of the slide is drawn first,
+/// followed by the shapes of the slide master and the slide layout, if the slide shows master shapes, and finally the
+/// shapes of the slide. Each shape is drawn in the coordinate space of its group, composing the child offset and child
+/// extents of the groups with the rotation and flips of the shapes.
+///
+/// Geometries are drawn as paths with solid, gradient or picture fills and outlines with dashes and arrowheads. Text is
+/// drawn as positioned text elements. Since SVG renderers can't break lines, lines are broken in advance using
+/// estimated font metrics, see slide_to_svg_with_measure to provide exact metrics.
+pub fn slide_to_svg(document: &PPTXDocument, slide_path: &Path) -> Result<String> {
+    slide_to_svg_with_measure(document, slide_path, &EstimatedTextMeasure)
+}
+
+/// Renders a slide into a standalone SVG document like slide_to_svg does, measuring text with `measure`.
+pub fn slide_to_svg_with_measure(
+    document: &PPTXDocument,
+    slide_path: &Path,
+    measure: &dyn TextMeasure,
+) -> Result<String> {
+    let context = document
+        .style_context(slide_path)
+        .ok_or_else(|| format!("slide not found: {}", slide_path.display()))?;
+    let slide_size = document.slide_size();
+    let width = slide_size.width as f64 / EMUS_PER_PIXEL;
+    let height = slide_size.height as f64 / EMUS_PER_PIXEL;
+
+    let mut renderer = SvgRenderer {
+        context,
+        measure,
+        data_uris: HashMap::new(),
+        defs: String::new(),
+        body: String::new(),
+        next_id: 1,
+    };
+
+    let background = document
+        .effective_background(renderer.context.slide)
+        .and_then(|background| {
+            renderer
+                .context
+                .resolve_fill(&background.fill, None, &background.source_part)
+        });
+    let background_fill = match background {
+        Some(paint) => renderer.paint_attributes(&paint)?,
+        None => String::from(" fill=\"#ffffff\""),
+    };
+    writeln!(
+        renderer.body,
+        "<rect width=\"{}\" height=\"{}\"{}/>",
+        number(width),
+        number(height),
+        background_fill
+    )?;
+
+    for (part_path, shape_tree) in renderer.context.shape_trees() {
+        let draw_placeholders = part_path == slide_path;
+        renderer.render_group(shape_tree, &part_path, draw_placeholders)?;
+    }
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" width=\"{0}\" \
+         height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        number(width),
+        number(height)
+    );
+    if !renderer.defs.is_empty() {
+        write!(svg, "<defs>\n{}</defs>\n", renderer.defs)?;
+    }
+    svg.push_str(&renderer.body);
+    svg.push_str("</svg>\n");
+
+    Ok(svg)
+}
+
+struct SvgRenderer<'a, 'b> {
+    context: StyleContext<'a>,
+    measure: &'b dyn TextMeasure,
+    data_uris: HashMap<PathBuf, String>,
+    defs: String,
+    body: String,
+    next_id: usize,
+}
+
+impl<'a, 'b> SvgRenderer<'a, 'b> {
+    /// Renders the shapes of a group. `part_path` is the path of the part containing the shapes. Placeholders are
+    /// only drawn if `draw_placeholders` is true, as the placeholders of slide layouts and slide masters are not
+    /// displayed on slides.
+    fn render_group(&mut self, group: &'a GroupShape, part_path: &Path, draw_placeholders: bool) -> Result<()> {
+        for shape_group in &group.shape_array {
+            match shape_group {
+                ShapeGroup::Shape(shape) => {
+                    let non_visual_props = &shape.non_visual_props;
+                    if is_drawn(
+                        &non_visual_props.drawing_props,
+                        &non_visual_props.app_props,
+                        draw_placeholders,
+                    ) {
+                        self.render_shape(shape, part_path)?;
+                    }
+                }
+                ShapeGroup::GroupShape(group_shape) => {
+                    let non_visual_props = &group_shape.non_visual_props;
+                    if !is_drawn(&non_visual_props.drawing_props, &non_visual_props.app_props, true) {
+                        continue;
+                    }
+
+                    match &group_shape.group_shape_props.transform {
+                        Some(transform) => {
+                            writeln!(self.body, "<g{}>", group_transform_attribute(transform))?;
+                            self.render_group(group_shape, part_path, draw_placeholders)?;
+                            self.body.push_str("</g>\n");
+                        }
+                        None => self.render_group(group_shape, part_path, draw_placeholders)?,
+                    }
+                }
+                ShapeGroup::Picture(picture) => {
+                    let non_visual_props = &picture.non_visual_props;
+                    if !is_drawn(
+                        &non_visual_props.drawing_props,
+                        &non_visual_props.app_props,
+                        draw_placeholders,
+                    ) {
+                        continue;
+                    }
+
+                    let transform = match self
+                        .context
+                        .transform(&picture.shape_props, non_visual_props.app_props.placeholder.as_ref())
+                    {
+                        Some(transform) => transform,
+                        None => continue,
+                    };
+                    let media_path = picture
+                        .blip_fill
+                        .blip
+                        .as_ref()
+                        .and_then(|blip| blip.embed_rel_id.as_ref())
+                        .and_then(|rel_id| self.context.document.relationship_target_path(part_path, rel_id));
+                    let data_uri = match media_path {
+                        Some(media_path) => self.data_uri(&media_path)?,
+                        None => continue,
+                    };
+
+                    let (width, height) = extents(transform);
+                    writeln!(
+                        self.body,
+                        "<g{}><image width=\"{}\" height=\"{}\" preserveAspectRatio=\"none\" xlink:href=\"{}\"/>",
+                        transform_attribute(transform),
+                        number(width),
+                        number(height),
+                        data_uri
+                    )?;
+                    let stroke = self.context.stroke(
+                        picture.shape_props.line_properties.as_deref(),
+                        picture.shape_style.as_deref(),
+                    );
+                    if let Some(stroke) = stroke {
+                        let paths = shape_paths(picture.shape_props.geometry.as_ref(), width, height);
+                        self.render_paths(&paths, None, Some(&stroke))?;
+                    }
+                    self.body.push_str("</g>\n");
+                }
+                ShapeGroup::Connector(connector) => {
+                    let non_visual_props = &connector.non_visual_props;
+                    if !is_drawn(&non_visual_props.drawing_props, &non_visual_props.app_props, true) {
+                        continue;
+                    }
+
+                    let transform = match &connector.shape_props.transform {
+                        Some(transform) => transform,
+                        None => continue,
+                    };
+                    let stroke = self.context.stroke(
+                        connector.shape_props.line_properties.as_deref(),
+                        connector.shape_style.as_deref(),
+                    );
+                    if let Some(stroke) = stroke {
+                        let (width, height) = extents(transform);
+                        let paths = shape_paths(connector.shape_props.geometry.as_ref(), width, height);
+                        writeln!(self.body, "<g{}>", transform_attribute(transform))?;
+                        self.render_paths(&paths, None, Some(&stroke))?;
+                        self.body.push_str("</g>\n");
+                    }
+                }
+                ShapeGroup::GraphicFrame(graphic_frame) => {
+                    let non_visual_props = &graphic_frame.non_visual_props;
+                    if !is_drawn(
+                        &non_visual_props.drawing_props,
+                        &non_visual_props.app_props,
+                        draw_placeholders,
+                    ) {
+                        continue;
+                    }
+
+                    if let Some(GraphicalObjectContent::Table(table)) = &graphic_frame.graphic_content {
+                        let transform = &graphic_frame.transform;
+                        writeln!(self.body, "<g{}>", transform_attribute(transform))?;
+
+                        let column_widths = table
+                            .grid_column_list
+                            .iter()
+                            .map(|column| column.width as f64 / EMUS_PER_PIXEL)
+                            .collect::<Vec<_>>();
+                        let mut top = 0.0;
+                        for row in &table.row_list {
+                            let row_height = row.height as f64 / EMUS_PER_PIXEL;
+                            let mut left = 0.0;
+                            for (column, cell) in row.cell_list.iter().enumerate() {
+                                let column_width = column_widths.get(column).copied().unwrap_or(0.0);
+                                if !cell.is_merged() {
+                                    let span = cell.grid_span.unwrap_or(1).max(1) as usize;
+                                    let cell_width = column_widths.iter().skip(column).take(span).sum::<f64>();
+                                    writeln!(
+                                        self.body,
+                                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" \
+                                         stroke=\"#000000\" stroke-width=\"1\"/>",
+                                        number(left),
+                                        number(top),
+                                        number(cell_width),
+                                        number(row_height)
+                                    )?;
+                                    if let Some(text_body) = &cell.text_body {
+                                        let lines = layout_text_body(
+                                            &self.context,
+                                            None,
+                                            text_body,
+                                            cell_width * EMUS_PER_PIXEL,
+                                            row_height * EMUS_PER_PIXEL,
+                                            self.measure,
+                                        );
+                                        self.render_text(&lines, left, top)?;
+                                    }
+                                }
+                                left += column_width;
+                            }
+                            top += row_height;
+                        }
+
+                        self.body.push_str("</g>\n");
+                    }
+                }
+                ShapeGroup::ContentPart(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_shape(&mut self, shape: &'a Shape, part_path: &Path) -> Result<()> {
+        let placeholder = shape.non_visual_props.app_props.placeholder.as_ref();
+        let transform = match self.context.transform(&shape.shape_props, placeholder) {
+            Some(transform) => transform,
+            None => return Ok(()),
+        };
+
+        let (width, height) = extents(transform);
+        let fill = self.context.shape_fill(shape, part_path);
+        let stroke = self.context.stroke(
+            shape.shape_props.line_properties.as_deref(),
+            shape.shape_style.as_deref(),
+        );
+        if fill.is_some() || stroke.is_some() {
+            let paths = shape_paths(shape.shape_props.geometry.as_ref(), width, height);
+            writeln!(self.body, "<g{}>", transform_attribute(transform))?;
+            self.render_paths(&paths, fill.as_ref(), stroke.as_ref())?;
+            self.body.push_str("</g>\n");
+        }
+
+        // Text isn't mirrored by flips, so it's drawn with the rotation of the shape only
+        if let Some(text_body) = &shape.text_body {
+            let lines = layout_text_body(
+                &self.context,
+                Some(shape),
+                text_body,
+                width * EMUS_PER_PIXEL,
+                height * EMUS_PER_PIXEL,
+                self.measure,
+            );
+            if !lines.is_empty() {
+                writeln!(
+                    self.body,
+                    "<g{}>",
+                    transform_attribute(&Transform2D {
+                        flip_horizontal: None,
+                        flip_vertical: None,
+                        ..transform.clone()
+                    })
+                )?;
+                self.render_text(&lines, 0.0, 0.0)?;
+                self.body.push_str("</g>\n");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renders the paths of a geometry in the coordinate space of the shape.
+    fn render_paths(&mut self, paths: &[ShapePath], fill: Option<&Paint>, stroke: Option<&Stroke>) -> Result<()> {
+        let fill_attributes = match fill {
+            Some(paint) => self.paint_attributes(paint)?,
+            None => String::new(),
+        };
+
+        for path in paths {
+            let filled = fill.is_some() && path.is_filled();
+            let stroked = stroke.is_some() && path.stroke;
+            if !filled && !stroked {
+                continue;
+            }
+
+            let path_data = path.to_svg_path_data(1.0);
+            let mut attributes = String::from(" fill=\"none\"");
+            if filled {
+                attributes = fill_attributes.clone();
+                // Lighten and darken modes are approximated with a translucent white or black layer
+                let overlay = match path.fill_mode {
+                    PathFillMode::Lighten => Some(("#ffffff", 0.4)),
+                    PathFillMode::LightenLess => Some(("#ffffff", 0.2)),
+                    PathFillMode::Darken => Some(("#000000", 0.4)),
+                    PathFillMode::DarkenLess => Some(("#000000", 0.2)),
+                    _ => None,
+                };
+                if let Some((color, opacity)) = overlay {
+                    writeln!(self.body, "<path d=\"{}\"{}/>", path_data, attributes)?;
+                    attributes = format!(" fill=\"{}\" fill-opacity=\"{}\"", color, opacity);
+                }
+            }
+            if let (Some(stroke), true) = (stroke, stroked) {
+                attributes.push_str(&stroke_attributes(stroke));
+                if !path.is_closed() {
+                    attributes.push_str(&self.marker_attributes(stroke));
+                }
+            }
+
+            writeln!(self.body, "<path d=\"{}\"{}/>", path_data, attributes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Renders laid out text whose text body has its top left corner at (`left`, `top`) in pixels.
+    fn render_text(&mut self, lines: &[TextLine], left: f64, top: f64) -> Result<()> {
+        for line in lines {
+            let first_fragment = match line.fragments.first() {
+                Some(fragment) => fragment,
+                None => continue,
+            };
+
+            write!(
+                self.body,
+                "<text x=\"{}\" y=\"{}\" xml:space=\"preserve\">",
+                number(left + first_fragment.x / EMUS_PER_PIXEL),
+                number(top + line.baseline / EMUS_PER_PIXEL)
+            )?;
+            for fragment in &line.fragments {
+                let style = &fragment.style;
+                let mut attributes = format!(
+                    " x=\"{}\" font-size=\"{}\"",
+                    number(left + fragment.x / EMUS_PER_PIXEL),
+                    number(style.font_size * EMUS_PER_POINT / EMUS_PER_PIXEL)
+                );
+                if let Some(typeface) = &style.typeface {
+                    write!(attributes, " font-family=\"{}\"", escape_xml(typeface))?;
+                }
+                if style.bold {
+                    attributes.push_str(" font-weight=\"bold\"");
+                }
+                if style.italic {
+                    attributes.push_str(" font-style=\"italic\"");
+                }
+                match (style.underline, style.strikethrough) {
+                    (true, true) => attributes.push_str(" text-decoration=\"underline line-through\""),
+                    (true, false) => attributes.push_str(" text-decoration=\"underline\""),
+                    (false, true) => attributes.push_str(" text-decoration=\"line-through\""),
+                    (false, false) => (),
+                }
+                match style.color {
+                    Some(color) => {
+                        write!(attributes, " fill=\"{}\"", color.to_hex())?;
+                        if color.alpha != 255 {
+                            write!(attributes, " fill-opacity=\"{:.3}\"", color.opacity())?;
+                        }
+                    }
+                    None => attributes.push_str(" fill=\"none\""),
+                }
+
+                write!(self.body, "<tspan{}>{}</tspan>", attributes, escape_xml(&fragment.text))?;
+            }
+            self.body.push_str("</text>\n");
+        }
+
+        Ok(())
+    }
+
+    /// Returns the fill attributes of a paint. Gradients and pictures are added to the definitions of the document.
+    fn paint_attributes(&mut self, paint: &Paint) -> Result<String> {
+        let attributes = match paint {
+            Paint::Solid(color) => {
+                let mut attributes = format!(" fill=\"{}\"", color.to_hex());
+                if color.alpha != 255 {
+                    write!(attributes, " fill-opacity=\"{:.3}\"", color.opacity())?;
+                }
+                attributes
+            }
+            Paint::LinearGradient { angle, stops } => {
+                let id = self.next_id("gradient");
+                let (sin, cos) = angle.to_radians().sin_cos();
+                writeln!(
+                    self.defs,
+                    "<linearGradient id=\"{}\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\">{}</linearGradient>",
+                    id,
+                    number(0.5 - cos / 2.0),
+                    number(0.5 - sin / 2.0),
+                    number(0.5 + cos / 2.0),
+                    number(0.5 + sin / 2.0),
+                    gradient_stops(stops)
+                )?;
+                format!(" fill=\"url(#{})\"", id)
+            }
+            Paint::RadialGradient { stops } => {
+                let id = self.next_id("gradient");
+                writeln!(
+                    self.defs,
+                    "<radialGradient id=\"{}\" cx=\"0.5\" cy=\"0.5\" r=\"0.71\">{}</radialGradient>",
+                    id,
+                    gradient_stops(stops)
+                )?;
+                format!(" fill=\"url(#{})\"", id)
+            }
+            Paint::Image { media_path } => {
+                let data_uri = self.data_uri(media_path)?;
+                let id = self.next_id("image");
+                writeln!(
+                    self.defs,
+                    "<pattern id=\"{}\" width=\"1\" height=\"1\" patternContentUnits=\"objectBoundingBox\">\
+                     <image width=\"1\" height=\"1\" preserveAspectRatio=\"none\" xlink:href=\"{}\"/></pattern>",
+                    id, data_uri
+                )?;
+                format!(" fill=\"url(#{})\"", id)
+            }
+        };
+
+        Ok(attributes)
+    }
+
+    /// Returns the marker attributes of the arrowheads of a stroke. The markers are added to the definitions of the
+    /// document.
+    fn marker_attributes(&mut self, stroke: &Stroke) -> String {
+        let mut attributes = String::new();
+        if let Some(head_end) = &stroke.head_end {
+            if let Some(id) = self.marker(head_end, stroke) {
+                write!(attributes, " marker-start=\"url(#{})\"", id).unwrap();
+            }
+        }
+        if let Some(tail_end) = &stroke.tail_end {
+            if let Some(id) = self.marker(tail_end, stroke) {
+                write!(attributes, " marker-end=\"url(#{})\"", id).unwrap();
+            }
+        }
+        attributes
+    }
+
+    /// Adds a marker drawing a line end decoration and returns its id. The marker is drawn in a 10 by 10 box pointing
+    /// to the right, which is scaled by the width and the length of the decoration.
+    fn marker(&mut self, line_end: &LineEndProperties, stroke: &Stroke) -> Option<String> {
+        let color = stroke.color.to_hex();
+        let shape = match line_end.end_type? {
+            LineEndType::None => return None,
+            LineEndType::Triangle => format!("<path d=\"M 0,0 L 10,5 L 0,10 Z\" fill=\"{}\"/>", color),
+            LineEndType::Stealth => format!("<path d=\"M 0,0 L 10,5 L 0,10 L 3,5 Z\" fill=\"{}\"/>", color),
+            LineEndType::Diamond => format!("<path d=\"M 0,5 L 5,0 L 10,5 L 5,10 Z\" fill=\"{}\"/>", color),
+            LineEndType::Oval => format!("<ellipse cx=\"5\" cy=\"5\" rx=\"5\" ry=\"5\" fill=\"{}\"/>", color),
+            LineEndType::Arrow => format!(
+                "<path d=\"M 0,0 L 10,5 L 0,10\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                color
+            ),
+        };
+        let size = |size: Option<f64>| size.unwrap_or(3.0);
+        let width = size(line_end.width.map(|width| match width {
+            LineEndWidth::Small => 2.0,
+            LineEndWidth::Medium => 3.0,
+            LineEndWidth::Large => 5.0,
+        }));
+        let length = size(line_end.length.map(|length| match length {
+            LineEndLength::Small => 2.0,
+            LineEndLength::Medium => 3.0,
+            LineEndLength::Large => 5.0,
+        }));
+        let reference_x = match line_end.end_type? {
+            LineEndType::Triangle | LineEndType::Stealth | LineEndType::Arrow => 9,
+            _ => 5,
+        };
+
+        let id = self.next_id("marker");
+        writeln!(
+            self.defs,
+            "<marker id=\"{}\" viewBox=\"0 0 10 10\" refX=\"{}\" refY=\"5\" markerWidth=\"{}\" markerHeight=\"{}\" \
+             orient=\"auto-start-reverse\" preserveAspectRatio=\"none\">{}</marker>",
+            id, reference_x, length, width, shape
+        )
+        .unwrap();
+        Some(id)
+    }
+
+    /// Returns the data URI of a media part. Data URIs are cached, so media used multiple times is only read once.
+    fn data_uri(&mut self, media_path: &Path) -> Result<String> {
+        if let Some(data_uri) = self.data_uris.get(media_path) {
+            return Ok(data_uri.clone());
+        }
+
+        let data_uri = self.context.document.part_data_uri(media_path)?;
+        self.data_uris.insert(media_path.to_path_buf(), data_uri.clone());
+        Ok(data_uri)
+    }
+
+    fn next_id(&mut self, prefix: &str) -> String {
+        let id = format!("{}{}", prefix, self.next_id);
+        self.next_id += 1;
+        id
+    }
+}
+
+/// Returns the width and height of a transform in pixels.
+fn extents(transform: &Transform2D) -> (f64, f64) {
+    let extents = transform.extents.unwrap_or_default();
+    (
+        extents.width as f64 / EMUS_PER_PIXEL,
+        extents.height as f64 / EMUS_PER_PIXEL,
+    )
+}
+
+/// Returns the transform attribute that maps the coordinate space of a shape into the coordinate space of its group.
+fn transform_attribute(transform: &Transform2D) -> String {
+    let offset = transform.offset.unwrap_or_default();
+    let (width, height) = extents(transform);
+    placement_attribute(
+        offset.x as f64 / EMUS_PER_PIXEL,
+        offset.y as f64 / EMUS_PER_PIXEL,
+        width,
+        height,
+        transform.rotate_angle,
+        transform.flip_horizontal,
+        transform.flip_vertical,
+        "",
+    )
+}
+
+/// Returns the transform attribute that maps the child coordinate space of a group into the coordinate space of its
+/// parent.
+fn group_transform_attribute(transform: &GroupTransform2D) -> String {
+    let offset = transform.offset.unwrap_or_default();
+    let extents = transform.extents.unwrap_or_default();
+    let child_offset = transform.child_offset.unwrap_or_default();
+    let child_extents = transform.child_extents.unwrap_or_default();
+    let scale = |extent: u64, child_extent: u64| {
+        if child_extent == 0 {
+            1.0
+        } else {
+            extent as f64 / child_extent as f64
+        }
+    };
+
+    let child_space = format!(
+        " scale({} {}) translate({} {})",
+        number(scale(extents.width, child_extents.width)),
+        number(scale(extents.height, child_extents.height)),
+        number(-child_offset.x as f64 / EMUS_PER_PIXEL),
+        number(-child_offset.y as f64 / EMUS_PER_PIXEL),
+    );
+    placement_attribute(
+        offset.x as f64 / EMUS_PER_PIXEL,
+        offset.y as f64 / EMUS_PER_PIXEL,
+        extents.width as f64 / EMUS_PER_PIXEL,
+        extents.height as f64 / EMUS_PER_PIXEL,
+        transform.rotate_angle,
+        transform.flip_horizontal,
+        transform.flip_vertical,
+        &child_space,
+    )
+}
+
+/// Returns a transform attribute placing a box of the given size at (x, y), rotated and flipped around its center.
+/// `inner` is appended to the transform list and applies within the box.
+#[allow(clippy::too_many_arguments)]
+fn placement_attribute(
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    rotate_angle: Option<i32>,
+    flip_horizontal: Option<bool>,
+    flip_vertical: Option<bool>,
+    inner: &str,
+) -> String {
+    let mut transform = format!("translate({} {})", number(x), number(y));
+    if let Some(angle) = rotate_angle.filter(|angle| *angle != 0) {
+        write!(
+            transform,
+            " rotate({} {} {})",
+            number(f64::from(angle) / 60000.0),
+            number(width / 2.0),
+            number(height / 2.0)
+        )
+        .unwrap();
+    }
+    let flip_horizontal = flip_horizontal.unwrap_or(false);
+    let flip_vertical = flip_vertical.unwrap_or(false);
+    if flip_horizontal || flip_vertical {
+        write!(
+            transform,
+            " matrix({} 0 0 {} {} {})",
+            if flip_horizontal { -1 } else { 1 },
+            if flip_vertical { -1 } else { 1 },
+            if flip_horizontal { number(width) } else { 0.0 },
+            if flip_vertical { number(height) } else { 0.0 },
+        )
+        .unwrap();
+    }
+    transform.push_str(inner);
+
+    format!(" transform=\"{}\"", transform)
+}
+
+fn stroke_attributes(stroke: &Stroke) -> String {
+    let width = stroke.width as f64 / EMUS_PER_PIXEL;
+    let mut attributes = format!(
+        " stroke=\"{}\" stroke-width=\"{}\"",
+        stroke.color.to_hex(),
+        number(width)
+    );
+    if stroke.color.alpha != 255 {
+        write!(attributes, " stroke-opacity=\"{:.3}\"", stroke.color.opacity()).unwrap();
+    }
+    match stroke.cap {
+        Some(LineCap::Round) => attributes.push_str(" stroke-linecap=\"round\""),
+        Some(LineCap::Square) => attributes.push_str(" stroke-linecap=\"square\""),
+        _ => (),
+    }
+    if let Some(dash_pattern) = stroke.dash_pattern() {
+        let dash_array = dash_pattern
+            .iter()
+            .map(|length| number(length / EMUS_PER_PIXEL).to_string())
+            .collect::<Vec<_>>();
+        write!(attributes, " stroke-dasharray=\"{}\"", dash_array.join(" ")).unwrap();
+    }
+    attributes
+}
+
+fn gradient_stops(stops: &[GradientStop]) -> String {
+    stops
+        .iter()
+        .map(|stop| {
+            let mut element = format!(
+                "<stop offset=\"{}\" stop-color=\"{}\"",
+                number(stop.position),
+                stop.color.to_hex()
+            );
+            if stop.color.alpha != 255 {
+                write!(element, " stop-opacity=\"{:.3}\"", stop.color.opacity()).unwrap();
+            }
+            element.push_str("/>");
+            element
+        })
+        .collect()
+}
+
+/// Rounds to two decimal places, which is precise enough for pixels and keeps the output short.
+fn number(value: f64) -> f64 {
+    // Adding zero turns negative zero into zero
+    (value * 100.0).round() / 100.0 + 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawingml::color::RgbaColor;
+    use msoffice_shared::drawingml::{
+        coordsys::{Point2D, PositiveSize2D},
+        simpletypes::PresetLineDashVal,
+    };
+
+    #[test]
+    pub fn test_group_transform_attribute() {
+        let transform = GroupTransform2D {
+            rotate_angle: Some(5_400_000),
+            flip_horizontal: Some(true),
+            flip_vertical: None,
+            offset: Some(Point2D::new(95250, 0)),
+            extents: Some(PositiveSize2D::new(952_500, 476_250)),
+            child_offset: Some(Point2D::new(9525, 9525)),
+            child_extents: Some(PositiveSize2D::new(1_905_000, 952_500)),
+        };
+        assert_eq!(
+            group_transform_attribute(&transform),
+            " transform=\"translate(10 0) rotate(90 50 25) matrix(-1 0 0 1 100 0) scale(0.5 0.5) translate(-1 -1)\""
+        );
+    }
+
+    #[test]
+    pub fn test_stroke_attributes() {
+        let stroke = Stroke {
+            width: 19050,
+            color: RgbaColor::new(0, 0, 255, 128),
+            dash: Some(PresetLineDashVal::SystemDot),
+            cap: Some(LineCap::Round),
+            head_end: None,
+            tail_end: None,
+        };
+        assert_eq!(
+            stroke_attributes(&stroke),
+            " stroke=\"#0000ff\" stroke-width=\"2\" stroke-opacity=\"0.502\" stroke-linecap=\"round\" \
+             stroke-dasharray=\"2 2\""
+        );
+    }
+
+    #[test]
+    pub fn test_svg() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let sample_pptx_path = test_dir.join("tests/samplepptx.pptx");
+
+        let document = PPTXDocument::from_file(&sample_pptx_path).unwrap();
+        let svg = slide_to_svg(&document, Path::new("ppt/slides/slide2.xml")).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("width=\"960\" height=\"720\" viewBox=\"0 0 960 720\""));
+        assert!(svg.contains("<pattern id=\"image1\""));
+        assert!(svg.contains(">This is a Sample Slide</tspan>"));
+        assert!(slide_to_svg(&document, Path::new("ppt/slides/slide3.xml")).is_err());
+    }
+}
@@ -0,0 +1,507 @@
+//! Filling template presentations with data, e.g. for mail merge.
+//!
+//! A template contains tokens like `{{customer}}` in the text of its shapes, tables and notes. PowerPoint often splits
+//! the text of a token into several runs, e.g. when it's been spell checked or edited, so tokens are matched across
+//! run boundaries within a paragraph. The value of a token takes over the formatting of the run the token starts in.
+//! Tokens that don't have a value in the record are left unchanged.
+
+use crate::document::PPTXDocument;
+use crate::pml::slides::{CommonSlideData, GraphicalObjectContent, ShapeGroup};
+use crate::xml::UnknownXml;
+use msoffice_shared::drawingml::{
+    core::TextBody,
+    text::{paragraphs::TextParagraph, runformatting::TextRun},
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// The values of the tokens, keyed by the token names. The names don't include the braces.
+pub type Record = HashMap<String, String>;
+
+pub const TOKEN_START: &str = "{{";
+pub const TOKEN_END: &str = "}}";
+
+/// Returns the names of the tokens in the text body, in order of appearance. Names are listed once for every
+/// occurrence.
+pub fn text_body_tokens(text_body: &TextBody) -> Vec<String> {
+    text_body
+        .paragraph_array
+        .iter()
+        .flat_map(|paragraph| text_segments(paragraph))
+        .flat_map(|range_list| {
+            let text = range_list.iter().map(|(_, text)| text.as_str()).collect::<String>();
+            find_tokens(&text)
+                .into_iter()
+                .map(|(_, name)| String::from(name))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Replaces the tokens of the text body with their values from `record`. Returns the number of replaced tokens.
+pub fn fill_text_body(text_body: &mut TextBody, record: &Record) -> usize {
+    fill_text_body_with_unknown_xml(text_body, record, None)
+}
+
+/// Replaces the tokens of the paragraph with their values from `record`. A token can span several runs, as long as
+/// it isn't interrupted by a line break or a text field. The value is inserted into the run where the token starts,
+/// the rest of the token is removed from the following runs and runs that are left empty are removed. Returns the
+/// number of replaced tokens.
+pub fn fill_paragraph(paragraph: &mut TextParagraph, record: &Record) -> usize {
+    replace_paragraph_tokens(paragraph, record).0
+}
+
+/// Replaces the tokens of the text body like fill_text_body. `unknown_xml` is the unknown xml kept for the text body
+/// by the element that owns it, which is indexed by the positions of the runs, so the unknown xml of the removed runs
+/// is dropped from it.
+fn fill_text_body_with_unknown_xml(
+    text_body: &mut TextBody,
+    record: &Record,
+    mut unknown_xml: Option<&mut UnknownXml>,
+) -> usize {
+    let mut replacement_count = 0;
+    for (paragraph_index, paragraph) in text_body.paragraph_array.iter_mut().enumerate() {
+        let (paragraph_replacement_count, removed_run_indices) = replace_paragraph_tokens(paragraph, record);
+        replacement_count += paragraph_replacement_count;
+
+        let paragraph_unknown_xml = unknown_xml
+            .as_deref_mut()
+            .and_then(|unknown_xml| unknown_xml.known_child_mut("p", paragraph_index));
+        if let Some(paragraph_unknown_xml) = paragraph_unknown_xml {
+            for run_index in removed_run_indices.into_iter().rev() {
+                paragraph_unknown_xml.remove_known_child_at("r", run_index);
+            }
+        }
+    }
+
+    replacement_count
+}
+
+/// Replaces the tokens of the paragraph like fill_paragraph. Returns the number of replaced tokens and the indices of
+/// the removed runs among the regular text runs of the paragraph, in ascending order.
+fn replace_paragraph_tokens(paragraph: &mut TextParagraph, record: &Record) -> (usize, Vec<usize>) {
+    let mut replacement_count = 0;
+    let mut new_texts = Vec::new();
+
+    for segment in text_segments(paragraph) {
+        let text = segment.iter().map(|(_, text)| text.as_str()).collect::<String>();
+        let replacements = find_tokens(&text)
+            .into_iter()
+            .filter_map(|(range, name)| record.get(name).map(|value| (range, value)))
+            .collect::<Vec<_>>();
+        if replacements.is_empty() {
+            continue;
+        }
+        replacement_count += replacements.len();
+
+        let mut run_start = 0;
+        for (index, run_text) in &segment {
+            let run_range = run_start..run_start + run_text.len();
+            run_start = run_range.end;
+
+            let overlapping_replacements = replacements
+                .iter()
+                .filter(|(range, _)| range.start < run_range.end && range.end > run_range.start)
+                .collect::<Vec<_>>();
+            if overlapping_replacements.is_empty() {
+                continue;
+            }
+
+            let mut new_text = String::new();
+            let mut position = run_range.start;
+            for (range, value) in overlapping_replacements {
+                if range.start >= position {
+                    new_text.push_str(&text[position..range.start]);
+                    new_text.push_str(value);
+                }
+                position = range.end.min(run_range.end);
+            }
+            new_text.push_str(&text[position..run_range.end]);
+            new_texts.push((*index, new_text));
+        }
+    }
+
+    if new_texts.is_empty() {
+        return (0, Vec::new());
+    }
+
+    let mut removed_indices = Vec::new();
+    for (index, new_text) in new_texts {
+        if let TextRun::RegularTextRun(regular_text_run) = &mut paragraph.text_run_list[index] {
+            if new_text.is_empty() {
+                removed_indices.push(index);
+            }
+            regular_text_run.text = new_text;
+        }
+    }
+    removed_indices.sort_unstable();
+    let removed_run_indices = removed_indices
+        .iter()
+        .map(|index| {
+            paragraph.text_run_list[..*index]
+                .iter()
+                .filter(|text_run| matches!(text_run, TextRun::RegularTextRun(_)))
+                .count()
+        })
+        .collect();
+
+    let mut index = 0;
+    paragraph.text_run_list.retain(|_| {
+        let is_retained = !removed_indices.contains(&index);
+        index += 1;
+        is_retained
+    });
+
+    (replacement_count, removed_run_indices)
+}
+
+impl CommonSlideData {
+    /// Replaces the tokens in the text of every shape and table cell with their values from `record`. Returns the
+    /// number of replaced tokens.
+    ///
+    /// # Note
+    ///
+    /// The text of diagrams is stored in separate parts, so it isn't filled.
+    pub fn fill_tokens(&mut self, record: &Record) -> usize {
+        let mut replacement_count = 0;
+        self.for_each_shape_mut(|_, _, shape_group| match shape_group {
+            ShapeGroup::Shape(shape) => {
+                if let Some(text_body) = &mut shape.text_body {
+                    let unknown_xml = shape.unknown_xml.known_child_mut("txBody", 0);
+                    replacement_count += fill_text_body_with_unknown_xml(text_body, record, unknown_xml);
+                }
+            }
+            ShapeGroup::GraphicFrame(graphic_frame) => {
+                if let Some(GraphicalObjectContent::Table(table)) = &mut graphic_frame.graphic_content {
+                    for (row_index, row) in table.row_list.iter_mut().enumerate() {
+                        for (cell_index, cell) in row.cell_list.iter_mut().enumerate() {
+                            if let Some(text_body) = &mut cell.text_body {
+                                let unknown_xml = graphic_frame
+                                    .unknown_graphic_data
+                                    .known_child_mut("tbl", 0)
+                                    .and_then(|unknown_xml| unknown_xml.known_child_mut("tr", row_index))
+                                    .and_then(|unknown_xml| unknown_xml.known_child_mut("tc", cell_index))
+                                    .and_then(|unknown_xml| unknown_xml.known_child_mut("txBody", 0));
+                                replacement_count += fill_text_body_with_unknown_xml(text_body, record, unknown_xml);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => (),
+        });
+
+        replacement_count
+    }
+}
+
+impl PPTXDocument {
+    /// Replaces the tokens of the slide at `slide_path` and its notes slide with their values from `record`. Modified
+    /// parts are marked as modified. Returns the number of replaced tokens.
+    pub fn fill_slide(&mut self, slide_path: &Path, record: &Record) -> Result<usize> {
+        let slide = self
+            .slide_map
+            .get_mut(slide_path)
+            .ok_or_else(|| format!("slide not found: {}", slide_path.display()))?;
+        let slide_replacement_count = slide.common_slide_data.fill_tokens(record);
+        if slide_replacement_count > 0 {
+            self.mark_modified(slide_path);
+        }
+
+        let mut notes_replacement_count = 0;
+        if let Some(notes_slide_path) = self.notes_slide_path(slide_path) {
+            if let Some(notes_slide) = self.notes_slide_map.get_mut(&notes_slide_path) {
+                notes_replacement_count = notes_slide.common_slide_data.fill_tokens(record);
+            }
+            if notes_replacement_count > 0 {
+                self.mark_modified(&notes_slide_path);
+            }
+        }
+
+        Ok(slide_replacement_count + notes_replacement_count)
+    }
+
+    /// Fills a copy of the slide at `slide_path` for every record, like a mail merge. The slide itself is filled with
+    /// the first record and the copies follow it in the order of the records. Copies share the media and the slide
+    /// layout of the slide and get a copy of its notes slide, charts, diagrams and embedded objects. Returns the paths
+    /// of the filled slides. If `records` is empty, the slide is left unchanged.
+    pub fn fill_slide_per_record(&mut self, slide_path: &Path, records: &[Record]) -> Result<Vec<PathBuf>> {
+        let mut slide_paths = Vec::with_capacity(records.len());
+        let mut current_slide_path = PathBuf::from(slide_path);
+        for (i, record) in records.iter().enumerate() {
+            // The next slide is copied before filling the current one, so every copy is made of the template
+            let next_slide_path = if i + 1 < records.len() {
+                Some(self.copy_slide(&current_slide_path)?)
+            } else {
+                None
+            };
+
+            self.fill_slide(&current_slide_path, record)?;
+            slide_paths.push(current_slide_path);
+            match next_slide_path {
+                Some(next_slide_path) => current_slide_path = next_slide_path,
+                None => break,
+            }
+        }
+
+        Ok(slide_paths)
+    }
+}
+
+/// Returns the runs of the paragraph that can contain a token, as lists of consecutive regular text runs, paired with
+/// their indices in the run list.
+fn text_segments(paragraph: &TextParagraph) -> Vec<Vec<(usize, &String)>> {
+    let mut segments = vec![Vec::new()];
+    for (index, text_run) in paragraph.text_run_list.iter().enumerate() {
+        match text_run {
+            TextRun::RegularTextRun(regular_text_run) => {
+                if let Some(segment) = segments.last_mut() {
+                    segment.push((index, &regular_text_run.text));
+                }
+            }
+            _ => segments.push(Vec::new()),
+        }
+    }
+
+    segments.retain(|segment| !segment.is_empty());
+    segments
+}
+
+/// Returns the byte ranges of the tokens in `text`, braces included, along with their trimmed names. A token ends at
+/// the first closing braces and starts at the last opening braces before them.
+fn find_tokens(text: &str) -> Vec<(Range<usize>, &str)> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    while let Some(end) = text[offset..].find(TOKEN_END).map(|end| offset + end) {
+        if let Some(start) = text[offset..end].rfind(TOKEN_START).map(|start| offset + start) {
+            let name = text[start + TOKEN_START.len()..end].trim();
+            if !name.is_empty() {
+                tokens.push((start..end + TOKEN_END.len(), name));
+            }
+        }
+        offset = end + TOKEN_END.len();
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::paragraph_text;
+    use msoffice_shared::xml::XmlNode;
+    use std::str::FromStr;
+
+    fn record(values: &[(&str, &str)]) -> Record {
+        values
+            .iter()
+            .map(|(name, value)| (String::from(*name), String::from(*value)))
+            .collect()
+    }
+
+    fn run_texts(paragraph: &TextParagraph) -> Vec<&str> {
+        paragraph
+            .text_run_list
+            .iter()
+            .filter_map(|text_run| match text_run {
+                TextRun::RegularTextRun(regular_text_run) => Some(regular_text_run.text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_tokens() {
+        assert_eq!(find_tokens("Dear {{ name }},"), vec![(5..15, "name")]);
+        assert_eq!(find_tokens("{{a}}{{b}}"), vec![(0..5, "a"), (5..10, "b")]);
+        assert_eq!(find_tokens("{{ {{a}} }}"), vec![(3..8, "a")]);
+        assert!(find_tokens("{{}} {{ }} }} {{").is_empty());
+    }
+
+    #[test]
+    fn test_fill_paragraph_across_runs() {
+        let xml = r#"<a:p xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+            <a:r><a:rPr b="1"/><a:t>Hello {{cust</a:t></a:r>
+            <a:r><a:rPr i="1"/><a:t>om</a:t></a:r>
+            <a:r><a:rPr/><a:t>er}}, total: {{total}}!</a:t></a:r>
+            <a:br/>
+            <a:r><a:rPr/><a:t>{{missing}} {{</a:t></a:r>
+            <a:r><a:rPr/><a:t>total}}</a:t></a:r>
+        </a:p>"#;
+        let mut paragraph = TextParagraph::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        let record = record(&[("customer", "ACME"), ("total", "42")]);
+
+        assert_eq!(fill_paragraph(&mut paragraph, &record), 3);
+        assert_eq!(paragraph_text(&paragraph), "Hello ACME, total: 42!\n{{missing}} 42");
+        assert_eq!(
+            run_texts(&paragraph),
+            vec!["Hello ACME", ", total: 42!", "{{missing}} 42"]
+        );
+
+        // The value keeps the formatting of the run where the token starts
+        match &paragraph.text_run_list[0] {
+            TextRun::RegularTextRun(regular_text_run) => {
+                let char_properties = regular_text_run.char_properties.as_ref().unwrap();
+                assert_eq!(char_properties.bold, Some(true));
+            }
+            _ => panic!("expected a regular text run"),
+        }
+    }
+
+    #[test]
+    fn test_fill_tokens_keeps_unknown_xml_of_runs() {
+        use crate::pml::slides::Slide;
+        use crate::xml::parse_part_xml;
+
+        let paragraph_xml = r#"<a:p>
+            <a:r><a:rPr><a:extLst><a:ext uri="{1}"><x:run/></a:ext></a:extLst></a:rPr><a:t>Hi {{cust</a:t></a:r>
+            <a:r><a:rPr><a:extLst><a:ext uri="{2}"><x:run/></a:ext></a:extLst></a:rPr><a:t>omer}}</a:t></a:r>
+            <a:r><a:rPr><a:extLst><a:ext uri="{3}"><x:run/></a:ext></a:extLst></a:rPr><a:t>!</a:t></a:r>
+        </a:p>"#;
+        let xml = format!(
+            r#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+                xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"
+                xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+                xmlns:x="urn:example">
+                <p:cSld>
+                    <p:spTree>
+                        <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                        <p:grpSpPr/>
+                        <p:sp>
+                            <p:nvSpPr><p:cNvPr id="2" name="Title 1"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>
+                            <p:spPr/>
+                            <p:txBody><a:bodyPr/>{paragraph}</p:txBody>
+                        </p:sp>
+                        <p:graphicFrame>
+                            <p:nvGraphicFramePr>
+                                <p:cNvPr id="3" name="Table 2"/><p:cNvGraphicFramePr/><p:nvPr/>
+                            </p:nvGraphicFramePr>
+                            <p:xfrm><a:off x="0" y="0"/><a:ext cx="100" cy="100"/></p:xfrm>
+                            <a:graphic>
+                                <a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/table">
+                                    <a:tbl>
+                                        <a:tblGrid><a:gridCol w="100"/></a:tblGrid>
+                                        <a:tr h="100">
+                                            <a:tc><a:txBody><a:bodyPr/>{paragraph}</a:txBody><a:tcPr/></a:tc>
+                                        </a:tr>
+                                    </a:tbl>
+                                </a:graphicData>
+                            </a:graphic>
+                        </p:graphicFrame>
+                    </p:spTree>
+                </p:cSld>
+            </p:sld>"#,
+            paragraph = paragraph_xml
+        );
+        let mut slide = Slide::from_xml_element(&parse_part_xml(&xml).unwrap()).unwrap();
+        assert_eq!(slide.common_slide_data.fill_tokens(&record(&[("customer", "ACME")])), 2);
+
+        let mut buffer = Vec::new();
+        slide.to_xml(&mut buffer).unwrap();
+        let written_xml = String::from_utf8(buffer).unwrap();
+        let run_extension_uris = |paragraph_node: &XmlNode| {
+            paragraph_node
+                .child_nodes
+                .iter()
+                .filter(|child_node| child_node.local_name() == "r")
+                .map(|run_node| {
+                    let run_text = run_node.child_nodes[1].text.clone().unwrap_or_default();
+                    let extension_uri = run_node.child_nodes[0].child_nodes[0].child_nodes[0].attributes["uri"].clone();
+                    (run_text, extension_uri)
+                })
+                .collect::<Vec<_>>()
+        };
+        let expected_runs = vec![
+            (String::from("Hi ACME"), String::from("{1}")),
+            (String::from("!"), String::from("{3}")),
+        ];
+
+        fn collect_paragraph_nodes<'a>(xml_node: &'a XmlNode, paragraph_nodes: &mut Vec<&'a XmlNode>) {
+            for child_node in &xml_node.child_nodes {
+                match child_node.local_name() {
+                    "p" => paragraph_nodes.push(child_node),
+                    _ => collect_paragraph_nodes(child_node, paragraph_nodes),
+                }
+            }
+        }
+        let xml_node = parse_part_xml(&written_xml).unwrap();
+        let mut paragraph_nodes = Vec::new();
+        collect_paragraph_nodes(&xml_node, &mut paragraph_nodes);
+        assert_eq!(paragraph_nodes.len(), 2);
+        for paragraph_node in paragraph_nodes {
+            assert_eq!(run_extension_uris(paragraph_node), expected_runs);
+        }
+        assert!(!written_xml.contains("{2}"));
+    }
+
+    #[test]
+    fn test_text_body_tokens() {
+        let xml = r#"<p:txBody xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+            <a:bodyPr/>
+            <a:p><a:r><a:t>{{first</a:t></a:r><a:r><a:t>}} and {{ second }}</a:t></a:r></a:p>
+            <a:p><a:r><a:t>{{first}}</a:t></a:r></a:p>
+        </p:txBody>"#;
+        let text_body = TextBody::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(text_body_tokens(&text_body), vec!["first", "second", "first"]);
+    }
+
+    #[test]
+    fn test_fill_slide_per_record() {
+        use crate::builder::PresentationBuilder;
+        use crate::document::load_saved_document;
+        use crate::pml::slides::{PlaceholderType, SlideLayoutType};
+        use std::io::Cursor;
+
+        let mut builder = PresentationBuilder::new().unwrap();
+        let cover_path = builder.add_slide(SlideLayoutType::Title).unwrap();
+        let template_path = builder.add_slide(SlideLayoutType::Title).unwrap();
+        let closing_path = builder.add_slide(SlideLayoutType::Title).unwrap();
+        builder
+            .set_placeholder_text(
+                &template_path,
+                PlaceholderType::CenteredTitle,
+                None,
+                "Offer for {{customer}}",
+            )
+            .unwrap();
+        let mut document = builder.build();
+
+        let records = vec![record(&[("customer", "ACME")]), record(&[("customer", "Globex")])];
+        let filled_paths = document.fill_slide_per_record(&template_path, &records).unwrap();
+        assert_eq!(filled_paths.len(), 2);
+        assert_eq!(filled_paths[0], template_path);
+
+        let data = document.save(Cursor::new(Vec::new())).unwrap().into_inner();
+        let saved_document = load_saved_document(&data);
+
+        let ordered_slide_paths = saved_document
+            .ordered_slides()
+            .into_iter()
+            .map(|(slide_path, _)| slide_path.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ordered_slide_paths,
+            vec![
+                cover_path,
+                filled_paths[0].clone(),
+                filled_paths[1].clone(),
+                closing_path
+            ]
+        );
+        let titles = filled_paths
+            .iter()
+            .map(|slide_path| saved_document.slide_map[slide_path].text().plain_text())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Offer for ACME", "Offer for Globex"]);
+        assert_eq!(
+            saved_document.slide_layout_path(&filled_paths[1]),
+            saved_document.slide_layout_path(&filled_paths[0])
+        );
+    }
+}
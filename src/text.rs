@@ -0,0 +1,354 @@
+use crate::document::PPTXDocument;
+use crate::drawingml::{diagram::DiagramData, table::Table};
+use crate::pml::slides::{
+    CommonSlideData, GraphicalObjectContent, GroupShape, NotesSlide, Placeholder, PlaceholderType, ShapeGroup, Slide,
+};
+use msoffice_shared::drawingml::{
+    core::TextBody,
+    text::{paragraphs::TextParagraph, runformatting::TextRun},
+};
+use std::path::Path;
+
+/// Options of the text extraction.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct TextOptions {
+    /// Specifies whether the text of the notes slides should be extracted as well.
+    ///
+    /// Defaults to false
+    pub include_notes: bool,
+}
+
+/// The text of a single paragraph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParagraphText {
+    /// The indentation level of the paragraph. Top level paragraphs have a level of 0.
+    pub level: u32,
+    /// The text of the paragraph. Line breaks within the paragraph are represented by '\n'.
+    pub text: String,
+}
+
+/// The text contained by a shape.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextContent {
+    /// The paragraphs of a text body or a diagram.
+    Paragraphs(Vec<ParagraphText>),
+    /// The cells of a table, row by row. Each cell is a list of paragraphs. Cells that are covered by a merged cell
+    /// are omitted.
+    Table(Vec<Vec<Vec<ParagraphText>>>),
+}
+
+/// The text of a shape along with the properties that identify it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeText {
+    /// The id of the shape, as specified by the cNvPr element.
+    pub shape_id: u32,
+    /// The name of the shape, as specified by the cNvPr element.
+    pub name: String,
+    /// The placeholder properties of the shape, if the shape is a placeholder.
+    pub placeholder: Option<Placeholder>,
+    pub content: TextContent,
+}
+
+impl ShapeText {
+    /// Returns the text of the shape as plain text. Paragraphs are separated by '\n', table cells are separated by
+    /// '\t' and table rows are separated by '\n'. Empty paragraphs are skipped.
+    pub fn plain_text(&self) -> String {
+        match &self.content {
+            TextContent::Paragraphs(paragraphs) => join_paragraphs(paragraphs, "\n"),
+            TextContent::Table(rows) => rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|cell| join_paragraphs(cell, " "))
+                        .collect::<Vec<_>>()
+                        .join("\t")
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    /// Returns true if the shape is a title or centered title placeholder.
+    pub fn is_title(&self) -> bool {
+        matches!(
+            self.placeholder
+                .as_ref()
+                .and_then(|placeholder| placeholder.placeholder_type),
+            Some(PlaceholderType::Title) | Some(PlaceholderType::CenteredTitle)
+        )
+    }
+}
+
+/// The text of a slide, shape by shape in reading order.
+///
+/// The reading order is the navigation order of the shape tree, which is the lexical order of the shapes. Shapes of
+/// groups are listed in place of the group.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SlideText {
+    pub shapes: Vec<ShapeText>,
+    /// The text of the notes slide. This is only filled if the notes were requested and the slide has a notes slide.
+    pub notes: Vec<ShapeText>,
+}
+
+impl SlideText {
+    /// Returns the text of the slide as plain text. Shapes are separated by '\n'. The notes, if any, follow the text
+    /// of the slide separated by an empty line.
+    pub fn plain_text(&self) -> String {
+        let slide_text = join_shapes(&self.shapes);
+        let notes_text = join_shapes(&self.notes);
+        match (slide_text.is_empty(), notes_text.is_empty()) {
+            (_, true) => slide_text,
+            (true, false) => notes_text,
+            (false, false) => format!("{}\n\n{}", slide_text, notes_text),
+        }
+    }
+}
+
+impl Slide {
+    /// Extracts the text of the slide in reading order.
+    ///
+    /// # Note
+    ///
+    /// The text of diagrams is stored in separate parts, so it's only included by PPTXDocument::slide_text.
+    pub fn text(&self) -> SlideText {
+        SlideText {
+            shapes: common_slide_data_text(&self.common_slide_data, None),
+            notes: Vec::new(),
+        }
+    }
+}
+
+impl NotesSlide {
+    /// Extracts the text of the notes slide in reading order. The slide image, header, footer, date and slide number
+    /// placeholders are skipped.
+    pub fn text(&self) -> Vec<ShapeText> {
+        notes_slide_text(self, None)
+    }
+}
+
+impl PPTXDocument {
+    /// Extracts the text of the given slide in reading order, including the text of diagrams and, if requested, the
+    /// text of the notes slide.
+    pub fn slide_text(&self, slide: &Slide, options: &TextOptions) -> SlideText {
+        let slide_path = self.slide_path(slide);
+        let shapes = common_slide_data_text(&slide.common_slide_data, slide_path.map(|path| (self, path.as_path())));
+
+        let notes = if options.include_notes {
+            slide_path
+                .and_then(|path| self.notes_slide_path(path))
+                .and_then(|notes_path| {
+                    self.notes_slide_map
+                        .get(&notes_path)
+                        .map(|notes_slide| notes_slide_text(notes_slide, Some((self, notes_path.as_path()))))
+                })
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        SlideText { shapes, notes }
+    }
+
+    /// Extracts the text of every slide of the presentation, in presentation order.
+    pub fn text(&self, options: &TextOptions) -> Vec<SlideText> {
+        self.ordered_slides()
+            .into_iter()
+            .map(|(_, slide)| self.slide_text(slide, options))
+            .collect()
+    }
+
+    /// Extracts the text of the whole presentation as plain text. Slides are separated by an empty line.
+    pub fn plain_text(&self, options: &TextOptions) -> String {
+        self.text(options)
+            .iter()
+            .map(SlideText::plain_text)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Returns the text of a paragraph. Line breaks are represented by '\n' and text fields are replaced by their
+/// current text.
+pub fn paragraph_text(paragraph: &TextParagraph) -> String {
+    paragraph
+        .text_run_list
+        .iter()
+        .map(|text_run| match text_run {
+            TextRun::RegularTextRun(regular_text_run) => regular_text_run.text.as_str(),
+            TextRun::LineBreak(_) => "\n",
+            TextRun::TextField(text_field) => text_field.text.as_deref().unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Returns the paragraphs of a text body along with their indentation levels.
+pub fn text_body_paragraphs(text_body: &TextBody) -> Vec<ParagraphText> {
+    text_body
+        .paragraph_array
+        .iter()
+        .map(|paragraph| ParagraphText {
+            level: paragraph
+                .properties
+                .as_ref()
+                .and_then(|properties| properties.level)
+                .unwrap_or(0) as u32,
+            text: paragraph_text(paragraph),
+        })
+        .collect()
+}
+
+/// Returns the paragraphs of a diagram in reading order. The level of each paragraph is the sum of the depth of its
+/// point in the diagram hierarchy and its own indentation level.
+pub fn diagram_paragraphs(diagram_data: &DiagramData) -> Vec<ParagraphText> {
+    diagram_data
+        .content_points()
+        .into_iter()
+        .filter_map(|(depth, point)| point.text_body.as_ref().map(|text_body| (depth, text_body)))
+        .flat_map(|(depth, text_body)| {
+            text_body_paragraphs(text_body)
+                .into_iter()
+                .map(move |paragraph| ParagraphText {
+                    level: paragraph.level + depth as u32,
+                    text: paragraph.text,
+                })
+        })
+        .collect()
+}
+
+/// Returns the cells of a table row by row, skipping cells that are covered by merged cells.
+pub fn table_cells(table: &Table) -> Vec<Vec<Vec<ParagraphText>>> {
+    table
+        .row_list
+        .iter()
+        .map(|row| {
+            row.cell_list
+                .iter()
+                .filter(|cell| !cell.is_merged())
+                .map(|cell| cell.text_body.as_ref().map(text_body_paragraphs).unwrap_or_default())
+                .collect()
+        })
+        .collect()
+}
+
+fn common_slide_data_text(
+    common_slide_data: &CommonSlideData,
+    context: Option<(&PPTXDocument, &Path)>,
+) -> Vec<ShapeText> {
+    let mut shapes = Vec::new();
+    collect_group_text(&common_slide_data.shape_tree, context, &mut shapes);
+    shapes
+}
+
+fn collect_group_text(group: &GroupShape, context: Option<(&PPTXDocument, &Path)>, shapes: &mut Vec<ShapeText>) {
+    for shape_group in &group.shape_array {
+        let shape_text = match shape_group {
+            ShapeGroup::Shape(shape) => shape.text_body.as_ref().map(|text_body| ShapeText {
+                shape_id: shape.non_visual_props.drawing_props.id,
+                name: shape.non_visual_props.drawing_props.name.clone(),
+                placeholder: shape.non_visual_props.app_props.placeholder.clone(),
+                content: TextContent::Paragraphs(text_body_paragraphs(text_body)),
+            }),
+            ShapeGroup::GroupShape(group_shape) => {
+                collect_group_text(group_shape, context, shapes);
+                None
+            }
+            ShapeGroup::GraphicFrame(graphic_frame) => {
+                let content = match &graphic_frame.graphic_content {
+                    Some(GraphicalObjectContent::Table(table)) => Some(TextContent::Table(table_cells(table))),
+                    Some(GraphicalObjectContent::Diagram(relationship_ids)) => context
+                        .and_then(|(document, part_path)| document.diagram_data(part_path, relationship_ids))
+                        .map(|diagram_data| TextContent::Paragraphs(diagram_paragraphs(diagram_data))),
+                    None => None,
+                };
+
+                content.map(|content| ShapeText {
+                    shape_id: graphic_frame.non_visual_props.drawing_props.id,
+                    name: graphic_frame.non_visual_props.drawing_props.name.clone(),
+                    placeholder: graphic_frame.non_visual_props.app_props.placeholder.clone(),
+                    content,
+                })
+            }
+            _ => None,
+        };
+
+        if let Some(shape_text) = shape_text {
+            if !shape_text.plain_text().is_empty() {
+                shapes.push(shape_text);
+            }
+        }
+    }
+}
+
+fn notes_slide_text(notes_slide: &NotesSlide, context: Option<(&PPTXDocument, &Path)>) -> Vec<ShapeText> {
+    common_slide_data_text(&notes_slide.common_slide_data, context)
+        .into_iter()
+        .filter(|shape_text| !is_notes_furniture(shape_text))
+        .collect()
+}
+
+fn is_notes_furniture(shape_text: &ShapeText) -> bool {
+    matches!(
+        shape_text
+            .placeholder
+            .as_ref()
+            .and_then(|placeholder| placeholder.placeholder_type),
+        Some(PlaceholderType::SlideImage)
+            | Some(PlaceholderType::Header)
+            | Some(PlaceholderType::Footer)
+            | Some(PlaceholderType::DateTime)
+            | Some(PlaceholderType::SlideNumber)
+    )
+}
+
+fn join_paragraphs(paragraphs: &[ParagraphText], separator: &str) -> String {
+    paragraphs
+        .iter()
+        .map(|paragraph| paragraph.text.as_str())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn join_shapes(shapes: &[ShapeText]) -> String {
+    shapes
+        .iter()
+        .map(ShapeText::plain_text)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    pub fn test_slide_text() {
+        let test_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let sample_pptx_path = test_dir.join("tests/samplepptx.pptx");
+
+        let mut document = PPTXDocument::from_file(&sample_pptx_path).unwrap();
+        let first_slide = document.slides().next().unwrap();
+        let slide_text = first_slide.text();
+        assert_eq!(slide_text.shapes.len(), 2);
+        assert!(slide_text.shapes[0].is_title());
+        assert_eq!(slide_text.shapes[0].plain_text(), "Sample PowerPoint File");
+        assert_eq!(slide_text.shapes[1].plain_text(), "St. Cloud Technical College");
+
+        let options = TextOptions { include_notes: true };
+        assert_eq!(document.slide_text(first_slide, &options), slide_text);
+        assert_eq!(document.text(&options).len(), 2);
+        assert!(document
+            .plain_text(&options)
+            .starts_with("Sample PowerPoint File\nSt. Cloud Technical College\n\n"));
+
+        // Slides are extracted in presentation order, regardless of their file names
+        let first_slide_path = document.slide_path_at(0).unwrap();
+        document.move_slide(&first_slide_path, 1).unwrap();
+        let texts = document.text(&options);
+        assert_eq!(texts[1], slide_text);
+        assert_ne!(texts[0], slide_text);
+    }
+}
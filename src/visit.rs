@@ -0,0 +1,350 @@
+use crate::pml::slides::{CommonSlideData, Connector, GraphicalObjectFrame, GroupShape, Picture, Shape, ShapeGroup};
+use msoffice_shared::relationship::RelationshipId;
+
+/// A visitor of the elements of a shape tree.
+///
+/// Every method has a default implementation, so only the methods of the interesting elements have to be implemented.
+/// visit_group_shape visits the children of the group by default. Implementations that override it should call
+/// walk_group_shape to continue the traversal into the group, or omit it to skip the group's children.
+pub trait Visitor<'a> {
+    fn visit_shape_group(&mut self, shape_group: &'a ShapeGroup) {
+        walk_shape_group(self, shape_group);
+    }
+
+    fn visit_shape(&mut self, _shape: &'a Shape) {}
+
+    fn visit_group_shape(&mut self, group_shape: &'a GroupShape) {
+        walk_group_shape(self, group_shape);
+    }
+
+    fn visit_graphic_frame(&mut self, _graphic_frame: &'a GraphicalObjectFrame) {}
+
+    fn visit_connector(&mut self, _connector: &'a Connector) {}
+
+    fn visit_picture(&mut self, _picture: &'a Picture) {}
+
+    fn visit_content_part(&mut self, _relationship_id: &'a RelationshipId) {}
+}
+
+/// Calls the method of the visitor matching the type of the element.
+pub fn walk_shape_group<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, shape_group: &'a ShapeGroup) {
+    match shape_group {
+        ShapeGroup::Shape(shape) => visitor.visit_shape(shape),
+        ShapeGroup::GroupShape(group_shape) => visitor.visit_group_shape(group_shape),
+        ShapeGroup::GraphicFrame(graphic_frame) => visitor.visit_graphic_frame(graphic_frame),
+        ShapeGroup::Connector(connector) => visitor.visit_connector(connector),
+        ShapeGroup::Picture(picture) => visitor.visit_picture(picture),
+        ShapeGroup::ContentPart(relationship_id) => visitor.visit_content_part(relationship_id),
+    }
+}
+
+/// Visits the elements of a group in document order.
+pub fn walk_group_shape<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, group_shape: &'a GroupShape) {
+    for shape_group in &group_shape.shape_array {
+        visitor.visit_shape_group(shape_group);
+    }
+}
+
+/// A visitor of the elements of a shape tree which is allowed to modify them. See Visitor.
+pub trait VisitorMut {
+    fn visit_shape_group_mut(&mut self, shape_group: &mut ShapeGroup) {
+        walk_shape_group_mut(self, shape_group);
+    }
+
+    fn visit_shape_mut(&mut self, _shape: &mut Shape) {}
+
+    fn visit_group_shape_mut(&mut self, group_shape: &mut GroupShape) {
+        walk_group_shape_mut(self, group_shape);
+    }
+
+    fn visit_graphic_frame_mut(&mut self, _graphic_frame: &mut GraphicalObjectFrame) {}
+
+    fn visit_connector_mut(&mut self, _connector: &mut Connector) {}
+
+    fn visit_picture_mut(&mut self, _picture: &mut Picture) {}
+
+    fn visit_content_part_mut(&mut self, _relationship_id: &mut RelationshipId) {}
+}
+
+/// Calls the method of the visitor matching the type of the element.
+pub fn walk_shape_group_mut<V: VisitorMut + ?Sized>(visitor: &mut V, shape_group: &mut ShapeGroup) {
+    match shape_group {
+        ShapeGroup::Shape(shape) => visitor.visit_shape_mut(shape),
+        ShapeGroup::GroupShape(group_shape) => visitor.visit_group_shape_mut(group_shape),
+        ShapeGroup::GraphicFrame(graphic_frame) => visitor.visit_graphic_frame_mut(graphic_frame),
+        ShapeGroup::Connector(connector) => visitor.visit_connector_mut(connector),
+        ShapeGroup::Picture(picture) => visitor.visit_picture_mut(picture),
+        ShapeGroup::ContentPart(relationship_id) => visitor.visit_content_part_mut(relationship_id),
+    }
+}
+
+/// Visits the elements of a group in document order.
+pub fn walk_group_shape_mut<V: VisitorMut + ?Sized>(visitor: &mut V, group_shape: &mut GroupShape) {
+    for shape_group in &mut group_shape.shape_array {
+        visitor.visit_shape_group_mut(shape_group);
+    }
+}
+
+/// A depth-first iterator over the elements of a shape tree, including the elements of nested groups.
+///
+/// Each element is returned with its depth and the chain of groups containing it, starting with the outermost group.
+/// The group the iteration started from isn't part of the chain, so the depth of its direct children is 0 and the
+/// depth always equals the length of the chain. A group is returned before its children.
+#[derive(Debug, Clone)]
+pub struct ShapeTreeIter<'a> {
+    parents: Vec<&'a GroupShape>,
+    stack: Vec<std::slice::Iter<'a, ShapeGroup>>,
+}
+
+impl<'a> ShapeTreeIter<'a> {
+    pub fn new(group_shape: &'a GroupShape) -> Self {
+        Self {
+            parents: Vec::new(),
+            stack: vec![group_shape.shape_array.iter()],
+        }
+    }
+}
+
+impl<'a> Iterator for ShapeTreeIter<'a> {
+    type Item = (usize, Vec<&'a GroupShape>, &'a ShapeGroup);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.last_mut()?.next() {
+                Some(shape_group) => {
+                    let item = (self.parents.len(), self.parents.clone(), shape_group);
+                    if let ShapeGroup::GroupShape(group_shape) = shape_group {
+                        self.parents.push(group_shape);
+                        self.stack.push(group_shape.shape_array.iter());
+                    }
+
+                    return Some(item);
+                }
+                None => {
+                    self.stack.pop();
+                    self.parents.pop();
+                }
+            }
+        }
+    }
+}
+
+impl GroupShape {
+    /// Returns a depth-first iterator over the elements of the group, including the elements of nested groups.
+    pub fn iter_shapes(&self) -> ShapeTreeIter<'_> {
+        ShapeTreeIter::new(self)
+    }
+
+    /// Calls `f` on every element of the group in depth-first order, including the elements of nested groups.
+    ///
+    /// This is the mutable variant of iter_shapes. As the containing groups can't be borrowed while one of their
+    /// elements is modified, the chain of groups is given as the indices of the groups in the shape array of their
+    /// parents instead. A group is passed to `f` before its children, so the children of a group replaced by `f` are
+    /// the children of the replacement.
+    pub fn for_each_shape_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &[usize], &mut ShapeGroup),
+    {
+        for_each_shape_mut(self, &mut Vec::new(), &mut f);
+    }
+
+    /// Visits the elements of the group with a visitor.
+    pub fn accept<'a, V: Visitor<'a> + ?Sized>(&'a self, visitor: &mut V) {
+        walk_group_shape(visitor, self);
+    }
+
+    /// Visits the elements of the group with a visitor that is allowed to modify them.
+    pub fn accept_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        walk_group_shape_mut(visitor, self);
+    }
+}
+
+impl CommonSlideData {
+    /// Returns a depth-first iterator over the elements of the shape tree. See GroupShape::iter_shapes.
+    pub fn iter_shapes(&self) -> ShapeTreeIter<'_> {
+        self.shape_tree.iter_shapes()
+    }
+
+    /// Calls `f` on every element of the shape tree in depth-first order. See GroupShape::for_each_shape_mut.
+    pub fn for_each_shape_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(usize, &[usize], &mut ShapeGroup),
+    {
+        self.shape_tree.for_each_shape_mut(f);
+    }
+}
+
+fn for_each_shape_mut<F>(group_shape: &mut GroupShape, path: &mut Vec<usize>, f: &mut F)
+where
+    F: FnMut(usize, &[usize], &mut ShapeGroup),
+{
+    for (index, shape_group) in group_shape.shape_array.iter_mut().enumerate() {
+        f(path.len(), path, shape_group);
+        if let ShapeGroup::GroupShape(group_shape) = shape_group {
+            path.push(index);
+            for_each_shape_mut(group_shape, path, f);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use msoffice_shared::xml::XmlNode;
+    use std::str::FromStr;
+
+    fn shape_tree() -> GroupShape {
+        let xml = r#"<p:spTree xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"
+                xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+                <p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                <p:grpSpPr/>
+                <p:sp>
+                    <p:nvSpPr><p:cNvPr id="2" name="Title"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>
+                    <p:spPr/>
+                </p:sp>
+                <p:grpSp>
+                    <p:nvGrpSpPr><p:cNvPr id="3" name="Group"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                    <p:grpSpPr/>
+                    <p:cxnSp>
+                        <p:nvCxnSpPr><p:cNvPr id="4" name="Connector"/><p:cNvCxnSpPr/><p:nvPr/></p:nvCxnSpPr>
+                        <p:spPr/>
+                    </p:cxnSp>
+                    <p:grpSp>
+                        <p:nvGrpSpPr><p:cNvPr id="5" name="Inner group"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+                        <p:grpSpPr/>
+                        <p:sp>
+                            <p:nvSpPr><p:cNvPr id="6" name="Inner shape"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>
+                            <p:spPr/>
+                        </p:sp>
+                    </p:grpSp>
+                </p:grpSp>
+                <p:sp>
+                    <p:nvSpPr><p:cNvPr id="7" name="Footer"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>
+                    <p:spPr/>
+                </p:sp>
+            </p:spTree>"#;
+        GroupShape::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    fn name(shape_group: &ShapeGroup) -> &str {
+        match shape_group {
+            ShapeGroup::Shape(shape) => &shape.non_visual_props.drawing_props.name,
+            ShapeGroup::GroupShape(group_shape) => &group_shape.non_visual_props.drawing_props.name,
+            ShapeGroup::Connector(connector) => &connector.non_visual_props.drawing_props.name,
+            _ => "",
+        }
+    }
+
+    #[test]
+    pub fn test_iter_shapes() {
+        let shape_tree = shape_tree();
+        let items = shape_tree
+            .iter_shapes()
+            .map(|(depth, parents, shape_group)| {
+                let parent_names = parents
+                    .iter()
+                    .map(|parent| parent.non_visual_props.drawing_props.name.as_str())
+                    .collect::<Vec<_>>();
+                (depth, parent_names, name(shape_group))
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            items,
+            vec![
+                (0, vec![], "Title"),
+                (0, vec![], "Group"),
+                (1, vec!["Group"], "Connector"),
+                (1, vec!["Group"], "Inner group"),
+                (2, vec!["Group", "Inner group"], "Inner shape"),
+                (0, vec![], "Footer"),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_visitor() {
+        struct ShapeNames<'a> {
+            names: Vec<&'a str>,
+            connectors: usize,
+        }
+
+        impl<'a> Visitor<'a> for ShapeNames<'a> {
+            fn visit_shape(&mut self, shape: &'a Shape) {
+                self.names.push(&shape.non_visual_props.drawing_props.name);
+            }
+
+            fn visit_group_shape(&mut self, group_shape: &'a GroupShape) {
+                // Skip the children of the inner group
+                if group_shape.non_visual_props.drawing_props.name != "Inner group" {
+                    walk_group_shape(self, group_shape);
+                }
+            }
+
+            fn visit_connector(&mut self, _connector: &'a Connector) {
+                self.connectors += 1;
+            }
+        }
+
+        let shape_tree = shape_tree();
+        let mut visitor = ShapeNames {
+            names: Vec::new(),
+            connectors: 0,
+        };
+        shape_tree.accept(&mut visitor);
+        assert_eq!(visitor.names, vec!["Title", "Footer"]);
+        assert_eq!(visitor.connectors, 1);
+
+        struct Rename;
+
+        impl VisitorMut for Rename {
+            fn visit_shape_mut(&mut self, shape: &mut Shape) {
+                shape.non_visual_props.drawing_props.name.make_ascii_uppercase();
+            }
+        }
+
+        let mut shape_tree = shape_tree;
+        shape_tree.accept_mut(&mut Rename);
+        let names = shape_tree
+            .iter_shapes()
+            .map(|(_, _, shape_group)| name(shape_group))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["TITLE", "Group", "Connector", "Inner group", "INNER SHAPE", "FOOTER"]
+        );
+    }
+
+    #[test]
+    pub fn test_for_each_shape_mut() {
+        let mut shape_tree = shape_tree();
+        let mut paths = Vec::new();
+        shape_tree.for_each_shape_mut(|depth, path, shape_group| {
+            paths.push((depth, path.to_vec()));
+            if let ShapeGroup::Shape(shape) = shape_group {
+                shape.non_visual_props.drawing_props.hidden = Some(true);
+            }
+        });
+
+        assert_eq!(
+            paths,
+            vec![
+                (0, vec![]),
+                (0, vec![]),
+                (1, vec![1]),
+                (1, vec![1]),
+                (2, vec![1, 1]),
+                (0, vec![]),
+            ]
+        );
+        let hidden = shape_tree
+            .iter_shapes()
+            .filter_map(|(_, _, shape_group)| match shape_group {
+                ShapeGroup::Shape(shape) => shape.non_visual_props.drawing_props.hidden,
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(hidden, vec![true, true, true]);
+    }
+}
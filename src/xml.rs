@@ -0,0 +1,571 @@
+use crate::pml::util::escape_xml;
+use msoffice_shared::xml::XmlNode;
+use std::{error::Error, fmt::Display, io::Write, str::FromStr};
+use xml::{
+    namespace::{Namespace, NS_NO_PREFIX, NS_XMLNS_PREFIX, NS_XML_PREFIX},
+    reader::{EventReader, XmlEvent},
+};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// The namespace of the PresentationML elements, bound to the `p` prefix.
+pub const PRESENTATIONML_NAMESPACE: &str = "http://schemas.openxmlformats.org/presentationml/2006/main";
+/// The namespace of the DrawingML elements, bound to the `a` prefix.
+pub const DRAWINGML_NAMESPACE: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+/// The namespace of the relationship id attributes, bound to the `r` prefix.
+pub const RELATIONSHIPS_NAMESPACE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\r\n";
+
+/// Namespaces declared on the root element of every written part, the same way PowerPoint does.
+const ROOT_NAMESPACES: [(&str, &str); 3] = [
+    ("xmlns:a", DRAWINGML_NAMESPACE),
+    ("xmlns:r", RELATIONSHIPS_NAMESPACE),
+    ("xmlns:p", PRESENTATIONML_NAMESPACE),
+];
+
+/// Namespaces that are declared on the root element of a written part when the part uses their prefix without declaring
+/// it. These are the prefixes conventionally used by Office, e.g. for xml added to the model instead of parsed.
+const KNOWN_NAMESPACES: [(&str, &str); 16] = [
+    ("mc", "http://schemas.openxmlformats.org/markup-compatibility/2006"),
+    ("dgm", "http://schemas.openxmlformats.org/drawingml/2006/diagram"),
+    ("c", "http://schemas.openxmlformats.org/drawingml/2006/chart"),
+    ("pic", "http://schemas.openxmlformats.org/drawingml/2006/picture"),
+    ("m", "http://schemas.openxmlformats.org/officeDocument/2006/math"),
+    ("v", "urn:schemas-microsoft-com:vml"),
+    ("o", "urn:schemas-microsoft-com:office:office"),
+    ("p14", "http://schemas.microsoft.com/office/powerpoint/2010/main"),
+    ("p15", "http://schemas.microsoft.com/office/powerpoint/2012/main"),
+    ("p159", "http://schemas.microsoft.com/office/powerpoint/2015/09/main"),
+    ("p188", "http://schemas.microsoft.com/office/powerpoint/2018/8/main"),
+    ("a14", "http://schemas.microsoft.com/office/drawing/2010/main"),
+    ("a16", "http://schemas.microsoft.com/office/drawing/2014/main"),
+    ("a15", "http://schemas.microsoft.com/office/drawing/2012/main"),
+    ("asvg", "http://schemas.microsoft.com/office/drawing/2016/SVG/main"),
+    (
+        "ahyp",
+        "http://schemas.microsoft.com/office/drawing/2018/hyperlinkcolor",
+    ),
+];
+
+/// Attributes and child elements of an element that aren't represented by the model of its type, like extension lists
+/// (extLst), markup compatibility blocks (mc:AlternateContent) or content of newer versions of the format. They are
+/// kept as raw xml and written back to the element when the model is written.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UnknownXml {
+    /// The unknown attributes sorted by their name.
+    pub attributes: Vec<(String, String)>,
+    /// The unknown child elements paired with their position among all the child elements of the original element.
+    pub child_nodes: Vec<(usize, XmlNode)>,
+    /// The unknown xml of the known child elements whose types don't keep it themselves, like the DrawingML elements of
+    /// msoffice_shared. It's paired with the local name of the child element and its index among the known child
+    /// elements with the same local name.
+    pub known_child_nodes: Vec<(String, usize, UnknownXml)>,
+}
+
+impl UnknownXml {
+    /// Collects the attributes of `xml_node` that aren't listed in `known_attributes` and the child elements whose
+    /// local name isn't accepted by `is_known_child`. This is used by types that don't iterate over the attributes or
+    /// the child elements of their element.
+    pub(crate) fn from_xml_element<F>(xml_node: &XmlNode, known_attributes: &[&str], is_known_child: F) -> Self
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut instance: Self = Default::default();
+        for (attr, value) in &xml_node.attributes {
+            if !known_attributes.contains(&attr.as_str()) {
+                instance.push_attribute(attr, value);
+            }
+        }
+
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            if !is_known_child(child_node.local_name()) {
+                instance.push_child(position, child_node);
+            }
+        }
+
+        instance
+    }
+
+    /// Collects the attributes and the child elements of `xml_node` that are missing from `written_node`, the element
+    /// written from the model parsed from `xml_node`. The child elements of the two are matched in order by their local
+    /// name, and the unknown xml of the matched ones is collected recursively.
+    fn from_written_xml_element(xml_node: &XmlNode, written_node: &XmlNode) -> Self {
+        let mut instance: Self = Default::default();
+        for (attr, value) in &xml_node.attributes {
+            if !written_node.attributes.contains_key(attr) {
+                instance.push_attribute(attr, value);
+            }
+        }
+
+        let mut next_written_position = 0;
+        for (position, child_node) in xml_node.child_nodes.iter().enumerate() {
+            let local_name = child_node.local_name();
+            let written_position = written_node.child_nodes[next_written_position..]
+                .iter()
+                .position(|written_child_node| written_child_node.local_name() == local_name)
+                .map(|offset| next_written_position + offset);
+            match written_position {
+                Some(written_position) => {
+                    let index = written_node.child_nodes[..written_position]
+                        .iter()
+                        .filter(|written_child_node| written_child_node.local_name() == local_name)
+                        .count();
+                    let unknown_xml =
+                        Self::from_written_xml_element(child_node, &written_node.child_nodes[written_position]);
+                    if !unknown_xml.is_empty() {
+                        instance
+                            .known_child_nodes
+                            .push((String::from(local_name), index, unknown_xml));
+                    }
+                    next_written_position = written_position + 1;
+                }
+                None => instance.push_child(position, child_node),
+            }
+        }
+
+        instance
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.child_nodes.is_empty() && self.known_child_nodes.is_empty()
+    }
+
+    /// Adds an unknown attribute. The attributes of a parsed element are kept in a hash map, so they are sorted by
+    /// name to make the unknown xml of the same element compare equal.
+    pub(crate) fn push_attribute(&mut self, attr: &str, value: &str) {
+        let index = self
+            .attributes
+            .binary_search_by(|(name, _)| name.as_str().cmp(attr))
+            .unwrap_or_else(|index| index);
+        self.attributes.insert(index, (String::from(attr), String::from(value)));
+    }
+
+    pub(crate) fn push_child(&mut self, position: usize, child_node: &XmlNode) {
+        self.child_nodes.push((position, child_node.clone()));
+    }
+
+    /// Parses the child element at `position` of `xml_node` with `parse` and keeps the unknown xml of the child
+    /// element, which is whatever is missing when the parsed value is written back. This is used for the child
+    /// elements whose types are defined by msoffice_shared.
+    pub(crate) fn parse_known_child<T, F>(&mut self, xml_node: &XmlNode, position: usize, parse: F) -> Result<T>
+    where
+        T: ToXmlElement,
+        F: FnOnce(&XmlNode) -> Result<T>,
+    {
+        let child_node = &xml_node.child_nodes[position];
+        let value = parse(child_node)?;
+        self.push_known_child(xml_node, position, &value.to_xml_element(&child_node.name));
+        Ok(value)
+    }
+
+    /// Keeps the unknown xml of the child element at `position` of `xml_node`, which is whatever is missing from
+    /// `written_node`, the element written from the parsed child element.
+    pub(crate) fn push_known_child(&mut self, xml_node: &XmlNode, position: usize, written_node: &XmlNode) {
+        let child_node = &xml_node.child_nodes[position];
+        let unknown_xml = Self::from_written_xml_element(child_node, written_node);
+        if unknown_xml.is_empty() {
+            return;
+        }
+
+        let local_name = child_node.local_name();
+        let index = xml_node.child_nodes[..position]
+            .iter()
+            .filter(|sibling_node| sibling_node.local_name() == local_name)
+            .count();
+        self.known_child_nodes
+            .push((String::from(local_name), index, unknown_xml));
+    }
+
+    /// Drops the unknown xml kept for the known child elements with the given local name. This is needed when such a
+    /// child is replaced, as its unknown xml doesn't belong to the new element.
+    pub(crate) fn remove_known_child(&mut self, local_name: &str) {
+        self.known_child_nodes.retain(|(name, _, _)| name != local_name);
+    }
+
+    /// Drops the unknown xml kept for the known child element with the given local name at `index` among the ones with
+    /// the same local name, and shifts the ones after it. This is needed when such a child is removed.
+    pub(crate) fn remove_known_child_at(&mut self, local_name: &str, index: usize) {
+        self.known_child_nodes
+            .retain(|(name, child_index, _)| name != local_name || *child_index != index);
+        for (name, child_index, _) in &mut self.known_child_nodes {
+            if name == local_name && *child_index > index {
+                *child_index -= 1;
+            }
+        }
+    }
+
+    /// Returns the unknown xml kept for the known child element with the given local name at `index` among the ones
+    /// with the same local name.
+    pub(crate) fn known_child_mut(&mut self, local_name: &str, index: usize) -> Option<&mut UnknownXml> {
+        self.known_child_nodes
+            .iter_mut()
+            .find(|(name, child_index, _)| name == local_name && *child_index == index)
+            .map(|(_, _, unknown_xml)| unknown_xml)
+    }
+}
+
+/// Converts a type that can appear under different element names back to an xml element. This is the inverse of the
+/// `from_xml_element` function of such types.
+pub trait ToXmlElement {
+    fn to_xml_element(&self, node_name: &str) -> XmlNode;
+}
+
+/// Converts a member of an xsd choice back to an xml element. The name of the element is determined by the variant.
+pub trait ToXmlChoice {
+    fn to_xml_element(&self) -> XmlNode;
+}
+
+/// An enumeration defined by a simple type of the schema that's written to an attribute as one of its schema values.
+pub(crate) trait SchemaValue: FromStr + PartialEq {
+    /// All the values of the simple type, in schema order.
+    const VALUES: &'static [&'static str];
+
+    /// Returns the schema value that parses to this value.
+    fn schema_value(&self) -> &'static str {
+        Self::VALUES
+            .iter()
+            .find(|value| value.parse::<Self>().ok().as_ref() == Some(self))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Helper functions to build an `XmlNode` in a single expression.
+pub(crate) trait XmlNodeBuilder: Sized {
+    fn with_attribute<T: Display>(self, name: &str, value: T) -> Self;
+
+    fn with_optional_attribute<T: Display>(self, name: &str, value: Option<T>) -> Self;
+
+    fn with_child(self, child: XmlNode) -> Self;
+
+    fn with_optional_child(self, child: Option<XmlNode>) -> Self;
+
+    fn with_children<I: IntoIterator<Item = XmlNode>>(self, children: I) -> Self;
+
+    fn with_text<T: Into<String>>(self, text: T) -> Self;
+
+    /// Adds the unknown attributes and inserts the unknown child elements back to their original positions. As the
+    /// known child elements are written in schema order, this restores the original order of the children for an
+    /// unchanged model. It has to be called after all the known children have been added. Attributes that are already
+    /// set are left unchanged.
+    fn with_unknown_xml(self, unknown_xml: &UnknownXml) -> Self;
+}
+
+impl XmlNodeBuilder for XmlNode {
+    fn with_attribute<T: Display>(mut self, name: &str, value: T) -> Self {
+        self.attributes.insert(String::from(name), value.to_string());
+        self
+    }
+
+    fn with_optional_attribute<T: Display>(self, name: &str, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.with_attribute(name, value),
+            None => self,
+        }
+    }
+
+    fn with_child(mut self, child: XmlNode) -> Self {
+        self.child_nodes.push(child);
+        self
+    }
+
+    fn with_optional_child(self, child: Option<XmlNode>) -> Self {
+        match child {
+            Some(child) => self.with_child(child),
+            None => self,
+        }
+    }
+
+    fn with_children<I: IntoIterator<Item = XmlNode>>(mut self, children: I) -> Self {
+        self.child_nodes.extend(children);
+        self
+    }
+
+    fn with_text<T: Into<String>>(mut self, text: T) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    fn with_unknown_xml(mut self, unknown_xml: &UnknownXml) -> Self {
+        for (local_name, index, child_unknown_xml) in &unknown_xml.known_child_nodes {
+            let child_node = self
+                .child_nodes
+                .iter_mut()
+                .filter(|child_node| child_node.local_name() == local_name)
+                .nth(*index);
+            if let Some(child_node) = child_node {
+                let written_node = std::mem::replace(child_node, XmlNode::new(""));
+                *child_node = written_node.with_unknown_xml(child_unknown_xml);
+            }
+        }
+
+        for (attr, value) in &unknown_xml.attributes {
+            self.attributes.entry(attr.clone()).or_insert_with(|| value.clone());
+        }
+
+        for (position, child_node) in &unknown_xml.child_nodes {
+            let position = (*position).min(self.child_nodes.len());
+            self.child_nodes.insert(position, child_node.clone());
+        }
+
+        self
+    }
+}
+
+/// Parses the xml of a part. Unlike `XmlNode::from_str`, the namespace declarations of the elements are kept as
+/// attributes, so that the prefixes of the unknown xml are still declared when the part is written. The declarations
+/// of the prefixes in `ROOT_NAMESPACES` and `KNOWN_NAMESPACES` are left out, as `write_xml` declares them anyway.
+pub(crate) fn parse_part_xml(xml: &str) -> Result<XmlNode> {
+    let mut xml_node = XmlNode::from_str(xml)?;
+    let mut declarations = namespace_declarations(xml)?.into_iter();
+    restore_namespace_declarations(&mut xml_node, &mut declarations);
+    Ok(xml_node)
+}
+
+/// Returns the namespace declarations of every element of `xml`, in document order. The parser only reports the
+/// namespaces in scope of an element, so the declarations are the ones that differ from the scope of the parent.
+fn namespace_declarations(xml: &str) -> Result<Vec<Vec<(String, String)>>> {
+    let mut declarations = Vec::new();
+    let mut scopes: Vec<Namespace> = Vec::new();
+    for event in EventReader::from_str(xml) {
+        match event? {
+            XmlEvent::StartElement { namespace, .. } => {
+                let parent_scope = scopes.last();
+                let element_declarations = namespace
+                    .iter()
+                    .filter(|(prefix, uri)| match parent_scope.and_then(|scope| scope.get(prefix)) {
+                        Some(parent_uri) => parent_uri != *uri,
+                        None => !uri.is_empty() && *prefix != NS_XML_PREFIX && *prefix != NS_XMLNS_PREFIX,
+                    })
+                    .map(|(prefix, uri)| match prefix {
+                        NS_NO_PREFIX => (String::from("xmlns"), String::from(uri)),
+                        prefix => (format!("xmlns:{}", prefix), String::from(uri)),
+                    })
+                    .collect();
+                declarations.push(element_declarations);
+                scopes.push(namespace);
+            }
+            XmlEvent::EndElement { .. } => {
+                scopes.pop();
+            }
+            _ => (),
+        }
+    }
+
+    Ok(declarations)
+}
+
+fn restore_namespace_declarations<I>(xml_node: &mut XmlNode, declarations: &mut I)
+where
+    I: Iterator<Item = Vec<(String, String)>>,
+{
+    for (name, uri) in declarations.next().unwrap_or_default() {
+        let is_conventional = ROOT_NAMESPACES.contains(&(name.as_str(), uri.as_str()))
+            || KNOWN_NAMESPACES
+                .iter()
+                .any(|(prefix, known_uri)| name.strip_prefix("xmlns:") == Some(prefix) && *known_uri == uri);
+        if is_conventional {
+            xml_node.attributes.remove(&name);
+        } else {
+            xml_node.attributes.entry(name).or_insert(uri);
+        }
+    }
+
+    for child_node in &mut xml_node.child_nodes {
+        restore_namespace_declarations(child_node, declarations);
+    }
+}
+
+/// Writes `xml_node` as a standalone xml document. The namespaces of the `p`, `a` and `r` prefixes are declared on the
+/// root element unless it already declares them, as well as the namespaces of the known prefixes used without a
+/// declaration. Using any other prefix without declaring it is an error.
+pub fn write_xml<W: Write>(xml_node: &XmlNode, writer: &mut W) -> Result<()> {
+    let mut undeclared_prefixes = Vec::new();
+    collect_undeclared_prefixes(xml_node, &mut Vec::new(), &mut undeclared_prefixes);
+    let root_prefixes = ROOT_NAMESPACES.iter().map(|(name, _)| &name["xmlns:".len()..]);
+    if let Some(prefix) = undeclared_prefixes.iter().find(|prefix| {
+        !matches!(**prefix, "xml" | "xmlns")
+            && !root_prefixes.clone().any(|root_prefix| root_prefix == **prefix)
+            && !KNOWN_NAMESPACES.iter().any(|(known_prefix, _)| known_prefix == *prefix)
+    }) {
+        return Err(format!("namespace prefix isn't declared: {}", prefix).into());
+    }
+
+    let known_namespaces = KNOWN_NAMESPACES
+        .iter()
+        .filter(|(prefix, _)| undeclared_prefixes.contains(prefix))
+        .map(|(prefix, uri)| (format!("xmlns:{}", prefix), *uri))
+        .collect::<Vec<_>>();
+
+    writer.write_all(XML_DECLARATION.as_bytes())?;
+
+    let namespaces = ROOT_NAMESPACES
+        .iter()
+        .map(|(name, uri)| (*name, *uri))
+        .chain(known_namespaces.iter().map(|(name, uri)| (name.as_str(), *uri)))
+        .filter(|(name, _)| !xml_node.attributes.contains_key(*name));
+    write_element(xml_node, namespaces, writer)?;
+    Ok(())
+}
+
+/// Collects the namespace prefixes used by the names of `xml_node` and its descendants that aren't declared by the
+/// element using them or one of its ancestors, which are in `declared_prefixes`. Prefixes listed by markup
+/// compatibility attributes (e.g. mc:Ignorable="p14") have to be declared as well.
+fn collect_undeclared_prefixes<'a>(
+    xml_node: &'a XmlNode,
+    declared_prefixes: &mut Vec<&'a str>,
+    prefixes: &mut Vec<&'a str>,
+) {
+    let scope_len = declared_prefixes.len();
+    declared_prefixes.extend(
+        xml_node
+            .attributes
+            .keys()
+            .filter(|attr| attr.starts_with("xmlns:"))
+            .map(|attr| &attr["xmlns:".len()..]),
+    );
+
+    let mut add_prefix = |prefix: &'a str| {
+        if !declared_prefixes.contains(&prefix) && !prefixes.contains(&prefix) {
+            prefixes.push(prefix);
+        }
+    };
+
+    let name_prefix = |name: &'a str| name.find(':').map(|index| &name[..index]);
+    if let Some(prefix) = name_prefix(&xml_node.name) {
+        add_prefix(prefix);
+    }
+    for (attr, value) in &xml_node.attributes {
+        if let Some(prefix) = name_prefix(attr) {
+            add_prefix(prefix);
+        }
+
+        let local_name = attr.rsplit(':').next().unwrap_or_default();
+        if let "Ignorable" | "Requires" | "ProcessContent" | "MustUnderstand" = local_name {
+            value
+                .split_whitespace()
+                .map(|token| token.split(':').next().unwrap_or(token))
+                .for_each(&mut add_prefix);
+        }
+    }
+
+    for child_node in &xml_node.child_nodes {
+        collect_undeclared_prefixes(child_node, declared_prefixes, prefixes);
+    }
+    declared_prefixes.truncate(scope_len);
+}
+
+/// Writes `xml_node` as a standalone xml document without declaring any namespaces on its root element. This is used
+/// for the parts of the package itself, like the content types and the relationship parts, whose elements are in the
+/// default namespace declared by the node.
+pub fn write_package_xml<W: Write>(xml_node: &XmlNode, writer: &mut W) -> Result<()> {
+    writer.write_all(XML_DECLARATION.as_bytes())?;
+    write_element(xml_node, std::iter::empty(), writer)
+}
+
+fn write_element<'a, W, I>(xml_node: &'a XmlNode, extra_attributes: I, writer: &mut W) -> Result<()>
+where
+    W: Write,
+    I: Iterator<Item = (&'a str, &'a str)>,
+{
+    // Attributes are kept in a hash map, so they are sorted to produce the same output for the same node. Namespace
+    // declarations come first, like in the files written by PowerPoint
+    let mut attributes = xml_node
+        .attributes
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .chain(extra_attributes)
+        .collect::<Vec<_>>();
+    attributes.sort_by_key(|(name, _)| (!name.starts_with("xmlns"), *name));
+
+    write!(writer, "<{}", xml_node.name)?;
+    for (name, value) in attributes {
+        write!(writer, " {}=\"{}\"", name, escape_xml(value))?;
+    }
+
+    if xml_node.child_nodes.is_empty() && xml_node.text.is_none() {
+        writer.write_all(b"/>")?;
+        return Ok(());
+    }
+
+    writer.write_all(b">")?;
+    if let Some(text) = &xml_node.text {
+        writer.write_all(escape_xml(text).as_bytes())?;
+    }
+    for child_node in &xml_node.child_nodes {
+        write_element(child_node, std::iter::empty(), writer)?;
+    }
+    write!(writer, "</{}>", xml_node.name)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_write_xml() {
+        let xml_node = XmlNode::new("p:sld").with_child(
+            XmlNode::new("a:t")
+                .with_attribute("z", "\"quoted\"")
+                .with_attribute("xmlns:x", "urn:x")
+                .with_text("a < b & c"),
+        );
+        let mut xml = Vec::new();
+        write_xml(&xml_node, &mut xml).unwrap();
+
+        let expected = concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\r\n",
+            "<p:sld xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\" ",
+            "xmlns:p=\"http://schemas.openxmlformats.org/presentationml/2006/main\" ",
+            "xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">",
+            "<a:t xmlns:x=\"urn:x\" z=\"&quot;quoted&quot;\">a &lt; b &amp; c</a:t>",
+            "</p:sld>",
+        );
+        assert_eq!(String::from_utf8(xml).unwrap(), expected);
+    }
+
+    #[test]
+    pub fn test_parse_part_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <!-- <p:comment xmlns:y="urn:y"/> -->
+            <p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+                xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main" xmlns:x='urn:x?a=1&amp;b=2'>
+                <p:extLst><p:ext uri="{1}"><y:data xmlns:y="urn:y" val="/>"/></p:ext></p:extLst>
+                <x:data val='&lt;z:data xmlns:z="urn:z"/>'><![CDATA[<z:data xmlns:z="urn:z"/>]]></x:data>
+            </p:sld>"#;
+        let xml_node = parse_part_xml(xml).unwrap();
+        assert_eq!(xml_node.attributes.len(), 1);
+        assert_eq!(xml_node.attributes["xmlns:x"], "urn:x?a=1&b=2");
+        let data_node = &xml_node.child_nodes[0].child_nodes[0].child_nodes[0];
+        assert_eq!(data_node.attributes["xmlns:y"], "urn:y");
+        assert_eq!(data_node.attributes["val"], "/>");
+        let data_node = &xml_node.child_nodes[1];
+        assert_eq!(data_node.attributes.len(), 1);
+        assert_eq!(data_node.attributes["val"], "<z:data xmlns:z=\"urn:z\"/>");
+
+        let mut written_xml = Vec::new();
+        write_xml(&xml_node, &mut written_xml).unwrap();
+        assert_eq!(
+            parse_part_xml(std::str::from_utf8(&written_xml).unwrap()).unwrap(),
+            xml_node
+        );
+    }
+
+    #[test]
+    pub fn test_write_xml_undeclared_prefix() {
+        let xml_node = XmlNode::new("p:sld")
+            .with_child(XmlNode::new("p14:creationId"))
+            .with_child(XmlNode::new("x:data"));
+        let error = write_xml(&xml_node, &mut Vec::new()).unwrap_err();
+        assert_eq!(error.to_string(), "namespace prefix isn't declared: x");
+
+        let xml_node = xml_node.with_attribute("xmlns:x", "urn:x");
+        let mut xml = Vec::new();
+        write_xml(&xml_node, &mut xml).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(xml.contains("xmlns:p14=\"http://schemas.microsoft.com/office/powerpoint/2010/main\""));
+        assert!(xml.contains("xmlns:x=\"urn:x\""));
+    }
+}